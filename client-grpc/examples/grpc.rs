@@ -49,7 +49,19 @@ async fn generate_sample_vehicle(hangar_id: String, hangar_bay_id: String) -> Re
     let vehicle_client = &clients.vehicle;
     println!("Vehicle Client created");
 
+    let vehicle_model = match clients
+        .vehicle_model
+        .insert(vehicle_model::mock::get_data_obj())
+        .await
+    {
+        Ok(vm) => vm.into_inner(),
+        Err(e) => panic!("Something went wrong inserting the vehicle_model: {}", e),
+    };
+
     let mut vehicle = vehicle::mock::get_data_obj();
+    if let Some(vehicle_model) = vehicle_model.object {
+        vehicle.vehicle_model_id = vehicle_model.id;
+    }
 
     // add hangar_id to some of our vehicles
     vehicle.hangar_id = Some(hangar_id);
@@ -381,6 +393,12 @@ async fn test_telemetry() -> Result<(), Box<dyn std::error::Error>> {
         message_type,
         network_timestamp: Some(timestamp_1.clone()),
         payload: payload_1.clone().to_vec(),
+        position: None,
+        baro_altitude: None,
+        ground_speed: None,
+        heading: None,
+        callsign: None,
+        receivers: vec![String::from("receiver-1")],
     };
 
     // Insert data and get the UUID of the adsb entry
@@ -400,6 +418,12 @@ async fn test_telemetry() -> Result<(), Box<dyn std::error::Error>> {
         message_type,
         network_timestamp: Some(timestamp_2),
         payload: payload_2.clone().to_vec(),
+        position: None,
+        baro_altitude: None,
+        ground_speed: None,
+        heading: None,
+        callsign: None,
+        receivers: vec![String::from("receiver-1")],
     };
     // Insert data and get the UUID of the adsb entry
     let Ok(response) = client.insert(request_data).await else {
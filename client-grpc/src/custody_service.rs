@@ -0,0 +1,74 @@
+//! GRPC Custody Service traits
+
+use crate::resources::parcel::{CustodyTimelineRequest, CustodyTimelineResponse};
+
+/// Generic gRPC object traits to provide wrappers for parcel custody functions
+#[tonic::async_trait]
+pub trait Client<T>
+where
+    Self: Sized + lib_common::grpc::Client<T> + lib_common::grpc::ClientConnect<T>,
+    T: Send + Clone,
+{
+    /// Returns a [`tonic::Response`] containing a [`CustodyTimelineResponse`]
+    /// with the current status and all scans, flight legs and status changes of the provided parcel.
+    ///
+    /// The events are ordered by their timestamp. Flight legs are placed at the actual departure time
+    /// of the flight plan, or at the start of its origin timeslot if it did not depart yet.
+    /// Deleted scans, deleted flight plans and cancelled flight plans are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`tonic::Status`] with [`tonic::Code::InvalidArgument`] if the provided id can not be converted to a [`uuid::Uuid`].
+    /// Returns [`tonic::Status`] with [`tonic::Code::NotFound`] if no parcel exists for the provided id.
+    /// Returns [`tonic::Status`] with [`tonic::Code::Internal`] if any error is returned from a db call.
+    ///
+    /// # Examples
+    /// ```
+    /// use lib_common::grpc::get_endpoint_from_env;
+    /// use svc_storage_client_grpc::prelude::*;
+    /// use svc_storage_client_grpc::parcel::CustodyTimelineRequest;
+    ///
+    /// async fn example () -> Result<(), Box<dyn std::error::Error>> {
+    ///     let (host, port) = get_endpoint_from_env("SERVER_HOSTNAME", "SERVER_PORT_GRPC");
+    ///     let clients = Clients::new(host, port);
+    ///     let response = clients.parcel_custody
+    ///         .get_custody_timeline(CustodyTimelineRequest {
+    ///             parcel_id: String::from("53acfe06-dd9b-42e8-8cb4-12a2fb2fa693"),
+    ///         })
+    ///         .await?;
+    ///     println!("RESPONSE={:?}", response.into_inner());
+    ///     Ok(())
+    /// }
+    /// ```
+    async fn get_custody_timeline(
+        &self,
+        request: CustodyTimelineRequest,
+    ) -> Result<tonic::Response<CustodyTimelineResponse>, tonic::Status>;
+
+    /// Returns a [`tonic::Response`] containing a [`ReadyResponse`](crate::ReadyResponse)
+    /// Takes an [`ReadyRequest`](crate::ReadyRequest)
+    ///
+    /// # Errors
+    ///
+    /// Returns [`tonic::Status`] with [`tonic::Code::Unknown`] if the server is not ready.
+    ///
+    /// # Examples
+    /// ```
+    /// use lib_common::grpc::get_endpoint_from_env;
+    /// use svc_storage_client_grpc::prelude::*;
+    ///
+    /// async fn example () -> Result<(), Box<dyn std::error::Error>> {
+    ///     let (host, port) = get_endpoint_from_env("SERVER_HOSTNAME", "SERVER_PORT_GRPC");
+    ///     let clients = Clients::new(host, port);
+    ///     let response = clients.parcel_custody
+    ///         .is_ready(ReadyRequest {})
+    ///         .await?;
+    ///     println!("RESPONSE={:?}", response.into_inner());
+    ///     Ok(())
+    /// }
+    /// ```
+    async fn is_ready(
+        &self,
+        request: crate::ReadyRequest,
+    ) -> Result<tonic::Response<crate::ReadyResponse>, tonic::Status>;
+}
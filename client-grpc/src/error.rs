@@ -0,0 +1,53 @@
+//! Helpers to process errors returned by the storage service
+
+use crate::resources::{ErrorDetails, ErrorReason};
+use prost::Message;
+use tonic::Status;
+
+/// Decodes the structured [`ErrorDetails`] from the provided [`Status`].
+///
+/// Returns [`None`] if the [`Status`] doesn't contain any (valid) error details.
+pub fn get_error_details(status: &Status) -> Option<ErrorDetails> {
+    if status.details().is_empty() {
+        return None;
+    }
+    ErrorDetails::decode(status.details()).ok()
+}
+
+/// Returns the [`ErrorReason`] provided by the structured error details of the [`Status`].
+///
+/// Returns [`ErrorReason::Unspecified`] if the [`Status`] doesn't contain any (valid) error details.
+pub fn get_error_reason(status: &Status) -> ErrorReason {
+    get_error_details(status)
+        .and_then(|details| ErrorReason::try_from(details.reason).ok())
+        .unwrap_or(ErrorReason::Unspecified)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tonic::Code;
+
+    #[test]
+    fn test_get_error_details() {
+        let details = ErrorDetails {
+            reason: ErrorReason::UniqueViolation as i32,
+            message: String::from("duplicate key value violates unique constraint"),
+            sqlstate: Some(String::from("23505")),
+            table: Some(String::from("vehicle")),
+            column: None,
+            constraint: Some(String::from("vehicle_registration_number_key")),
+        };
+        let status = Status::with_details(
+            Code::AlreadyExists,
+            details.message.clone(),
+            details.encode_to_vec().into(),
+        );
+        assert_eq!(get_error_details(&status), Some(details));
+        assert_eq!(get_error_reason(&status), ErrorReason::UniqueViolation);
+
+        let status = Status::internal("error");
+        assert_eq!(get_error_details(&status), None);
+        assert_eq!(get_error_reason(&status), ErrorReason::Unspecified);
+    }
+}
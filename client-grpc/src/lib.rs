@@ -4,16 +4,26 @@
 /// macros module exposing gRPC include macro
 mod macros;
 
+#[cfg(feature = "parcel")]
+pub mod custody_service;
+pub mod error;
 pub mod link_service;
 pub mod resources;
+#[cfg(feature = "flight_plan")]
+pub mod scheduling_service;
 pub mod simple_service;
 pub mod simple_service_linked;
+#[cfg(feature = "adsb")]
+pub mod tracking_service;
 
 #[cfg(test)]
 mod enum_tests;
 
 /// Provide search helpers
 pub mod search {
+    #[cfg(any(feature = "stub_client", feature = "stub_server"))]
+    use crate::resources::grpc_geo_types::{GeoLineString, GeoPoint, GeoPolygon};
+
     include!("../includes/search.rs");
 }
 pub mod prelude;
@@ -405,7 +405,7 @@ macro_rules! link_grpc_client {
                                 grpc_error!("(get_linked MOCK) {}", error);
                                 return Err(tonic::Status::not_found(error));
                             }
-                            Ok(tonic::Response::new(Self::OtherList { list: other_resource_list }))
+                            Ok(tonic::Response::new(Self::OtherList { total_count: other_resource_list.len() as i64, list: other_resource_list, next_page_token: String::new(), distances: vec![] }))
                         },
                         _ => Err(tonic::Status::not_found("Not found")),
                     }
@@ -424,6 +424,652 @@ macro_rules! link_grpc_client {
     };
 }
 
+/// Generates Client implementation for scheduling gRPC clients
+#[cfg(not(feature = "stub_client"))]
+#[macro_export]
+macro_rules! scheduling_grpc_client {
+    ($rpc_scheduling_client:ident) => {
+        #[tonic::async_trait]
+        impl $crate::SchedulingClient<$rpc_scheduling_client<Channel>>
+            for GrpcClient<$rpc_scheduling_client<Channel>>
+        {
+            async fn check_vertipad_availability(
+                &self,
+                request: flight_plan::VertipadAvailabilityRequest,
+            ) -> Result<tonic::Response<flight_plan::VertipadAvailabilityResponse>, tonic::Status>
+            {
+                grpc_info!("(check_vertipad_availability) {} client.", self.get_name());
+                grpc_debug!("(check_vertipad_availability) request: {:?}", request);
+                self.get_client()
+                    .await?
+                    .check_vertipad_availability(request)
+                    .await
+            }
+
+            async fn get_committed_time_ranges(
+                &self,
+                request: flight_plan::CommittedTimeRangesRequest,
+            ) -> Result<tonic::Response<flight_plan::CommittedTimeRangesResponse>, tonic::Status>
+            {
+                grpc_info!("(get_committed_time_ranges) {} client.", self.get_name());
+                grpc_debug!("(get_committed_time_ranges) request: {:?}", request);
+                self.get_client()
+                    .await?
+                    .get_committed_time_ranges(request)
+                    .await
+            }
+
+            async fn find_conflicting_flight_plans(
+                &self,
+                request: flight_plan::ConflictingFlightPlansRequest,
+            ) -> Result<tonic::Response<flight_plan::ConflictingFlightPlansResponse>, tonic::Status>
+            {
+                grpc_info!("(find_conflicting_flight_plans) {} client.", self.get_name());
+                grpc_debug!("(find_conflicting_flight_plans) request: {:?}", request);
+                self.get_client()
+                    .await?
+                    .find_conflicting_flight_plans(request)
+                    .await
+            }
+
+            async fn transition_status(
+                &self,
+                request: flight_plan::TransitionStatusRequest,
+            ) -> Result<tonic::Response<flight_plan::Response>, tonic::Status> {
+                grpc_info!("(transition_status) {} client.", self.get_name());
+                grpc_debug!("(transition_status) request: {:?}", request);
+                self.get_client().await?.transition_status(request).await
+            }
+
+            async fn is_ready(
+                &self,
+                request: $crate::ReadyRequest,
+            ) -> Result<tonic::Response<$crate::ReadyResponse>, tonic::Status> {
+                grpc_info!("(is_ready) {} client.", self.get_name());
+                grpc_debug!("(is_ready) request: {:?}", request);
+                self.get_client().await?.is_ready(request).await
+            }
+        }
+    };
+}
+
+/// Generates Client implementation for scheduling gRPC MOCK clients
+#[cfg(feature = "stub_client")]
+#[macro_export]
+macro_rules! scheduling_grpc_client {
+    ($rpc_scheduling_client:ident) => {
+        #[tonic::async_trait]
+        impl $crate::SchedulingClient<$rpc_scheduling_client<Channel>>
+            for GrpcClient<$rpc_scheduling_client<Channel>>
+        {
+            async fn check_vertipad_availability(
+                &self,
+                request: flight_plan::VertipadAvailabilityRequest,
+            ) -> Result<tonic::Response<flight_plan::VertipadAvailabilityResponse>, tonic::Status>
+            {
+                grpc_warn!(
+                    "(check_vertipad_availability MOCK) {} client.",
+                    self.get_name()
+                );
+                grpc_debug!("(check_vertipad_availability MOCK) request: {:?}", request);
+                let (start, end) = match (request.timeslot_start, request.timeslot_end) {
+                    (Some(start), Some(end)) => {
+                        ((start.seconds, start.nanos), (end.seconds, end.nanos))
+                    }
+                    _ => {
+                        return Err(tonic::Status::invalid_argument(
+                            "Both [timeslot_start] and [timeslot_end] should be provided.",
+                        ))
+                    }
+                };
+                if end <= start {
+                    return Err(tonic::Status::invalid_argument(
+                        "[timeslot_end] should be after [timeslot_start].",
+                    ));
+                }
+
+                let overlaps =
+                    |id: &str,
+                     slot_start: &Option<prost_wkt_types::Timestamp>,
+                     slot_end: &Option<prost_wkt_types::Timestamp>| {
+                        match (slot_start, slot_end) {
+                            (Some(slot_start), Some(slot_end)) => {
+                                id == request.vertipad_id
+                                    && (slot_start.seconds, slot_start.nanos) < end
+                                    && (slot_end.seconds, slot_end.nanos) > start
+                            }
+                            _ => false,
+                        }
+                    };
+
+                let conflicting_flight_plan_ids: Vec<String> = flight_plan::MEM_DATA
+                    .lock()
+                    .await
+                    .iter()
+                    .filter(|object| Some(&object.id) != request.exclude_flight_plan_id.as_ref())
+                    .filter(|object| match &object.data {
+                        Some(data) => {
+                            data.flight_status != flight_plan::FlightStatus::Cancelled as i32
+                                && (overlaps(
+                                    &data.origin_vertipad_id,
+                                    &data.origin_timeslot_start,
+                                    &data.origin_timeslot_end,
+                                ) || overlaps(
+                                    &data.target_vertipad_id,
+                                    &data.target_timeslot_start,
+                                    &data.target_timeslot_end,
+                                ))
+                        }
+                        None => false,
+                    })
+                    .map(|object| object.id.clone())
+                    .collect();
+
+                Ok(tonic::Response::new(
+                    flight_plan::VertipadAvailabilityResponse {
+                        available: conflicting_flight_plan_ids.is_empty(),
+                        conflicting_flight_plan_ids,
+                    },
+                ))
+            }
+
+            async fn get_committed_time_ranges(
+                &self,
+                request: flight_plan::CommittedTimeRangesRequest,
+            ) -> Result<tonic::Response<flight_plan::CommittedTimeRangesResponse>, tonic::Status>
+            {
+                grpc_warn!(
+                    "(get_committed_time_ranges MOCK) {} client.",
+                    self.get_name()
+                );
+                grpc_debug!("(get_committed_time_ranges MOCK) request: {:?}", request);
+                let asset_type = flight_plan::AssetType::try_from(request.asset_type).map_err(|_| {
+                    tonic::Status::invalid_argument("Unknown [asset_type] provided.")
+                })?;
+                let (window_start, window_end) = match (request.window_start, request.window_end) {
+                    (Some(start), Some(end)) => {
+                        ((start.seconds, start.nanos), (end.seconds, end.nanos))
+                    }
+                    _ => {
+                        return Err(tonic::Status::invalid_argument(
+                            "Both [window_start] and [window_end] should be provided.",
+                        ))
+                    }
+                };
+                if window_end <= window_start {
+                    return Err(tonic::Status::invalid_argument(
+                        "[window_end] should be after [window_start].",
+                    ));
+                }
+
+                let mut list: Vec<flight_plan::CommittedTimeRange> = flight_plan::MEM_DATA
+                    .lock()
+                    .await
+                    .iter()
+                    .filter_map(|object| {
+                        let data = object.data.as_ref()?;
+                        if data.flight_status == flight_plan::FlightStatus::Cancelled as i32
+                            || data.flight_status == flight_plan::FlightStatus::Finished as i32
+                        {
+                            return None;
+                        }
+                        let asset_id = match asset_type {
+                            flight_plan::AssetType::Vehicle => &data.vehicle_id,
+                            flight_plan::AssetType::Pilot => &data.pilot_id,
+                        };
+                        let start = data.origin_timeslot_start.clone()?;
+                        let end = data.target_timeslot_end.clone()?;
+                        if *asset_id != request.asset_id
+                            || (start.seconds, start.nanos) >= window_end
+                            || (end.seconds, end.nanos) <= window_start
+                        {
+                            return None;
+                        }
+                        Some(flight_plan::CommittedTimeRange {
+                            flight_plan_id: object.id.clone(),
+                            start: Some(start),
+                            end: Some(end),
+                        })
+                    })
+                    .collect();
+                list.sort_by_key(|range| range.start.clone().map(|start| (start.seconds, start.nanos)));
+
+                Ok(tonic::Response::new(
+                    flight_plan::CommittedTimeRangesResponse { list },
+                ))
+            }
+
+            async fn find_conflicting_flight_plans(
+                &self,
+                request: flight_plan::ConflictingFlightPlansRequest,
+            ) -> Result<tonic::Response<flight_plan::ConflictingFlightPlansResponse>, tonic::Status>
+            {
+                grpc_warn!(
+                    "(find_conflicting_flight_plans MOCK) {} client.",
+                    self.get_name()
+                );
+                grpc_debug!("(find_conflicting_flight_plans MOCK) request: {:?}", request);
+                let path = match request.path {
+                    Some(path) if !path.points.is_empty() => path,
+                    _ => {
+                        return Err(tonic::Status::invalid_argument(
+                            "A [path] with at least 1 point should be provided.",
+                        ))
+                    }
+                };
+                let (start, end) = match (request.window_start, request.window_end) {
+                    (Some(start), Some(end)) => (start, end),
+                    _ => {
+                        return Err(tonic::Status::invalid_argument(
+                            "Both [window_start] and [window_end] should be provided.",
+                        ))
+                    }
+                };
+                if (end.seconds, end.nanos) <= (start.seconds, start.nanos) {
+                    return Err(tonic::Status::invalid_argument(
+                        "[window_end] should be after [window_start].",
+                    ));
+                }
+                if request.min_separation_meters.is_nan() || request.min_separation_meters < 0.0 {
+                    return Err(tonic::Status::invalid_argument(
+                        "[min_separation_meters] should not be negative.",
+                    ));
+                }
+
+                let mut list: Vec<flight_plan::ConflictingFlightPlan> = flight_plan::MEM_DATA
+                    .lock()
+                    .await
+                    .iter()
+                    .filter(|object| Some(&object.id) != request.exclude_flight_plan_id.as_ref())
+                    .filter_map(|object| {
+                        let data = object.data.as_ref()?;
+                        if data.flight_status == flight_plan::FlightStatus::Cancelled as i32 {
+                            return None;
+                        }
+                        let active_start = data.origin_timeslot_start.clone()?;
+                        let active_end = data.target_timeslot_end.clone()?;
+                        if (active_start.seconds, active_start.nanos) >= (end.seconds, end.nanos)
+                            || (active_end.seconds, active_end.nanos) <= (start.seconds, start.nanos)
+                        {
+                            return None;
+                        }
+                        let (own, other) =
+                            path.get_concurrent_sections(data.path.as_ref()?, &start, &end)?;
+                        let distance_meters = own.get_min_point_distance(&other)?;
+                        if distance_meters > request.min_separation_meters {
+                            return None;
+                        }
+                        Some(flight_plan::ConflictingFlightPlan {
+                            flight_plan: Some(object.clone()),
+                            distance_meters,
+                        })
+                    })
+                    .collect();
+                list.sort_by(|a, b| a.distance_meters.total_cmp(&b.distance_meters));
+
+                Ok(tonic::Response::new(
+                    flight_plan::ConflictingFlightPlansResponse { list },
+                ))
+            }
+
+            async fn transition_status(
+                &self,
+                request: flight_plan::TransitionStatusRequest,
+            ) -> Result<tonic::Response<flight_plan::Response>, tonic::Status> {
+                grpc_warn!("(transition_status MOCK) {} client.", self.get_name());
+                grpc_debug!("(transition_status MOCK) request: {:?}", request);
+                let flight_status = flight_plan::FlightStatus::try_from(request.flight_status)
+                    .map_err(|_| tonic::Status::invalid_argument("Unknown [flight_status] provided."))?;
+
+                let mut mem_data = flight_plan::MEM_DATA.lock().await;
+                let object = match mem_data.iter_mut().find(|object| object.id == request.id) {
+                    Some(object) => object,
+                    None => {
+                        return Err(tonic::Status::not_found(format!(
+                            "No [flight_plan] record found for id [{}].",
+                            request.id
+                        )))
+                    }
+                };
+                let data = match object.data.as_mut() {
+                    Some(data) => data,
+                    None => return Err(tonic::Status::internal("No data found for flight_plan.")),
+                };
+
+                let current = flight_plan::FlightStatus::try_from(data.flight_status)
+                    .unwrap_or(flight_plan::FlightStatus::Draft);
+                if !current.can_transition_to(flight_status) {
+                    return Ok(tonic::Response::new(flight_plan::Response {
+                        validation_result: Some($crate::ValidationResult {
+                            success: false,
+                            errors: vec![$crate::ValidationError {
+                                field: String::from("flight_status"),
+                                error: format!(
+                                    "Invalid [flight_status] transition from [{}] to [{}]",
+                                    current.as_str_name(),
+                                    flight_status.as_str_name()
+                                ),
+                            }],
+                        }),
+                        object: None,
+                    }));
+                }
+
+                if current != flight_status {
+                    match flight_status {
+                        flight_plan::FlightStatus::InFlight if data.actual_departure_time.is_none() => {
+                            data.actual_departure_time = Some(std::time::SystemTime::now().into())
+                        }
+                        flight_plan::FlightStatus::Finished if data.actual_arrival_time.is_none() => {
+                            data.actual_arrival_time = Some(std::time::SystemTime::now().into())
+                        }
+                        _ => {}
+                    }
+                    data.flight_status = flight_status as i32;
+                }
+
+                Ok(tonic::Response::new(flight_plan::Response {
+                    validation_result: Some($crate::ValidationResult {
+                        success: true,
+                        errors: vec![],
+                    }),
+                    object: Some(object.clone()),
+                }))
+            }
+
+            async fn is_ready(
+                &self,
+                request: $crate::ReadyRequest,
+            ) -> Result<tonic::Response<$crate::ReadyResponse>, tonic::Status> {
+                grpc_warn!("(is_ready MOCK) {} client.", self.get_name());
+                grpc_debug!("(is_ready MOCK) request: {:?}", request);
+                Ok(tonic::Response::new($crate::ReadyResponse { ready: true }))
+            }
+        }
+    };
+}
+
+/// Generates Client implementation for tracking gRPC clients
+#[cfg(not(feature = "stub_client"))]
+#[macro_export]
+macro_rules! tracking_grpc_client {
+    ($rpc_tracking_client:ident) => {
+        #[tonic::async_trait]
+        impl $crate::TrackingClient<$rpc_tracking_client<Channel>>
+            for GrpcClient<$rpc_tracking_client<Channel>>
+        {
+            async fn get_latest_positions(
+                &self,
+                request: adsb::LatestPositionsRequest,
+            ) -> Result<tonic::Response<adsb::LatestPositionsResponse>, tonic::Status> {
+                grpc_info!("(get_latest_positions) {} client.", self.get_name());
+                grpc_debug!("(get_latest_positions) request: {:?}", request);
+                self.get_client().await?.get_latest_positions(request).await
+            }
+
+            async fn is_ready(
+                &self,
+                request: $crate::ReadyRequest,
+            ) -> Result<tonic::Response<$crate::ReadyResponse>, tonic::Status> {
+                grpc_info!("(is_ready) {} client.", self.get_name());
+                grpc_debug!("(is_ready) request: {:?}", request);
+                self.get_client().await?.is_ready(request).await
+            }
+        }
+    };
+}
+
+/// Generates Client implementation for tracking gRPC MOCK clients
+#[cfg(feature = "stub_client")]
+#[macro_export]
+macro_rules! tracking_grpc_client {
+    ($rpc_tracking_client:ident) => {
+        #[tonic::async_trait]
+        impl $crate::TrackingClient<$rpc_tracking_client<Channel>>
+            for GrpcClient<$rpc_tracking_client<Channel>>
+        {
+            async fn get_latest_positions(
+                &self,
+                request: adsb::LatestPositionsRequest,
+            ) -> Result<tonic::Response<adsb::LatestPositionsResponse>, tonic::Status> {
+                grpc_warn!("(get_latest_positions MOCK) {} client.", self.get_name());
+                grpc_debug!("(get_latest_positions MOCK) request: {:?}", request);
+                if let Some(bounds) = &request.bounds {
+                    let closed = match &bounds.exterior {
+                        Some(exterior) if exterior.points.len() > 2 => {
+                            exterior.points.first() == exterior.points.last()
+                        }
+                        _ => false,
+                    };
+                    if !closed {
+                        return Err(tonic::Status::invalid_argument(
+                            "The provided [bounds] should be a closed loop of at least 3 points.",
+                        ));
+                    }
+                }
+                // Defaults to a maximum age of 300 seconds, like the server
+                let max_age = request.max_age_seconds.unwrap_or(300);
+                let since: prost_wkt_types::Timestamp = (std::time::SystemTime::now()
+                    - std::time::Duration::from_secs(max_age.into()))
+                .into();
+                let since = (since.seconds, since.nanos);
+
+                let timestamp = |object: &adsb::Object| -> Option<(i64, i32)> {
+                    let timestamp = object.data.as_ref()?.network_timestamp.clone()?;
+                    Some((timestamp.seconds, timestamp.nanos))
+                };
+                let mut latest: std::collections::BTreeMap<i64, (adsb::Object, Option<adsb::Object>)> =
+                    std::collections::BTreeMap::new();
+                for object in adsb::MEM_DATA.lock().await.iter() {
+                    let (data, received) = match (object.data.as_ref(), timestamp(object)) {
+                        (Some(data), Some(received)) => (data, received),
+                        _ => continue,
+                    };
+                    if received < since {
+                        continue;
+                    }
+                    let entry = latest
+                        .entry(data.icao_address)
+                        .or_insert_with(|| (object.clone(), None));
+                    if timestamp(&entry.0) < Some(received) {
+                        entry.0 = object.clone();
+                    }
+                    if data.position.is_some() && entry.1.as_ref().and_then(timestamp) < Some(received) {
+                        entry.1 = Some(object.clone());
+                    }
+                }
+
+                let list: Vec<adsb::LatestPosition> = latest
+                    .into_iter()
+                    .filter_map(|(icao_address, (object, positioned))| {
+                        let positioned = positioned.and_then(|object| object.data);
+                        let position = positioned.as_ref().and_then(|data| data.position);
+                        if let Some(bounds) = &request.bounds {
+                            if !bounds.contains_point(&position?) {
+                                return None;
+                            }
+                        }
+                        Some(adsb::LatestPosition {
+                            icao_address,
+                            latest: Some(object),
+                            position,
+                            position_timestamp: positioned
+                                .as_ref()
+                                .and_then(|data| data.network_timestamp.clone()),
+                            baro_altitude: positioned.and_then(|data| data.baro_altitude),
+                        })
+                    })
+                    .collect();
+
+                Ok(tonic::Response::new(adsb::LatestPositionsResponse { list }))
+            }
+
+            async fn is_ready(
+                &self,
+                request: $crate::ReadyRequest,
+            ) -> Result<tonic::Response<$crate::ReadyResponse>, tonic::Status> {
+                grpc_warn!("(is_ready MOCK) {} client.", self.get_name());
+                grpc_debug!("(is_ready MOCK) request: {:?}", request);
+                Ok(tonic::Response::new($crate::ReadyResponse { ready: true }))
+            }
+        }
+    };
+}
+
+/// Generates Client implementation for custody gRPC clients
+#[cfg(not(feature = "stub_client"))]
+#[macro_export]
+macro_rules! custody_grpc_client {
+    ($rpc_custody_client:ident) => {
+        #[tonic::async_trait]
+        impl $crate::CustodyClient<$rpc_custody_client<Channel>>
+            for GrpcClient<$rpc_custody_client<Channel>>
+        {
+            async fn get_custody_timeline(
+                &self,
+                request: parcel::CustodyTimelineRequest,
+            ) -> Result<tonic::Response<parcel::CustodyTimelineResponse>, tonic::Status> {
+                grpc_info!("(get_custody_timeline) {} client.", self.get_name());
+                grpc_debug!("(get_custody_timeline) request: {:?}", request);
+                self.get_client().await?.get_custody_timeline(request).await
+            }
+
+            async fn is_ready(
+                &self,
+                request: $crate::ReadyRequest,
+            ) -> Result<tonic::Response<$crate::ReadyResponse>, tonic::Status> {
+                grpc_info!("(is_ready) {} client.", self.get_name());
+                grpc_debug!("(is_ready) request: {:?}", request);
+                self.get_client().await?.is_ready(request).await
+            }
+        }
+    };
+}
+
+/// Generates Client implementation for custody gRPC MOCK clients
+#[cfg(feature = "stub_client")]
+#[macro_export]
+macro_rules! custody_grpc_client {
+    ($rpc_custody_client:ident) => {
+        #[tonic::async_trait]
+        impl $crate::CustodyClient<$rpc_custody_client<Channel>>
+            for GrpcClient<$rpc_custody_client<Channel>>
+        {
+            async fn get_custody_timeline(
+                &self,
+                request: parcel::CustodyTimelineRequest,
+            ) -> Result<tonic::Response<parcel::CustodyTimelineResponse>, tonic::Status> {
+                grpc_warn!("(get_custody_timeline MOCK) {} client.", self.get_name());
+                grpc_debug!("(get_custody_timeline MOCK) request: {:?}", request);
+                let parcel_id = request.parcel_id;
+                let status = match parcel::MEM_DATA
+                    .lock()
+                    .await
+                    .iter()
+                    .find(|object| object.id == parcel_id)
+                {
+                    Some(object) => object.data.as_ref().map_or(0, |data| data.status),
+                    None => {
+                        return Err(tonic::Status::not_found(format!(
+                            "No [parcel] found for specified uuid: {}",
+                            parcel_id
+                        )))
+                    }
+                };
+
+                let mut list: Vec<parcel::CustodyEvent> = parcel_scan::MEM_DATA
+                    .lock()
+                    .await
+                    .iter()
+                    .filter_map(|object| {
+                        let data = object.data.as_ref()?;
+                        if data.parcel_id != parcel_id {
+                            return None;
+                        }
+                        Some(parcel::CustodyEvent {
+                            event_type: parcel::CustodyEventType::Scan as i32,
+                            timestamp: data.created_at.clone(),
+                            scan: Some(parcel::CustodyScan {
+                                parcel_scan_id: object.id.clone(),
+                                scanner_id: data.scanner_id.clone(),
+                                geo_location: data.geo_location,
+                            }),
+                            flight_leg: None,
+                            status_change: None,
+                        })
+                    })
+                    .collect();
+
+                let flight_plans = flight_plan::MEM_DATA.lock().await;
+                for link in flight_plan_parcel::MEM_DATA
+                    .lock()
+                    .await
+                    .iter()
+                    .filter(|link| link.parcel_id == parcel_id)
+                {
+                    let data = match flight_plans
+                        .iter()
+                        .find(|object| object.id == link.flight_plan_id)
+                        .and_then(|object| object.data.as_ref())
+                    {
+                        Some(data) => data,
+                        None => continue,
+                    };
+                    if data.flight_status == flight_plan::FlightStatus::Cancelled as i32 {
+                        continue;
+                    }
+                    let departure = data
+                        .actual_departure_time
+                        .clone()
+                        .or(data.origin_timeslot_start.clone());
+                    let arrival = data
+                        .actual_arrival_time
+                        .clone()
+                        .or(data.target_timeslot_end.clone());
+                    list.push(parcel::CustodyEvent {
+                        event_type: parcel::CustodyEventType::FlightLeg as i32,
+                        timestamp: departure.clone(),
+                        scan: None,
+                        flight_leg: Some(parcel::CustodyFlightLeg {
+                            flight_plan_id: link.flight_plan_id.clone(),
+                            acquire: link.acquire,
+                            deliver: link.deliver,
+                            origin_vertipad_id: data.origin_vertipad_id.clone(),
+                            target_vertipad_id: data.target_vertipad_id.clone(),
+                            departure,
+                            arrival,
+                            departed: data.actual_departure_time.is_some(),
+                            arrived: data.actual_arrival_time.is_some(),
+                        }),
+                        status_change: None,
+                    });
+                }
+                list.sort_by_key(|event| {
+                    event
+                        .timestamp
+                        .clone()
+                        .map(|timestamp| (timestamp.seconds, timestamp.nanos))
+                });
+
+                Ok(tonic::Response::new(parcel::CustodyTimelineResponse {
+                    parcel_id,
+                    status,
+                    list,
+                }))
+            }
+
+            async fn is_ready(
+                &self,
+                request: $crate::ReadyRequest,
+            ) -> Result<tonic::Response<$crate::ReadyResponse>, tonic::Status> {
+                grpc_warn!("(is_ready MOCK) {} client.", self.get_name());
+                grpc_debug!("(is_ready MOCK) request: {:?}", request);
+                Ok(tonic::Response::new($crate::ReadyResponse { ready: true }))
+            }
+        }
+    };
+}
+
 /// Generates Client implementation for simple gRPC clients
 #[cfg(not(feature = "stub_client"))]
 #[macro_export]
@@ -437,6 +1083,10 @@ macro_rules! simple_grpc_client {
                 type UpdateObject = $resource::UpdateObject;
                 type List = $resource::List;
                 type Response = $resource::Response;
+                type InsertManyRequest = $resource::InsertManyRequest;
+                type UpdateManyRequest = $resource::UpdateManyRequest;
+                type ResponseList = $resource::ResponseList;
+                type ChangeEvent = $resource::ChangeEvent;
 
                 async fn get_by_id(
                     &self,
@@ -447,6 +1097,15 @@ macro_rules! simple_grpc_client {
                     self.get_client().await?.get_by_id(request).await
                 }
 
+                async fn get_by_id_include_deleted(
+                    &self,
+                    request: $crate::Id,
+                ) -> Result<tonic::Response<Self::Object>, tonic::Status> {
+                    grpc_info!("(get_by_id_include_deleted) {} client.", self.get_name());
+                    grpc_debug!("(get_by_id_include_deleted) request: {:?}", request);
+                    self.get_client().await?.get_by_id_include_deleted(request).await
+                }
+
                 async fn search(
                     &self,
                     request: $crate::AdvancedSearchFilter,
@@ -456,6 +1115,35 @@ macro_rules! simple_grpc_client {
                     self.get_client().await?.search(request).await
                 }
 
+                async fn search_stream(
+                    &self,
+                    request: $crate::AdvancedSearchFilter,
+                ) -> Result<tonic::Response<tonic::codegen::BoxStream<Self::Object>>, tonic::Status> {
+                    grpc_info!("(search_stream) {} client.", self.get_name());
+                    grpc_debug!("(search_stream) request: {:?}", request);
+                    let response = self.get_client().await?.search_stream(request).await?;
+                    Ok(response.map(|stream| -> tonic::codegen::BoxStream<Self::Object> { Box::pin(stream) }))
+                }
+
+                async fn watch(
+                    &self,
+                    request: $crate::AdvancedSearchFilter,
+                ) -> Result<tonic::Response<tonic::codegen::BoxStream<Self::ChangeEvent>>, tonic::Status> {
+                    grpc_info!("(watch) {} client.", self.get_name());
+                    grpc_debug!("(watch) request: {:?}", request);
+                    let response = self.get_client().await?.watch(request).await?;
+                    Ok(response.map(|stream| -> tonic::codegen::BoxStream<Self::ChangeEvent> { Box::pin(stream) }))
+                }
+
+                async fn get_health(
+                    &self,
+                    request: $crate::ReadyRequest,
+                ) -> Result<tonic::Response<$crate::HealthResponse>, tonic::Status> {
+                    grpc_info!("(get_health) {} client.", self.get_name());
+                    grpc_debug!("(get_health) request: {:?}", request);
+                    self.get_client().await?.get_health(request).await
+                }
+
                 async fn insert(
                     &self,
                     request: Self::Data,
@@ -482,6 +1170,49 @@ macro_rules! simple_grpc_client {
                     grpc_debug!("(delete) request: {:?}", request);
                     self.get_client().await?.delete(request).await
                 }
+                async fn restore(
+                    &self,
+                    request: $crate::Id,
+                ) -> Result<tonic::Response<()>, tonic::Status> {
+                    grpc_info!("(restore) {} client.", self.get_name());
+                    grpc_debug!("(restore) request: {:?}", request);
+                    self.get_client().await?.restore(request).await
+                }
+                async fn purge(
+                    &self,
+                    request: $crate::Id,
+                ) -> Result<tonic::Response<()>, tonic::Status> {
+                    grpc_info!("(purge) {} client.", self.get_name());
+                    grpc_debug!("(purge) request: {:?}", request);
+                    self.get_client().await?.purge(request).await
+                }
+
+                async fn insert_many(
+                    &self,
+                    request: Self::InsertManyRequest,
+                ) -> Result<tonic::Response<Self::ResponseList>, tonic::Status> {
+                    grpc_info!("(insert_many) {} client.", self.get_name());
+                    grpc_debug!("(insert_many) request: {:?}", request);
+                    self.get_client().await?.insert_many(request).await
+                }
+
+                async fn update_many(
+                    &self,
+                    request: Self::UpdateManyRequest,
+                ) -> Result<tonic::Response<Self::ResponseList>, tonic::Status> {
+                    grpc_info!("(update_many) {} client.", self.get_name());
+                    grpc_debug!("(update_many) request: {:?}", request);
+                    self.get_client().await?.update_many(request).await
+                }
+
+                async fn delete_many(
+                    &self,
+                    request: $crate::DeleteManyRequest,
+                ) -> Result<tonic::Response<$crate::DeleteManyResponse>, tonic::Status> {
+                    grpc_info!("(delete_many) {} client.", self.get_name());
+                    grpc_debug!("(delete_many) request: {:?}", request);
+                    self.get_client().await?.delete_many(request).await
+                }
 
                 async fn is_ready(
                     &self,
@@ -502,6 +1233,8 @@ macro_rules! simple_grpc_client {
 macro_rules! simple_grpc_client {
     ($($resource:tt),+) => {
         $(
+            // Not all resource responses have additional fields
+            #[allow(clippy::needless_update)]
             #[tonic::async_trait]
             impl $crate::SimpleClient<$resource::RpcServiceClient<Channel>> for GrpcClient<$resource::RpcServiceClient<Channel>> {
                 type Data = $resource::Data;
@@ -509,6 +1242,10 @@ macro_rules! simple_grpc_client {
                 type UpdateObject = $resource::UpdateObject;
                 type List = $resource::List;
                 type Response = $resource::Response;
+                type InsertManyRequest = $resource::InsertManyRequest;
+                type UpdateManyRequest = $resource::UpdateManyRequest;
+                type ResponseList = $resource::ResponseList;
+                type ChangeEvent = $resource::ChangeEvent;
 
                 async fn get_by_id(
                     &self,
@@ -532,6 +1269,15 @@ macro_rules! simple_grpc_client {
                     Ok(tonic::Response::new(resource_list[0].clone()))
                 }
 
+                async fn get_by_id_include_deleted(
+                    &self,
+                    request: $crate::Id,
+                ) -> Result<tonic::Response<Self::Object>, tonic::Status> {
+                    grpc_warn!("(get_by_id_include_deleted MOCK) {} client.", self.get_name());
+                    // Deleted objects are removed from memory, so this is the same as get_by_id
+                    self.get_by_id(request).await
+                }
+
                 async fn search(
                     &self,
                     request: $crate::AdvancedSearchFilter,
@@ -544,7 +1290,10 @@ macro_rules! simple_grpc_client {
                     if filters.len() == 0 {
                         grpc_debug!("(search MOCK) no filters provided, returning all.");
                         return Ok(tonic::Response::new(Self::List {
-                            list
+                            total_count: list.len() as i64,
+                            list,
+                            next_page_token: String::new(),
+                            distances: vec![],
                         }));
                     }
 
@@ -554,45 +1303,8 @@ macro_rules! simple_grpc_client {
                     }
                     grpc_debug!("(search MOCK) unfiltered serialized objects: {:?}", unfiltered);
 
-                    let mut collected: Vec<serde_json::Value> = vec![];
-                    for filter in filters {
-                        let operator: PredicateOperator =
-                            match PredicateOperator::try_from(filter.predicate_operator) {
-                                Ok(val) => val,
-                                Err(e) => {
-                                    return Err(tonic::Status::internal(format!(
-                                        "Can't convert i32 [{}] into PredicateOperator Enum value: {}",
-                                        filter.predicate_operator, e
-                                    )));
-                                }
-                            };
-
-
-                        match filter.comparison_operator {
-                            Some(comparison_operator) => match ComparisonOperator::try_from(comparison_operator) {
-                                Ok(comparison_operator) => match comparison_operator {
-                                    ComparisonOperator::And => {
-                                        let unfiltered = collected.clone();
-                                        collected = vec![];
-                                        $crate::search::filter_for_operator(&filter.search_field, &filter.search_value, &unfiltered, &mut collected, operator)
-                                            .map_err(|e| tonic::Status::internal(format!("Could not get filtered values for provided filter: {}", e)))?
-                                    }
-                                    ComparisonOperator::Or => {
-                                        $crate::search::filter_for_operator(&filter.search_field, &filter.search_value, &unfiltered, &mut collected, operator)
-                                            .map_err(|e| tonic::Status::internal(format!("Could not get filtered values for provided filter: {}", e)))?
-                                    }
-                                }
-                                Err(e) => {
-                                    return Err(tonic::Status::internal(format!(
-                                        "Can't convert i32 [{}] into ComparisonOperator Enum value: {}",
-                                        comparison_operator, e
-                                    )));
-                                }
-                            },
-                            None => $crate::search::filter_for_operator(&filter.search_field, &filter.search_value, &unfiltered, &mut collected, operator)
-                                .map_err(|e| tonic::Status::internal(format!("Could not get filtered values for provided filter: {}", e)))?
-                        };
-                    }
+                    let collected: Vec<serde_json::Value> = $crate::search::filter_for_options(&filters, &unfiltered)
+                        .map_err(|e| tonic::Status::internal(format!("Could not get filtered values for provided filter: {}", e)))?;
                     let mut filtered: Vec<Self::Object> = vec![];
                     for val in collected.iter() {
                         filtered.push(
@@ -601,11 +1313,56 @@ macro_rules! simple_grpc_client {
                         );
                     }
                     let response = Self::List {
-                        list: filtered
+                        total_count: filtered.len() as i64,
+                        list: filtered,
+                        next_page_token: String::new(),
+                        distances: vec![],
                     };
                     Ok(tonic::Response::new(response))
                 }
 
+                async fn search_stream(
+                    &self,
+                    request: $crate::AdvancedSearchFilter,
+                ) -> Result<tonic::Response<tonic::codegen::BoxStream<Self::Object>>, tonic::Status> {
+                    grpc_warn!("(search_stream MOCK) {} client.", self.get_name());
+                    grpc_debug!("(search_stream MOCK) request: {:?}", request);
+                    let list = self.search(request).await?.into_inner().list;
+                    Ok(tonic::Response::new(Box::pin(futures::stream::iter(list.into_iter().map(Ok)))))
+                }
+
+                async fn watch(
+                    &self,
+                    request: $crate::AdvancedSearchFilter,
+                ) -> Result<tonic::Response<tonic::codegen::BoxStream<Self::ChangeEvent>>, tonic::Status> {
+                    grpc_warn!("(watch MOCK) {} client.", self.get_name());
+                    grpc_debug!("(watch MOCK) request: {:?}", request);
+                    // There is no change log for the in memory data, so a `CREATED` event will be
+                    // sent for each matching object before closing the stream.
+                    let list = self.search(request).await?.into_inner().list;
+                    let events = list.into_iter().enumerate().map(|(index, object)| {
+                        Ok(Self::ChangeEvent {
+                            sequence: index as i64 + 1,
+                            change_type: $crate::ChangeType::Created as i32,
+                            id: object.id.clone(),
+                            object: Some(object),
+                        })
+                    });
+                    Ok(tonic::Response::new(Box::pin(futures::stream::iter(events))))
+                }
+
+                async fn get_health(
+                    &self,
+                    request: $crate::ReadyRequest,
+                ) -> Result<tonic::Response<$crate::HealthResponse>, tonic::Status> {
+                    grpc_warn!("(get_health MOCK) {} client.", self.get_name());
+                    grpc_debug!("(get_health MOCK) request: {:?}", request);
+                    Ok(tonic::Response::new($crate::HealthResponse {
+                        ready: true,
+                        ..Default::default()
+                    }))
+                }
+
                 async fn insert(
                     &self,
                     request: Self::Data,
@@ -622,7 +1379,8 @@ macro_rules! simple_grpc_client {
                         validation_result: Some(super::ValidationResult {
                             success: true,
                             errors: Vec::new()
-                        })
+                        }),
+                        ..Default::default()
                     };
                     mem_data.push(object.clone());
                     Ok(tonic::Response::new(response))
@@ -650,6 +1408,7 @@ macro_rules! simple_grpc_client {
                                     success: true,
                                     errors: Vec::new(),
                                 }),
+                                ..Default::default()
                             };
 
                             return Ok(tonic::Response::new(response));
@@ -670,6 +1429,135 @@ macro_rules! simple_grpc_client {
                     Ok(tonic::Response::new(()))
                 }
 
+                async fn restore(
+                    &self,
+                    request: $crate::Id,
+                ) -> Result<tonic::Response<()>, tonic::Status> {
+                    grpc_warn!("(restore MOCK) {} client.", self.get_name());
+                    grpc_debug!("(restore MOCK) request: {:?}", request);
+                    // Deleted objects are removed from memory, so there is nothing to restore
+                    Ok(tonic::Response::new(()))
+                }
+
+                async fn purge(
+                    &self,
+                    request: $crate::Id,
+                ) -> Result<tonic::Response<()>, tonic::Status> {
+                    grpc_warn!("(purge MOCK) {} client.", self.get_name());
+                    grpc_debug!("(purge MOCK) request: {:?}", request);
+                    let id = request.id;
+                    let mut list = $resource::MEM_DATA.lock().await;
+                    list.retain(|object| object.id != id);
+                    Ok(tonic::Response::new(()))
+                }
+
+                async fn insert_many(
+                    &self,
+                    request: Self::InsertManyRequest,
+                ) -> Result<tonic::Response<Self::ResponseList>, tonic::Status> {
+                    grpc_warn!("(insert_many MOCK) {} client.", self.get_name());
+                    grpc_debug!("(insert_many MOCK) request: {:?}", request);
+                    let mut mem_data = $resource::MEM_DATA.lock().await;
+                    let mut list: Vec<Self::Response> = vec![];
+                    for data in request.list {
+                        let object = Self::Object {
+                            id: uuid::Uuid::new_v4().to_string(),
+                            data: Some(data),
+                        };
+                        mem_data.push(object.clone());
+                        list.push(Self::Response {
+                            object: Some(object),
+                            validation_result: Some(super::ValidationResult {
+                                success: true,
+                                errors: Vec::new()
+                            }),
+                            ..Default::default()
+                        });
+                    }
+                    Ok(tonic::Response::new(Self::ResponseList { success: true, list }))
+                }
+
+                async fn update_many(
+                    &self,
+                    request: Self::UpdateManyRequest,
+                ) -> Result<tonic::Response<Self::ResponseList>, tonic::Status> {
+                    grpc_warn!("(update_many MOCK) {} client.", self.get_name());
+                    grpc_debug!("(update_many MOCK) request: {:?}", request);
+                    let mut mem_data = $resource::MEM_DATA.lock().await;
+                    let mut updated = mem_data.clone();
+                    let mut list: Vec<Self::Response> = vec![];
+                    let mut success = true;
+                    for update in request.list {
+                        match updated.iter_mut().find(|object| object.id == update.id) {
+                            Some(object) if update.data.is_some() => {
+                                object.data = update.data;
+                                list.push(Self::Response {
+                                    object: Some(object.clone()),
+                                    validation_result: Some(super::ValidationResult {
+                                        success: true,
+                                        errors: Vec::new(),
+                                    }),
+                                    ..Default::default()
+                                });
+                            }
+                            _ => {
+                                success = false;
+                                list.push(Self::Response {
+                                    object: None,
+                                    validation_result: Some(super::ValidationResult {
+                                        success: false,
+                                        errors: vec![super::ValidationError {
+                                            field: String::from("id"),
+                                            error: format!("No update data or [{}] found for specified uuid: {}", stringify!($resource), update.id),
+                                        }],
+                                    }),
+                                    ..Default::default()
+                                });
+                            }
+                        }
+                    }
+                    if success || request.mode == $crate::BatchMode::BestEffort as i32 {
+                        *mem_data = updated;
+                    } else {
+                        list.iter_mut().for_each(|response| response.object = None);
+                    }
+                    Ok(tonic::Response::new(Self::ResponseList { success, list }))
+                }
+
+                async fn delete_many(
+                    &self,
+                    request: $crate::DeleteManyRequest,
+                ) -> Result<tonic::Response<$crate::DeleteManyResponse>, tonic::Status> {
+                    grpc_warn!("(delete_many MOCK) {} client.", self.get_name());
+                    grpc_debug!("(delete_many MOCK) request: {:?}", request);
+                    let mut mem_data = $resource::MEM_DATA.lock().await;
+                    let mut remaining = mem_data.clone();
+                    let mut results: Vec<super::ValidationResult> = vec![];
+                    let mut success = true;
+                    for id in request.ids {
+                        if remaining.iter().any(|object| object.id == id) {
+                            remaining.retain(|object| object.id != id);
+                            results.push(super::ValidationResult {
+                                success: true,
+                                errors: Vec::new(),
+                            });
+                        } else {
+                            success = false;
+                            results.push(super::ValidationResult {
+                                success: false,
+                                errors: vec![super::ValidationError {
+                                    field: String::from("id"),
+                                    error: format!("No [{}] found for specified uuid: {}", stringify!($resource), id),
+                                }],
+                            });
+                        }
+                    }
+                    if success || request.mode == $crate::BatchMode::BestEffort as i32 {
+                        *mem_data = remaining;
+                    }
+                    Ok(tonic::Response::new($crate::DeleteManyResponse { success, results }))
+                }
+
                 async fn is_ready(
                     &self,
                     request: $crate::ReadyRequest,
@@ -770,6 +1658,22 @@ macro_rules! simple_linked_grpc_client {
                     grpc_debug!("(delete) request: {:?}", request);
                     self.get_client().await?.delete(request).await
                 }
+                async fn restore(
+                    &self,
+                    request: $crate::Ids,
+                ) -> Result<tonic::Response<()>, tonic::Status> {
+                    grpc_info!("(restore) {} client.", self.get_name());
+                    grpc_debug!("(restore) request: {:?}", request);
+                    self.get_client().await?.restore(request).await
+                }
+                async fn purge(
+                    &self,
+                    request: $crate::Ids,
+                ) -> Result<tonic::Response<()>, tonic::Status> {
+                    grpc_info!("(purge) {} client.", self.get_name());
+                    grpc_debug!("(purge) request: {:?}", request);
+                    self.get_client().await?.purge(request).await
+                }
 
                 async fn is_ready(
                     &self,
@@ -870,7 +1774,7 @@ macro_rules! simple_linked_grpc_client {
                         grpc_error!("(get_linked MOCK) {}", error);
                         return Err(tonic::Status::not_found(error));
                     }
-                    Ok(tonic::Response::new(Self::OtherList { list: other_resource_list }))
+                    Ok(tonic::Response::new(Self::OtherList { total_count: other_resource_list.len() as i64, list: other_resource_list, next_page_token: String::new(), distances: vec![] }))
                 }
 
                 async fn get_by_id(
@@ -952,7 +1856,10 @@ macro_rules! simple_linked_grpc_client {
                     if filters.len() == 0 {
                         grpc_debug!("(search MOCK) no filters provided, returning all.");
                         return Ok(tonic::Response::new(Self::LinkedRowDataList {
-                            list
+                            total_count: list.len() as i64,
+                            list,
+                            next_page_token: String::new(),
+                            distances: vec![],
                         }));
                     }
 
@@ -980,45 +1887,8 @@ macro_rules! simple_linked_grpc_client {
                     }
                     grpc_debug!("(search MOCK) unfiltered serialized objects: {:?}", unfiltered);
 
-                    let mut collected: Vec<serde_json::Value> = vec![];
-                    for filter in filters {
-                        let operator: PredicateOperator =
-                            match PredicateOperator::try_from(filter.predicate_operator) {
-                                Ok(val) => val,
-                                Err(e) => {
-                                    return Err(tonic::Status::internal(format!(
-                                        "Can't convert i32 [{}] into PredicateOperator Enum value: {}",
-                                        filter.predicate_operator, e
-                                    )));
-                                }
-                            };
-
-
-                        match filter.comparison_operator {
-                            Some(comparison_operator) => match ComparisonOperator::try_from(comparison_operator) {
-                                Ok(comparison_operator) => match comparison_operator {
-                                    ComparisonOperator::And => {
-                                        let unfiltered = collected.clone();
-                                        collected = vec![];
-                                        $crate::search::filter_for_operator(&filter.search_field, &filter.search_value, &unfiltered, &mut collected, operator)
-                                            .map_err(|e| tonic::Status::internal(format!("Could not get filtered values for provided filter: {}", e)))?
-                                    }
-                                    ComparisonOperator::Or => {
-                                        $crate::search::filter_for_operator(&filter.search_field, &filter.search_value, &unfiltered, &mut collected, operator)
-                                            .map_err(|e| tonic::Status::internal(format!("Could not get filtered values for provided filter: {}", e)))?
-                                    }
-                                }
-                                Err(e) => {
-                                    return Err(tonic::Status::internal(format!(
-                                        "Can't convert i32 [{}] into ComparisonOperator Enum value: {}",
-                                        comparison_operator, e
-                                    )));
-                                }
-                            },
-                            None => $crate::search::filter_for_operator(&filter.search_field, &filter.search_value, &unfiltered, &mut collected, operator)
-                                .map_err(|e| tonic::Status::internal(format!("Could not get filtered values for provided filter: {}", e)))?
-                        };
-                    }
+                    let collected: Vec<serde_json::Value> = $crate::search::filter_for_options(&filters, &unfiltered)
+                        .map_err(|e| tonic::Status::internal(format!("Could not get filtered values for provided filter: {}", e)))?;
 
                     let mut filtered: Vec<Self::LinkedRowData> = vec![];
                     for val in collected.iter() {
@@ -1037,7 +1907,10 @@ macro_rules! simple_linked_grpc_client {
                         filtered.push(row_data);
                     }
                     let response = Self::LinkedRowDataList {
-                        list: filtered
+                        total_count: filtered.len() as i64,
+                        list: filtered,
+                        next_page_token: String::new(),
+                        distances: vec![],
                     };
                     Ok(tonic::Response::new(response))
                 }
@@ -1171,6 +2044,42 @@ macro_rules! simple_linked_grpc_client {
                     Ok(tonic::Response::new(()))
                 }
 
+                async fn restore(
+                    &self,
+                    request: $crate::Ids,
+                ) -> Result<tonic::Response<()>, tonic::Status> {
+                    grpc_warn!("(restore MOCK) {} client.", self.get_name());
+                    grpc_debug!("(restore MOCK) request: {:?}", request);
+                    // Deleted objects are removed from memory, so there is nothing to restore
+                    Ok(tonic::Response::new(()))
+                }
+
+                async fn purge(
+                    &self,
+                    request: $crate::Ids,
+                ) -> Result<tonic::Response<()>, tonic::Status> {
+                    grpc_warn!("(purge MOCK) {} client.", self.get_name());
+                    grpc_debug!("(purge MOCK) request: {:?}", request);
+                    let ids = request.ids;
+                    let id_field = concat!(stringify!($resource), "_id");
+                    let other_id_field = concat!(stringify!($other_resource), "_id");
+                    let mut resource_id = String::from("");
+                    let mut other_resource_id = String::from("");
+                    for id in &ids {
+                        if id.field.as_str() == id_field {
+                            resource_id = id.value.clone();
+                        }
+                        if id.field.as_str() == other_id_field {
+                            other_resource_id = id.value.clone();
+                        }
+                    }
+                    let mut linked_resource_list = $linked_resource::MEM_DATA.lock().await;
+                    paste::paste!{
+                        linked_resource_list.retain(|object| object.[<$resource _id>] != resource_id || object.[<$other_resource _id>] != other_resource_id);
+                    }
+                    Ok(tonic::Response::new(()))
+                }
+
                 async fn is_ready(
                     &self,
                     request: $crate::ReadyRequest,
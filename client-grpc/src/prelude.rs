@@ -4,12 +4,25 @@ pub use crate::resources::grpc_geo_types::*;
 pub use crate::resources::*;
 pub use crate::Clients;
 
+#[cfg(feature = "parcel")]
+pub use crate::custody_service;
+pub use crate::error::{get_error_details, get_error_reason};
 pub use crate::link_service;
+#[cfg(feature = "flight_plan")]
+pub use crate::scheduling_service;
 pub use crate::simple_service;
 pub use crate::simple_service_linked;
+#[cfg(feature = "adsb")]
+pub use crate::tracking_service;
+#[cfg(feature = "parcel")]
+pub use custody_service::Client as CustodyClient;
 pub use link_service::Client as LinkClient;
+#[cfg(feature = "flight_plan")]
+pub use scheduling_service::Client as SchedulingClient;
 pub use simple_service::Client as SimpleClient;
 pub use simple_service_linked::Client as SimpleLinkedClient;
+#[cfg(feature = "adsb")]
+pub use tracking_service::Client as TrackingClient;
 
 pub use lib_common::grpc::Client;
 pub use lib_common::time::Timestamp;
@@ -20,6 +20,10 @@ pub struct GeoPoint {
     /// range: -90 - 90
     #[prost(double, tag = "2")]
     pub latitude: f64,
+    /// altitude (z) in meters above the WGS 84 ellipsoid
+    /// points without an altitude are stored as 2D points
+    #[prost(double, optional, tag = "3")]
+    pub altitude: ::core::option::Option<f64>,
 }
 /// Geo Location Line representation
 #[derive(Copy, Serialize, Deserialize, ToSchema, IntoParams)]
@@ -41,6 +45,11 @@ pub struct GeoLineString {
     /// list of points
     #[prost(message, repeated, tag = "1")]
     pub points: ::prost::alloc::vec::Vec<GeoPoint>,
+    /// optional list of times at which the points are passed, making the points time-tagged waypoints
+    /// should either be empty or contain one (non decreasing) time for each point
+    #[prost(message, repeated, tag = "2")]
+    #[schema(value_type = Vec<String>)]
+    pub times: ::prost::alloc::vec::Vec<::prost_wkt_types::Timestamp>,
 }
 /// Geo Location Polygon representation
 #[derive(Serialize, Deserialize, ToSchema, IntoParams)]
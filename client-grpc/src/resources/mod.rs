@@ -35,6 +35,21 @@ cfg_if::cfg_if! {
                 simple_grpc_client!(adsb);
                 /// GrpcClient implementation for adsb RpcServiceClient
                 pub type AdsbClient = GrpcClient<adsb::RpcServiceClient<Channel>>;
+
+                /// GrpcClient implementation for adsb RpcTrackingClient
+                pub type AdsbTrackingClient = GrpcClient<adsb::rpc_tracking_client::RpcTrackingClient<Channel>>;
+                use adsb::rpc_tracking_client::RpcTrackingClient as AdsbRpcTrackingClient;
+
+                cfg_if::cfg_if! {
+                    if #[cfg(feature = "stub_backends")] {
+                        use svc_storage::grpc::tracking_service::{RpcTrackingServer, GrpcServer as AdsbTrackingGrpcServer};
+                        lib_common::grpc_mock_client!(AdsbRpcTrackingClient, RpcTrackingServer, AdsbTrackingGrpcServer);
+                    } else {
+                        lib_common::grpc_client!(AdsbRpcTrackingClient);
+                    }
+                }
+
+                tracking_grpc_client!(AdsbRpcTrackingClient);
             }
         }
 
@@ -44,6 +59,27 @@ cfg_if::cfg_if! {
                 simple_grpc_client!(flight_plan);
                 /// GrpcClient implementation for flight_plan RpcServiceClient
                 pub type FlightPlanClient = GrpcClient<flight_plan::RpcServiceClient<Channel>>;
+
+                /// [`FlightStatus`](flight_plan::FlightStatus) lifecycle, shared with the server
+                mod flight_status {
+                    use super::flight_plan::FlightStatus;
+                    include!("../../includes/flight_plan/status.rs");
+                }
+
+                /// GrpcClient implementation for flight_plan RpcSchedulingClient
+                pub type FlightPlanSchedulingClient = GrpcClient<flight_plan::rpc_scheduling_client::RpcSchedulingClient<Channel>>;
+                use flight_plan::rpc_scheduling_client::RpcSchedulingClient as FlightPlanRpcSchedulingClient;
+
+                cfg_if::cfg_if! {
+                    if #[cfg(feature = "stub_backends")] {
+                        use svc_storage::grpc::scheduling_service::{RpcSchedulingServer, GrpcServer as FlightPlanSchedulingGrpcServer};
+                        lib_common::grpc_mock_client!(FlightPlanRpcSchedulingClient, RpcSchedulingServer, FlightPlanSchedulingGrpcServer);
+                    } else {
+                        lib_common::grpc_client!(FlightPlanRpcSchedulingClient);
+                    }
+                }
+
+                scheduling_grpc_client!(FlightPlanRpcSchedulingClient);
             }
         }
 
@@ -160,6 +196,27 @@ cfg_if::cfg_if! {
                 simple_grpc_client!(parcel);
                 /// GrpcClient implementation for parcel RpcServiceClient
                 pub type ParcelClient = GrpcClient<parcel::RpcServiceClient<Channel>>;
+
+                /// [`ParcelStatus`](parcel::ParcelStatus) lifecycle, shared with the server
+                mod parcel_status {
+                    use super::parcel::ParcelStatus;
+                    include!("../../includes/parcel/status.rs");
+                }
+
+                /// GrpcClient implementation for parcel RpcCustodyClient
+                pub type ParcelCustodyClient = GrpcClient<parcel::rpc_custody_client::RpcCustodyClient<Channel>>;
+                use parcel::rpc_custody_client::RpcCustodyClient as ParcelRpcCustodyClient;
+
+                cfg_if::cfg_if! {
+                    if #[cfg(feature = "stub_backends")] {
+                        use svc_storage::grpc::custody_service::{RpcCustodyServer, GrpcServer as ParcelCustodyGrpcServer};
+                        lib_common::grpc_mock_client!(ParcelRpcCustodyClient, RpcCustodyServer, ParcelCustodyGrpcServer);
+                    } else {
+                        lib_common::grpc_client!(ParcelRpcCustodyClient);
+                    }
+                }
+
+                custody_grpc_client!(ParcelRpcCustodyClient);
             }
         }
 
@@ -248,6 +305,15 @@ cfg_if::cfg_if! {
             }
         }
 
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "vehicle_model")] {
+                grpc_client_mod!(vehicle_model);
+                simple_grpc_client!(vehicle_model);
+                /// GrpcClient implementation for vehicle_model RpcServiceClient
+                pub type VehicleModelClient = GrpcClient<vehicle_model::RpcServiceClient<Channel>>;
+            }
+        }
+
         cfg_if::cfg_if! {
             if #[cfg(feature = "vertipad")] {
                 grpc_client_mod!(vertipad);
@@ -312,9 +378,15 @@ cfg_if::cfg_if! {
             #[cfg(feature = "adsb")]
             /// GrpcClient representation of the AdsbClient
             pub adsb: AdsbClient,
+            #[cfg(feature = "adsb")]
+            /// GrpcClient representation of the AdsbTrackingClient
+            pub adsb_tracking: AdsbTrackingClient,
             #[cfg(feature = "flight_plan")]
             /// GrpcClient representation of the FlightPlanClient
             pub flight_plan: FlightPlanClient,
+            #[cfg(feature = "flight_plan")]
+            /// GrpcClient representation of the FlightPlanSchedulingClient
+            pub flight_plan_scheduling: FlightPlanSchedulingClient,
             #[cfg(feature = "flight_plan_parcel")]
             /// GrpcClient representation of the FlightPlanParcelClient
             pub flight_plan_parcel: FlightPlanParcelClient,
@@ -338,6 +410,9 @@ cfg_if::cfg_if! {
             #[cfg(feature = "parcel")]
             /// GrpcClient representation of the ParcelClient
             pub parcel: ParcelClient,
+            #[cfg(feature = "parcel")]
+            /// GrpcClient representation of the ParcelCustodyClient
+            pub parcel_custody: ParcelCustodyClient,
             #[cfg(feature = "parcel_scan")]
             /// GrpcClient representation of the ParcelScanClient
             pub parcel_scan: ParcelScanClient,
@@ -365,6 +440,9 @@ cfg_if::cfg_if! {
             #[cfg(feature = "vehicle")]
             /// GrpcClient representation of the VehicleGroupClient
             pub vehicle_group_link: VehicleGroupLinkClient,
+            #[cfg(feature = "vehicle_model")]
+            /// GrpcClient representation of the VehicleModelClient
+            pub vehicle_model: VehicleModelClient,
             #[cfg(feature = "vertipad")]
             /// GrpcClient representation of the VertipadClient
             pub vertipad: VertipadClient,
@@ -384,9 +462,13 @@ cfg_if::cfg_if! {
             pub fn new(host: String, port: u16) -> Self {
                 #[cfg(feature = "adsb")]
                 let adsb = AdsbClient::new_client(&host, port, "adsb");
+                #[cfg(feature = "adsb")]
+                let adsb_tracking = AdsbTrackingClient::new_client(&host, port, "adsb_tracking");
 
                 #[cfg(feature = "flight_plan")]
                 let flight_plan = FlightPlanClient::new_client(&host, port, "flight_plan");
+                #[cfg(feature = "flight_plan")]
+                let flight_plan_scheduling = FlightPlanSchedulingClient::new_client(&host, port, "flight_plan_scheduling");
 
                 #[cfg(feature = "flight_plan_parcel")]
                 let flight_plan_parcel = FlightPlanParcelClient::new_client(&host, port, "flight_plan_parcel");
@@ -409,6 +491,8 @@ cfg_if::cfg_if! {
 
                 #[cfg(feature = "parcel")]
                 let parcel = ParcelClient::new_client(&host, port, "parcel");
+                #[cfg(feature = "parcel")]
+                let parcel_custody = ParcelCustodyClient::new_client(&host, port, "parcel_custody");
 
                 #[cfg(feature = "parcel_scan")]
                 let parcel_scan = ParcelScanClient::new_client(&host, port, "parcel_scan");
@@ -429,6 +513,9 @@ cfg_if::cfg_if! {
                 #[cfg(feature = "vehicle")]
                 let vehicle_group_link = VehicleGroupLinkClient::new_client(&host, port, "vehicle_group_link");
 
+                #[cfg(feature = "vehicle_model")]
+                let vehicle_model = VehicleModelClient::new_client(&host, port, "vehicle_model");
+
                 #[cfg(feature = "vertipad")]
                 let vertipad = VertipadClient::new_client(&host, port, "vertipad");
                 #[cfg(feature = "vertipad")]
@@ -442,8 +529,12 @@ cfg_if::cfg_if! {
                 Clients {
                     #[cfg(feature = "adsb")]
                     adsb,
+                    #[cfg(feature = "adsb")]
+                    adsb_tracking,
                     #[cfg(feature = "flight_plan")]
                     flight_plan,
+                    #[cfg(feature = "flight_plan")]
+                    flight_plan_scheduling,
                     #[cfg(feature = "flight_plan_parcel")]
                     flight_plan_parcel,
                     #[cfg(feature = "group")]
@@ -462,6 +553,8 @@ cfg_if::cfg_if! {
                     itinerary_flight_plan_link,
                     #[cfg(feature = "parcel")]
                     parcel,
+                    #[cfg(feature = "parcel")]
+                    parcel_custody,
                     #[cfg(feature = "parcel_scan")]
                     parcel_scan,
                     #[cfg(feature = "pilot")]
@@ -476,6 +569,8 @@ cfg_if::cfg_if! {
                     vehicle,
                     #[cfg(feature = "vehicle")]
                     vehicle_group_link,
+                    #[cfg(feature = "vehicle_model")]
+                    vehicle_model,
                     #[cfg(feature = "vertipad")]
                     vertipad,
                     #[cfg(feature = "vertipad")]
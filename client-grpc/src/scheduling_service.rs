@@ -0,0 +1,214 @@
+//! GRPC Scheduling Service traits
+
+use crate::resources::flight_plan::{
+    CommittedTimeRangesRequest, CommittedTimeRangesResponse, ConflictingFlightPlansRequest,
+    ConflictingFlightPlansResponse, Response, TransitionStatusRequest, VertipadAvailabilityRequest,
+    VertipadAvailabilityResponse,
+};
+
+/// Generic gRPC object traits to provide wrappers for flight plan scheduling and lifecycle functions
+#[tonic::async_trait]
+pub trait Client<T>
+where
+    Self: Sized + lib_common::grpc::Client<T> + lib_common::grpc::ClientConnect<T>,
+    T: Send + Clone,
+{
+    /// Returns a [`tonic::Response`] containing a [`VertipadAvailabilityResponse`]
+    /// with the ids of all flight plans claiming the provided vertipad during (part of) the proposed timeslot.
+    ///
+    /// Both the origin and target timeslots of existing flight plans are taken into account.
+    /// Cancelled and deleted flight plans are ignored. An `exclude_flight_plan_id` can be provided
+    /// to ignore a flight plan that is being rescheduled.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`tonic::Status`] with [`tonic::Code::InvalidArgument`] if the provided ids can not be converted to a [`uuid::Uuid`].
+    /// Returns [`tonic::Status`] with [`tonic::Code::InvalidArgument`] if no valid timeslot is provided.
+    /// Returns [`tonic::Status`] with [`tonic::Code::Internal`] if any error is returned from a db call.
+    ///
+    /// # Examples
+    /// ```
+    /// use lib_common::grpc::get_endpoint_from_env;
+    /// use svc_storage_client_grpc::prelude::*;
+    /// use svc_storage_client_grpc::flight_plan::VertipadAvailabilityRequest;
+    /// use std::time::{Duration, SystemTime};
+    ///
+    /// async fn example () -> Result<(), Box<dyn std::error::Error>> {
+    ///     let (host, port) = get_endpoint_from_env("SERVER_HOSTNAME", "SERVER_PORT_GRPC");
+    ///     let clients = Clients::new(host, port);
+    ///     let now = SystemTime::now();
+    ///     let response = clients.flight_plan_scheduling
+    ///         .check_vertipad_availability(VertipadAvailabilityRequest {
+    ///             vertipad_id: String::from("53acfe06-dd9b-42e8-8cb4-12a2fb2fa693"),
+    ///             timeslot_start: Some(now.into()),
+    ///             timeslot_end: Some((now + Duration::from_secs(300)).into()),
+    ///             exclude_flight_plan_id: None,
+    ///         })
+    ///         .await?;
+    ///     println!("RESPONSE={:?}", response.into_inner());
+    ///     Ok(())
+    /// }
+    /// ```
+    async fn check_vertipad_availability(
+        &self,
+        request: VertipadAvailabilityRequest,
+    ) -> Result<tonic::Response<VertipadAvailabilityResponse>, tonic::Status>;
+
+    /// Returns a [`tonic::Response`] containing a [`CommittedTimeRangesResponse`]
+    /// with all time ranges the provided vehicle or pilot is committed to during (part of) the provided window.
+    ///
+    /// An asset is committed from the start of a flight plan's origin timeslot until the end of its target timeslot.
+    /// Cancelled, finished and deleted flight plans are ignored. The time ranges are ordered by their start time.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`tonic::Status`] with [`tonic::Code::InvalidArgument`] if the provided id can not be converted to a [`uuid::Uuid`].
+    /// Returns [`tonic::Status`] with [`tonic::Code::InvalidArgument`] if no valid window is provided.
+    /// Returns [`tonic::Status`] with [`tonic::Code::Internal`] if any error is returned from a db call.
+    ///
+    /// # Examples
+    /// ```
+    /// use lib_common::grpc::get_endpoint_from_env;
+    /// use svc_storage_client_grpc::prelude::*;
+    /// use svc_storage_client_grpc::flight_plan::{AssetType, CommittedTimeRangesRequest};
+    /// use std::time::{Duration, SystemTime};
+    ///
+    /// async fn example () -> Result<(), Box<dyn std::error::Error>> {
+    ///     let (host, port) = get_endpoint_from_env("SERVER_HOSTNAME", "SERVER_PORT_GRPC");
+    ///     let clients = Clients::new(host, port);
+    ///     let now = SystemTime::now();
+    ///     let response = clients.flight_plan_scheduling
+    ///         .get_committed_time_ranges(CommittedTimeRangesRequest {
+    ///             asset_type: AssetType::Vehicle as i32,
+    ///             asset_id: String::from("53acfe06-dd9b-42e8-8cb4-12a2fb2fa693"),
+    ///             window_start: Some(now.into()),
+    ///             window_end: Some((now + Duration::from_secs(86400)).into()),
+    ///         })
+    ///         .await?;
+    ///     println!("RESPONSE={:?}", response.into_inner());
+    ///     Ok(())
+    /// }
+    /// ```
+    async fn get_committed_time_ranges(
+        &self,
+        request: CommittedTimeRangesRequest,
+    ) -> Result<tonic::Response<CommittedTimeRangesResponse>, tonic::Status>;
+
+    /// Returns a [`tonic::Response`] containing a [`ConflictingFlightPlansResponse`]
+    /// with all flight plans passing within the minimum separation of the provided path during (part of) the provided window.
+    ///
+    /// Flight plans are considered active from the start of their origin timeslot until the end of their target timeslot.
+    /// If both paths have times for their points, only the sections flown during the same time are compared.
+    /// Cancelled and deleted flight plans are ignored. The conflicts are ordered by their distance.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`tonic::Status`] with [`tonic::Code::InvalidArgument`] if no valid path is provided.
+    /// Returns [`tonic::Status`] with [`tonic::Code::InvalidArgument`] if no valid window is provided.
+    /// Returns [`tonic::Status`] with [`tonic::Code::InvalidArgument`] if the minimum separation is negative.
+    /// Returns [`tonic::Status`] with [`tonic::Code::InvalidArgument`] if the provided id can not be converted to a [`uuid::Uuid`].
+    /// Returns [`tonic::Status`] with [`tonic::Code::Internal`] if any error is returned from a db call.
+    ///
+    /// # Examples
+    /// ```
+    /// use lib_common::grpc::get_endpoint_from_env;
+    /// use svc_storage_client_grpc::prelude::*;
+    /// use svc_storage_client_grpc::flight_plan::ConflictingFlightPlansRequest;
+    /// use std::time::{Duration, SystemTime};
+    ///
+    /// async fn example () -> Result<(), Box<dyn std::error::Error>> {
+    ///     let (host, port) = get_endpoint_from_env("SERVER_HOSTNAME", "SERVER_PORT_GRPC");
+    ///     let clients = Clients::new(host, port);
+    ///     let now = SystemTime::now();
+    ///     let path = GeoLineString {
+    ///         points: vec![
+    ///             GeoPoint { longitude: 4.9160036, latitude: 52.3745905, altitude: Some(120.0) },
+    ///             GeoPoint { longitude: 4.9156925, latitude: 52.3749819, altitude: Some(120.0) },
+    ///         ],
+    ///         times: vec![now.into(), (now + Duration::from_secs(60)).into()],
+    ///     };
+    ///     let response = clients.flight_plan_scheduling
+    ///         .find_conflicting_flight_plans(ConflictingFlightPlansRequest {
+    ///             path: Some(path),
+    ///             window_start: Some(now.into()),
+    ///             window_end: Some((now + Duration::from_secs(60)).into()),
+    ///             min_separation_meters: 50.0,
+    ///             exclude_flight_plan_id: None,
+    ///         })
+    ///         .await?;
+    ///     println!("RESPONSE={:?}", response.into_inner());
+    ///     Ok(())
+    /// }
+    /// ```
+    async fn find_conflicting_flight_plans(
+        &self,
+        request: ConflictingFlightPlansRequest,
+    ) -> Result<tonic::Response<ConflictingFlightPlansResponse>, tonic::Status>;
+
+    /// Changes the [`FlightStatus`](crate::resources::flight_plan::FlightStatus) of a flight plan, recording who made the change.
+    /// Returns a [`tonic::Response`] containing a flight_plan [`Response`] with the updated object
+    /// and a [`ValidationResult`](crate::ValidationResult) listing the reasons why the transition was rejected, if any.
+    ///
+    /// Transitions not allowed by [`FlightStatus::get_allowed_transitions`](crate::resources::flight_plan::FlightStatus::get_allowed_transitions)
+    /// are rejected. The `actual_departure_time` and `actual_arrival_time` will be set on the
+    /// `IN_FLIGHT` and `FINISHED` transitions if not set already.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`tonic::Status`] with [`tonic::Code::InvalidArgument`] if the provided ids can not be converted to a [`uuid::Uuid`].
+    /// Returns [`tonic::Status`] with [`tonic::Code::InvalidArgument`] if an unknown `flight_status` is provided.
+    /// Returns [`tonic::Status`] with [`tonic::Code::NotFound`] if no flight plan exists for the provided id.
+    /// Returns [`tonic::Status`] with [`tonic::Code::Internal`] if any error is returned from a db call.
+    ///
+    /// # Examples
+    /// ```
+    /// use lib_common::grpc::get_endpoint_from_env;
+    /// use svc_storage_client_grpc::prelude::*;
+    /// use svc_storage_client_grpc::flight_plan::{FlightStatus, TransitionStatusRequest};
+    ///
+    /// async fn example () -> Result<(), Box<dyn std::error::Error>> {
+    ///     let (host, port) = get_endpoint_from_env("SERVER_HOSTNAME", "SERVER_PORT_GRPC");
+    ///     let clients = Clients::new(host, port);
+    ///     let response = clients.flight_plan_scheduling
+    ///         .transition_status(TransitionStatusRequest {
+    ///             id: String::from("53acfe06-dd9b-42e8-8cb4-12a2fb2fa693"),
+    ///             flight_status: FlightStatus::Boarding as i32,
+    ///             changed_by: String::from("c5b0c8f9-2ad4-4f62-9f2b-dd4e0ba37f6a"),
+    ///         })
+    ///         .await?;
+    ///     println!("RESPONSE={:?}", response.into_inner());
+    ///     Ok(())
+    /// }
+    /// ```
+    async fn transition_status(
+        &self,
+        request: TransitionStatusRequest,
+    ) -> Result<tonic::Response<Response>, tonic::Status>;
+
+    /// Returns a [`tonic::Response`] containing a [`ReadyResponse`](crate::ReadyResponse)
+    /// Takes an [`ReadyRequest`](crate::ReadyRequest)
+    ///
+    /// # Errors
+    ///
+    /// Returns [`tonic::Status`] with [`tonic::Code::Unknown`] if the server is not ready.
+    ///
+    /// # Examples
+    /// ```
+    /// use lib_common::grpc::get_endpoint_from_env;
+    /// use svc_storage_client_grpc::prelude::*;
+    ///
+    /// async fn example () -> Result<(), Box<dyn std::error::Error>> {
+    ///     let (host, port) = get_endpoint_from_env("SERVER_HOSTNAME", "SERVER_PORT_GRPC");
+    ///     let clients = Clients::new(host, port);
+    ///     let response = clients.flight_plan_scheduling
+    ///         .is_ready(ReadyRequest {})
+    ///         .await?;
+    ///     println!("RESPONSE={:?}", response.into_inner());
+    ///     Ok(())
+    /// }
+    /// ```
+    async fn is_ready(
+        &self,
+        request: crate::ReadyRequest,
+    ) -> Result<tonic::Response<crate::ReadyResponse>, tonic::Status>;
+}
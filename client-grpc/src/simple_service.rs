@@ -17,6 +17,14 @@ where
     type List;
     /// The type expected for Response structs.
     type Response;
+    /// The type expected for InsertManyRequest structs.
+    type InsertManyRequest;
+    /// The type expected for UpdateManyRequest structs.
+    type UpdateManyRequest;
+    /// The type expected for ResponseList structs.
+    type ResponseList;
+    /// The type expected for ChangeEvent structs.
+    type ChangeEvent;
 
     /// Returns a [`tonic::Response`] containing the [`Object`](Self::Object)
     ///
@@ -50,6 +58,39 @@ where
         request: crate::Id,
     ) -> Result<tonic::Response<Self::Object>, tonic::Status>;
 
+    /// Returns a [`tonic::Response`] containing the [`Object`](Self::Object)
+    ///
+    /// Takes a [`Id`](crate::Id) and uses the provided `id` field to determine which object record to retrieve from the database.
+    /// Unlike [`get_by_id`](Self::get_by_id), soft-deleted records are returned as well.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`tonic::Status`] with [`tonic::Code::NotFound`] if the provided `id` is not found in the database.
+    /// Returns [`tonic::Status`] with [`tonic::Code::Internal`] if the provided Id can not be converted to a [`uuid::Uuid`].
+    /// Returns [`tonic::Status`] with [`tonic::Code::Internal`] if any error is returned from the db search result.
+    /// Returns [`tonic::Status`] with [`tonic::Code::Unknown`] if the server is not ready.
+    ///
+    /// # Examples
+    /// ```
+    /// use lib_common::grpc::get_endpoint_from_env;
+    /// use svc_storage_client_grpc::prelude::*;
+    ///
+    /// async fn example () -> Result<(), Box<dyn std::error::Error>> {
+    ///     let (host, port) = get_endpoint_from_env("SERVER_HOSTNAME", "SERVER_PORT_GRPC");
+    ///     let clients = Clients::new(host, port);
+    ///     let client = clients.flight_plan;
+    ///     let flight_plan_id = String::from("40ef6e51-c7db-4ce7-a806-a754d6baa641");
+    ///
+    ///     client.get_by_id_include_deleted(Id { id: flight_plan_id }).await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    async fn get_by_id_include_deleted(
+        &self,
+        request: crate::Id,
+    ) -> Result<tonic::Response<Self::Object>, tonic::Status>;
+
     /// Returns a [`tonic::Response`] containing a [`Response`](Self::Response) object
     /// of the inserted record after saving the provided [`Data`](Self::Data)
     ///
@@ -199,9 +240,188 @@ where
     /// ```
     async fn delete(&self, request: crate::Id) -> Result<tonic::Response<()>, tonic::Status>;
 
+    /// Returns a [`tonic::Response`] containing a [`ResponseList`](Self::ResponseList) object
+    /// with a [`Response`](Self::Response) for each of the provided items, in the same order as the request.
+    ///
+    /// All items will be inserted using a single transaction.
+    /// Each item will be validated before insert, any errors found will be added to the item's [`ValidationResult`](crate::ValidationResult).
+    /// Using [`BatchMode::AllOrNothing`](crate::BatchMode::AllOrNothing), no records will be inserted if any of the items fail.
+    /// Using [`BatchMode::BestEffort`](crate::BatchMode::BestEffort), failing items will be skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`tonic::Status`] with [`tonic::Code::InvalidArgument`] if an unknown [`BatchMode`](crate::BatchMode) is provided.
+    /// Returns [`tonic::Status`] with [`tonic::Code::Internal`] if any error is returned from a db call.
+    /// Returns [`tonic::Status`] with [`tonic::Code::Unknown`] if the server is not ready.
+    ///
+    /// # Examples
+    /// ```
+    /// use lib_common::grpc::get_endpoint_from_env;
+    /// use svc_storage_client_grpc::prelude::*;
+    /// use flight_plan::*;
+    ///
+    /// async fn example (list: Vec<Data>) -> Result<(), Box<dyn std::error::Error>> {
+    ///     let (host, port) = get_endpoint_from_env("SERVER_HOSTNAME", "SERVER_PORT_GRPC");
+    ///     let clients = Clients::new(host, port);
+    ///     let client = clients.flight_plan;
+    ///
+    ///     let response = client.insert_many(InsertManyRequest {
+    ///         list,
+    ///         mode: BatchMode::AllOrNothing as i32,
+    ///     }).await?.into_inner();
+    ///     if !response.success {
+    ///         println!("Nothing inserted: {:?}", response.list);
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    async fn insert_many(
+        &self,
+        request: Self::InsertManyRequest,
+    ) -> Result<tonic::Response<Self::ResponseList>, tonic::Status>;
+
+    /// Returns a [`tonic::Response`] containing a [`ResponseList`](Self::ResponseList) object
+    /// with a [`Response`](Self::Response) for each of the provided items, in the same order as the request.
+    ///
+    /// All items will be updated using a single transaction.
+    /// Each item will be validated before update, any errors found will be added to the item's [`ValidationResult`](crate::ValidationResult).
+    /// Using [`BatchMode::AllOrNothing`](crate::BatchMode::AllOrNothing), no records will be updated if any of the items fail.
+    /// Using [`BatchMode::BestEffort`](crate::BatchMode::BestEffort), failing items will be skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`tonic::Status`] with [`tonic::Code::InvalidArgument`] if an unknown [`BatchMode`](crate::BatchMode) is provided.
+    /// Returns [`tonic::Status`] with [`tonic::Code::Internal`] if any error is returned from a db call.
+    /// Returns [`tonic::Status`] with [`tonic::Code::Internal`] if the resulting tokio_postgres::Row data could not be converted into [`Data`](Self::Data).
+    /// Returns [`tonic::Status`] with [`tonic::Code::Unknown`] if the server is not ready.
+    ///
+    /// # Examples
+    /// ```
+    /// use lib_common::grpc::get_endpoint_from_env;
+    /// use svc_storage_client_grpc::prelude::*;
+    /// use flight_plan::*;
+    ///
+    /// async fn example (list: Vec<UpdateObject>) -> Result<(), Box<dyn std::error::Error>> {
+    ///     let (host, port) = get_endpoint_from_env("SERVER_HOSTNAME", "SERVER_PORT_GRPC");
+    ///     let clients = Clients::new(host, port);
+    ///     let client = clients.flight_plan;
+    ///
+    ///     client.update_many(UpdateManyRequest {
+    ///         list,
+    ///         mode: BatchMode::BestEffort as i32,
+    ///     }).await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    async fn update_many(
+        &self,
+        request: Self::UpdateManyRequest,
+    ) -> Result<tonic::Response<Self::ResponseList>, tonic::Status>;
+
+    /// Takes a [`DeleteManyRequest`](crate::DeleteManyRequest) object to remove the associated records
+    /// from the database using a single transaction.
+    ///
+    /// The returned [`DeleteManyResponse`](crate::DeleteManyResponse) contains a [`ValidationResult`](crate::ValidationResult)
+    /// for each of the provided ids, in the same order as the request.
+    /// Using [`BatchMode::AllOrNothing`](crate::BatchMode::AllOrNothing), no records will be deleted if any of the items fail.
+    /// Using [`BatchMode::BestEffort`](crate::BatchMode::BestEffort), failing items will be skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`tonic::Status`] with [`tonic::Code::InvalidArgument`] if an unknown [`BatchMode`](crate::BatchMode) is provided.
+    /// Returns [`tonic::Status`] with [`tonic::Code::Internal`] if any error is returned from a db call.
+    /// Returns [`tonic::Status`] with [`tonic::Code::Unknown`] if the server is not ready.
+    ///
+    /// # Examples
+    /// ```
+    /// use lib_common::grpc::get_endpoint_from_env;
+    /// use svc_storage_client_grpc::prelude::*;
+    ///
+    /// async fn example () -> Result<(), Box<dyn std::error::Error>> {
+    ///     let (host, port) = get_endpoint_from_env("SERVER_HOSTNAME", "SERVER_PORT_GRPC");
+    ///     let clients = Clients::new(host, port);
+    ///     let client = clients.flight_plan;
+    ///
+    ///     let ids = vec![String::from("40ef6e51-c7db-4ce7-a806-a754d6baa641")];
+    ///     client.delete_many(DeleteManyRequest {
+    ///         ids,
+    ///         mode: BatchMode::AllOrNothing as i32,
+    ///     }).await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    async fn delete_many(
+        &self,
+        request: crate::DeleteManyRequest,
+    ) -> Result<tonic::Response<crate::DeleteManyResponse>, tonic::Status>;
+
+    /// Takes an [`Id`](crate::Id) object to restore the associated soft-deleted record
+    /// in the database.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`tonic::Status`] with [`tonic::Code::Internal`] if the record is not deleted or any error is returned from a db call.
+    /// Returns [`tonic::Status`] with [`tonic::Code::Internal`] if the provided Id can not be converted to a [`uuid::Uuid`].
+    /// Returns [`tonic::Status`] with [`tonic::Code::Unknown`] if the server is not ready.
+    ///
+    /// # Examples
+    /// ```
+    /// use lib_common::grpc::get_endpoint_from_env;
+    /// use svc_storage_client_grpc::prelude::*;
+    /// use flight_plan::*;
+    ///
+    /// async fn example () -> Result<(), Box<dyn std::error::Error>> {
+    ///     let (host, port) = get_endpoint_from_env("SERVER_HOSTNAME", "SERVER_PORT_GRPC");
+    ///     let clients = Clients::new(host, port);
+    ///     let client = clients.flight_plan;
+    ///
+    ///     let flight_plan_id = String::from("40ef6e51-c7db-4ce7-a806-a754d6baa641");
+    ///     client.restore(Id { id: flight_plan_id } ).await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    async fn restore(&self, request: crate::Id) -> Result<tonic::Response<()>, tonic::Status>;
+
+    /// Takes an [`Id`](crate::Id) object to permanently remove the associated record
+    /// and any link rows referencing it from the database.
+    ///
+    /// Records need to be deleted using [`delete`](Self::delete) before they can be purged.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`tonic::Status`] with [`tonic::Code::Internal`] if the record is not deleted yet or any error is returned from a db call.
+    /// Returns [`tonic::Status`] with [`tonic::Code::Internal`] if the provided Id can not be converted to a [`uuid::Uuid`].
+    /// Returns [`tonic::Status`] with [`tonic::Code::Unknown`] if the server is not ready.
+    ///
+    /// # Examples
+    /// ```
+    /// use lib_common::grpc::get_endpoint_from_env;
+    /// use svc_storage_client_grpc::prelude::*;
+    /// use flight_plan::*;
+    ///
+    /// async fn example () -> Result<(), Box<dyn std::error::Error>> {
+    ///     let (host, port) = get_endpoint_from_env("SERVER_HOSTNAME", "SERVER_PORT_GRPC");
+    ///     let clients = Clients::new(host, port);
+    ///     let client = clients.flight_plan;
+    ///
+    ///     let flight_plan_id = String::from("40ef6e51-c7db-4ce7-a806-a754d6baa641");
+    ///     client.purge(Id { id: flight_plan_id } ).await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    async fn purge(&self, request: crate::Id) -> Result<tonic::Response<()>, tonic::Status>;
+
     /// Search database records using an advanced filter
     ///
     /// This method supports paged results.
+    /// The returned [`List`](Self::List) contains the `total_count` of matching records and a
+    /// `next_page_token` which can be passed as `page_token` to get the next page.
+    /// When sorted by distance, the list contains the `distances` in meters of the returned items.
     ///
     /// # Errors
     ///
@@ -222,9 +442,14 @@ where
     ///
     ///     let pilot_id = "a2093c5e-9bbe-4f0f-97ee-276b43fa3759".to_owned();
     ///     let filter = AdvancedSearchFilter::search_equals("pilot_id".to_owned(), pilot_id)
-    ///         .and_is_not_null("origin_timeslot_start".to_owned());
+    ///         .and_is_not_null("origin_timeslot_start".to_owned())
+    ///         .results_per_page(10)
+    ///         .page_number(1);
     ///
-    ///     client.search(filter).await?;
+    ///     let page = client.search(filter.clone()).await?.into_inner();
+    ///     if !page.next_page_token.is_empty() {
+    ///         client.search(filter.page_token(page.next_page_token)).await?;
+    ///     }
     ///
     ///     Ok(())
     /// }
@@ -234,6 +459,115 @@ where
         request: crate::AdvancedSearchFilter,
     ) -> Result<tonic::Response<Self::List>, tonic::Status>;
 
+    /// Search database records using an advanced filter, streaming the results
+    ///
+    /// Objects are received as soon as they are read from the database, making this
+    /// method suitable for large result sets like `adsb` or `parcel_scan` history.
+    /// Page numbers and page sizes are ignored, a `page_token` can be used to resume a previous search.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`tonic::Status`] with [`tonic::Code::Internal`] if any error is returned from the db search result.
+    /// Returns [`tonic::Status`] with [`tonic::Code::Unknown`] if the server is not ready.
+    /// Each streamed item will contain a [`tonic::Status`] with [`tonic::Code::Internal`] if the tokio_postgres::Row data could not be converted into an [`Object`](Self::Object).
+    ///
+    /// # Examples
+    /// ```
+    /// use futures::StreamExt;
+    /// use lib_common::grpc::get_endpoint_from_env;
+    /// use svc_storage_client_grpc::prelude::*;
+    ///
+    /// async fn example () -> Result<(), Box<dyn std::error::Error>> {
+    ///     let (host, port) = get_endpoint_from_env("SERVER_HOSTNAME", "SERVER_PORT_GRPC");
+    ///     let clients = Clients::new(host, port);
+    ///     let client = clients.flight_plan;
+    ///
+    ///     let filter = AdvancedSearchFilter::search_is_not_null("origin_timeslot_start".to_owned());
+    ///     let mut stream = client.search_stream(filter).await?.into_inner();
+    ///     while let Some(flight_plan) = stream.next().await {
+    ///         println!("{:?}", flight_plan?);
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    async fn search_stream(
+        &self,
+        filter: crate::AdvancedSearchFilter,
+    ) -> Result<tonic::Response<tonic::codegen::BoxStream<Self::Object>>, tonic::Status>;
+
+    /// Watch database records matching an advanced filter for changes
+    ///
+    /// A [`ChangeEvent`](Self::ChangeEvent) will be received for each created, updated or deleted record,
+    /// including the current [`Object`](Self::Object) if it still exists.
+    /// Soft-deleted records are always included, the filter's `include_deleted` setting is ignored.
+    /// The filter's `page_token` can be set to the `sequence` of the last received event to resume watching
+    /// after a reconnect, making sure no changes are missed. If no `page_token` is provided, only new changes will be received.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`tonic::Status`] with [`tonic::Code::Internal`] if the change log could not be read or the `page_token` is invalid.
+    /// Returns [`tonic::Status`] with [`tonic::Code::Unknown`] if the server is not ready.
+    /// Each streamed item will contain a [`tonic::Status`] with [`tonic::Code::Internal`] if the change could not be converted into an [`Object`](Self::Object).
+    ///
+    /// # Examples
+    /// ```
+    /// use futures::StreamExt;
+    /// use lib_common::grpc::get_endpoint_from_env;
+    /// use svc_storage_client_grpc::prelude::*;
+    ///
+    /// async fn example (last_sequence: Option<i64>) -> Result<(), Box<dyn std::error::Error>> {
+    ///     let (host, port) = get_endpoint_from_env("SERVER_HOSTNAME", "SERVER_PORT_GRPC");
+    ///     let clients = Clients::new(host, port);
+    ///     let client = clients.vertipad;
+    ///
+    ///     let mut filter = AdvancedSearchFilter::search_equals("occupied".to_owned(), true.to_string());
+    ///     if let Some(sequence) = last_sequence {
+    ///         filter = filter.page_token(sequence.to_string());
+    ///     }
+    ///     let mut stream = client.watch(filter).await?.into_inner();
+    ///     while let Some(event) = stream.next().await {
+    ///         let event = event?;
+    ///         println!("{:?} [{}]: {:?}", event.change_type(), event.sequence, event.object);
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    async fn watch(
+        &self,
+        filter: crate::AdvancedSearchFilter,
+    ) -> Result<tonic::Response<tonic::codegen::BoxStream<Self::ChangeEvent>>, tonic::Status>;
+
+    /// Returns a [`tonic::Response`] containing a [`HealthResponse`](crate::HealthResponse)
+    /// Takes an [`ReadyRequest`](crate::ReadyRequest)
+    ///
+    /// The [`HealthResponse`](crate::HealthResponse) contains the results of the last database health check
+    /// performed by the server, including the database latency, connection pool utilization and database schema version.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`tonic::Status`] with [`tonic::Code::Unknown`] if the server could not be reached.
+    ///
+    /// # Examples
+    /// ```
+    /// use lib_common::grpc::get_endpoint_from_env;
+    /// use svc_storage_client_grpc::prelude::*;
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let (host, port) = get_endpoint_from_env("SERVER_HOSTNAME", "SERVER_PORT_GRPC");
+    ///     let clients = Clients::new(host, port);
+    ///     let health = clients.flight_plan.get_health(ReadyRequest {}).await?.into_inner();
+    ///     println!("Database ready: {}, schema version: {:?}", health.ready, health.schema_version);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    async fn get_health(
+        &self,
+        request: crate::ReadyRequest,
+    ) -> Result<tonic::Response<crate::HealthResponse>, tonic::Status>;
+
     /// Returns a [`tonic::Response`] containing a [`ReadyResponse`](crate::ReadyResponse)
     /// Takes an [`ReadyRequest`](crate::ReadyRequest)
     ///
@@ -301,6 +301,84 @@ where
     /// ```
     async fn delete(&self, request: crate::Ids) -> Result<tonic::Response<()>, tonic::Status>;
 
+    /// Takes an [`Ids`](crate::Ids) object to restore the associated soft-deleted record in the database.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`tonic::Status`] with [`tonic::Code::Internal`] if the record is not deleted or any error is returned from a db call.
+    /// Returns [`tonic::Status`] with [`tonic::Code::Internal`] if the provided Ids can not be converted to a [`uuid::Uuid`].
+    /// Returns [`tonic::Status`] with [`tonic::Code::Unknown`] if the server is not ready.
+    ///
+    /// # Examples
+    /// ```
+    /// use lib_common::grpc::get_endpoint_from_env;
+    /// use svc_storage_client_grpc::prelude::*;
+    /// use flight_plan_parcel::*;
+    ///
+    /// async fn example () -> Result<(), Box<dyn std::error::Error>> {
+    ///     let (host, port) = get_endpoint_from_env("SERVER_HOSTNAME", "SERVER_PORT_GRPC");
+    ///     let clients = Clients::new(host, port);
+    ///     let link_client = clients.flight_plan_parcel;
+    ///     let flight_plan_id = String::from("53acfe06-dd9b-42e8-8cb4-12a2fb2fa693");
+    ///     let parcel_id = String::from("73acfe06-dd9b-41e8-4cb4-12a2fb2fa693");
+    ///     let ids = Ids {
+    ///         ids: vec![
+    ///             FieldValue {
+    ///                 field: String::from("flight_plan_id"),
+    ///                 value: flight_plan_id
+    ///             },
+    ///             FieldValue {
+    ///                 field: String::from("parcel_id"),
+    ///                 value: parcel_id
+    ///             },
+    ///         ]
+    ///     };
+    ///     link_client.restore(ids).await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    async fn restore(&self, request: crate::Ids) -> Result<tonic::Response<()>, tonic::Status>;
+
+    /// Takes an [`Ids`](crate::Ids) object to permanently remove the associated records from the database.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`tonic::Status`] with [`tonic::Code::Internal`] if the record is not deleted yet or any error is returned from a db call.
+    /// Returns [`tonic::Status`] with [`tonic::Code::Internal`] if the provided Ids can not be converted to a [`uuid::Uuid`].
+    /// Returns [`tonic::Status`] with [`tonic::Code::Unknown`] if the server is not ready.
+    ///
+    /// # Examples
+    /// ```
+    /// use lib_common::grpc::get_endpoint_from_env;
+    /// use svc_storage_client_grpc::prelude::*;
+    /// use flight_plan_parcel::*;
+    ///
+    /// async fn example () -> Result<(), Box<dyn std::error::Error>> {
+    ///     let (host, port) = get_endpoint_from_env("SERVER_HOSTNAME", "SERVER_PORT_GRPC");
+    ///     let clients = Clients::new(host, port);
+    ///     let link_client = clients.flight_plan_parcel;
+    ///     let flight_plan_id = String::from("53acfe06-dd9b-42e8-8cb4-12a2fb2fa693");
+    ///     let parcel_id = String::from("73acfe06-dd9b-41e8-4cb4-12a2fb2fa693");
+    ///     let ids = Ids {
+    ///         ids: vec![
+    ///             FieldValue {
+    ///                 field: String::from("flight_plan_id"),
+    ///                 value: flight_plan_id
+    ///             },
+    ///             FieldValue {
+    ///                 field: String::from("parcel_id"),
+    ///                 value: parcel_id
+    ///             },
+    ///         ]
+    ///     };
+    ///     link_client.purge(ids).await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    async fn purge(&self, request: crate::Ids) -> Result<tonic::Response<()>, tonic::Status>;
+
     /// Search database records using an advanced filter
     ///
     /// This method supports paged results.
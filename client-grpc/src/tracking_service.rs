@@ -0,0 +1,74 @@
+//! GRPC Tracking Service traits
+
+use crate::resources::adsb::{LatestPositionsRequest, LatestPositionsResponse};
+
+/// Generic gRPC object traits to provide wrappers for adsb tracking functions
+#[tonic::async_trait]
+pub trait Client<T>
+where
+    Self: Sized + lib_common::grpc::Client<T> + lib_common::grpc::ClientConnect<T>,
+    T: Send + Clone,
+{
+    /// Returns a [`tonic::Response`] containing a [`LatestPositionsResponse`]
+    /// with the latest known state of each aircraft, ordered by ICAO address.
+    ///
+    /// For each aircraft, the most recent message is returned, together with the most recent decoded position if available.
+    /// If `bounds` are provided, only aircraft with a latest known position within the polygon are returned.
+    /// Only messages received within `max_age_seconds` seconds are taken into account, defaulting to 300 seconds if not provided.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`tonic::Status`] with [`tonic::Code::InvalidArgument`] if the provided bounds are not a valid polygon.
+    /// Returns [`tonic::Status`] with [`tonic::Code::Internal`] if any error is returned from a db call.
+    ///
+    /// # Examples
+    /// ```
+    /// use lib_common::grpc::get_endpoint_from_env;
+    /// use svc_storage_client_grpc::prelude::*;
+    /// use svc_storage_client_grpc::adsb::LatestPositionsRequest;
+    ///
+    /// async fn example () -> Result<(), Box<dyn std::error::Error>> {
+    ///     let (host, port) = get_endpoint_from_env("SERVER_HOSTNAME", "SERVER_PORT_GRPC");
+    ///     let clients = Clients::new(host, port);
+    ///     let response = clients.adsb_tracking
+    ///         .get_latest_positions(LatestPositionsRequest {
+    ///             bounds: None,
+    ///             max_age_seconds: Some(60),
+    ///         })
+    ///         .await?;
+    ///     println!("RESPONSE={:?}", response.into_inner());
+    ///     Ok(())
+    /// }
+    /// ```
+    async fn get_latest_positions(
+        &self,
+        request: LatestPositionsRequest,
+    ) -> Result<tonic::Response<LatestPositionsResponse>, tonic::Status>;
+
+    /// Returns a [`tonic::Response`] containing a [`ReadyResponse`](crate::ReadyResponse)
+    /// Takes an [`ReadyRequest`](crate::ReadyRequest)
+    ///
+    /// # Errors
+    ///
+    /// Returns [`tonic::Status`] with [`tonic::Code::Unknown`] if the server is not ready.
+    ///
+    /// # Examples
+    /// ```
+    /// use lib_common::grpc::get_endpoint_from_env;
+    /// use svc_storage_client_grpc::prelude::*;
+    ///
+    /// async fn example () -> Result<(), Box<dyn std::error::Error>> {
+    ///     let (host, port) = get_endpoint_from_env("SERVER_HOSTNAME", "SERVER_PORT_GRPC");
+    ///     let clients = Clients::new(host, port);
+    ///     let response = clients.adsb_tracking
+    ///         .is_ready(ReadyRequest {})
+    ///         .await?;
+    ///     println!("RESPONSE={:?}", response.into_inner());
+    ///     Ok(())
+    /// }
+    /// ```
+    async fn is_ready(
+        &self,
+        request: crate::ReadyRequest,
+    ) -> Result<tonic::Response<crate::ReadyResponse>, tonic::Status>;
+}
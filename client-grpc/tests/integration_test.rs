@@ -25,6 +25,12 @@ async fn test_client_requests_and_logs() {
     println!("{:?}", result);
     #[cfg(not(any(feature = "stub_backends", feature = "stub_client")))]
     assert!(result.is_ok());
+    #[cfg(not(any(feature = "stub_backends", feature = "stub_client")))]
+    let result = adsb::test_deduplication(&clients.adsb).await;
+    #[cfg(not(any(feature = "stub_backends", feature = "stub_client")))]
+    println!("{:?}", result);
+    #[cfg(not(any(feature = "stub_backends", feature = "stub_client")))]
+    assert!(result.is_ok());
 
     // generate 5 random messages
     let mut messages_data: Vec<adsb::Data> = vec![];
@@ -34,7 +40,8 @@ async fn test_client_requests_and_logs() {
     }
 
     // play scenario
-    let _messages: adsb::List = adsb::scenario(&clients.adsb, messages_data, &mut logger).await;
+    let messages: adsb::List = adsb::scenario(&clients.adsb, messages_data, &mut logger).await;
+    adsb::test_latest_positions(&clients.adsb_tracking, &messages).await;
 
     //----------------------------------------------------
     // Vertiports
@@ -79,6 +86,20 @@ async fn test_client_requests_and_logs() {
         vertipads.push(vertipad.id.clone());
         vertiport_vertipads.insert(vertiport_id.clone(), vertipads);
     }
+    //----------------------------------------------------
+    // Vehicle Models
+    //----------------------------------------------------
+    // generate 2 random vehicle models
+    let mut vehicle_models_data: Vec<vehicle_model::Data> = vec![];
+    for _ in 0..2 {
+        vehicle_models_data.push(vehicle_model::mock::get_data_obj());
+    }
+
+    // play scenario
+    let vehicle_models: vehicle_model::List =
+        vehicle_model::scenario(&clients.vehicle_model, vehicle_models_data, &mut logger).await;
+    let vehicle_model_id = vehicle_models.list[0].id.clone();
+
     //----------------------------------------------------
     // Vehicles
     //----------------------------------------------------
@@ -86,11 +107,13 @@ async fn test_client_requests_and_logs() {
     let mut vehicles_data: Vec<vehicle::Data> = vec![];
     for index in 0..5 {
         let mut vehicle = vehicle::mock::get_data_obj();
+        vehicle.vehicle_model_id = vehicle_model_id.clone();
         vehicle.description = Some(format!("Mock vehicle {}", index + 1));
         vehicles_data.push(vehicle);
     }
     for (vertiport, vertipads) in &vertiport_vertipads {
         let mut vehicle = vehicle::mock::get_data_obj();
+        vehicle.vehicle_model_id = vehicle_model_id.clone();
         vehicle.description = Some(format!("Mock vehicle vertiports {}", vertiport.clone()));
         vehicle.hangar_id = Some(vertiport.clone());
         vehicle.hangar_bay_id = Some(vertipads[0].clone());
@@ -105,25 +128,51 @@ async fn test_client_requests_and_logs() {
     // Flight Plans
     //----------------------------------------------------
     // generate 5 random future flight_plans
+    // use a different vehicle for each flight_plan, a vehicle can't be assigned to overlapping flights
+    // the flight path has to start at the origin vertipad and end at the target vertipad
+    let vertipad_locations: Vec<flight_plan::GeoPoint> = vertipads
+        .list
+        .iter()
+        .map(|vertipad| {
+            vertipad
+                .data
+                .as_ref()
+                .and_then(|data| data.geo_location)
+                .expect("No Geo location found for vertipad")
+        })
+        .collect();
     let mut flight_plans_data: Vec<flight_plan::Data> = vec![];
-    for _ in 0..5 {
+    for index in 0..5 {
         let mut flight_plan = flight_plan::mock::get_future_data_obj();
         flight_plan.origin_vertipad_id = vertipads.list[0].id.clone();
         flight_plan.target_vertipad_id = vertipads.list[1].id.clone();
-        flight_plan.vehicle_id = vehicles.list[0].id.clone();
+        flight_plan.path = Some(flight_plan::GeoLineString {
+            points: vec![vertipad_locations[0], vertipad_locations[1]],
+            times: vec![],
+        });
+        flight_plan.vehicle_id = vehicles.list[index].id.clone();
         flight_plans_data.push(flight_plan);
     }
-    for _ in 0..5 {
+    for index in 0..5 {
         let mut flight_plan = flight_plan::mock::get_past_data_obj();
         flight_plan.origin_vertipad_id = vertipads.list[1].id.clone();
         flight_plan.target_vertipad_id = vertipads.list[0].id.clone();
-        flight_plan.vehicle_id = vehicles.list[1].id.clone();
+        flight_plan.path = Some(flight_plan::GeoLineString {
+            points: vec![vertipad_locations[1], vertipad_locations[0]],
+            times: vec![],
+        });
+        flight_plan.vehicle_id = vehicles.list[index + 5].id.clone();
         flight_plans_data.push(flight_plan);
     }
 
     // play scenario
-    let _flight_plans: flight_plan::List =
+    let flight_plans: flight_plan::List =
         flight_plan::scenario(&clients.flight_plan, flight_plans_data, &mut logger).await;
+    flight_plan::test_vertipad_availability(&clients.flight_plan_scheduling, &flight_plans).await;
+    flight_plan::test_committed_time_ranges(&clients.flight_plan_scheduling, &flight_plans).await;
+    flight_plan::test_conflicting_flight_plans(&clients.flight_plan_scheduling, &flight_plans)
+        .await;
+    flight_plan::test_transition_status(&clients.flight_plan_scheduling, &flight_plans).await;
 
     //----------------------------------------------------
     // Users
@@ -140,6 +189,12 @@ async fn test_client_requests_and_logs() {
     // play scenario
     let users: user::List = user::scenario(&clients.user, users_data, &mut logger).await;
 
+    //----------------------------------------------------
+    // Parcels
+    //----------------------------------------------------
+    parcel::test_custody_timeline(&clients.parcel, &clients.parcel_custody, &users.list[0].id)
+        .await;
+
     //----------------------------------------------------
     // groups
     //----------------------------------------------------
@@ -39,7 +39,10 @@ pub async fn scenario(client: &AdsbClient, data: Vec<Data>, logger: &mut Logger)
         assert_eq!(data.network_timestamp, adsb_data.network_timestamp);
         assert_eq!(data.payload, adsb_data.payload);
     }
-    let messages = List { list: adsb_objects };
+    let messages = List {
+        list: adsb_objects,
+        ..Default::default()
+    };
 
     // Check if all messages can be retrieved from the backend
     println!("Starting search adsb");
@@ -89,6 +92,86 @@ pub async fn scenario(client: &AdsbClient, data: Vec<Data>, logger: &mut Logger)
     messages
 }
 
+pub async fn test_latest_positions(client: &AdsbTrackingClient, messages: &List) {
+    assert_eq!(client.get_name(), "adsb_tracking");
+
+    // The first message has been deleted by the scenario, the others should be returned
+    let result = client
+        .get_latest_positions(LatestPositionsRequest {
+            bounds: None,
+            max_age_seconds: Some(3600),
+        })
+        .await;
+    println!("{:?}", result);
+    assert!(result.is_ok());
+    let response = result.unwrap().into_inner();
+    for message in &messages.list[1..] {
+        let data = message.data.clone().unwrap();
+        let latest = response
+            .list
+            .iter()
+            .find(|latest| latest.icao_address == data.icao_address);
+        assert!(latest.is_some());
+        let latest = latest.unwrap().latest.clone().unwrap().data.unwrap();
+        let (received, expected) = (
+            latest.network_timestamp.unwrap(),
+            data.network_timestamp.unwrap(),
+        );
+        assert!((received.seconds, received.nanos) >= (expected.seconds, expected.nanos));
+    }
+
+    // The mock messages don't contain a position, so they can't be within any bounds
+    let point = |longitude: f64, latitude: f64| GeoPoint {
+        longitude,
+        latitude,
+        altitude: None,
+    };
+    let bounds = GeoPolygon {
+        exterior: Some(GeoLineString {
+            points: vec![
+                point(-180.0, -90.0),
+                point(180.0, -90.0),
+                point(180.0, 90.0),
+                point(-180.0, 90.0),
+                point(-180.0, -90.0),
+            ],
+            times: vec![],
+        }),
+        interiors: vec![],
+    };
+    let result = client
+        .get_latest_positions(LatestPositionsRequest {
+            bounds: Some(bounds.clone()),
+            max_age_seconds: Some(3600),
+        })
+        .await;
+    println!("{:?}", result);
+    assert!(result.is_ok());
+    let response = result.unwrap().into_inner();
+    assert!(response.list.iter().all(|latest| latest.position.is_some()));
+    for message in &messages.list[1..] {
+        let icao_address = message.data.as_ref().unwrap().icao_address;
+        assert!(!response
+            .list
+            .iter()
+            .any(|latest| latest.icao_address == icao_address));
+    }
+
+    // Bounds should be a closed loop
+    let mut open_bounds = bounds;
+    if let Some(exterior) = open_bounds.exterior.as_mut() {
+        exterior.points.pop();
+    }
+    let result = client
+        .get_latest_positions(LatestPositionsRequest {
+            bounds: Some(open_bounds),
+            max_age_seconds: None,
+        })
+        .await;
+    println!("{:?}", result);
+    assert_eq!(result.unwrap_err().code(), tonic::Code::InvalidArgument);
+}
+
 #[cfg(not(any(feature = "stub_backends", feature = "stub_client")))]
 pub async fn test_telemetry(client: &AdsbClient) -> Result<(), Box<dyn std::error::Error>> {
     use chrono::naive::NaiveDate;
@@ -133,6 +216,12 @@ pub async fn test_telemetry(client: &AdsbClient) -> Result<(), Box<dyn std::erro
         message_type,
         network_timestamp: Some(timestamp_1.clone()),
         payload: payload_1.clone().to_vec(),
+        position: None,
+        baro_altitude: None,
+        ground_speed: None,
+        heading: None,
+        callsign: None,
+        receivers: vec![String::from("receiver-1")],
     };
 
     // Insert data and get the UUID of the adsb entry
@@ -150,6 +239,12 @@ pub async fn test_telemetry(client: &AdsbClient) -> Result<(), Box<dyn std::erro
         message_type,
         network_timestamp: Some(timestamp_2),
         payload: payload_2.clone().to_vec(),
+        position: None,
+        baro_altitude: None,
+        ground_speed: None,
+        heading: None,
+        callsign: None,
+        receivers: vec![String::from("receiver-1")],
     };
     // Insert data and get the UUID of the adsb entry
     let response = client.insert(request_data).await?;
@@ -187,6 +282,9 @@ pub async fn test_telemetry(client: &AdsbClient) -> Result<(), Box<dyn std::erro
         assert_eq!(data.icao_address, icao_address);
         assert_eq!(data.message_type, message_type);
         assert_eq!(data.payload, payload_1);
+        // payload_1 is a valid identification message, decoded on insert
+        #[cfg(not(any(feature = "stub_client", feature = "stub_backends")))]
+        assert_eq!(data.callsign, Some(String::from("KLM1023")));
     }
 
     {
@@ -240,3 +338,74 @@ pub async fn test_telemetry(client: &AdsbClient) -> Result<(), Box<dyn std::erro
 
     Ok(())
 }
+
+#[cfg(not(any(feature = "stub_backends", feature = "stub_client")))]
+pub async fn test_deduplication(client: &AdsbClient) -> Result<(), Box<dyn std::error::Error>> {
+    use chrono::{Duration, SubsecRound, Utc};
+
+    // Stored timestamps have a limited precision
+    let now = Utc::now().trunc_subsecs(3);
+    let timestamp_1: Timestamp = now.into();
+    let timestamp_2: Timestamp = (now - Duration::milliseconds(200)).into();
+
+    // Identification message of a made up aircraft, so no other messages will match
+    let payload = [
+        0x8D, 0x4C, 0xA2, 0xD1, 0x20, 0x2C, 0xC3, 0x71, 0xC3, 0x2C, 0xE0, 0x57, 0x60, 0x98,
+    ];
+    let icao_address = 0x4CA2D1;
+    let message_type = 4;
+    let get_data = |network_timestamp: &Timestamp, receiver: &str| Data {
+        icao_address,
+        message_type,
+        network_timestamp: Some(network_timestamp.clone()),
+        payload: payload.to_vec(),
+        position: None,
+        baro_altitude: None,
+        ground_speed: None,
+        heading: None,
+        callsign: None,
+        receivers: vec![receiver.to_owned()],
+    };
+
+    // The first receiver's message is stored
+    let response = client
+        .insert(get_data(&timestamp_1, "receiver-1"))
+        .await?
+        .into_inner();
+    assert!(!response.deduplicated);
+    let Some(object) = response.object else {
+        panic!("Failed to return object.");
+    };
+    let id = object.id;
+
+    // The same message, received earlier by another receiver, is merged into the stored message
+    let response = client
+        .insert(get_data(&timestamp_2, "receiver-2"))
+        .await?
+        .into_inner();
+    assert!(response.deduplicated);
+    let Some(object) = response.object else {
+        panic!("Failed to return object.");
+    };
+    assert_eq!(object.id, id);
+
+    let object = client.get_by_id(Id { id: id.clone() }).await?.into_inner();
+    let data = object.data.unwrap();
+    assert_eq!(data.network_timestamp, Some(timestamp_2.clone()));
+    assert_eq!(
+        data.receivers,
+        vec![String::from("receiver-1"), String::from("receiver-2")]
+    );
+
+    let filter =
+        AdvancedSearchFilter::search_equals("icao_address".to_owned(), icao_address.to_string())
+            .page_number(1)
+            .results_per_page(50);
+    let list: List = client.search(filter).await?.into_inner();
+    assert_eq!(list.list.len(), 1);
+
+    // clean up test data
+    client.delete(Id { id }).await?;
+
+    Ok(())
+}
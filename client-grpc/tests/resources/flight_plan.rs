@@ -85,6 +85,7 @@ pub async fn scenario(client: &FlightPlanClient, data: Vec<Data>, logger: &mut L
     }
     let flight_plans = List {
         list: flight_plan_objects,
+        ..Default::default()
     };
 
     // Check if all flight_plans can be retrieved from the backend
@@ -148,3 +149,195 @@ pub async fn scenario(client: &FlightPlanClient, data: Vec<Data>, logger: &mut L
 
     flight_plans
 }
+
+pub async fn test_vertipad_availability(client: &FlightPlanSchedulingClient, flight_plans: &List) {
+    // The first flight_plan has been deleted by the scenario, use the second one
+    let flight_plan = flight_plans.list[1].clone();
+    let data = flight_plan.data.unwrap();
+
+    // The origin timeslot of an existing flight_plan should not be available
+    let result = client
+        .check_vertipad_availability(VertipadAvailabilityRequest {
+            vertipad_id: data.origin_vertipad_id.clone(),
+            timeslot_start: data.origin_timeslot_start.clone(),
+            timeslot_end: data.origin_timeslot_end.clone(),
+            exclude_flight_plan_id: None,
+        })
+        .await;
+    println!("{:?}", result);
+    assert!(result.is_ok());
+    let response = result.unwrap().into_inner();
+    assert!(!response.available);
+    assert!(response
+        .conflicting_flight_plan_ids
+        .contains(&flight_plan.id));
+
+    // Unless we're excluding the flight_plan itself
+    let result = client
+        .check_vertipad_availability(VertipadAvailabilityRequest {
+            vertipad_id: data.origin_vertipad_id.clone(),
+            timeslot_start: data.origin_timeslot_start.clone(),
+            timeslot_end: data.origin_timeslot_end.clone(),
+            exclude_flight_plan_id: Some(flight_plan.id.clone()),
+        })
+        .await;
+    println!("{:?}", result);
+    assert!(result.is_ok());
+    let response = result.unwrap().into_inner();
+    assert!(!response
+        .conflicting_flight_plan_ids
+        .contains(&flight_plan.id));
+
+    // An invalid timeslot should be rejected
+    let result = client
+        .check_vertipad_availability(VertipadAvailabilityRequest {
+            vertipad_id: data.origin_vertipad_id,
+            timeslot_start: data.origin_timeslot_end,
+            timeslot_end: data.origin_timeslot_start,
+            exclude_flight_plan_id: None,
+        })
+        .await;
+    println!("{:?}", result);
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err().code(), tonic::Code::InvalidArgument);
+}
+
+pub async fn test_committed_time_ranges(client: &FlightPlanSchedulingClient, flight_plans: &List) {
+    // The first flight_plan has been deleted by the scenario, use the second one
+    let flight_plan = flight_plans.list[1].clone();
+    let data = flight_plan.data.unwrap();
+
+    // The vehicle should be committed from the origin timeslot start until the target timeslot end
+    let result = client
+        .get_committed_time_ranges(CommittedTimeRangesRequest {
+            asset_type: AssetType::Vehicle as i32,
+            asset_id: data.vehicle_id.clone(),
+            window_start: data.origin_timeslot_start.clone(),
+            window_end: data.target_timeslot_end.clone(),
+        })
+        .await;
+    println!("{:?}", result);
+    assert!(result.is_ok());
+    let response = result.unwrap().into_inner();
+    assert_eq!(response.list.len(), 1);
+    assert_eq!(response.list[0].flight_plan_id, flight_plan.id);
+    assert_eq!(response.list[0].start, data.origin_timeslot_start);
+    assert_eq!(response.list[0].end, data.target_timeslot_end);
+
+    // The pilot should be committed for the same time range
+    let result = client
+        .get_committed_time_ranges(CommittedTimeRangesRequest {
+            asset_type: AssetType::Pilot as i32,
+            asset_id: data.pilot_id,
+            window_start: data.origin_timeslot_start,
+            window_end: data.target_timeslot_end,
+        })
+        .await;
+    println!("{:?}", result);
+    assert!(result.is_ok());
+    let response = result.unwrap().into_inner();
+    assert!(response
+        .list
+        .iter()
+        .any(|range| range.flight_plan_id == flight_plan.id));
+}
+
+pub async fn test_conflicting_flight_plans(
+    client: &FlightPlanSchedulingClient,
+    flight_plans: &List,
+) {
+    // The first flight_plan has been deleted by the scenario, use the second one
+    let flight_plan = flight_plans.list[1].clone();
+    let data = flight_plan.data.unwrap();
+
+    // A flight_plan conflicts with its own path during its timeslots
+    let result = client
+        .find_conflicting_flight_plans(ConflictingFlightPlansRequest {
+            path: data.path.clone(),
+            window_start: data.origin_timeslot_start.clone(),
+            window_end: data.target_timeslot_end.clone(),
+            min_separation_meters: 0.0,
+            exclude_flight_plan_id: None,
+        })
+        .await;
+    println!("{:?}", result);
+    assert!(result.is_ok());
+    let response = result.unwrap().into_inner();
+    let conflict = response.list.iter().find(|conflict| {
+        conflict.flight_plan.as_ref().map(|object| &object.id) == Some(&flight_plan.id)
+    });
+    assert!(conflict.is_some());
+    assert_eq!(conflict.unwrap().distance_meters, 0.0);
+
+    // Unless we're excluding the flight_plan itself
+    let result = client
+        .find_conflicting_flight_plans(ConflictingFlightPlansRequest {
+            path: data.path.clone(),
+            window_start: data.origin_timeslot_start.clone(),
+            window_end: data.target_timeslot_end.clone(),
+            min_separation_meters: 0.0,
+            exclude_flight_plan_id: Some(flight_plan.id.clone()),
+        })
+        .await;
+    println!("{:?}", result);
+    assert!(result.is_ok());
+    assert!(!result
+        .unwrap()
+        .into_inner()
+        .list
+        .iter()
+        .any(
+            |conflict| conflict.flight_plan.as_ref().map(|object| &object.id)
+                == Some(&flight_plan.id)
+        ));
+
+    // A negative separation should be rejected
+    let result = client
+        .find_conflicting_flight_plans(ConflictingFlightPlansRequest {
+            path: data.path,
+            window_start: data.origin_timeslot_start,
+            window_end: data.target_timeslot_end,
+            min_separation_meters: -1.0,
+            exclude_flight_plan_id: None,
+        })
+        .await;
+    println!("{:?}", result);
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err().code(), tonic::Code::InvalidArgument);
+}
+
+pub async fn test_transition_status(client: &FlightPlanSchedulingClient, flight_plans: &List) {
+    let flight_plan = flight_plans.list[2].clone();
+    let changed_by = uuid::Uuid::new_v4().to_string();
+
+    // A future flight_plan can always be cancelled
+    let result = client
+        .transition_status(TransitionStatusRequest {
+            id: flight_plan.id.clone(),
+            flight_status: FlightStatus::Cancelled as i32,
+            changed_by: changed_by.clone(),
+        })
+        .await;
+    println!("{:?}", result);
+    assert!(result.is_ok());
+    let response = result.unwrap().into_inner();
+    assert!(response.validation_result.unwrap().success);
+    let data = response.object.unwrap().data.unwrap();
+    assert_eq!(data.flight_status, FlightStatus::Cancelled as i32);
+
+    // A cancelled flight_plan can not be reactivated
+    let result = client
+        .transition_status(TransitionStatusRequest {
+            id: flight_plan.id.clone(),
+            flight_status: FlightStatus::Ready as i32,
+            changed_by,
+        })
+        .await;
+    println!("{:?}", result);
+    assert!(result.is_ok());
+    let response = result.unwrap().into_inner();
+    assert!(response.object.is_none());
+    let validation_result = response.validation_result.unwrap();
+    assert!(!validation_result.success);
+    assert_eq!(validation_result.errors[0].field, "flight_status");
+}
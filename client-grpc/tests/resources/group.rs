@@ -68,6 +68,7 @@ pub async fn scenario(client: &GroupClient, data: Vec<Data>, logger: &mut Logger
     }
     let groups = List {
         list: group_objects,
+        ..Default::default()
     };
 
     // Check if all groups can be retrieved from the backend
@@ -9,10 +9,12 @@ pub mod group_user;
 pub mod group_vehicle;
 pub mod group_vertipad;
 pub mod group_vertiport;
+pub mod parcel;
 pub mod user;
 pub mod user_group;
 pub mod vehicle;
 pub mod vehicle_group;
+pub mod vehicle_model;
 pub mod vertipad;
 pub mod vertipad_group;
 pub mod vertiport;
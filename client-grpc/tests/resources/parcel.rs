@@ -0,0 +1,42 @@
+//! parcel test helper functions
+
+use svc_storage_client_grpc::prelude::*;
+
+pub use parcel::*;
+
+pub async fn test_custody_timeline(
+    client: &ParcelClient,
+    custody_client: &ParcelCustodyClient,
+    user_id: &str,
+) {
+    let mut data = mock::get_data_obj();
+    data.user_id = user_id.to_owned();
+    data.status = ParcelStatus::Notdroppedoff as i32;
+
+    let result = client.insert(data).await;
+    println!("{:?}", result);
+    assert!(result.is_ok());
+    let parcel = result.unwrap().into_inner().object.unwrap();
+
+    // A new parcel has no scans, flight legs or status changes yet
+    let result = custody_client
+        .get_custody_timeline(CustodyTimelineRequest {
+            parcel_id: parcel.id.clone(),
+        })
+        .await;
+    println!("{:?}", result);
+    assert!(result.is_ok());
+    let timeline = result.unwrap().into_inner();
+    assert_eq!(timeline.parcel_id, parcel.id);
+    assert_eq!(timeline.status, ParcelStatus::Notdroppedoff as i32);
+    assert!(timeline.list.is_empty());
+
+    // Unknown parcels should return a NotFound status
+    let result = custody_client
+        .get_custody_timeline(CustodyTimelineRequest {
+            parcel_id: uuid::Uuid::new_v4().to_string(),
+        })
+        .await;
+    println!("{:?}", result);
+    assert_eq!(result.unwrap_err().code(), tonic::Code::NotFound);
+}
@@ -38,7 +38,10 @@ pub async fn scenario(client: &UserClient, data: Vec<Data>, logger: &mut Logger)
         assert_eq!(data.email, user_data.email);
         assert_eq!(data.auth_method, user_data.auth_method);
     }
-    let users = List { list: user_objects };
+    let users = List {
+        list: user_objects,
+        ..Default::default()
+    };
 
     // Check if all users can be retrieved from the backend
     let result = client.search(not_deleted_filter.clone()).await;
@@ -47,6 +47,7 @@ pub async fn scenario(client: &VehicleClient, data: Vec<Data>, logger: &mut Logg
     }
     let vehicles = List {
         list: vehicle_objects,
+        ..Default::default()
     };
 
     // Check if all vehicles can be retrieved from the backend
@@ -0,0 +1,201 @@
+//! Vehicle Model test helper functions
+
+use super::utils::{check_log_string_matches, get_log_string};
+use futures::StreamExt;
+use logtest::Logger;
+use svc_storage_client_grpc::prelude::*;
+
+pub use vehicle_model::*;
+
+pub async fn scenario(client: &VehicleModelClient, data: Vec<Data>, logger: &mut Logger) -> List {
+    let name = "vehicle_model";
+    assert_eq!(client.get_name(), name);
+
+    let not_deleted_filter = AdvancedSearchFilter::search_is_null("deleted_at".to_owned())
+        .page_number(1)
+        .results_per_page(50);
+
+    let mut vehicle_model_objects = vec![];
+
+    // Insert vehicle_models for each mock object
+    for vehicle_model_data in data {
+        println!("Starting insert vehicle_model");
+        let result = client.insert(vehicle_model_data.clone()).await;
+
+        let expected = get_log_string("insert", name);
+        println!("expected message: {}", expected);
+        assert!(logger.any(|log| check_log_string_matches(log, &expected)));
+
+        println!("{:?}", result);
+        assert!(result.is_ok());
+        let vehicle_model: Response = (result.unwrap()).into_inner();
+        assert!(vehicle_model.object.is_some());
+        let vehicle_model = vehicle_model.object.unwrap();
+        vehicle_model_objects.push(vehicle_model.clone());
+
+        assert!(vehicle_model.clone().data.is_some());
+        let data = vehicle_model.data.unwrap();
+        assert_eq!(data.manufacturer_id, vehicle_model_data.manufacturer_id);
+        assert_eq!(data.model_name, vehicle_model_data.model_name);
+        assert_eq!(data.r#type, vehicle_model_data.r#type);
+        assert_eq!(data.max_payload_kg, vehicle_model_data.max_payload_kg);
+        assert_eq!(data.max_range_km, vehicle_model_data.max_range_km);
+    }
+    let vehicle_models = List {
+        list: vehicle_model_objects,
+        ..Default::default()
+    };
+
+    // Check if all vehicle_models can be retrieved from the backend
+    let result = client.search(not_deleted_filter.clone()).await;
+    let expected = get_log_string("search", name);
+    println!("expected message: {}", expected);
+    assert!(logger.any(|log| check_log_string_matches(log, &expected)));
+
+    println!("{:?}", result);
+    assert!(result.is_ok());
+    let vehicle_models_from_db: List = result.unwrap().into_inner();
+    assert_eq!(vehicle_models_from_db.list.len(), vehicle_models.list.len());
+
+    // Check if all vehicle_models can be streamed from the backend
+    let result = client.search_stream(not_deleted_filter.clone()).await;
+    let expected = get_log_string("search_stream", name);
+    println!("expected message: {}", expected);
+    assert!(logger.any(|log| check_log_string_matches(log, &expected)));
+
+    assert!(result.is_ok());
+    let streamed: Vec<Result<Object, tonic::Status>> = result.unwrap().into_inner().collect().await;
+    assert_eq!(streamed.len(), vehicle_models.list.len());
+    assert!(streamed.iter().all(|object| object.is_ok()));
+
+    let vehicle_model_id = vehicle_models.list[0].id.clone();
+
+    // Check if we can get a single vehicle_model based on their id
+    let result = client
+        .get_by_id(Id {
+            id: vehicle_model_id.clone(),
+        })
+        .await;
+
+    let expected = get_log_string("get_by_id", name);
+    println!("expected message: {}", expected);
+    assert!(logger.any(|log| check_log_string_matches(log, &expected)));
+
+    println!("{:?}", result);
+    assert!(result.is_ok());
+    let vehicle_model_from_db: Object = result.unwrap().into_inner();
+    assert_eq!(vehicle_model_from_db.id, vehicle_model_id);
+
+    // Check if we can update the vehicle_model's payload limit
+    let mut vehicle_model_data = vehicle_model_from_db.data.unwrap();
+    vehicle_model_data.max_payload_kg = Some(250.0);
+    let result = client
+        .update(UpdateObject {
+            id: vehicle_model_id.clone(),
+            data: Some(vehicle_model_data),
+            mask: Some(FieldMask {
+                paths: vec!["max_payload_kg".to_owned()],
+            }),
+        })
+        .await;
+
+    let expected = get_log_string("update", name);
+    println!("expected message: {}", expected);
+    assert!(logger.any(|log| check_log_string_matches(log, &expected)));
+
+    println!("{:?}", result);
+    assert!(result.is_ok());
+    let vehicle_model: Response = result.unwrap().into_inner();
+    assert!(vehicle_model.object.is_some());
+    let data = vehicle_model.object.unwrap().data.unwrap();
+    assert_eq!(data.max_payload_kg, Some(250.0));
+
+    // Check if we receive change events for the vehicle_models, starting from the first recorded change
+    let filter =
+        AdvancedSearchFilter::search_equals("model_name".to_owned(), data.model_name.clone())
+            .page_token(String::from("0"));
+    let result = client.watch(filter).await;
+    let expected = get_log_string("watch", name);
+    println!("expected message: {}", expected);
+    assert!(logger.any(|log| check_log_string_matches(log, &expected)));
+
+    assert!(result.is_ok());
+    let event = result.unwrap().into_inner().next().await;
+    println!("{:?}", event);
+    assert!(event.is_some());
+    let event: ChangeEvent = event.unwrap().unwrap();
+    assert_eq!(event.change_type(), ChangeType::Created);
+    assert!(event.object.is_some());
+
+    // Check if we can insert and delete multiple vehicle_models in a single batch
+    let result = client
+        .insert_many(InsertManyRequest {
+            list: vec![data.clone(), data],
+            mode: BatchMode::AllOrNothing as i32,
+        })
+        .await;
+
+    let expected = get_log_string("insert_many", name);
+    println!("expected message: {}", expected);
+    assert!(logger.any(|log| check_log_string_matches(log, &expected)));
+
+    println!("{:?}", result);
+    assert!(result.is_ok());
+    let inserted: ResponseList = result.unwrap().into_inner();
+    assert!(inserted.success);
+    assert_eq!(inserted.list.len(), 2);
+
+    let ids: Vec<String> = inserted
+        .list
+        .into_iter()
+        .filter_map(|response| response.object.map(|object| object.id))
+        .collect();
+    assert_eq!(ids.len(), 2);
+
+    // A failing item should fail all items of an AllOrNothing batch, including the items after it
+    #[cfg(not(any(feature = "stub_client", feature = "stub_backends")))]
+    {
+        let result = client
+            .delete_many(DeleteManyRequest {
+                ids: vec![String::from("INVALID"), ids[0].clone()],
+                mode: BatchMode::AllOrNothing as i32,
+            })
+            .await;
+        println!("{:?}", result);
+        assert!(result.is_ok());
+        let deleted: DeleteManyResponse = result.unwrap().into_inner();
+        assert!(!deleted.success);
+        assert_eq!(deleted.results.len(), 2);
+        assert!(deleted.results.iter().all(|result| !result.success));
+    }
+
+    let result = client
+        .delete_many(DeleteManyRequest {
+            ids,
+            mode: BatchMode::AllOrNothing as i32,
+        })
+        .await;
+
+    let expected = get_log_string("delete_many", name);
+    println!("expected message: {}", expected);
+    assert!(logger.any(|log| check_log_string_matches(log, &expected)));
+
+    println!("{:?}", result);
+    assert!(result.is_ok());
+    let deleted: DeleteManyResponse = result.unwrap().into_inner();
+    assert!(deleted.success);
+    assert_eq!(deleted.results.len(), 2);
+
+    // Check if the service reports a healthy database connection
+    let result = client.get_health(ReadyRequest {}).await;
+    let expected = get_log_string("get_health", name);
+    println!("expected message: {}", expected);
+    assert!(logger.any(|log| check_log_string_matches(log, &expected)));
+
+    println!("{:?}", result);
+    assert!(result.is_ok());
+    let health: HealthResponse = result.unwrap().into_inner();
+    assert!(health.ready);
+
+    vehicle_models_from_db
+}
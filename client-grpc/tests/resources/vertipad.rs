@@ -43,6 +43,7 @@ pub async fn scenario(client: &VertipadClient, data: Vec<Data>, logger: &mut Log
     }
     let vertipads = List {
         list: vertipad_objects,
+        ..Default::default()
     };
 
     // Check if all vertipads can be retrieved from the backend
@@ -51,11 +52,74 @@ pub async fn scenario(client: &VertipadClient, data: Vec<Data>, logger: &mut Log
     println!("expected message: {}", expected);
     assert!(logger.any(|log| check_log_string_matches(log, &expected)));
 
+    println!("{:?}", result);
+    assert!(result.is_ok());
+    let vertipads_from_db: List = result.unwrap().into_inner();
+    assert_eq!(vertipads_from_db.list.len(), vertipads.list.len());
+    assert_eq!(vertipads_from_db.total_count, vertipads.list.len() as i64);
+
+    // Check if we can page through the vertipads using the returned page token
+    // The mock implementations don't support paging, so only test this against a real backend
+    #[cfg(not(any(feature = "stub_client", feature = "stub_backends")))]
+    {
+        let mut filter = not_deleted_filter.clone().results_per_page(1);
+        let mut paged_ids = vec![];
+        loop {
+            let result = client.search(filter.clone()).await;
+            println!("{:?}", result);
+            assert!(result.is_ok());
+            let page: List = result.unwrap().into_inner();
+            assert!(page.list.len() <= 1);
+            assert_eq!(page.total_count, vertipads.list.len() as i64);
+            for object in page.list {
+                assert!(!paged_ids.contains(&object.id));
+                paged_ids.push(object.id);
+            }
+            if page.next_page_token.is_empty() {
+                break;
+            }
+            filter = filter.page_token(page.next_page_token);
+        }
+        assert_eq!(paged_ids.len(), vertipads.list.len());
+    }
+
+    // Check if nested filter groups are combined correctly
+    let group_filter = AdvancedSearchFilter::search_is_null("deleted_at".to_owned()).and_group(
+        AdvancedSearchFilter::search_is_not_null("deleted_at".to_owned())
+            .or_is_null("deleted_at".to_owned()),
+    );
+    let result = client.search(group_filter).await;
     println!("{:?}", result);
     assert!(result.is_ok());
     let vertipads_from_db: List = result.unwrap().into_inner();
     assert_eq!(vertipads_from_db.list.len(), vertipads.list.len());
 
+    // Check if we can find the nearest vertipads to the location of the first vertipad
+    // The mock implementations don't support geographic searches, so only test this against a real backend
+    #[cfg(not(any(feature = "stub_client", feature = "stub_backends")))]
+    {
+        let location = vertipads.list[0]
+            .data
+            .as_ref()
+            .and_then(|data| data.geo_location)
+            .expect("No Geo location found for vertipad");
+        let wkt = format!("POINT({} {})", location.longitude, location.latitude);
+        let filter = not_deleted_filter
+            .clone()
+            .and_geo_dwithin("geo_location".to_owned(), wkt.clone(), 1000.0)
+            .sort_by_distance("geo_location".to_owned(), wkt)
+            .results_per_page(2);
+        let result = client.search(filter).await;
+        println!("{:?}", result);
+        assert!(result.is_ok());
+        let nearest: List = result.unwrap().into_inner();
+        assert!(!nearest.list.is_empty());
+        assert_eq!(nearest.distances.len(), nearest.list.len());
+        assert_eq!(nearest.list[0].id, vertipads.list[0].id);
+        assert!(nearest.distances[0] < 0.01);
+        assert!(nearest.distances.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
     let vertipad_id = vertipads.list[0].id.clone();
 
     // Check if we can get a single vertipad based on their id
@@ -99,5 +163,77 @@ pub async fn scenario(client: &VertipadClient, data: Vec<Data>, logger: &mut Log
     let vertipads_from_db: List = result.unwrap().into_inner();
     assert_eq!(vertipads_from_db.list.len(), vertipads.list.len() - 1);
 
+    // A deleted vertipad should only be returned when deleted records are included
+    #[cfg(not(any(feature = "stub_client", feature = "stub_backends")))]
+    {
+        let result = client
+            .get_by_id(Id {
+                id: vertipad_id.clone(),
+            })
+            .await;
+        println!("{:?}", result);
+        assert!(result.is_err());
+
+        let result = client
+            .get_by_id_include_deleted(Id {
+                id: vertipad_id.clone(),
+            })
+            .await;
+
+        let expected = get_log_string("get_by_id_include_deleted", name);
+        println!("expected message: {}", expected);
+        assert!(logger.any(|log| check_log_string_matches(log, &expected)));
+
+        println!("{:?}", result);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().into_inner().id, vertipad_id);
+    }
+
+    // Check if we can restore the deleted vertipad
+    let result = client
+        .restore(Id {
+            id: vertipad_id.clone(),
+        })
+        .await;
+
+    let expected = get_log_string("restore", name);
+    println!("expected message: {}", expected);
+    assert!(logger.any(|log| check_log_string_matches(log, &expected)));
+
+    println!("{:?}", result);
+    assert!(result.is_ok());
+
+    // Delete it again so it can be purged
+    let result = client
+        .delete(Id {
+            id: vertipad_id.clone(),
+        })
+        .await;
+    println!("{:?}", result);
+    assert!(result.is_ok());
+
+    // Check if we can permanently remove the vertipad
+    let result = client
+        .purge(Id {
+            id: vertipad_id.clone(),
+        })
+        .await;
+
+    let expected = get_log_string("purge", name);
+    println!("expected message: {}", expected);
+    assert!(logger.any(|log| check_log_string_matches(log, &expected)));
+
+    println!("{:?}", result);
+    assert!(result.is_ok());
+
+    // A purged vertipad should no longer be found
+    let result = client
+        .get_by_id(Id {
+            id: vertipad_id.clone(),
+        })
+        .await;
+    println!("{:?}", result);
+    assert!(result.is_err());
+
     vertipads_from_db
 }
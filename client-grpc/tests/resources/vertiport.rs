@@ -41,6 +41,7 @@ pub async fn scenario(client: &VertiportClient, data: Vec<Data>, logger: &mut Lo
     }
     let vertiports = List {
         list: vertiport_objects,
+        ..Default::default()
     };
 
     // Check if all vertiports can be retrieved from the backend
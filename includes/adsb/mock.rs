@@ -10,6 +10,12 @@ pub fn get_data_obj() -> Data {
         message_type: rng.gen_range(0..22),
         network_timestamp: Some(prost_wkt_types::Timestamp::from(SystemTime::now())),
         payload: [0; 14].to_vec(),
+        position: None,
+        baro_altitude: None,
+        ground_speed: None,
+        heading: None,
+        callsign: None,
+        receivers: vec![format!("receiver-{}", rng.gen_range(0..10))],
     }
 }
 
@@ -26,4 +32,6 @@ fn test_get_data_obj() {
     assert!(data.network_timestamp.is_some());
 
     assert_eq!(data.payload, [0; 14].to_vec());
+
+    assert_eq!(data.receivers.len(), 1);
 }
@@ -23,6 +23,7 @@ fn get_types() -> Vec<String> {
         "scanner".to_owned(),
         "user".to_owned(),
         "vehicle".to_owned(),
+        "vehicle_model".to_owned(),
         "vertipad".to_owned(),
         "vertiport".to_owned(),
         "flight_plan_parcel".to_owned(),
@@ -89,20 +90,26 @@ fn get_grpc_builder_config(out_path: &str) -> tonic_build::Builder {
         .extern_path(".grpc.geo_types.GeoLine", "GeoLine")
         .type_attribute("ReadyRequest", "#[derive(Eq, Copy)]")
         .type_attribute("ReadyResponse", "#[derive(Eq, Copy)]")
+        .type_attribute("HealthResponse", "#[derive(Serialize, Deserialize)]")
         .type_attribute("Id", "#[derive(Eq)]")
         .type_attribute("SearchFilter", "#[derive(Eq)]")
         .type_attribute("AdvancedSearchFilter", "#[derive(Eq)]")
         .type_attribute("FilterOption", "#[derive(Eq)]")
+        .type_attribute("FilterGroup", "#[derive(Eq)]")
         .type_attribute("SortOption", "#[derive(Eq)]")
         .type_attribute("SortOrder", "#[derive(num_derive::FromPrimitive)]")
         .type_attribute("PredicateOperator", "#[derive(num_derive::FromPrimitive)]")
         .type_attribute("ComparisonOperator", "#[derive(num_derive::FromPrimitive)]")
+        .type_attribute("BatchMode", "#[derive(num_derive::FromPrimitive)]")
+        .type_attribute("ChangeType", "#[derive(num_derive::FromPrimitive)]")
+        .type_attribute("ErrorReason", "#[derive(num_derive::FromPrimitive)]")
         .type_attribute("ScannerType", "#[derive(num_derive::FromPrimitive)]")
         .type_attribute("ScannerStatus", "#[derive(num_derive::FromPrimitive)]")
         .type_attribute("FlightStatus", "#[derive(num_derive::FromPrimitive)]")
         .type_attribute("FlightPriority", "#[derive(num_derive::FromPrimitive)]")
         .type_attribute("ParcelStatus", "#[derive(num_derive::FromPrimitive)]")
         .type_attribute("AuthMethod", "#[derive(num_derive::FromPrimitive)]")
+        .type_attribute("VehicleModelType", "#[derive(num_derive::FromPrimitive)]")
         // Add serde derive attributes for structs
         .type_attribute("Id", "#[derive(Serialize, Deserialize)]")
         .type_attribute("Ids", "#[derive(Serialize, Deserialize)]")
@@ -111,6 +118,7 @@ fn get_grpc_builder_config(out_path: &str) -> tonic_build::Builder {
         .type_attribute("RowDataList", "#[derive(Serialize, Deserialize)]")
         .type_attribute("ValidationError", "#[derive(Serialize, Deserialize)]")
         .type_attribute("ValidationResult", "#[derive(Serialize, Deserialize)]")
+        .type_attribute("ErrorDetails", "#[derive(Serialize, Deserialize)]")
         .type_attribute("Object", "#[derive(Serialize, Deserialize)]")
         .type_attribute("Data", "#[derive(Serialize, Deserialize)]")
         .type_attribute("RowData", "#[derive(Serialize, Deserialize)]")
@@ -0,0 +1,60 @@
+impl FlightStatus {
+    /// Returns the [`FlightStatus`] values a flight plan with this status can transition to
+    ///
+    /// [`FlightStatus::Finished`] and [`FlightStatus::Cancelled`] are final, a flight plan
+    /// can not transition to any other status once it has been finished or cancelled.
+    pub fn get_allowed_transitions(&self) -> Vec<FlightStatus> {
+        match self {
+            FlightStatus::Draft => vec![FlightStatus::Ready, FlightStatus::Cancelled],
+            FlightStatus::Ready => vec![
+                FlightStatus::Draft,
+                FlightStatus::Boarding,
+                FlightStatus::Cancelled,
+            ],
+            FlightStatus::Boarding => vec![
+                FlightStatus::Ready,
+                FlightStatus::InFlight,
+                FlightStatus::Cancelled,
+            ],
+            FlightStatus::InFlight => vec![FlightStatus::Finished],
+            FlightStatus::Finished | FlightStatus::Cancelled => vec![],
+        }
+    }
+
+    /// Returns `true` if a flight plan with this status can transition to the provided status
+    ///
+    /// Keeping the current status is always allowed.
+    pub fn can_transition_to(&self, status: FlightStatus) -> bool {
+        *self == status || self.get_allowed_transitions().contains(&status)
+    }
+}
+
+#[test]
+fn test_flight_status_transitions() {
+    let all = [
+        FlightStatus::Draft,
+        FlightStatus::Ready,
+        FlightStatus::Boarding,
+        FlightStatus::InFlight,
+        FlightStatus::Finished,
+        FlightStatus::Cancelled,
+    ];
+    for status in all {
+        assert!(status.can_transition_to(status));
+        assert!(!status.get_allowed_transitions().contains(&status));
+    }
+
+    assert!(FlightStatus::Draft.can_transition_to(FlightStatus::Ready));
+    assert!(FlightStatus::Boarding.can_transition_to(FlightStatus::InFlight));
+    assert!(FlightStatus::InFlight.can_transition_to(FlightStatus::Finished));
+    assert!(!FlightStatus::Draft.can_transition_to(FlightStatus::InFlight));
+    assert!(!FlightStatus::InFlight.can_transition_to(FlightStatus::Cancelled));
+    for status in all {
+        if status != FlightStatus::Finished {
+            assert!(!FlightStatus::Finished.can_transition_to(status));
+        }
+        if status != FlightStatus::Cancelled {
+            assert!(!FlightStatus::Cancelled.can_transition_to(status));
+        }
+    }
+}
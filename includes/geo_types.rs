@@ -1,18 +1,219 @@
+/// Mean earth radius in meters, used to calculate haversine distances
+const EARTH_RADIUS_METERS: f64 = 6_371_008.8;
+
+/// Returns the number of seconds since the UNIX epoch for the provided timestamp
+fn get_epoch_seconds(timestamp: &::prost_wkt_types::Timestamp) -> f64 {
+    timestamp.seconds as f64 + timestamp.nanos as f64 / 1_000_000_000.0
+}
+
+impl GeoPoint {
+    /// Returns `true` if the point has an altitude
+    pub fn has_altitude(&self) -> bool {
+        self.altitude.is_some()
+    }
+
+    /// Returns the great-circle distance in meters to the provided point, ignoring the altitude
+    pub fn haversine_distance(&self, other: &GeoPoint) -> f64 {
+        let lat_1 = self.latitude.to_radians();
+        let lat_2 = other.latitude.to_radians();
+        let d_lat = lat_2 - lat_1;
+        let d_lon = (other.longitude - self.longitude).to_radians();
+        let a =
+            (d_lat / 2.0).sin().powi(2) + lat_1.cos() * lat_2.cos() * (d_lon / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS_METERS * a.sqrt().asin()
+    }
+
+    /// Returns the point at the provided fraction (`0.0` - `1.0`) of the way to the provided point
+    ///
+    /// Coordinates are interpolated linearly, which is accurate enough for the short
+    /// distances between waypoints. The altitude is only interpolated if both points have one.
+    pub fn interpolate(&self, other: &GeoPoint, fraction: f64) -> GeoPoint {
+        let lerp = |from: f64, to: f64| from + (to - from) * fraction;
+        GeoPoint {
+            longitude: lerp(self.longitude, other.longitude),
+            latitude: lerp(self.latitude, other.latitude),
+            altitude: match (self.altitude, other.altitude) {
+                (Some(from), Some(to)) => Some(lerp(from, to)),
+                _ => None,
+            },
+        }
+    }
+}
+
+impl GeoLineString {
+    /// Returns `true` if any of the points has an altitude
+    pub fn has_altitude(&self) -> bool {
+        self.points.iter().any(GeoPoint::has_altitude)
+    }
+
+    /// Returns `true` if the line string has a time for each of its points
+    pub fn has_times(&self) -> bool {
+        !self.points.is_empty() && self.times.len() == self.points.len()
+    }
+
+    /// Returns the section of the line string that is passed during the provided time window
+    ///
+    /// Positions at the window boundaries are interpolated between the surrounding waypoints.
+    /// Line strings without times are returned as is, as it's unknown when their points are passed.
+    /// An empty line string is returned if no part of the line string is passed during the window.
+    pub fn get_section(
+        &self,
+        start: &::prost_wkt_types::Timestamp,
+        end: &::prost_wkt_types::Timestamp,
+    ) -> GeoLineString {
+        if !self.has_times() {
+            return self.clone();
+        }
+
+        let window = (get_epoch_seconds(start), get_epoch_seconds(end));
+        let times: Vec<f64> = self.times.iter().map(get_epoch_seconds).collect();
+        let mut section = GeoLineString {
+            points: vec![],
+            times: vec![],
+        };
+        let mut push = |point: GeoPoint, time: &::prost_wkt_types::Timestamp| {
+            if section.points.last() != Some(&point) || section.times.last() != Some(time) {
+                section.points.push(point);
+                section.times.push(time.clone());
+            }
+        };
+
+        if self.points.len() == 1 {
+            if times[0] >= window.0 && times[0] <= window.1 {
+                push(self.points[0], &self.times[0]);
+            }
+            return section;
+        }
+
+        for (i, pair) in self.points.windows(2).enumerate() {
+            let (from, to) = (&pair[0], &pair[1]);
+            let (t_from, t_to) = (times[i], times[i + 1]);
+            if t_to < window.0 || t_from > window.1 {
+                continue;
+            }
+
+            if t_from >= window.0 {
+                push(*from, &self.times[i]);
+            } else {
+                push(
+                    from.interpolate(to, (window.0 - t_from) / (t_to - t_from)),
+                    start,
+                );
+            }
+            if t_to <= window.1 {
+                push(*to, &self.times[i + 1]);
+            } else {
+                push(
+                    from.interpolate(to, (window.1 - t_from) / (t_to - t_from)),
+                    end,
+                );
+            }
+        }
+        section
+    }
+
+    /// Returns the sections of this and the provided line string that are passed during the
+    /// same time, limited to the provided time window
+    ///
+    /// A line string without times is assumed to be passed during the whole window.
+    /// Returns `None` if the line strings are not passed during the same time.
+    pub fn get_concurrent_sections(
+        &self,
+        other: &GeoLineString,
+        start: &::prost_wkt_types::Timestamp,
+        end: &::prost_wkt_types::Timestamp,
+    ) -> Option<(GeoLineString, GeoLineString)> {
+        let key = |time: &::prost_wkt_types::Timestamp| (time.seconds, time.nanos);
+        let mut start = start.clone();
+        let mut end = end.clone();
+        for line_string in [self, other] {
+            if !line_string.has_times() {
+                continue;
+            }
+            let (first, last) = (
+                &line_string.times[0],
+                &line_string.times[line_string.times.len() - 1],
+            );
+            if key(first) > key(&start) {
+                start = first.clone();
+            }
+            if key(last) < key(&end) {
+                end = last.clone();
+            }
+        }
+        if key(&start) > key(&end) {
+            return None;
+        }
+
+        let sections = (
+            self.get_section(&start, &end),
+            other.get_section(&start, &end),
+        );
+        if sections.0.points.is_empty() || sections.1.points.is_empty() {
+            return None;
+        }
+        Some(sections)
+    }
+
+    /// Returns the smallest haversine distance in meters between the points of this and the provided line string
+    ///
+    /// Only the points are compared, the distance between the segments connecting them can be smaller.
+    /// Returns `None` if either of the line strings has no points.
+    pub fn get_min_point_distance(&self, other: &GeoLineString) -> Option<f64> {
+        self.points
+            .iter()
+            .flat_map(|from| other.points.iter().map(|to| from.haversine_distance(to)))
+            .min_by(|a, b| a.total_cmp(b))
+    }
+}
+
+impl GeoPolygon {
+    /// Returns `true` if the provided point lies within the exterior and outside all interiors
+    ///
+    /// Uses the even-odd rule on the longitude/latitude coordinates, ignoring the altitude.
+    /// Points exactly on the boundary may be considered either inside or outside.
+    pub fn contains_point(&self, point: &GeoPoint) -> bool {
+        let ring_contains = |ring: &GeoLineString| {
+            let points = &ring.points;
+            let mut inside = false;
+            for (index, from) in points.iter().enumerate() {
+                let to = &points[(index + 1) % points.len()];
+                if (from.latitude > point.latitude) != (to.latitude > point.latitude)
+                    && point.longitude
+                        < from.longitude
+                            + (to.longitude - from.longitude) * (point.latitude - from.latitude)
+                                / (to.latitude - from.latitude)
+                {
+                    inside = !inside;
+                }
+            }
+            inside
+        };
+        match &self.exterior {
+            Some(exterior) => ring_contains(exterior) && !self.interiors.iter().any(ring_contains),
+            None => false,
+        }
+    }
+}
+
 impl From<Point> for GeoPoint {
     fn from(field: Point) -> Self {
         Self {
             longitude: field.x(),
             latitude: field.y(),
+            altitude: None,
         }
     }
 }
 
+/// [`geo_types`] are 2D only, the altitude will be dropped
 impl From<GeoPoint> for Point {
     fn from(field: GeoPoint) -> Self {
         Self::new(field.longitude, field.latitude)
     }
 }
 
+/// [`geo_types`] are 2D only, the altitude will be dropped
 impl From<GeoPoint> for Coord {
     fn from(field: GeoPoint) -> Self {
         Coord {
@@ -29,7 +230,10 @@ impl From<LineString> for GeoLineString {
             let point: Point = (*coord).into();
             points.push(point.into());
         }
-        Self { points }
+        Self {
+            points,
+            times: vec![],
+        }
     }
 }
 impl From<GeoLineString> for LineString {
@@ -100,6 +304,7 @@ mod tests {
         let expected = GeoPoint {
             longitude: x,
             latitude: y,
+            altitude: None,
         };
 
         // Point into GeoPoint
@@ -113,6 +318,7 @@ mod tests {
         let from = GeoPoint {
             longitude: x,
             latitude: y,
+            altitude: None,
         };
         let expected = Point::new(x, y);
 
@@ -127,6 +333,7 @@ mod tests {
         let from = GeoPoint {
             longitude: x,
             latitude: y,
+            altitude: None,
         };
         let expected = Coord { x, y };
 
@@ -147,12 +354,15 @@ mod tests {
                 GeoPoint {
                     longitude: x_1,
                     latitude: y_1,
+                    altitude: None,
                 },
                 GeoPoint {
                     longitude: x_2,
                     latitude: y_2,
+                    altitude: None,
                 },
             ],
+            times: vec![],
         };
 
         // LineString into GeoLineString
@@ -170,12 +380,15 @@ mod tests {
                 GeoPoint {
                     longitude: x_1,
                     latitude: y_1,
+                    altitude: None,
                 },
                 GeoPoint {
                     longitude: x_2,
                     latitude: y_2,
+                    altitude: None,
                 },
             ],
+            times: vec![],
         };
         let expected = LineString::from(vec![(x_1, y_1), (x_2, y_2)]);
 
@@ -202,32 +415,40 @@ mod tests {
                     GeoPoint {
                         longitude: x_1,
                         latitude: y_1,
+                        altitude: None,
                     },
                     GeoPoint {
                         longitude: x_2,
                         latitude: y_2,
+                        altitude: None,
                     },
                     GeoPoint {
                         longitude: x_1,
                         latitude: y_1,
+                        altitude: None,
                     },
                 ],
+                times: vec![],
             }),
             interiors: vec![GeoLineString {
                 points: vec![
                     GeoPoint {
                         longitude: x_1 - 1.0,
                         latitude: y_1 - 1.0,
+                        altitude: None,
                     },
                     GeoPoint {
                         longitude: x_2 - 1.0,
                         latitude: y_2 - 1.0,
+                        altitude: None,
                     },
                     GeoPoint {
                         longitude: x_1 - 1.0,
                         latitude: y_1 - 1.0,
+                        altitude: None,
                     },
                 ],
+                times: vec![],
             }],
         };
 
@@ -252,32 +473,40 @@ mod tests {
                     GeoPoint {
                         longitude: x_1,
                         latitude: y_1,
+                        altitude: None,
                     },
                     GeoPoint {
                         longitude: x_2,
                         latitude: y_2,
+                        altitude: None,
                     },
                     GeoPoint {
                         longitude: x_1,
                         latitude: y_1,
+                        altitude: None,
                     },
                 ],
+                times: vec![],
             }),
             interiors: vec![GeoLineString {
                 points: vec![
                     GeoPoint {
                         longitude: x_1 - 1.0,
                         latitude: y_1 - 1.0,
+                        altitude: None,
                     },
                     GeoPoint {
                         longitude: x_2 - 1.0,
                         latitude: y_2 - 1.0,
+                        altitude: None,
                     },
                     GeoPoint {
                         longitude: x_1 - 1.0,
                         latitude: y_1 - 1.0,
+                        altitude: None,
                     },
                 ],
+                times: vec![],
             }],
         };
         let expected = Polygon::new(exterior, interiors);
@@ -302,32 +531,40 @@ mod tests {
                     GeoPoint {
                         longitude: x_1,
                         latitude: y_1,
+                        altitude: None,
                     },
                     GeoPoint {
                         longitude: x_2,
                         latitude: y_2,
+                        altitude: None,
                     },
                     GeoPoint {
                         longitude: x_1,
                         latitude: y_1,
+                        altitude: None,
                     },
                 ],
+                times: vec![],
             }),
             interiors: vec![GeoLineString {
                 points: vec![
                     GeoPoint {
                         longitude: x_1 - 1.0,
                         latitude: y_1 - 1.0,
+                        altitude: None,
                     },
                     GeoPoint {
                         longitude: x_2 - 1.0,
                         latitude: y_2 - 1.0,
+                        altitude: None,
                     },
                     GeoPoint {
                         longitude: x_1 - 1.0,
                         latitude: y_1 - 1.0,
+                        altitude: None,
                     },
                 ],
+                times: vec![],
             }],
         };
 
@@ -335,4 +572,223 @@ mod tests {
         let result: GeoPolygon = from.into();
         assert_eq!(result, expected);
     }
+    #[test]
+    fn test_has_altitude() {
+        let point = GeoPoint {
+            longitude: 120.8,
+            latitude: -45.12,
+            altitude: None,
+        };
+        let point_z = GeoPoint {
+            altitude: Some(120.0),
+            ..point
+        };
+        assert!(!point.has_altitude());
+        assert!(point_z.has_altitude());
+
+        let line_string = GeoLineString {
+            points: vec![point, point],
+            times: vec![],
+        };
+        assert!(!line_string.has_altitude());
+        let line_string = GeoLineString {
+            points: vec![point, point_z],
+            times: vec![],
+        };
+        assert!(line_string.has_altitude());
+
+        // Altitude is dropped when converting to 2D types
+        let result: Point = point_z.into();
+        assert_eq!(result, Point::new(120.8, -45.12));
+    }
+
+    #[test]
+    fn test_haversine_distance() {
+        let from = GeoPoint {
+            longitude: 4.9164,
+            latitude: 52.37466,
+            altitude: None,
+        };
+        assert_eq!(from.haversine_distance(&from), 0.0);
+
+        // 1 degree latitude is roughly 111.2 km
+        let to = GeoPoint {
+            latitude: from.latitude + 1.0,
+            ..from
+        };
+        let distance = from.haversine_distance(&to);
+        assert!((distance - 111_195.0).abs() < 10.0);
+        assert_eq!(distance, to.haversine_distance(&from));
+    }
+
+    #[test]
+    fn test_get_section() {
+        let timestamp = |seconds: i64| ::prost_wkt_types::Timestamp { seconds, nanos: 0 };
+        let point = |latitude: f64, altitude: Option<f64>| GeoPoint {
+            longitude: 4.9164,
+            latitude,
+            altitude,
+        };
+        let line_string = GeoLineString {
+            points: vec![
+                point(52.0, Some(0.0)),
+                point(53.0, Some(100.0)),
+                point(54.0, Some(100.0)),
+            ],
+            times: vec![timestamp(1000), timestamp(2000), timestamp(3000)],
+        };
+        assert!(line_string.has_times());
+
+        // The whole line string is passed during the window
+        let result = line_string.get_section(&timestamp(0), &timestamp(4000));
+        assert_eq!(result, line_string);
+
+        // Boundaries are interpolated
+        let result = line_string.get_section(&timestamp(1500), &timestamp(2500));
+        assert_eq!(
+            result,
+            GeoLineString {
+                points: vec![
+                    point(52.5, Some(50.0)),
+                    point(53.0, Some(100.0)),
+                    point(53.5, Some(100.0)),
+                ],
+                times: vec![timestamp(1500), timestamp(2000), timestamp(2500)],
+            }
+        );
+
+        // Window before, after or in between the waypoints
+        let result = line_string.get_section(&timestamp(0), &timestamp(500));
+        assert!(result.points.is_empty());
+        let result = line_string.get_section(&timestamp(3500), &timestamp(4000));
+        assert!(result.points.is_empty());
+        let result = line_string.get_section(&timestamp(1200), &timestamp(1400));
+        assert_eq!(result.points.len(), 2);
+        assert_eq!(result.times, vec![timestamp(1200), timestamp(1400)]);
+
+        // Line strings without times are returned as is
+        let line_string = GeoLineString {
+            points: line_string.points,
+            times: vec![],
+        };
+        assert!(!line_string.has_times());
+        let result = line_string.get_section(&timestamp(0), &timestamp(500));
+        assert_eq!(result, line_string);
+    }
+
+    #[test]
+    fn test_get_concurrent_sections() {
+        let timestamp = |seconds: i64| ::prost_wkt_types::Timestamp { seconds, nanos: 0 };
+        let point = |latitude: f64| GeoPoint {
+            longitude: 4.9164,
+            latitude,
+            altitude: None,
+        };
+        let line_string = GeoLineString {
+            points: vec![point(52.0), point(53.0)],
+            times: vec![timestamp(1000), timestamp(2000)],
+        };
+        let other = GeoLineString {
+            points: vec![point(52.5), point(53.5)],
+            times: vec![timestamp(1500), timestamp(2500)],
+        };
+
+        // Only the time both line strings are passed is compared
+        let (own, concurrent) = line_string
+            .get_concurrent_sections(&other, &timestamp(0), &timestamp(4000))
+            .unwrap();
+        assert_eq!(own.times, vec![timestamp(1500), timestamp(2000)]);
+        assert_eq!(own.points, vec![point(52.5), point(53.0)]);
+        assert_eq!(concurrent.times, vec![timestamp(1500), timestamp(2000)]);
+        assert_eq!(concurrent.points, vec![point(52.5), point(53.0)]);
+        assert_eq!(own.get_min_point_distance(&concurrent), Some(0.0));
+
+        // The window limits the sections
+        let (own, _) = line_string
+            .get_concurrent_sections(&other, &timestamp(1800), &timestamp(4000))
+            .unwrap();
+        assert_eq!(own.times, vec![timestamp(1800), timestamp(2000)]);
+
+        // No concurrent time
+        let later = GeoLineString {
+            times: vec![timestamp(3000), timestamp(4000)],
+            ..other.clone()
+        };
+        assert!(line_string
+            .get_concurrent_sections(&later, &timestamp(0), &timestamp(5000))
+            .is_none());
+        assert!(line_string
+            .get_concurrent_sections(&other, &timestamp(2500), &timestamp(3000))
+            .is_none());
+
+        // Line strings without times are passed during the whole window
+        let untimed = GeoLineString {
+            times: vec![],
+            ..other.clone()
+        };
+        let (own, concurrent) = line_string
+            .get_concurrent_sections(&untimed, &timestamp(0), &timestamp(1500))
+            .unwrap();
+        assert_eq!(own.times, vec![timestamp(1000), timestamp(1500)]);
+        assert_eq!(concurrent, untimed);
+        let distance = own.get_min_point_distance(&concurrent).unwrap();
+        assert!(distance > 0.0);
+        assert_eq!(
+            untimed.get_min_point_distance(&GeoLineString {
+                points: vec![],
+                times: vec![],
+            }),
+            None
+        );
+    }
+
+    #[test]
+    fn test_contains_point() {
+        let ring = |points: &[(f64, f64)]| GeoLineString {
+            points: points
+                .iter()
+                .map(|(longitude, latitude)| GeoPoint {
+                    longitude: *longitude,
+                    latitude: *latitude,
+                    altitude: None,
+                })
+                .collect(),
+            times: vec![],
+        };
+        let point = |longitude: f64, latitude: f64| GeoPoint {
+            longitude,
+            latitude,
+            altitude: Some(120.0),
+        };
+        let polygon = GeoPolygon {
+            exterior: Some(ring(&[
+                (0.0, 0.0),
+                (4.0, 0.0),
+                (4.0, 4.0),
+                (0.0, 4.0),
+                (0.0, 0.0),
+            ])),
+            interiors: vec![ring(&[
+                (1.0, 1.0),
+                (2.0, 1.0),
+                (2.0, 2.0),
+                (1.0, 2.0),
+                (1.0, 1.0),
+            ])],
+        };
+
+        assert!(polygon.contains_point(&point(3.0, 3.0)));
+        assert!(polygon.contains_point(&point(0.5, 3.5)));
+        // inside the interior (hole)
+        assert!(!polygon.contains_point(&point(1.5, 1.5)));
+        // outside the exterior
+        assert!(!polygon.contains_point(&point(5.0, 3.0)));
+        assert!(!polygon.contains_point(&point(-1.0, -1.0)));
+
+        let empty = GeoPolygon {
+            exterior: None,
+            interiors: vec![],
+        };
+        assert!(!empty.contains_point(&point(3.0, 3.0)));
+    }
 }
@@ -0,0 +1,48 @@
+impl ParcelStatus {
+    /// All [`ParcelStatus`] values, in the order a parcel progresses through them
+    pub const PROGRESSION: [ParcelStatus; 6] = [
+        ParcelStatus::Notdroppedoff,
+        ParcelStatus::Droppedoff,
+        ParcelStatus::Enroute,
+        ParcelStatus::Arrived,
+        ParcelStatus::Pickedup,
+        ParcelStatus::Complete,
+    ];
+
+    /// Returns the [`ParcelStatus`] values a parcel with this status can transition to
+    ///
+    /// A parcel can only move forward, skipping statuses is allowed (a scan might have been missed).
+    /// [`ParcelStatus::Complete`] is final.
+    pub fn get_allowed_transitions(&self) -> Vec<ParcelStatus> {
+        Self::PROGRESSION
+            .iter()
+            .skip_while(|status| *status != self)
+            .skip(1)
+            .copied()
+            .collect()
+    }
+
+    /// Returns `true` if a parcel with this status can transition to the provided status
+    ///
+    /// Keeping the current status is always allowed.
+    pub fn can_transition_to(&self, status: ParcelStatus) -> bool {
+        *self == status || self.get_allowed_transitions().contains(&status)
+    }
+}
+
+#[test]
+fn test_parcel_status_transitions() {
+    for status in ParcelStatus::PROGRESSION {
+        assert!(status.can_transition_to(status));
+        assert!(!status.get_allowed_transitions().contains(&status));
+    }
+
+    assert!(ParcelStatus::Notdroppedoff.can_transition_to(ParcelStatus::Droppedoff));
+    assert!(ParcelStatus::Droppedoff.can_transition_to(ParcelStatus::Enroute));
+    assert!(ParcelStatus::Notdroppedoff.can_transition_to(ParcelStatus::Enroute));
+    assert!(ParcelStatus::Arrived.can_transition_to(ParcelStatus::Pickedup));
+    assert!(ParcelStatus::Pickedup.can_transition_to(ParcelStatus::Complete));
+    assert!(!ParcelStatus::Enroute.can_transition_to(ParcelStatus::Droppedoff));
+    assert!(!ParcelStatus::Arrived.can_transition_to(ParcelStatus::Notdroppedoff));
+    assert!(ParcelStatus::Complete.get_allowed_transitions().is_empty());
+}
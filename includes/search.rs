@@ -1,4 +1,7 @@
-use super::{AdvancedSearchFilter, ComparisonOperator, FilterOption, PredicateOperator};
+use super::{
+    AdvancedSearchFilter, ComparisonOperator, FilterGroup, FilterOption, PredicateOperator,
+    SortOption, SortOrder,
+};
 
 /// Implement helper functions for [AdvancedSearchFilter] to provide a more readable way to
 /// build up the struct's values.
@@ -11,6 +14,23 @@ use super::{AdvancedSearchFilter, ComparisonOperator, FilterOption, PredicateOpe
 ///     .and_between(String::from("created_at"), String::from("2022-04-10 22:10:57+02:00"), String::from("2022-04-12 22:10:57+02:00"))
 ///     .results_per_page(10)
 ///     .page_number(1);
+///
+/// // Use the `next_page_token` returned with the results to get the next page
+/// let next_page = filter.page_token(String::from("<next_page_token>"));
+///
+/// // The 5 vertiports nearest to a location, within 10 km, including their distance in meters.
+/// let filter = AdvancedSearchFilter::search_geo_dwithin(String::from("geo_location"), String::from("POINT(4.9 52.3)"), 10000.0)
+///     .sort_by_distance(String::from("geo_location"), String::from("POINT(4.9 52.3)"))
+///     .results_per_page(5)
+///     .page_number(1);
+///
+/// // AND is evaluated before OR, use groups to nest conditions.
+/// // status = 'enabled' AND (vehicle_model_id = '...' OR description IS NULL)
+/// let filter = AdvancedSearchFilter::search_equals(String::from("status"), String::from("enabled"))
+///     .and_group(
+///         AdvancedSearchFilter::search_equals(String::from("vehicle_model_id"), String::from("53acfe06-dd9b-42e8-8cb4-12a2fb2fa693"))
+///             .or_is_null(String::from("description"))
+///     );
 /// ```
 impl AdvancedSearchFilter {
     fn search(column: String, values: Vec<String>, predicate_operator: PredicateOperator) -> Self {
@@ -20,10 +40,13 @@ impl AdvancedSearchFilter {
                 search_value: values,
                 predicate_operator: predicate_operator.into(),
                 comparison_operator: None,
+                group: None,
             }],
             page_number: 0,
             results_per_page: -1,
             order_by: vec![],
+            include_deleted: false,
+            page_token: String::new(),
         }
     }
 
@@ -190,6 +213,21 @@ impl AdvancedSearchFilter {
     pub fn search_geo_disjoint(column: String, value: String) -> Self {
         Self::search(column, vec![value], PredicateOperator::GeoDisjoint)
     }
+    /// wrapper function for internal `search` function returning a new [AdvancedSearchFilter] object
+    ///
+    /// Adds a [FilterOption] to `filters` using:
+    /// * search_field: the provided `column` [String]
+    /// * search_value: the provided `value` in a [Well Know Text](https://www.cockroachlabs.com/docs/v23.1/well-known-text) format
+    ///   and the provided `distance` in meters as entries in a [Vec\<String\>]
+    /// * predicate operator: [PredicateOperator::GeoDwithin]
+    /// * comparison operator: [None]
+    pub fn search_geo_dwithin(column: String, value: String, distance: f64) -> Self {
+        Self::search(
+            column,
+            vec![value, distance.to_string()],
+            PredicateOperator::GeoDwithin,
+        )
+    }
 
     fn add_filter(
         mut self,
@@ -203,10 +241,64 @@ impl AdvancedSearchFilter {
             search_value: values,
             predicate_operator: predicate_operator.into(),
             comparison_operator: Some(comparison_operator.into()),
+            group: None,
+        });
+        self
+    }
+
+    /// Returns a new [AdvancedSearchFilter] object with the `filters` of the provided `group` as a nested [FilterGroup]
+    ///
+    /// Only the `filters` of the provided `group` will be used, any paging or sorting options will be ignored.
+    ///
+    /// Adds a [FilterOption] to `filters` using:
+    /// * group: a [FilterGroup] containing the `filters` of the provided `group`
+    /// * comparison operator: [None]
+    pub fn search_group(group: AdvancedSearchFilter) -> Self {
+        let mut filter = Self::search(String::new(), vec![], PredicateOperator::Equals);
+        filter.filters[0].group = Some(FilterGroup {
+            filters: group.filters,
+        });
+        filter
+    }
+
+    fn add_group(
+        mut self,
+        group: AdvancedSearchFilter,
+        comparison_operator: ComparisonOperator,
+    ) -> Self {
+        self.filters.push(FilterOption {
+            search_field: String::new(),
+            search_value: vec![],
+            predicate_operator: PredicateOperator::Equals.into(),
+            comparison_operator: Some(comparison_operator.into()),
+            group: Some(FilterGroup {
+                filters: group.filters,
+            }),
         });
         self
     }
 
+    /// wrapper function for internal `add_group` function returning [Self]
+    ///
+    /// Only the `filters` of the provided `group` will be used, any paging or sorting options will be ignored.
+    ///
+    /// Adds a [FilterOption] to `filters` using:
+    /// * group: a [FilterGroup] containing the `filters` of the provided `group`
+    /// * comparison operator: [ComparisonOperator::And]
+    pub fn and_group(self, group: AdvancedSearchFilter) -> Self {
+        self.add_group(group, ComparisonOperator::And)
+    }
+    /// wrapper function for internal `add_group` function returning [Self]
+    ///
+    /// Only the `filters` of the provided `group` will be used, any paging or sorting options will be ignored.
+    ///
+    /// Adds a [FilterOption] to `filters` using:
+    /// * group: a [FilterGroup] containing the `filters` of the provided `group`
+    /// * comparison operator: [ComparisonOperator::Or]
+    pub fn or_group(self, group: AdvancedSearchFilter) -> Self {
+        self.add_group(group, ComparisonOperator::Or)
+    }
+
     /// wrapper function for internal `add_filter` function returning [Self]
     ///
     /// Adds a [FilterOption] to `filters` using:
@@ -450,6 +542,22 @@ impl AdvancedSearchFilter {
             ComparisonOperator::And,
         )
     }
+    /// wrapper function for internal `add_filter` function returning [Self]
+    ///
+    /// Adds a [FilterOption] to `filters` using:
+    /// * search_field: the provided `column` [String]
+    /// * search_value: the provided `value` in a [Well Know Text](https://www.cockroachlabs.com/docs/v23.1/well-known-text) format
+    ///   and the provided `distance` in meters as entries in a [Vec\<String\>]
+    /// * predicate operator: [PredicateOperator::GeoDwithin]
+    /// * comparison operator: [ComparisonOperator::And]
+    pub fn and_geo_dwithin(self, column: String, value: String, distance: f64) -> Self {
+        self.add_filter(
+            column,
+            vec![value, distance.to_string()],
+            PredicateOperator::GeoDwithin,
+            ComparisonOperator::And,
+        )
+    }
 
     /// wrapper function for internal `add_filter` function returning [Self]
     ///
@@ -694,6 +802,37 @@ impl AdvancedSearchFilter {
             ComparisonOperator::Or,
         )
     }
+    /// wrapper function for internal `add_filter` function returning [Self]
+    ///
+    /// Adds a [FilterOption] to `filters` using:
+    /// * search_field: the provided `column` [String]
+    /// * search_value: the provided `value` in a [Well Know Text](https://www.cockroachlabs.com/docs/v23.1/well-known-text) format
+    ///   and the provided `distance` in meters as entries in a [Vec\<String\>]
+    /// * predicate operator: [PredicateOperator::GeoDwithin]
+    /// * comparison operator: [ComparisonOperator::Or]
+    pub fn or_geo_dwithin(self, column: String, value: String, distance: f64) -> Self {
+        self.add_filter(
+            column,
+            vec![value, distance.to_string()],
+            PredicateOperator::GeoDwithin,
+            ComparisonOperator::Or,
+        )
+    }
+
+    /// adds a [SortOption] to `order_by`, sorting the results on the distance between the provided
+    /// `column` and the provided `value` in a [Well Know Text](https://www.cockroachlabs.com/docs/v23.1/well-known-text) format,
+    /// nearest first
+    ///
+    /// Records without a value for `column` will be skipped. The distance in meters of each result
+    /// will be returned in the `distances` field of the search results.
+    pub fn sort_by_distance(mut self, column: String, value: String) -> Self {
+        self.order_by.push(SortOption {
+            sort_field: column,
+            sort_order: SortOrder::Asc.into(),
+            distance_from: Some(value),
+        });
+        self
+    }
 
     /// sets `results_per_page` field with given `amount`
     pub fn results_per_page(mut self, amount: i32) -> Self {
@@ -706,6 +845,23 @@ impl AdvancedSearchFilter {
         self.page_number = page;
         self
     }
+
+    /// sets `include_deleted` field with given `include`
+    ///
+    /// Soft-deleted (archived) records are excluded from search results unless this is set to `true`
+    pub fn include_deleted(mut self, include: bool) -> Self {
+        self.include_deleted = include;
+        self
+    }
+
+    /// sets `page_token` field with given `token`
+    ///
+    /// The `token` should be the `next_page_token` returned by a previous search using the same filters and sort order.
+    /// Results following the cursor will be returned, `page_number` will be ignored.
+    pub fn page_token(mut self, token: String) -> Self {
+        self.page_token = token;
+        self
+    }
 }
 
 /// Helper function for search library to get a single value from the provided
@@ -1144,17 +1300,269 @@ pub(crate) fn filter_for_operator(
                     next_param_index += 1;
                 */
             }
+            PredicateOperator::GeoDwithin => {
+                let (search_point, search_distance) = get_dwithin_search_values(search_values)?;
+                log::debug!(
+                    "(filter_for_operator) GeoDwithin filter with point [{:?}] and distance [{}] for val [{}].",
+                    search_point,
+                    search_distance,
+                    val
+                );
+                match get_geo_distance(val, &search_point) {
+                    Some(distance) if distance <= search_distance => {
+                        log::debug!("(filter_for_operator) found!");
+                        filtered.push(object.clone())
+                    }
+                    Some(_) => {}
+                    None => log::warn!(
+                        "(filter_for_operator) Can't convert val [{}] to geometry, don't know what to do.",
+                        &val.to_string()
+                    ),
+                }
+            }
         }
     }
 
     Ok(())
 }
 
+/// Returns the [GeoPoint] and distance in meters of the `search_value` of a GeoDwithin filter.
+///
+/// The point should be provided as `POINT(<longitude> <latitude>)` WKT string, other geometries are not supported.
+#[cfg(any(feature = "stub_client", feature = "stub_server"))]
+fn get_dwithin_search_values(search_value: &[String]) -> Result<(GeoPoint, f64), String> {
+    let [wkt, distance] = search_value else {
+        return Err(format!(
+            "Error in advanced search parameters. Expecting a geometry and a distance, but got [{}] values",
+            search_value.len()
+        ));
+    };
+    let coords = wkt
+        .trim()
+        .strip_prefix("POINT")
+        .and_then(|coords| coords.trim().strip_prefix('('))
+        .and_then(|coords| coords.strip_suffix(')'))
+        .ok_or(format!(
+            "Could not convert search_value [{}] to POINT, other geometries are not supported.",
+            wkt
+        ))?
+        .split_whitespace()
+        .map(|coord| coord.parse::<f64>())
+        .collect::<Result<Vec<f64>, _>>()
+        .map_err(|e| format!("Could not convert search_value [{}] to POINT: {}", wkt, e))?;
+    let point = match coords[..] {
+        [longitude, latitude] => GeoPoint {
+            longitude,
+            latitude,
+            altitude: None,
+        },
+        _ => {
+            return Err(format!(
+                "Could not convert search_value [{}] to POINT, expecting a longitude and latitude.",
+                wkt
+            ))
+        }
+    };
+    let distance = distance.parse::<f64>().map_err(|e| {
+        format!(
+            "Could not convert search_value [{}] to f64: {}",
+            distance, e
+        )
+    })?;
+
+    Ok((point, distance))
+}
+
+/// Returns the haversine distance in meters between the provided point and the geometry `val`, mimicking `st_dwithin`.
+///
+/// The distance to a line string or polygon is the distance to its nearest point, points within a polygon have a distance of `0`.
+/// Returns `None` if `val` is not a (non empty) [GeoPoint], [GeoLineString] or [GeoPolygon].
+#[cfg(any(feature = "stub_client", feature = "stub_server"))]
+fn get_geo_distance(val: &serde_json::Value, point: &GeoPoint) -> Option<f64> {
+    let search_line_string = GeoLineString {
+        points: vec![*point],
+        times: vec![],
+    };
+    if let Ok(val) = serde_json::from_value::<GeoPoint>(val.clone()) {
+        Some(val.haversine_distance(point))
+    } else if let Ok(val) = serde_json::from_value::<GeoLineString>(val.clone()) {
+        val.get_min_point_distance(&search_line_string)
+    } else if let Ok(val) = serde_json::from_value::<GeoPolygon>(val.clone()) {
+        if val.contains_point(point) {
+            Some(0.0)
+        } else {
+            val.exterior?.get_min_point_distance(&search_line_string)
+        }
+    } else {
+        None
+    }
+}
+
+/// Filters the `unfiltered` values for the provided `filters`, mimicking the database search.
+///
+/// Filters are combined using their [ComparisonOperator], following SQL operator precedence,
+/// so `AND` filters are evaluated before `OR` filters.
+/// Nested [FilterGroup]s are evaluated first and combined as a single filter.
+/// Empty groups are ignored.
+#[cfg(any(feature = "stub_client", feature = "stub_server"))]
+pub(crate) fn filter_for_options(
+    filters: &[FilterOption],
+    unfiltered: &Vec<serde_json::Value>,
+) -> Result<Vec<serde_json::Value>, String> {
+    let mut collected: Vec<serde_json::Value> = vec![];
+    // Values matching the current chain of `AND` filters
+    let mut current: Option<Vec<serde_json::Value>> = None;
+    for filter in filters.iter() {
+        let mut matched: Vec<serde_json::Value> = vec![];
+        match &filter.group {
+            Some(group) if is_empty_group(&group.filters) => continue,
+            Some(group) => matched = filter_for_options(&group.filters, unfiltered)?,
+            None => {
+                let operator =
+                    PredicateOperator::try_from(filter.predicate_operator).map_err(|e| {
+                        format!(
+                            "Can't convert i32 [{}] into PredicateOperator Enum value: {}",
+                            filter.predicate_operator, e
+                        )
+                    })?;
+                filter_for_operator(
+                    &filter.search_field,
+                    &filter.search_value,
+                    unfiltered,
+                    &mut matched,
+                    operator,
+                )?
+            }
+        }
+
+        let chain = match current.as_mut() {
+            Some(chain) => chain,
+            None => {
+                // The comparison operator of the first filter has nothing to compare with
+                current = Some(matched);
+                continue;
+            }
+        };
+
+        let comparison_operator = match filter.comparison_operator {
+            Some(comparison_operator) => ComparisonOperator::try_from(comparison_operator)
+                .map_err(|e| {
+                    format!(
+                        "Can't convert i32 [{}] into ComparisonOperator Enum value: {}",
+                        comparison_operator, e
+                    )
+                })?,
+            None => ComparisonOperator::And,
+        };
+
+        match comparison_operator {
+            ComparisonOperator::And => chain.retain(|value| matched.contains(value)),
+            ComparisonOperator::Or => {
+                for value in chain.drain(..) {
+                    if !collected.contains(&value) {
+                        collected.push(value);
+                    }
+                }
+                *chain = matched;
+            }
+        }
+    }
+
+    for value in current.unwrap_or_default() {
+        if !collected.contains(&value) {
+            collected.push(value);
+        }
+    }
+
+    Ok(collected)
+}
+
+/// Returns `true` if the provided filters don't contain any filter to search on,
+/// meaning they only consist of (nested) empty groups.
+#[cfg(any(feature = "stub_client", feature = "stub_server"))]
+fn is_empty_group(filters: &[FilterOption]) -> bool {
+    filters.iter().all(|filter| match &filter.group {
+        Some(group) => is_empty_group(&group.filters),
+        None => false,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::SortOrder;
     use super::*;
 
+    #[cfg(any(feature = "stub_client", feature = "stub_server"))]
+    #[test]
+    fn test_filter_for_options() {
+        let unfiltered = vec![
+            serde_json::json!({"id": "1", "data": {"a": 1, "b": 1}}),
+            serde_json::json!({"id": "2", "data": {"a": 2, "b": 1}}),
+            serde_json::json!({"id": "3", "data": {"a": 3, "b": 2}}),
+        ];
+
+        // AND filters should be evaluated before OR filters
+        let filter = AdvancedSearchFilter::search_equals(String::from("a"), String::from("1"))
+            .or_equals(String::from("a"), String::from("2"))
+            .and_equals(String::from("b"), String::from("2"));
+        let result = filter_for_options(&filter.filters, &unfiltered).unwrap();
+        assert_eq!(result, vec![unfiltered[0].clone()]);
+
+        // Explicit groups should be evaluated first
+        let filter = AdvancedSearchFilter::search_group(
+            AdvancedSearchFilter::search_equals(String::from("a"), String::from("1"))
+                .or_equals(String::from("a"), String::from("3")),
+        )
+        .and_equals(String::from("b"), String::from("2"));
+        let result = filter_for_options(&filter.filters, &unfiltered).unwrap();
+        assert_eq!(result, vec![unfiltered[2].clone()]);
+
+        // Empty groups should be ignored
+        let filter = AdvancedSearchFilter::search_group(AdvancedSearchFilter::default())
+            .and_equals(String::from("a"), String::from("2"));
+        let result = filter_for_options(&filter.filters, &unfiltered).unwrap();
+        assert_eq!(result, vec![unfiltered[1].clone()]);
+    }
+
+    #[cfg(any(feature = "stub_client", feature = "stub_server"))]
+    #[test]
+    fn test_filter_for_options_geo_dwithin() {
+        let unfiltered = vec![
+            serde_json::json!({"id": "1", "data": {"geo": {"longitude": 4.9, "latitude": 52.3, "altitude": null}}}),
+            serde_json::json!({"id": "2", "data": {"geo": {"longitude": 4.9, "latitude": 52.4, "altitude": null}}}),
+            serde_json::json!({"id": "3", "data": {"geo": {"points": [
+                {"longitude": 5.9, "latitude": 52.3, "altitude": null},
+                {"longitude": 4.901, "latitude": 52.3, "altitude": null}
+            ], "times": []}}}),
+            serde_json::json!({"id": "4", "data": {"geo": null}}),
+        ];
+
+        // 52.3 to 52.4 latitude is about 11 km, the line string passes within 100 meters
+        let filter = AdvancedSearchFilter::search_geo_dwithin(
+            String::from("geo"),
+            String::from("POINT(4.9 52.3)"),
+            10000.0,
+        );
+        let result = filter_for_options(&filter.filters, &unfiltered).unwrap();
+        assert_eq!(result, vec![unfiltered[0].clone(), unfiltered[2].clone()]);
+
+        let filter = AdvancedSearchFilter::search_geo_dwithin(
+            String::from("geo"),
+            String::from("POINT(4.9 52.3)"),
+            12000.0,
+        );
+        let result = filter_for_options(&filter.filters, &unfiltered).unwrap();
+        assert_eq!(result, unfiltered[..3].to_vec());
+
+        // Only points are supported as search geometry
+        let filter = AdvancedSearchFilter::search_geo_dwithin(
+            String::from("geo"),
+            String::from("LINESTRING(4.9 52.3, 5.9 52.3)"),
+            10000.0,
+        );
+        assert!(filter_for_options(&filter.filters, &unfiltered).is_err());
+    }
+
     // Test all of search, and, or options for predicate operator; equals
     #[test]
     fn test_search_equals() {
@@ -1505,6 +1913,122 @@ mod tests {
         );
     }
 
+    // Test all of search, and, or options for predicate operator; geo_dwithin
+    #[test]
+    fn test_search_geo_dwithin() {
+        let point = String::from("POINT(4.9 52.3)");
+        let filter =
+            AdvancedSearchFilter::search_geo_dwithin(String::from("dwithin"), point.clone(), 100.0)
+                .and_geo_dwithin(String::from("and_dwithin"), point.clone(), 250.5)
+                .or_geo_dwithin(String::from("or_dwithin"), point.clone(), 0.0);
+
+        assert_eq!(filter.filters.len(), 3);
+        for filter_option in &filter.filters {
+            assert_eq!(
+                filter_option.predicate_operator,
+                PredicateOperator::GeoDwithin as i32
+            );
+        }
+        assert_eq!(filter.filters[0].search_field, "dwithin");
+        assert_eq!(
+            filter.filters[0].search_value,
+            vec![point.clone(), String::from("100")]
+        );
+        assert_eq!(
+            filter.filters[1].search_value,
+            vec![point.clone(), String::from("250.5")]
+        );
+        assert_eq!(
+            filter.filters[1].comparison_operator,
+            Some(ComparisonOperator::And as i32)
+        );
+        assert_eq!(
+            filter.filters[2].comparison_operator,
+            Some(ComparisonOperator::Or as i32)
+        );
+    }
+
+    #[test]
+    fn test_sort_by_distance() {
+        let point = String::from("POINT(4.9 52.3)");
+        let filter =
+            AdvancedSearchFilter::search_geo_dwithin(String::from("geo"), point.clone(), 100.0)
+                .sort_by_distance(String::from("geo"), point.clone());
+
+        assert_eq!(filter.order_by.len(), 1);
+        assert_eq!(filter.order_by[0].sort_field, "geo");
+        assert_eq!(filter.order_by[0].sort_order, SortOrder::Asc as i32);
+        assert_eq!(filter.order_by[0].distance_from, Some(point));
+        assert_eq!(filter.filters.len(), 1);
+    }
+
+    #[test]
+    fn test_include_deleted() {
+        let filter =
+            AdvancedSearchFilter::search_equals(String::from("equals"), String::from("test"));
+        assert_eq!(filter.include_deleted, false);
+
+        let filter = filter.include_deleted(true);
+        assert_eq!(filter.include_deleted, true);
+        assert_eq!(filter.filters.len(), 1);
+    }
+
+    #[test]
+    fn test_page_token() {
+        let filter =
+            AdvancedSearchFilter::search_equals(String::from("equals"), String::from("test"))
+                .results_per_page(10);
+        assert_eq!(filter.page_token, String::new());
+
+        let filter = filter.page_token(String::from("cursor"));
+        assert_eq!(filter.page_token, String::from("cursor"));
+        assert_eq!(filter.results_per_page, 10);
+        assert_eq!(filter.filters.len(), 1);
+    }
+
+    #[test]
+    fn test_filter_groups() {
+        let filter =
+            AdvancedSearchFilter::search_equals(String::from("status"), String::from("enabled"))
+                .and_group(
+                    AdvancedSearchFilter::search_equals(String::from("x"), String::from("1"))
+                        .or_equals(String::from("y"), String::from("2")),
+                )
+                .or_group(
+                    AdvancedSearchFilter::search_is_null(String::from("z")).results_per_page(10),
+                );
+
+        assert_eq!(filter.filters.len(), 3);
+        assert_eq!(filter.results_per_page, -1);
+        assert!(filter.filters[0].group.is_none());
+
+        let group_option = &filter.filters[1];
+        assert_eq!(group_option.search_field, "");
+        assert_eq!(
+            group_option.comparison_operator,
+            Some(ComparisonOperator::And as i32)
+        );
+        let group = group_option.group.as_ref().unwrap();
+        assert_eq!(group.filters.len(), 2);
+        assert_eq!(group.filters[0].search_field, "x");
+        assert_eq!(
+            group.filters[1].comparison_operator,
+            Some(ComparisonOperator::Or as i32)
+        );
+
+        let group_option = &filter.filters[2];
+        assert_eq!(
+            group_option.comparison_operator,
+            Some(ComparisonOperator::Or as i32)
+        );
+        assert_eq!(group_option.group.as_ref().unwrap().filters.len(), 1);
+
+        let filter = AdvancedSearchFilter::search_group(filter);
+        assert_eq!(filter.filters.len(), 1);
+        assert_eq!(filter.filters[0].comparison_operator, None);
+        assert_eq!(filter.filters[0].group.as_ref().unwrap().filters.len(), 3);
+    }
+
     #[test]
     fn test_predicate_operator_as_str_name() {
         assert_eq!(PredicateOperator::Equals.as_str_name(), "EQUALS");
@@ -1532,6 +2056,7 @@ mod tests {
         );
         assert_eq!(PredicateOperator::GeoWithin.as_str_name(), "GEO_WITHIN");
         assert_eq!(PredicateOperator::GeoDisjoint.as_str_name(), "GEO_DISJOINT");
+        assert_eq!(PredicateOperator::GeoDwithin.as_str_name(), "GEO_DWITHIN");
     }
 
     #[test]
@@ -1588,6 +2113,10 @@ mod tests {
             PredicateOperator::from_str_name("GEO_DISJOINT"),
             Some(PredicateOperator::GeoDisjoint)
         );
+        assert_eq!(
+            PredicateOperator::from_str_name("GEO_DWITHIN"),
+            Some(PredicateOperator::GeoDwithin)
+        );
 
         assert_eq!(PredicateOperator::from_str_name("INVALID"), None);
     }
@@ -0,0 +1,34 @@
+use super::{Data, VehicleModelType};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use uuid::Uuid;
+
+/// Creates a new [Data] object with fields set with random data
+pub fn get_data_obj() -> Data {
+    let mut rng = rand::thread_rng();
+
+    let r#type = *[VehicleModelType::VtolCargo, VehicleModelType::VtolPassenger]
+        .choose(&mut rng)
+        .expect("invalid vehicle model type generated");
+
+    Data {
+        manufacturer_id: Some(Uuid::new_v4().to_string()),
+        model_name: format!("M-MOCK-{:0>8}", rng.gen_range(0..10000000)),
+        r#type: r#type as i32,
+        max_payload_kg: Some(rng.gen_range(50.0..500.0)),
+        max_range_km: Some(rng.gen_range(20.0..300.0)),
+    }
+}
+
+#[test]
+fn test_get_data_obj() {
+    let data: Data = get_data_obj();
+
+    assert!(data
+        .manufacturer_id
+        .map_or(false, |id| Uuid::parse_str(&id).is_ok()));
+    assert!(data.model_name.len() > 0);
+    assert!(VehicleModelType::try_from(data.r#type).is_ok());
+    assert!(data.max_payload_kg.map_or(false, |kg| kg > 0.0));
+    assert!(data.max_range_km.map_or(false, |km| km > 0.0));
+}
@@ -68,6 +68,7 @@ pub fn get_data_obj() -> Data {
         geo_location: Some(GeoPoint {
             longitude: -122.4194,
             latitude: 37.7746,
+            altitude: None,
         }),
         enabled: true,
         occupied: false,
@@ -2,6 +2,7 @@
 //!
 //! Commonly used libraries, functions and statics, made public for easy use in modules.
 
+use crate::grpc::server::{ErrorDetails, ErrorReason};
 use config::ConfigError;
 use std::sync::atomic::{AtomicBool, Ordering};
 use tokio::task::JoinError;
@@ -53,4 +54,72 @@ pub enum ArrErr {
     #[error("error: {0}")]
     /// return new [`anyhow::Error`] with calling params
     AnyhowError(#[from] anyhow::Error),
+
+    #[error("not found: {}", .0.message)]
+    /// the requested record does not exist
+    NotFound(ErrorDetails),
+
+    #[error("already exists: {}", .0.message)]
+    /// the record conflicts with an existing record
+    AlreadyExists(ErrorDetails),
+
+    #[error("failed precondition: {}", .0.message)]
+    /// the system is not in the state required for the requested operation
+    FailedPrecondition(ErrorDetails),
+
+    #[error("invalid argument: {}", .0.message)]
+    /// the provided data or filter could not be processed
+    InvalidArgument(ErrorDetails),
+
+    #[error("aborted: {}", .0.message)]
+    /// the operation was aborted due to a concurrency conflict and can be retried
+    Aborted(ErrorDetails),
+
+    #[error("unavailable: {}", .0.message)]
+    /// the database is not available
+    Unavailable(ErrorDetails),
+}
+
+impl ArrErr {
+    /// return new [`ArrErr::NotFound`] with the provided message
+    pub fn not_found(message: impl Into<String>) -> Self {
+        ArrErr::NotFound(get_error_details(ErrorReason::NotFound, message))
+    }
+
+    /// return new [`ArrErr::FailedPrecondition`] with the provided message
+    pub fn invalid_state(message: impl Into<String>) -> Self {
+        ArrErr::FailedPrecondition(get_error_details(ErrorReason::InvalidState, message))
+    }
+
+    /// return new [`ArrErr::InvalidArgument`] with the provided message
+    pub fn invalid_argument(message: impl Into<String>) -> Self {
+        ArrErr::InvalidArgument(get_error_details(ErrorReason::InvalidArgument, message))
+    }
+
+    /// return new [`ArrErr::Unavailable`] with the provided message
+    pub fn unavailable(message: impl Into<String>) -> Self {
+        ArrErr::Unavailable(get_error_details(ErrorReason::Unavailable, message))
+    }
+
+    /// Returns the structured [`ErrorDetails`] for typed errors, [`None`] for all other errors
+    pub fn get_details(&self) -> Option<&ErrorDetails> {
+        match self {
+            ArrErr::NotFound(details)
+            | ArrErr::AlreadyExists(details)
+            | ArrErr::FailedPrecondition(details)
+            | ArrErr::InvalidArgument(details)
+            | ArrErr::Aborted(details)
+            | ArrErr::Unavailable(details) => Some(details),
+            _ => None,
+        }
+    }
+}
+
+/// Returns [`ErrorDetails`] for the provided reason and message, without any database details
+fn get_error_details(reason: ErrorReason, message: impl Into<String>) -> ErrorDetails {
+    ErrorDetails {
+        reason: reason as i32,
+        message: message.into(),
+        ..Default::default()
+    }
 }
@@ -25,6 +25,19 @@ pub struct Config {
     pub docker_port_grpc: u16,
     /// path to log configuration YAML file
     pub log_config: String,
+    /// interval in seconds used to check the database health
+    pub health_check_interval_secs: u64,
+    /// maximum distance in meters allowed between the start and end points of a flight path and its vertipads
+    pub flight_path_tolerance_meters: f64,
+    /// number of days ADS-B telemetry is kept before it is removed by the database's row-level TTL job, `0` to keep all telemetry
+    pub adsb_retention_days: u32,
+    /// interval in seconds used to remove expired recorded changes
+    pub retention_interval_secs: u64,
+    /// maximum difference in milliseconds between the network timestamps of identical ADS-B messages
+    /// received by multiple receivers to be stored as a single message, `0` to store all messages
+    pub adsb_dedup_tolerance_ms: u64,
+    /// number of days recorded changes are kept for watchers to resume from, `0` to keep all changes
+    pub change_log_retention_days: u32,
 }
 
 impl Default for Config {
@@ -40,6 +53,12 @@ impl Config {
         Config {
             docker_port_grpc: 50051,
             log_config: String::from("log4rs.yaml"),
+            health_check_interval_secs: 10,
+            flight_path_tolerance_meters: 10.0,
+            adsb_retention_days: 30,
+            retention_interval_secs: 3600,
+            adsb_dedup_tolerance_ms: 1000,
+            change_log_retention_days: 7,
             pg: deadpool_postgres::Config::new(),
             use_tls: true,
             db_ca_cert: "".to_string(),
@@ -58,6 +77,27 @@ impl Config {
             .set_default("docker_port_grpc", default_config.docker_port_grpc)?
             .set_default("log_config", default_config.log_config)?
             .set_default("use_tls", default_config.use_tls)?
+            .set_default(
+                "health_check_interval_secs",
+                default_config.health_check_interval_secs,
+            )?
+            .set_default(
+                "flight_path_tolerance_meters",
+                default_config.flight_path_tolerance_meters,
+            )?
+            .set_default("adsb_retention_days", default_config.adsb_retention_days)?
+            .set_default(
+                "retention_interval_secs",
+                default_config.retention_interval_secs,
+            )?
+            .set_default(
+                "adsb_dedup_tolerance_ms",
+                default_config.adsb_dedup_tolerance_ms,
+            )?
+            .set_default(
+                "change_log_retention_days",
+                default_config.change_log_retention_days,
+            )?
             .add_source(Environment::default().separator("__"))
             .build()?
             .try_deserialize()
@@ -78,6 +118,12 @@ mod tests {
         assert_eq!(config.docker_port_grpc, 50051);
         assert_eq!(config.log_config, String::from("log4rs.yaml"));
         assert_eq!(config.use_tls, true);
+        assert_eq!(config.health_check_interval_secs, 10);
+        assert_eq!(config.flight_path_tolerance_meters, 10.0);
+        assert_eq!(config.adsb_retention_days, 30);
+        assert_eq!(config.retention_interval_secs, 3600);
+        assert_eq!(config.adsb_dedup_tolerance_ms, 1000);
+        assert_eq!(config.change_log_retention_days, 7);
 
         ut_info!("(test_config_from_default) Success.");
     }
@@ -0,0 +1,178 @@
+//! gRPC server implementation of the parcel custody service
+
+pub use super::server::parcel::rpc_custody_server::*;
+
+use super::parse_uuid;
+use super::server::parcel::{CustodyTimelineRequest, CustodyTimelineResponse};
+use super::server::{ReadyRequest, ReadyResponse};
+use tonic::{Request, Response, Status};
+
+/// Implementation of gRPC endpoints
+#[derive(Clone, Default, Debug, Copy)]
+pub struct GrpcServer {}
+impl GrpcServer {
+    /// Get name string for service
+    pub fn get_name(&self) -> String {
+        String::from("parcel_custody")
+    }
+}
+
+#[tonic::async_trait]
+impl RpcCustody for GrpcServer {
+    /// Returns all scans, flight legs and status changes of the provided parcel, ordered by their timestamp
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Status`] with [`tonic::Code::InvalidArgument`] if the provided id can not be converted to a [`Uuid`](uuid::Uuid).
+    /// Returns [`Status`] with [`tonic::Code::NotFound`] if no parcel exists for the provided id.
+    /// Returns [`Status`] with [`tonic::Code::Internal`] if any error is returned from a db call.
+    #[cfg(not(feature = "stub_server"))]
+    async fn get_custody_timeline(
+        &self,
+        request: Request<CustodyTimelineRequest>,
+    ) -> Result<Response<CustodyTimelineResponse>, Status> {
+        grpc_info!("(get_custody_timeline) {} server.", self.get_name());
+        grpc_debug!("(get_custody_timeline) request: {:?}", request);
+        let parcel_id = parse_uuid("parcel_id", &request.into_inner().parcel_id)?;
+        let timeline = crate::resources::parcel::custody::get_custody_timeline(&parcel_id)
+            .await
+            .map_err(super::get_status_with_message)?;
+
+        Ok(Response::new(CustodyTimelineResponse {
+            parcel_id: parcel_id.to_string(),
+            status: timeline.status as i32,
+            list: timeline.events,
+        }))
+    }
+    #[cfg(feature = "stub_server")]
+    async fn get_custody_timeline(
+        &self,
+        request: Request<CustodyTimelineRequest>,
+    ) -> Result<Response<CustodyTimelineResponse>, Status> {
+        use super::server::parcel::{
+            CustodyEvent, CustodyEventType, CustodyFlightLeg, CustodyScan,
+        };
+        use super::server::{flight_plan, flight_plan_parcel, parcel, parcel_scan};
+        use crate::common::ArrErr;
+        use crate::resources::parcel::custody::sort_custody_events;
+
+        grpc_warn!("(get_custody_timeline MOCK) {} server.", self.get_name());
+        grpc_debug!("(get_custody_timeline MOCK) request: {:?}", request);
+        let parcel_id = parse_uuid("parcel_id", &request.into_inner().parcel_id)?.to_string();
+
+        let status = match parcel::MEM_DATA
+            .lock()
+            .await
+            .iter()
+            .find(|object| object.id == parcel_id)
+        {
+            Some(object) => object.data.as_ref().map_or(0, |data| data.status),
+            None => {
+                return Err(ArrErr::not_found(format!(
+                    "No [parcel] found for specified uuid: {}",
+                    parcel_id
+                ))
+                .into())
+            }
+        };
+
+        let mut events: Vec<CustodyEvent> = parcel_scan::MEM_DATA
+            .lock()
+            .await
+            .iter()
+            .filter_map(|object| {
+                let data = object.data.as_ref()?;
+                if data.parcel_id != parcel_id {
+                    return None;
+                }
+                Some(CustodyEvent {
+                    event_type: CustodyEventType::Scan as i32,
+                    timestamp: data.created_at.clone(),
+                    scan: Some(CustodyScan {
+                        parcel_scan_id: object.id.clone(),
+                        scanner_id: data.scanner_id.clone(),
+                        geo_location: data.geo_location,
+                    }),
+                    flight_leg: None,
+                    status_change: None,
+                })
+            })
+            .collect();
+
+        let flight_plans = flight_plan::MEM_DATA.lock().await;
+        for link in flight_plan_parcel::MEM_DATA
+            .lock()
+            .await
+            .iter()
+            .filter(|link| link.parcel_id == parcel_id)
+        {
+            let data = match flight_plans
+                .iter()
+                .find(|object| object.id == link.flight_plan_id)
+                .and_then(|object| object.data.as_ref())
+            {
+                Some(data) => data,
+                None => continue,
+            };
+            if data.flight_status == flight_plan::FlightStatus::Cancelled as i32 {
+                continue;
+            }
+            let departure = data
+                .actual_departure_time
+                .clone()
+                .or(data.origin_timeslot_start.clone());
+            let arrival = data
+                .actual_arrival_time
+                .clone()
+                .or(data.target_timeslot_end.clone());
+            events.push(CustodyEvent {
+                event_type: CustodyEventType::FlightLeg as i32,
+                timestamp: departure.clone(),
+                scan: None,
+                flight_leg: Some(CustodyFlightLeg {
+                    flight_plan_id: link.flight_plan_id.clone(),
+                    acquire: link.acquire,
+                    deliver: link.deliver,
+                    origin_vertipad_id: data.origin_vertipad_id.clone(),
+                    target_vertipad_id: data.target_vertipad_id.clone(),
+                    departure,
+                    arrival,
+                    departed: data.actual_departure_time.is_some(),
+                    arrived: data.actual_arrival_time.is_some(),
+                }),
+                status_change: None,
+            });
+        }
+        sort_custody_events(&mut events);
+
+        Ok(Response::new(CustodyTimelineResponse {
+            parcel_id,
+            status,
+            list: events,
+        }))
+    }
+
+    /// Returns ready:true when service is available
+    #[cfg(not(feature = "stub_server"))]
+    async fn is_ready(
+        &self,
+        request: Request<ReadyRequest>,
+    ) -> Result<Response<ReadyResponse>, Status> {
+        grpc_info!("(is_ready) {} server.", self.get_name());
+        grpc_debug!("(is_ready) request: {:?}", request);
+        let response = ReadyResponse {
+            ready: super::health::is_ready(),
+        };
+        Ok(Response::new(response))
+    }
+    #[cfg(feature = "stub_server")]
+    async fn is_ready(
+        &self,
+        request: Request<ReadyRequest>,
+    ) -> Result<Response<ReadyResponse>, Status> {
+        grpc_info!("(is_ready MOCK) {} server.", self.get_name());
+        grpc_debug!("(is_ready MOCK) request: {:?}", request);
+        let response = ReadyResponse { ready: true };
+        Ok(Response::new(response))
+    }
+}
@@ -0,0 +1,222 @@
+//! Database health monitoring
+//!
+//! A background task periodically checks the database using [`PostgresPool::readiness`](crate::postgres::PostgresPool::readiness).
+//! The result is used to update the [`HealthReporter`] status of all gRPC services
+//! and returned by the `is_ready` and `get_health` RPCs.
+
+use super::server::HealthResponse;
+use std::sync::RwLock;
+use std::time::Duration;
+use tonic_health::server::HealthReporter;
+use tonic_health::ServingStatus;
+
+/// Result of the last health check, [`None`] if no check has been performed yet
+static HEALTH_STATUS: RwLock<Option<HealthResponse>> = RwLock::new(None);
+
+/// Returns the result of the last health check
+///
+/// A [`HealthResponse`] with `ready` set to `false` will be returned if no check has been performed yet.
+#[cfg(not(feature = "stub_backends"))]
+pub fn get_health() -> HealthResponse {
+    match HEALTH_STATUS.read() {
+        Ok(status) => status.clone().unwrap_or_else(|| HealthResponse {
+            error: Some(String::from("No health check performed yet.")),
+            ..Default::default()
+        }),
+        Err(e) => HealthResponse {
+            error: Some(format!("Could not read health status: {}", e)),
+            ..Default::default()
+        },
+    }
+}
+
+/// Returns the result of the last health check
+#[cfg(feature = "stub_backends")]
+pub fn get_health() -> HealthResponse {
+    grpc_debug!("(get_health MOCK) Returning mock health status.");
+    match HEALTH_STATUS.read() {
+        Ok(status) => status.clone().unwrap_or(HealthResponse {
+            ready: true,
+            ..Default::default()
+        }),
+        Err(_) => HealthResponse {
+            ready: true,
+            ..Default::default()
+        },
+    }
+}
+
+/// Returns `true` if the last health check succeeded
+pub fn is_ready() -> bool {
+    get_health().ready
+}
+
+/// Stores the provided health check result
+///
+/// Returns `true` if the ready state changed compared to the previous health check.
+fn set_health(health: HealthResponse) -> bool {
+    match HEALTH_STATUS.write() {
+        Ok(mut status) => {
+            let changed = status.as_ref().map(|status| status.ready) != Some(health.ready);
+            *status = Some(health);
+            changed
+        }
+        Err(e) => {
+            grpc_error!("(set_health) Could not store health status: {}", e);
+            false
+        }
+    }
+}
+
+/// Checks the database connection, returning the check results as [`HealthResponse`]
+#[cfg(not(feature = "stub_backends"))]
+pub async fn check_health() -> HealthResponse {
+    use crate::postgres::migrations::get_schema_version;
+    use crate::postgres::try_get_postgres_pool;
+    use std::time::{Instant, SystemTime};
+
+    let mut health = HealthResponse {
+        last_check: Some(SystemTime::now().into()),
+        ..Default::default()
+    };
+
+    let pg = match try_get_postgres_pool().await {
+        Ok(pg) => pg,
+        Err(e) => {
+            grpc_warn!("(check_health) Database pool not available: {}", e);
+            health.error = Some(e.to_string());
+            return health;
+        }
+    };
+
+    let start = Instant::now();
+    let readiness = pg.readiness().await;
+    health.db_latency_ms = Some(start.elapsed().as_secs_f64() * 1000.0);
+
+    let status = pg.pool.status();
+    health.pool_max_size = u32::try_from(status.max_size).unwrap_or(u32::MAX);
+    health.pool_size = u32::try_from(status.size).unwrap_or(u32::MAX);
+    health.pool_available = u32::try_from(status.available).unwrap_or_default();
+    health.pool_waiting = u32::try_from(status.waiting).unwrap_or(u32::MAX);
+
+    if let Err(e) = readiness {
+        grpc_warn!("(check_health) Database not ready: {}", e);
+        health.error = Some(e.to_string());
+        return health;
+    }
+
+    match get_schema_version().await {
+        Ok(version) => health.schema_version = version,
+        Err(e) => {
+            grpc_warn!("(check_health) Could not get schema version: {}", e);
+            health.error = Some(e.to_string());
+            return health;
+        }
+    }
+
+    health.ready = true;
+    health
+}
+
+/// Periodically checks the database health and updates the [`HealthReporter`] status
+/// of the provided services when the ready state changes.
+///
+/// The empty service name (`""`) can be provided to update the overall server status.
+#[cfg(not(feature = "stub_backends"))]
+pub async fn health_monitor(
+    mut health_reporter: HealthReporter,
+    service_names: Vec<&'static str>,
+    interval: Duration,
+) {
+    grpc_info!(
+        "(health_monitor) Starting database health monitor with interval [{:?}].",
+        interval
+    );
+    loop {
+        let health = check_health().await;
+        let ready = health.ready;
+        if set_health(health) {
+            let status = if ready {
+                grpc_info!("(health_monitor) Database is ready, setting services to SERVING.");
+                ServingStatus::Serving
+            } else {
+                grpc_error!(
+                    "(health_monitor) Database is not ready, setting services to NOT_SERVING."
+                );
+                ServingStatus::NotServing
+            };
+            set_services_status(&mut health_reporter, &service_names, status).await;
+        }
+        tokio::time::sleep(interval).await;
+    }
+}
+
+/// Sets all provided services to serving, the database is not monitored for stubbed backends
+#[cfg(feature = "stub_backends")]
+pub async fn health_monitor(
+    mut health_reporter: HealthReporter,
+    service_names: Vec<&'static str>,
+    _interval: Duration,
+) {
+    grpc_info!("(health_monitor MOCK) Setting all services to SERVING.");
+    set_health(HealthResponse {
+        ready: true,
+        ..Default::default()
+    });
+    set_services_status(&mut health_reporter, &service_names, ServingStatus::Serving).await;
+}
+
+/// Updates the [`HealthReporter`] status for all provided services
+async fn set_services_status(
+    health_reporter: &mut HealthReporter,
+    service_names: &[&'static str],
+    status: ServingStatus,
+) {
+    for service_name in service_names {
+        health_reporter
+            .set_service_status(service_name, status)
+            .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_set_health() {
+        crate::get_log_handle().await;
+        ut_info!("(test_set_health) start");
+
+        // The ready state is only reported as changed on transitions
+        set_health(HealthResponse {
+            ready: false,
+            ..Default::default()
+        });
+        assert!(!is_ready());
+        assert!(set_health(HealthResponse {
+            ready: true,
+            db_latency_ms: Some(1.0),
+            schema_version: Some(2),
+            ..Default::default()
+        }));
+        assert!(is_ready());
+        assert_eq!(get_health().schema_version, Some(2));
+        assert!(!set_health(HealthResponse {
+            ready: true,
+            ..Default::default()
+        }));
+        assert!(set_health(HealthResponse {
+            ready: false,
+            error: Some(String::from("Database connection closed.")),
+            ..Default::default()
+        }));
+        assert!(!is_ready());
+        assert_eq!(
+            get_health().error,
+            Some(String::from("Database connection closed."))
+        );
+
+        ut_info!("(test_set_health) success");
+    }
+}
@@ -5,11 +5,11 @@ pub use crate::common::ArrErr;
 use std::collections::HashMap;
 use std::str::FromStr;
 use tokio_postgres::Row;
-use tonic::{Code, Request, Response, Status};
+use tonic::{Request, Response, Status};
 use uuid::Uuid;
 
 use super::server::*;
-use super::GrpcDataObjectType;
+use super::{get_status_with_message, GrpcDataObjectType};
 use crate::postgres::linked_resource::PsqlType;
 use crate::postgres::simple_resource::PsqlType as PsqlSimpleType;
 use crate::postgres::PsqlSearch;
@@ -101,9 +101,9 @@ where
     ///
     /// # Errors
     ///
-    /// Returns [`Status`] with [`Code::NotFound`] if no record exists for the given `id`.
-    /// Returns [`Status`] with [`Code::Internal`] if the provided Id can not be converted to a [`uuid::Uuid`].  
-    /// Returns [`Status`] with [`Code::Internal`] if any error is returned from the db search result.  
+    /// Returns [`Status`] with [`Code::NotFound`](tonic::Code::NotFound) if no record exists for the given `id`.
+    /// Returns [`Status`] with [`Code::Internal`](tonic::Code::Internal) if the provided Id can not be converted to a [`uuid::Uuid`].  
+    /// Returns [`Status`] with [`Code::Internal`](tonic::Code::Internal) if any error is returned from the db search result.  
     ///
     async fn generic_link(
         &self,
@@ -122,7 +122,7 @@ where
                     id, e
                 );
                 grpc_error!("(generic_link) {}", error);
-                return Err(Status::new(Code::NotFound, error));
+                return Err(ArrErr::not_found(error).into());
             }
         };
         if Self::ResourceObject::get_by_id(&id).await.is_err() {
@@ -132,7 +132,7 @@ where
                 id
             );
             grpc_error!("(generic_link) {}", error);
-            return Err(Status::new(Code::NotFound, error));
+            return Err(ArrErr::not_found(error).into());
         }
 
         let mut ids: Vec<HashMap<String, Uuid>> = vec![];
@@ -158,9 +158,9 @@ where
     ///
     /// # Errors
     ///
-    /// Returns [`Status`] with [`Code::NotFound`] if no record exists for the given `id`.
-    /// Returns [`Status`] with [`Code::Internal`] if the provided Id can not be converted to valid [`uuid::Uuid`].  
-    /// Returns [`Status`] with [`Code::Internal`] if any error is returned from the db search result.  
+    /// Returns [`Status`] with [`Code::NotFound`](tonic::Code::NotFound) if no record exists for the given `id`.
+    /// Returns [`Status`] with [`Code::Internal`](tonic::Code::Internal) if the provided Id can not be converted to valid [`uuid::Uuid`].  
+    /// Returns [`Status`] with [`Code::Internal`](tonic::Code::Internal) if any error is returned from the db search result.  
     ///
     async fn generic_unlink(&self, request: Request<Id>) -> Result<Response<()>, Status> {
         let id: Id = request.into_inner();
@@ -172,7 +172,7 @@ where
         {
             let error = format!("No resource found for specified uuids: {:?}", id);
             grpc_error!("(generic_unlink) {}", error);
-            return Err(Status::new(Code::NotFound, error));
+            return Err(ArrErr::not_found(error).into());
         }
 
         match Self::LinkedResourceObject::delete_for_ids(
@@ -185,7 +185,7 @@ where
         .await
         {
             Ok(_) => Ok(tonic::Response::new(())),
-            Err(e) => Err(Status::new(Code::Internal, e.to_string())),
+            Err(e) => Err(get_status_with_message(e)),
         }
     }
     /// Returns a [`tonic`] gRCP [`Response`] with [`IdList`] of found ids on success
@@ -194,9 +194,9 @@ where
     ///
     /// # Errors
     ///
-    /// Returns [`Status`] with [`Code::NotFound`] if no record exists for the given `id`.
-    /// Returns [`Status`] with [`Code::Internal`] if the provided Id can not be converted to a [`uuid::Uuid`].  
-    /// Returns [`Status`] with [`Code::Internal`] if any error is returned from the db search result.  
+    /// Returns [`Status`] with [`Code::NotFound`](tonic::Code::NotFound) if no record exists for the given `id`.
+    /// Returns [`Status`] with [`Code::Internal`](tonic::Code::Internal) if the provided Id can not be converted to a [`uuid::Uuid`].  
+    /// Returns [`Status`] with [`Code::Internal`](tonic::Code::Internal) if any error is returned from the db search result.  
     async fn generic_get_linked_ids(&self, request: Request<Id>) -> Result<Response<IdList>, Status>
     where
         Self: Send + 'async_trait,
@@ -212,9 +212,9 @@ where
     ///
     /// # Errors
     ///
-    /// Returns [`Status`] with [`Code::NotFound`] if no record exists for the given `id`.
-    /// Returns [`Status`] with [`Code::Internal`] if the provided Id can not be converted to a [`uuid::Uuid`].  
-    /// Returns [`Status`] with [`Code::Internal`] if any error is returned from the db search result.  
+    /// Returns [`Status`] with [`Code::NotFound`](tonic::Code::NotFound) if no record exists for the given `id`.
+    /// Returns [`Status`] with [`Code::Internal`](tonic::Code::Internal) if the provided Id can not be converted to a [`uuid::Uuid`].  
+    /// Returns [`Status`] with [`Code::Internal`](tonic::Code::Internal) if any error is returned from the db search result.  
     async fn generic_get_linked(
         &self,
         request: Request<Id>,
@@ -229,7 +229,7 @@ where
 
         match Self::OtherResourceObject::advanced_search(filter).await {
             Ok(rows) => Ok(tonic::Response::new(rows.try_into()?)),
-            Err(e) => Err(Status::new(Code::Internal, e.to_string())),
+            Err(e) => Err(get_status_with_message(e)),
         }
     }
 
@@ -262,11 +262,15 @@ where
     }
 
     /// Returns ready:true when service is available
+    ///
+    /// The service is available if the last database health check succeeded.
     async fn generic_is_ready(
         &self,
         _request: Request<ReadyRequest>,
     ) -> Result<Response<ReadyResponse>, Status> {
-        let response = ReadyResponse { ready: true };
+        let response = ReadyResponse {
+            ready: super::health::is_ready(),
+        };
         Ok(Response::new(response))
     }
 }
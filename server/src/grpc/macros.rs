@@ -316,7 +316,7 @@ macro_rules! grpc_server_link_service_mod {
                             grpc_error!("(get_linked MOCK) {}", error);
                             return Err(tonic::Status::not_found(error));
                         }
-                        Ok(tonic::Response::new($other_resource::List { list: other_resource_list }))
+                        Ok(tonic::Response::new($other_resource::List { total_count: other_resource_list.len() as i64, list: other_resource_list, next_page_token: String::new(), distances: vec![] }))
                     },
                     _ => Err(tonic::Status::not_found("Not found")),
                 }
@@ -356,8 +356,10 @@ macro_rules! grpc_server_simple_service_mod {
         ///
         pub mod $resource {
             #![allow(unused_qualifications)]
+            // Not all resource responses have additional fields
+            #![allow(clippy::needless_update)]
             use super::{
-                AdvancedSearchFilter, GrpcSimpleService, Id, Request, ResourceObject, Status, Serialize, Deserialize, ReadyRequest, ReadyResponse
+                AdvancedSearchFilter, BoxStream, DeleteManyRequest, DeleteManyResponse, GrpcSimpleService, HealthResponse, Id, Request, ResourceObject, Status, Serialize, Deserialize, ReadyRequest, ReadyResponse
             };
 
             cfg_if::cfg_if! {
@@ -406,6 +408,10 @@ macro_rules! grpc_server_simple_service_mod {
                 type UpdateObject = UpdateObject;
                 type List = List;
                 type Response = Response;
+                type InsertManyRequest = InsertManyRequest;
+                type UpdateManyRequest = UpdateManyRequest;
+                type ResponseList = ResponseList;
+                type ChangeEvent = ChangeEvent;
             }
 
             #[tonic::async_trait]
@@ -470,6 +476,53 @@ macro_rules! grpc_server_simple_service_mod {
 
                     Ok(tonic::Response::new(resource_list[0].clone()))
                 }
+                #[doc = concat!("Returns a [`tonic`] gRCP [`Response`] containing an ", stringify!($resource), " [`Object`], including soft-deleted records")]
+                ///
+                /// # Errors
+                ///
+                /// Returns [`tonic::Status`] with [`tonic::Code::NotFound`] if no record is returned from the database
+                ///
+                /// # Examples
+                ///
+                /// ```
+                /// use svc_storage::resources::Id;
+                #[doc = concat!("use svc_storage::resources::", stringify!($resource), "::{Object, GrpcServer, RpcService};")]
+                ///
+                /// async fn example() -> Result<Object, tonic::Status> {
+                ///     let server = GrpcServer::default();
+                ///
+                ///     let id = "53acfe06-dd9b-42e8-8cb4-12a2fb2fa693".to_string();
+                ///     let result = match server.get_by_id_include_deleted(tonic::Request::new(Id { id })).await
+                ///     {
+                ///         Ok(res) => res.into_inner(),
+                ///         Err(e) => {
+                ///             return Err(e);
+                ///         },
+                ///     };
+                ///     log::debug!("{:?}", result);
+                ///
+                ///     Ok(result)
+                /// }
+                /// ```
+                #[cfg(not(feature = "stub_server"))]
+                async fn get_by_id_include_deleted(
+                    &self,
+                    request: Request<Id>,
+                ) -> Result<tonic::Response<Object>, Status> {
+                    grpc_info!("(get_by_id_include_deleted) {} server.", self.get_name());
+                    grpc_debug!("(get_by_id_include_deleted) request: {:?}", request);
+                    self.generic_get_by_id_include_deleted(request).await
+                }
+                // MOCK implementation
+                #[cfg(feature = "stub_server")]
+                async fn get_by_id_include_deleted(
+                    &self,
+                    request: Request<Id>,
+                ) -> Result<tonic::Response<Object>, Status> {
+                    grpc_warn!("(get_by_id_include_deleted MOCK) {} server.", self.get_name());
+                    // Deleted objects are removed from memory, so this is the same as get_by_id
+                    self.get_by_id(request).await
+                }
                 /// Takes an [`AdvancedSearchFilter`] object to search the database with the provided values.
                 ///
                 /// This method supports paged results.
@@ -490,6 +543,8 @@ macro_rules! grpc_server_simple_service_mod {
                 ///         page_number: 1,
                 ///         results_per_page: 10,
                 ///         order_by: vec![],
+                ///         include_deleted: false,
+                ///         page_token: String::new(),
                 ///     };
                 ///
                 ///     let result = match server.search(tonic::Request::new(advanced_filter)).await
@@ -527,7 +582,10 @@ macro_rules! grpc_server_simple_service_mod {
                     if filters.len() == 0 {
                         grpc_debug!("(search MOCK) no filters provided, returning all.");
                         return Ok(tonic::Response::new(List {
-                            list
+                            total_count: list.len() as i64,
+                            list,
+                            next_page_token: String::new(),
+                            distances: vec![],
                         }));
                     }
 
@@ -537,45 +595,8 @@ macro_rules! grpc_server_simple_service_mod {
                     }
                     grpc_debug!("(search MOCK) unfiltered serialized objects: {:?}", unfiltered);
 
-                    let mut collected: Vec<serde_json::Value> = vec![];
-                    for filter in filters {
-                        let operator: super::PredicateOperator =
-                            match super::PredicateOperator::try_from(filter.predicate_operator) {
-                                Ok(val) => val,
-                                Err(e) => {
-                                    return Err(tonic::Status::internal(format!(
-                                        "Can't convert i32 [{}] into PredicateOperator Enum value: {}",
-                                        filter.predicate_operator, e
-                                    )));
-                                }
-                            };
-
-
-                        match filter.comparison_operator {
-                            Some(comparison_operator) => match super::ComparisonOperator::try_from(comparison_operator) {
-                                Ok(comparison_operator) => match comparison_operator {
-                                    super::ComparisonOperator::And => {
-                                        let unfiltered = collected.clone();
-                                        collected = vec![];
-                                        $crate::grpc::server::search::filter_for_operator(&filter.search_field, &filter.search_value, &unfiltered, &mut collected, operator)
-                                            .map_err(|e| tonic::Status::internal(format!("Could not get filtered values for provided filter: {}", e)))?
-                                    }
-                                    super::ComparisonOperator::Or => {
-                                        $crate::grpc::server::search::filter_for_operator(&filter.search_field, &filter.search_value, &unfiltered, &mut collected, operator)
-                                            .map_err(|e| tonic::Status::internal(format!("Could not get filtered values for provided filter: {}", e)))?
-                                    }
-                                }
-                                Err(e) => {
-                                    return Err(tonic::Status::internal(format!(
-                                        "Can't convert i32 [{}] into ComparisonOperator Enum value: {}",
-                                        comparison_operator, e
-                                    )));
-                                }
-                            },
-                            None => $crate::grpc::server::search::filter_for_operator(&filter.search_field, &filter.search_value, &unfiltered, &mut collected, operator)
-                                .map_err(|e| tonic::Status::internal(format!("Could not get filtered values for provided filter: {}", e)))?
-                        };
-                    }
+                    let collected: Vec<serde_json::Value> = $crate::grpc::server::search::filter_for_options(&filters, &unfiltered)
+                        .map_err(|e| tonic::Status::internal(format!("Could not get filtered values for provided filter: {}", e)))?;
 
                     let mut filtered: Vec<Object> = vec![];
                     for val in collected.iter() {
@@ -585,7 +606,147 @@ macro_rules! grpc_server_simple_service_mod {
                         );
                     }
                     let response = List {
-                        list: filtered
+                        total_count: filtered.len() as i64,
+                        list: filtered,
+                        next_page_token: String::new(),
+                        distances: vec![],
+                    };
+                    Ok(tonic::Response::new(response))
+                }
+
+                /// Server streaming response type for the search_stream method.
+                type search_streamStream = BoxStream<Object>;
+
+                /// Takes an [`AdvancedSearchFilter`] object to search the database with the provided values,
+                /// streaming the resulting objects.
+                ///
+                /// Page numbers and page sizes are ignored, all matching records will be returned.
+                ///
+                /// # Examples
+                ///
+                /// ```
+                /// use futures::StreamExt;
+                /// use svc_storage::resources::AdvancedSearchFilter;
+                #[doc = concat!("use svc_storage::resources::", stringify!($resource), "::{GrpcServer, RpcService};")]
+                ///
+                /// async fn example() -> Result<(), tonic::Status> {
+                ///     let server = GrpcServer::default();
+                ///
+                ///     let advanced_filter = AdvancedSearchFilter::search_is_not_null("created_at".to_owned());
+                ///     let mut stream = server
+                ///         .search_stream(tonic::Request::new(advanced_filter))
+                ///         .await?
+                ///         .into_inner();
+                ///     while let Some(object) = stream.next().await {
+                ///         log::debug!("{:?}", object?);
+                ///     }
+                ///
+                ///     Ok(())
+                /// }
+                /// ```
+                #[cfg(not(feature = "stub_server"))]
+                async fn search_stream(
+                    &self,
+                    request: Request<AdvancedSearchFilter>,
+                ) -> Result<tonic::Response<Self::search_streamStream>, Status> {
+                    grpc_info!("(search_stream) {} server.", self.get_name());
+                    grpc_debug!("(search_stream) request: {:?}", request);
+                    self.generic_search_stream(request).await
+                }
+                // MOCK implementation
+                #[cfg(feature = "stub_server")]
+                async fn search_stream(
+                    &self,
+                    request: Request<AdvancedSearchFilter>,
+                ) -> Result<tonic::Response<Self::search_streamStream>, Status> {
+                    grpc_warn!("(search_stream MOCK) {} server.", self.get_name());
+                    grpc_debug!("(search_stream MOCK) request: {:?}", request);
+                    let list = self.search(request).await?.into_inner().list;
+                    Ok(tonic::Response::new(Box::pin(futures::stream::iter(list.into_iter().map(Ok)))))
+                }
+
+                /// Server streaming response type for the watch method.
+                type watchStream = BoxStream<ChangeEvent>;
+
+                /// Takes an [`AdvancedSearchFilter`] object to watch matching records for changes.
+                ///
+                /// A [`ChangeEvent`] will be sent for each created, updated or deleted record.
+                /// The filter's `page_token` can be set to the `sequence` of the last received event to resume watching.
+                ///
+                /// # Examples
+                ///
+                /// ```
+                /// use futures::StreamExt;
+                /// use svc_storage::resources::AdvancedSearchFilter;
+                #[doc = concat!("use svc_storage::resources::", stringify!($resource), "::{GrpcServer, RpcService};")]
+                ///
+                /// async fn example() -> Result<(), tonic::Status> {
+                ///     let server = GrpcServer::default();
+                ///
+                ///     let advanced_filter = AdvancedSearchFilter::search_is_not_null("created_at".to_owned());
+                ///     let mut stream = server
+                ///         .watch(tonic::Request::new(advanced_filter))
+                ///         .await?
+                ///         .into_inner();
+                ///     while let Some(event) = stream.next().await {
+                ///         log::debug!("{:?}", event?);
+                ///     }
+                ///
+                ///     Ok(())
+                /// }
+                /// ```
+                #[cfg(not(feature = "stub_server"))]
+                async fn watch(
+                    &self,
+                    request: Request<AdvancedSearchFilter>,
+                ) -> Result<tonic::Response<Self::watchStream>, Status> {
+                    grpc_info!("(watch) {} server.", self.get_name());
+                    grpc_debug!("(watch) request: {:?}", request);
+                    self.generic_watch(request).await
+                }
+                // MOCK implementation
+                // There is no change log for the in memory data, so a `CREATED` event will be
+                // sent for each matching object before closing the stream.
+                #[cfg(feature = "stub_server")]
+                async fn watch(
+                    &self,
+                    request: Request<AdvancedSearchFilter>,
+                ) -> Result<tonic::Response<Self::watchStream>, Status> {
+                    grpc_warn!("(watch MOCK) {} server.", self.get_name());
+                    grpc_debug!("(watch MOCK) request: {:?}", request);
+                    let list = self.search(request).await?.into_inner().list;
+                    let events = list.into_iter().enumerate().map(|(index, object)| {
+                        Ok(ChangeEvent {
+                            sequence: index as i64 + 1,
+                            change_type: super::ChangeType::Created as i32,
+                            id: object.id.clone(),
+                            object: Some(object),
+                        })
+                    });
+                    Ok(tonic::Response::new(Box::pin(futures::stream::iter(events))))
+                }
+
+                /// Returns detailed health information of the service, including the database latency,
+                /// connection pool utilization and database schema version.
+                #[cfg(not(feature = "stub_server"))]
+                async fn get_health(
+                    &self,
+                    request: Request<ReadyRequest>,
+                ) -> Result<tonic::Response<HealthResponse>, Status> {
+                    grpc_info!("(get_health) {} server.", self.get_name());
+                    grpc_debug!("(get_health) request: {:?}", request);
+                    self.generic_get_health(request).await
+                }
+                #[cfg(feature = "stub_server")]
+                async fn get_health(
+                    &self,
+                    request: Request<ReadyRequest>,
+                ) -> Result<tonic::Response<HealthResponse>, Status> {
+                    grpc_info!("(get_health MOCK) {} server.", self.get_name());
+                    grpc_debug!("(get_health MOCK) request: {:?}", request);
+                    let response = HealthResponse {
+                        ready: true,
+                        ..Default::default()
                     };
                     Ok(tonic::Response::new(response))
                 }
@@ -649,7 +810,8 @@ macro_rules! grpc_server_simple_service_mod {
                         validation_result: Some(super::ValidationResult {
                             success: true,
                             errors: Vec::new()
-                        })
+                        }),
+                        ..Default::default()
                     };
                     mem_data.push(object.clone());
                     Ok(tonic::Response::new(response))
@@ -719,6 +881,7 @@ macro_rules! grpc_server_simple_service_mod {
                                     success: true,
                                     errors: Vec::new(),
                                 }),
+                                ..Default::default()
                             };
 
                             return Ok(tonic::Response::new(response));
@@ -770,6 +933,316 @@ macro_rules! grpc_server_simple_service_mod {
                     Ok(tonic::Response::new(()))
                 }
 
+                #[doc = concat!("Takes an [`Id`] to restore the matching soft-deleted ", stringify!($resource), " record in the database.")]
+                ///
+                /// # Examples
+                /// ```
+                /// use svc_storage::resources::Id;
+                #[doc = concat!("use svc_storage::resources::", stringify!($resource), "::{GrpcServer, RpcService};")]
+                ///
+                /// async fn example() -> Result<(), tonic::Status> {
+                ///     let server = GrpcServer::default();
+                ///
+                ///     let id = "53acfe06-dd9b-42e8-8cb4-12a2fb2fa693".to_string();
+                ///     let result = match server.restore(tonic::Request::new(Id { id })).await
+                ///     {
+                ///         Ok(res) => res.into_inner(),
+                ///         Err(e) => {
+                ///             return Err(e);
+                ///         },
+                ///     };
+                ///     log::debug!("{:?}", result);
+                ///
+                ///     Ok(())
+                /// }
+                /// ```
+                #[cfg(not(feature = "stub_server"))]
+                async fn restore(&self, request: Request<Id>) -> Result<tonic::Response<()>, Status> {
+                    grpc_info!("(restore) {} server.", self.get_name());
+                    grpc_debug!("(restore) request: {:?}", request);
+                    self.generic_restore(request).await
+                }
+                #[cfg(feature = "stub_server")]
+                async fn restore(
+                    &self,
+                    request: tonic::Request<Id>,
+                ) -> Result<tonic::Response<()>, tonic::Status> {
+                    grpc_warn!("(restore MOCK) {} server.", self.get_name());
+                    grpc_debug!("(restore MOCK) request: {:?}", request);
+                    // Deleted objects are removed from memory, so there is nothing to restore
+                    Ok(tonic::Response::new(()))
+                }
+
+                #[doc = concat!("Takes an [`Id`] to permanently remove the matching ", stringify!($resource), " record and its dependent link rows from the database.")]
+                ///
+                /// # Examples
+                /// ```
+                /// use svc_storage::resources::Id;
+                #[doc = concat!("use svc_storage::resources::", stringify!($resource), "::{GrpcServer, RpcService};")]
+                ///
+                /// async fn example() -> Result<(), tonic::Status> {
+                ///     let server = GrpcServer::default();
+                ///
+                ///     let id = "53acfe06-dd9b-42e8-8cb4-12a2fb2fa693".to_string();
+                ///     let result = match server.purge(tonic::Request::new(Id { id })).await
+                ///     {
+                ///         Ok(res) => res.into_inner(),
+                ///         Err(e) => {
+                ///             return Err(e);
+                ///         },
+                ///     };
+                ///     log::debug!("{:?}", result);
+                ///
+                ///     Ok(())
+                /// }
+                /// ```
+                #[cfg(not(feature = "stub_server"))]
+                async fn purge(&self, request: Request<Id>) -> Result<tonic::Response<()>, Status> {
+                    grpc_info!("(purge) {} server.", self.get_name());
+                    grpc_debug!("(purge) request: {:?}", request);
+                    self.generic_purge(request).await
+                }
+                #[cfg(feature = "stub_server")]
+                async fn purge(
+                    &self,
+                    request: tonic::Request<Id>,
+                ) -> Result<tonic::Response<()>, tonic::Status> {
+                    grpc_warn!("(purge MOCK) {} server.", self.get_name());
+                    grpc_debug!("(purge MOCK) request: {:?}", request);
+                    let id = request.into_inner().id;
+                    let mut list = $crate::resources::$resource::MEM_DATA.lock().await;
+                    list.retain(|object| object.id != id);
+                    Ok(tonic::Response::new(()))
+                }
+
+                #[doc = concat!("Takes an [`InsertManyRequest`] to create multiple ", stringify!($resource), " records using a single transaction.")]
+                ///
+                /// Returns a [`ResponseList`] containing a [`Response`] for each of the provided items.
+                ///
+                /// # Examples
+                /// ```
+                /// use svc_storage::resources::BatchMode;
+                #[doc = concat!("use svc_storage::resources::", stringify!($resource), "::{InsertManyRequest, GrpcServer, RpcService};")]
+                #[doc = concat!("use svc_storage::resources::", stringify!($resource), "::mock;")]
+                ///
+                /// async fn example() -> Result<(), tonic::Status> {
+                ///     let server = GrpcServer::default();
+                ///
+                ///     let result = match server.insert_many(tonic::Request::new(InsertManyRequest {
+                ///         list: vec![mock::get_data_obj(), mock::get_data_obj()],
+                ///         mode: BatchMode::AllOrNothing as i32,
+                ///     })).await
+                ///     {
+                ///         Ok(res) => res.into_inner(),
+                ///         Err(e) => {
+                ///             return Err(e);
+                ///         },
+                ///     };
+                ///     log::debug!("{:?}", result);
+                ///
+                ///     Ok(())
+                /// }
+                /// ```
+                #[cfg(not(feature = "stub_server"))]
+                async fn insert_many(
+                    &self,
+                    request: Request<InsertManyRequest>,
+                ) -> Result<tonic::Response<ResponseList>, Status> {
+                    grpc_info!("(insert_many) {} server.", self.get_name());
+                    grpc_debug!("(insert_many) request: {:?}", request);
+                    self.generic_insert_many(request).await
+                }
+                // MOCK implementation
+                #[cfg(feature = "stub_server")]
+                async fn insert_many(
+                    &self,
+                    request: tonic::Request<InsertManyRequest>,
+                ) -> Result<tonic::Response<ResponseList>, tonic::Status> {
+                    grpc_warn!("(insert_many MOCK) {} server.", self.get_name());
+                    grpc_debug!("(insert_many MOCK) request: {:?}", request);
+                    let mut mem_data = $crate::resources::$resource::MEM_DATA.lock().await;
+                    let mut list: Vec<Response> = vec![];
+                    for data in request.into_inner().list {
+                        let object = Object {
+                            id: uuid::Uuid::new_v4().to_string(),
+                            data: Some(data),
+                        };
+                        mem_data.push(object.clone());
+                        list.push(Response {
+                            object: Some(object),
+                            validation_result: Some(super::ValidationResult {
+                                success: true,
+                                errors: Vec::new()
+                            }),
+                            ..Default::default()
+                        });
+                    }
+                    Ok(tonic::Response::new(ResponseList { success: true, list }))
+                }
+
+                #[doc = concat!("Takes an [`UpdateManyRequest`] to update multiple ", stringify!($resource), " records using a single transaction.")]
+                ///
+                /// Returns a [`ResponseList`] containing a [`Response`] for each of the provided items.
+                ///
+                /// # Examples
+                /// ```
+                /// use svc_storage::resources::BatchMode;
+                #[doc = concat!("use svc_storage::resources::", stringify!($resource), "::{UpdateManyRequest, UpdateObject, GrpcServer, RpcService};")]
+                #[doc = concat!("use svc_storage::resources::", stringify!($resource), "::mock;")]
+                ///
+                /// async fn example() -> Result<(), tonic::Status> {
+                ///     let server = GrpcServer::default();
+                ///
+                ///     let result = match server.update_many(tonic::Request::new(UpdateManyRequest {
+                ///         list: vec![UpdateObject {
+                ///             id: "54acfe06-dd9b-42e8-8cb4-12a2fb2fa693".to_string(),
+                ///             data: Some(mock::get_data_obj()),
+                ///             mask: None
+                ///         }],
+                ///         mode: BatchMode::BestEffort as i32,
+                ///     })).await
+                ///     {
+                ///         Ok(res) => res.into_inner(),
+                ///         Err(e) => {
+                ///             return Err(e);
+                ///         },
+                ///     };
+                ///     log::debug!("{:?}", result);
+                ///
+                ///     Ok(())
+                /// }
+                /// ```
+                #[cfg(not(feature = "stub_server"))]
+                async fn update_many(
+                    &self,
+                    request: Request<UpdateManyRequest>,
+                ) -> Result<tonic::Response<ResponseList>, Status> {
+                    grpc_info!("(update_many) {} server.", self.get_name());
+                    grpc_debug!("(update_many) request: {:?}", request);
+                    self.generic_update_many(request).await
+                }
+                // MOCK implementation
+                #[cfg(feature = "stub_server")]
+                async fn update_many(
+                    &self,
+                    request: tonic::Request<UpdateManyRequest>,
+                ) -> Result<tonic::Response<ResponseList>, tonic::Status> {
+                    grpc_warn!("(update_many MOCK) {} server.", self.get_name());
+                    grpc_debug!("(update_many MOCK) request: {:?}", request);
+                    let request = request.into_inner();
+                    let mut mem_data = $crate::resources::$resource::MEM_DATA.lock().await;
+                    let mut updated = mem_data.clone();
+                    let mut list: Vec<Response> = vec![];
+                    let mut success = true;
+                    for update in request.list {
+                        match updated.iter_mut().find(|object| object.id == update.id) {
+                            Some(object) if update.data.is_some() => {
+                                object.data = update.data;
+                                list.push(Response {
+                                    object: Some(object.clone()),
+                                    validation_result: Some(super::ValidationResult {
+                                        success: true,
+                                        errors: Vec::new(),
+                                    }),
+                                    ..Default::default()
+                                });
+                            }
+                            _ => {
+                                success = false;
+                                list.push(Response {
+                                    object: None,
+                                    validation_result: Some(super::ValidationResult {
+                                        success: false,
+                                        errors: vec![super::ValidationError {
+                                            field: String::from("id"),
+                                            error: format!("No update data or [{}] found for specified uuid: {}", stringify!($resource), update.id),
+                                        }],
+                                    }),
+                                    ..Default::default()
+                                });
+                            }
+                        }
+                    }
+                    if success || request.mode == super::BatchMode::BestEffort as i32 {
+                        *mem_data = updated;
+                    } else {
+                        list.iter_mut().for_each(|response| response.object = None);
+                    }
+                    Ok(tonic::Response::new(ResponseList { success, list }))
+                }
+
+                #[doc = concat!("Takes a [`DeleteManyRequest`] to set the matching ", stringify!($resource), " records as deleted using a single transaction.")]
+                ///
+                /// # Examples
+                /// ```
+                /// use svc_storage::resources::{BatchMode, DeleteManyRequest};
+                #[doc = concat!("use svc_storage::resources::", stringify!($resource), "::{GrpcServer, RpcService};")]
+                ///
+                /// async fn example() -> Result<(), tonic::Status> {
+                ///     let server = GrpcServer::default();
+                ///
+                ///     let ids = vec!["53acfe06-dd9b-42e8-8cb4-12a2fb2fa693".to_string()];
+                ///     let result = match server.delete_many(tonic::Request::new(DeleteManyRequest {
+                ///         ids,
+                ///         mode: BatchMode::AllOrNothing as i32,
+                ///     })).await
+                ///     {
+                ///         Ok(res) => res.into_inner(),
+                ///         Err(e) => {
+                ///             return Err(e);
+                ///         },
+                ///     };
+                ///     log::debug!("{:?}", result);
+                ///
+                ///     Ok(())
+                /// }
+                /// ```
+                #[cfg(not(feature = "stub_server"))]
+                async fn delete_many(
+                    &self,
+                    request: Request<DeleteManyRequest>,
+                ) -> Result<tonic::Response<DeleteManyResponse>, Status> {
+                    grpc_info!("(delete_many) {} server.", self.get_name());
+                    grpc_debug!("(delete_many) request: {:?}", request);
+                    self.generic_delete_many(request).await
+                }
+                // MOCK implementation
+                #[cfg(feature = "stub_server")]
+                async fn delete_many(
+                    &self,
+                    request: tonic::Request<DeleteManyRequest>,
+                ) -> Result<tonic::Response<DeleteManyResponse>, tonic::Status> {
+                    grpc_warn!("(delete_many MOCK) {} server.", self.get_name());
+                    grpc_debug!("(delete_many MOCK) request: {:?}", request);
+                    let request = request.into_inner();
+                    let mut mem_data = $crate::resources::$resource::MEM_DATA.lock().await;
+                    let mut remaining = mem_data.clone();
+                    let mut results: Vec<super::ValidationResult> = vec![];
+                    let mut success = true;
+                    for id in request.ids {
+                        if remaining.iter().any(|object| object.id == id) {
+                            remaining.retain(|object| object.id != id);
+                            results.push(super::ValidationResult {
+                                success: true,
+                                errors: Vec::new(),
+                            });
+                        } else {
+                            success = false;
+                            results.push(super::ValidationResult {
+                                success: false,
+                                errors: vec![super::ValidationError {
+                                    field: String::from("id"),
+                                    error: format!("No [{}] found for specified uuid: {}", stringify!($resource), id),
+                                }],
+                            });
+                        }
+                    }
+                    if success || request.mode == super::BatchMode::BestEffort as i32 {
+                        *mem_data = remaining;
+                    }
+                    Ok(tonic::Response::new(DeleteManyResponse { success, results }))
+                }
+
                 /// Returns ready:true when service is available
                 #[cfg(not(feature = "stub_server"))]
                 async fn is_ready(
@@ -985,7 +1458,7 @@ macro_rules! grpc_server_simple_service_linked_mod {
                         grpc_error!("(get_linked MOCK) {}", error);
                         return Err(tonic::Status::not_found(error));
                     }
-                    Ok(tonic::Response::new($other_resource::List { list: other_resource_list }))
+                    Ok(tonic::Response::new($other_resource::List { total_count: other_resource_list.len() as i64, list: other_resource_list, next_page_token: String::new(), distances: vec![] }))
                 }
 
                 #[doc = concat!("Returns a [`tonic`] gRCP [`Response`] containing an ", stringify!($linked_resource), " [`Object`]")]
@@ -1125,6 +1598,8 @@ macro_rules! grpc_server_simple_service_linked_mod {
                 ///         page_number: 1,
                 ///         results_per_page: 10,
                 ///         order_by: vec![],
+                ///         include_deleted: false,
+                ///         page_token: String::new(),
                 ///     };
                 ///
                 ///     let result = match server.search(tonic::Request::new(advanced_filter)).await
@@ -1162,7 +1637,10 @@ macro_rules! grpc_server_simple_service_linked_mod {
                     if filters.len() == 0 {
                         grpc_debug!("(search MOCK) no filters provided, returning all.");
                         return Ok(tonic::Response::new(RowDataList {
-                            list
+                            total_count: list.len() as i64,
+                            list,
+                            next_page_token: String::new(),
+                            distances: vec![],
                         }));
                     }
 
@@ -1190,45 +1668,8 @@ macro_rules! grpc_server_simple_service_linked_mod {
                     }
                     grpc_debug!("(search MOCK) unfiltered serialized objects: {:?}", unfiltered);
 
-                    let mut collected: Vec<serde_json::Value> = vec![];
-                    for filter in filters {
-                        let operator: super::PredicateOperator =
-                            match super::PredicateOperator::try_from(filter.predicate_operator) {
-                                Ok(val) => val,
-                                Err(e) => {
-                                    return Err(tonic::Status::internal(format!(
-                                        "Can't convert i32 [{}] into PredicateOperator Enum value: {}",
-                                        filter.predicate_operator, e
-                                    )));
-                                }
-                            };
-
-
-                        match filter.comparison_operator {
-                            Some(comparison_operator) => match super::ComparisonOperator::try_from(comparison_operator) {
-                                Ok(comparison_operator) => match comparison_operator {
-                                    super::ComparisonOperator::And => {
-                                        let unfiltered = collected.clone();
-                                        collected = vec![];
-                                        $crate::grpc::server::search::filter_for_operator(&filter.search_field, &filter.search_value, &unfiltered, &mut collected, operator)
-                                            .map_err(|e| tonic::Status::internal(format!("Could not get filtered values for provided filter: {}", e)))?
-                                    }
-                                    super::ComparisonOperator::Or => {
-                                        $crate::grpc::server::search::filter_for_operator(&filter.search_field, &filter.search_value, &unfiltered, &mut collected, operator)
-                                            .map_err(|e| tonic::Status::internal(format!("Could not get filtered values for provided filter: {}", e)))?
-                                    }
-                                }
-                                Err(e) => {
-                                    return Err(tonic::Status::internal(format!(
-                                        "Can't convert i32 [{}] into ComparisonOperator Enum value: {}",
-                                        comparison_operator, e
-                                    )));
-                                }
-                            },
-                            None => $crate::grpc::server::search::filter_for_operator(&filter.search_field, &filter.search_value, &unfiltered, &mut collected, operator)
-                                .map_err(|e| tonic::Status::internal(format!("Could not get filtered values for provided filter: {}", e)))?
-                        };
-                    }
+                    let collected: Vec<serde_json::Value> = $crate::grpc::server::search::filter_for_options(&filters, &unfiltered)
+                        .map_err(|e| tonic::Status::internal(format!("Could not get filtered values for provided filter: {}", e)))?;
 
                     let mut filtered: Vec<RowData> = vec![];
                     for val in collected.iter() {
@@ -1247,7 +1688,10 @@ macro_rules! grpc_server_simple_service_linked_mod {
                         filtered.push(row_data);
                     }
                     let response = RowDataList {
-                        list: filtered
+                        total_count: filtered.len() as i64,
+                        list: filtered,
+                        next_page_token: String::new(),
+                        distances: vec![],
                     };
                     Ok(tonic::Response::new(response))
                 }
@@ -1519,6 +1963,126 @@ macro_rules! grpc_server_simple_service_linked_mod {
                     Ok(tonic::Response::new(()))
                 }
 
+                #[doc = concat!("Takes [`Ids`] to restore the matching soft-deleted ", stringify!($linked_resource), " record in the database.")]
+                ///
+                /// # Examples
+                /// ```
+                /// use svc_storage::resources::{FieldValue, Ids};
+                #[doc = concat!("use svc_storage::resources::", stringify!($linked_resource), "::{GrpcServer, RpcServiceLinked};")]
+                ///
+                /// async fn example() -> Result<(), tonic::Status> {
+                ///     let server = GrpcServer::default();
+                ///
+                ///     let id1 = "53acfe06-dd9b-42e8-8cb4-12a2fb2fa693";
+                ///     let id2 = "279f750f-712f-4c3b-8c92-331b79ffa7f9";
+                ///     let result = match server.restore(tonic::Request::new(Ids {
+                ///         ids: vec![
+                ///             FieldValue {
+                ///                 field: String::from("field_1"),
+                ///                 value: String::from(id1)
+                ///             },
+                ///             FieldValue {
+                ///                 field: String::from("field_2"),
+                ///                 value: String::from(id2)
+                ///             },
+                ///         ]
+                ///     })).await
+                ///     {
+                ///         Ok(res) => res.into_inner(),
+                ///         Err(e) => {
+                ///             return Err(e);
+                ///         },
+                ///     };
+                ///     log::debug!("{:?}", result);
+                ///
+                ///     Ok(())
+                /// }
+                /// ```
+                #[cfg(not(feature = "stub_server"))]
+                async fn restore(&self, request: Request<Ids>) -> Result<tonic::Response<()>, Status> {
+                    grpc_info!("(restore) {} server.", self.get_name());
+                    grpc_debug!("(restore) request: {:?}", request);
+                    self.generic_restore(request).await
+                }
+                #[cfg(feature = "stub_server")]
+                async fn restore(
+                    &self,
+                    request: tonic::Request<Ids>,
+                ) -> Result<tonic::Response<()>, tonic::Status> {
+                    grpc_warn!("(restore MOCK) {} server.", self.get_name());
+                    grpc_debug!("(restore MOCK) request: {:?}", request);
+                    // Deleted objects are removed from memory, so there is nothing to restore
+                    Ok(tonic::Response::new(()))
+                }
+
+                #[doc = concat!("Takes [`Ids`] to permanently remove the matching ", stringify!($linked_resource), " record in the database.")]
+                ///
+                /// # Examples
+                /// ```
+                /// use svc_storage::resources::{FieldValue, Ids};
+                #[doc = concat!("use svc_storage::resources::", stringify!($linked_resource), "::{GrpcServer, RpcServiceLinked};")]
+                ///
+                /// async fn example() -> Result<(), tonic::Status> {
+                ///     let server = GrpcServer::default();
+                ///
+                ///     let id1 = "53acfe06-dd9b-42e8-8cb4-12a2fb2fa693";
+                ///     let id2 = "279f750f-712f-4c3b-8c92-331b79ffa7f9";
+                ///     let result = match server.purge(tonic::Request::new(Ids {
+                ///         ids: vec![
+                ///             FieldValue {
+                ///                 field: String::from("field_1"),
+                ///                 value: String::from(id1)
+                ///             },
+                ///             FieldValue {
+                ///                 field: String::from("field_2"),
+                ///                 value: String::from(id2)
+                ///             },
+                ///         ]
+                ///     })).await
+                ///     {
+                ///         Ok(res) => res.into_inner(),
+                ///         Err(e) => {
+                ///             return Err(e);
+                ///         },
+                ///     };
+                ///     log::debug!("{:?}", result);
+                ///
+                ///     Ok(())
+                /// }
+                /// ```
+                #[cfg(not(feature = "stub_server"))]
+                async fn purge(&self, request: Request<Ids>) -> Result<tonic::Response<()>, Status> {
+                    grpc_info!("(purge) {} server.", self.get_name());
+                    grpc_debug!("(purge) request: {:?}", request);
+                    self.generic_purge(request).await
+                }
+                #[cfg(feature = "stub_server")]
+                async fn purge(
+                    &self,
+                    request: tonic::Request<Ids>,
+                ) -> Result<tonic::Response<()>, tonic::Status> {
+                    grpc_warn!("(purge MOCK) {} server.", self.get_name());
+                    grpc_debug!("(purge MOCK) request: {:?}", request);
+                    let ids = request.into_inner().ids;
+                    let id_field = concat!(stringify!($resource), "_id");
+                    let other_id_field = concat!(stringify!($other_resource), "_id");
+                    let mut resource_id = String::from("");
+                    let mut other_resource_id = String::from("");
+                    for id in &ids {
+                        if id.field.as_str() == id_field {
+                            resource_id = id.value.clone();
+                        }
+                        if id.field.as_str() == other_id_field {
+                            other_resource_id = id.value.clone();
+                        }
+                    }
+                    let mut linked_resource_list = MEM_DATA.lock().await;
+                    paste::paste!{
+                        linked_resource_list.retain(|object| object.[<$resource _id>] != resource_id || object.[<$other_resource _id>] != other_resource_id);
+                    }
+                    Ok(tonic::Response::new(()))
+                }
+
                 /// Returns ready:true when service is available
                 #[cfg(not(feature = "stub_server"))]
                 async fn is_ready(
@@ -3,7 +3,11 @@
 
 #[macro_use]
 pub mod macros;
+pub mod custody_service;
+pub mod health;
+pub mod scheduling_service;
 pub mod server;
+pub mod tracking_service;
 
 mod link_service;
 mod simple_service;
@@ -15,6 +19,7 @@ pub use simple_service::GrpcSimpleService;
 pub use simple_service_linked::GrpcSimpleServiceLinked;
 
 use anyhow::Error;
+use prost::Message;
 use prost_wkt_types::Timestamp;
 use std::time::SystemTime;
 use std::{fmt::Debug, vec};
@@ -53,11 +58,11 @@ pub enum GrpcField {
     /// Timestamp
     Timestamp(Timestamp),
     /// Geometric Point
-    GeoPoint(Point),
+    GeoPoint(GeoPoint),
     /// Geometric Polygon
     GeoPolygon(Polygon),
     /// Geometric Line
-    GeoLineString(LineString),
+    GeoLineString(GeoLineString),
     /// Option GrpcFieldOption
     Option(GrpcFieldOption),
 }
@@ -92,11 +97,11 @@ pub enum GrpcFieldOption {
     /// Option\<Timestamp\>
     Timestamp(Option<Timestamp>),
     /// Geo Point
-    GeoPoint(Option<Point>),
+    GeoPoint(Option<GeoPoint>),
     /// Geo Polygon
     GeoPolygon(Option<Polygon>),
     /// Geo Line
-    GeoLineString(Option<LineString>),
+    GeoLineString(Option<GeoLineString>),
     /// [None]
     None,
 }
@@ -107,8 +112,50 @@ pub trait GrpcDataObjectType: prost::Message + Clone {
     fn get_field_value(&self, key: &str) -> Result<GrpcField, ArrErr>;
 }
 
+/// Provides functions to set pagination details on gRPC `List` objects
+pub trait GrpcListType {
+    /// set the total number of records matching the search filters
+    fn set_total_count(&mut self, total_count: i64);
+    /// set the opaque cursor which can be used to get the next page
+    fn set_next_page_token(&mut self, next_page_token: String);
+    /// set the distance in meters of each list item to the geometry provided with a distance sort option
+    fn set_distances(&mut self, distances: Vec<f64>);
+}
+
+/// Provides functions to get the items and mode of gRPC batch request objects
+pub trait GrpcBatchRequestType<T> {
+    /// get the requested [`BatchMode`](server::BatchMode) as [`i32`]
+    fn get_mode(&self) -> i32;
+    /// consume the request, returning the list of items
+    fn into_list(self) -> Vec<T>;
+}
+
+/// Provides a function to create gRPC batch response objects
+pub trait GrpcBatchResponseType<T> {
+    /// create a new batch response using the per item responses and overall success status
+    fn from_list(list: Vec<T>, success: bool) -> Self;
+}
+
+/// Provides a function to create gRPC change event objects
+pub trait GrpcChangeEventType<T> {
+    /// create a new change event using the change details and the current object, if any
+    fn from_change(
+        sequence: i64,
+        change_type: server::ChangeType,
+        id: String,
+        object: Option<T>,
+    ) -> Self;
+}
+
 impl From<ArrErr> for Status {
     fn from(err: ArrErr) -> Self {
+        // Typed errors are safe to return to the client, their details
+        // will be encoded as the Status details so clients can decode them
+        if let Some(status) = get_typed_status(&err) {
+            grpc_info!("(from) {}", err);
+            return status;
+        }
+
         // These errors come from modules like Postgres, where you
         // probably wouldn't want to include error details in the
         // response, log them here instead which will include
@@ -123,6 +170,48 @@ impl From<ArrErr> for Status {
     }
 }
 
+/// Returns a [`Status`] with the [`Code`](tonic::Code) matching the typed [`ArrErr`] variant
+/// and its encoded [`ErrorDetails`](server::ErrorDetails) as details.
+///
+/// Returns [`None`] for untyped errors.
+fn get_typed_status(err: &ArrErr) -> Option<Status> {
+    let code = match err {
+        ArrErr::NotFound(_) => tonic::Code::NotFound,
+        ArrErr::AlreadyExists(_) => tonic::Code::AlreadyExists,
+        ArrErr::FailedPrecondition(_) => tonic::Code::FailedPrecondition,
+        ArrErr::InvalidArgument(_) => tonic::Code::InvalidArgument,
+        ArrErr::Aborted(_) => tonic::Code::Aborted,
+        ArrErr::Unavailable(_) => tonic::Code::Unavailable,
+        _ => return None,
+    };
+    let details = err.get_details()?;
+    Some(Status::with_details(
+        code,
+        details.message.clone(),
+        details.encode_to_vec().into(),
+    ))
+}
+
+/// Converts an [`ArrErr`] into a [`Status`].
+///
+/// Typed errors will be converted using the [`From<ArrErr>`] implementation for [`Status`],
+/// all other errors will return a [`Status`] with [`Code::Internal`](tonic::Code::Internal) and the error message.
+pub(crate) fn get_status_with_message(err: ArrErr) -> Status {
+    match get_typed_status(&err) {
+        Some(status) => status,
+        None => Status::internal(err.to_string()),
+    }
+}
+
+/// Converts the provided value of the given request field into a [`Uuid`](uuid::Uuid)
+///
+/// Returns an [`ArrErr`] InvalidArgument if the value is not a valid UUID.
+pub(crate) fn parse_uuid(field: &str, value: &str) -> Result<uuid::Uuid, ArrErr> {
+    uuid::Uuid::parse_str(value).map_err(|e| {
+        ArrErr::invalid_argument(format!("Could not convert [{}] to UUID: {}", field, e))
+    })
+}
+
 impl From<GrpcField> for Vec<u8> {
     fn from(field: GrpcField) -> Self {
         match field {
@@ -242,36 +331,39 @@ impl From<GrpcField> for Timestamp {
 impl From<Option<GeoPoint>> for GrpcFieldOption {
     fn from(field: Option<GeoPoint>) -> Self {
         match field {
-            Some(field) => GrpcFieldOption::GeoPoint(Some(field.into())),
+            Some(field) => GrpcFieldOption::GeoPoint(Some(field)),
             _ => GrpcFieldOption::GeoPoint(None),
         }
     }
 }
-impl From<GrpcField> for Point {
+impl From<GrpcField> for GeoPoint {
     fn from(field: GrpcField) -> Self {
         match field {
             GrpcField::GeoPoint(field) => field,
             _ => GeoPoint {
                 longitude: 0.0,
                 latitude: 0.0,
-            }
-            .into(),
+                altitude: None,
+            },
         }
     }
 }
 impl From<Option<GeoLineString>> for GrpcFieldOption {
     fn from(field: Option<GeoLineString>) -> Self {
         match field {
-            Some(field) => GrpcFieldOption::GeoLineString(Some(field.into())),
+            Some(field) => GrpcFieldOption::GeoLineString(Some(field)),
             _ => GrpcFieldOption::GeoLineString(None),
         }
     }
 }
-impl From<GrpcField> for LineString {
+impl From<GrpcField> for GeoLineString {
     fn from(field: GrpcField) -> Self {
         match field {
             GrpcField::GeoLineString(field) => field,
-            _ => LineString::new(vec![]),
+            _ => GeoLineString {
+                points: vec![],
+                times: vec![],
+            },
         }
     }
 }
@@ -371,6 +463,52 @@ mod tests {
         ut_info!("(test_from_arrerr_to_status) success");
     }
 
+    #[tokio::test]
+    async fn test_from_typed_arrerr_to_status() {
+        crate::get_log_handle().await;
+        ut_info!("(test_from_typed_arrerr_to_status) start");
+
+        let status = Status::from(ArrErr::not_found("No resource found."));
+        assert_eq!(status.code(), tonic::Code::NotFound);
+        assert_eq!(status.message(), "No resource found.");
+        let details = server::ErrorDetails::decode(status.details());
+        assert!(details.is_ok());
+        let details = details.unwrap();
+        assert_eq!(details.reason, server::ErrorReason::NotFound as i32);
+        assert_eq!(details.message, "No resource found.");
+
+        let status = Status::from(ArrErr::invalid_argument("Invalid filter."));
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+        let status = Status::from(ArrErr::invalid_state("Already deleted."));
+        assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+
+        let status = Status::from(ArrErr::unavailable("Database connection closed."));
+        assert_eq!(status.code(), tonic::Code::Unavailable);
+
+        // Untyped errors should keep their message when requested
+        let status = get_status_with_message(ArrErr::Error("test error message".to_string()));
+        assert_eq!(status.code(), tonic::Code::Internal);
+        assert_eq!(status.message(), "error: test error message");
+
+        ut_info!("(test_from_typed_arrerr_to_status) success");
+    }
+
+    #[tokio::test]
+    async fn test_parse_uuid() {
+        crate::get_log_handle().await;
+        ut_info!("(test_parse_uuid) start");
+
+        let id = uuid::Uuid::new_v4();
+        assert_eq!(parse_uuid("id", &id.to_string()).unwrap(), id);
+
+        let status = Status::from(parse_uuid("parcel_id", "INVALID").unwrap_err());
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+        assert!(status.message().contains("[parcel_id]"));
+
+        ut_info!("(test_parse_uuid) success");
+    }
+
     #[tokio::test]
     async fn test_from_grpc_field_to_bytes() {
         crate::get_log_handle().await;
@@ -751,17 +889,21 @@ mod tests {
         crate::get_log_handle().await;
         ut_info!("(test_from_grpc_field_to_point) start");
 
-        let point = Point::new(120.8, 45.12);
+        let point = GeoPoint {
+            longitude: 120.8,
+            latitude: 45.12,
+            altitude: Some(150.0),
+        };
 
         // GrpcField into GeoPoint
-        let field = GrpcField::GeoPoint(point.clone());
-        let result: Point = field.into();
-        assert_eq!(result, point.clone());
+        let field = GrpcField::GeoPoint(point);
+        let result: GeoPoint = field.into();
+        assert_eq!(result, point);
 
         // GrpcFieldOption into GeoPoint
-        let field_option = GrpcFieldOption::GeoPoint(Some(point.clone()));
+        let field_option = GrpcFieldOption::GeoPoint(Some(point));
         let result: Option<GrpcField> = field_option.into();
-        assert_eq!(result, Some(GrpcField::GeoPoint(point.clone())));
+        assert_eq!(result, Some(GrpcField::GeoPoint(point)));
 
         let field = GrpcFieldOption::GeoPoint(None);
         let result: Option<GrpcField> = field.into();
@@ -775,11 +917,18 @@ mod tests {
         crate::get_log_handle().await;
         ut_info!("(test_from_grpc_field_to_linestring) start");
 
-        let line_string = LineString::from(vec![(0.12, 1.23)]);
+        let line_string = GeoLineString {
+            points: vec![GeoPoint {
+                longitude: 0.12,
+                latitude: 1.23,
+                altitude: Some(50.0),
+            }],
+            times: vec![],
+        };
 
         // GrpcField into GeoLineString
         let field = GrpcField::GeoLineString(line_string.clone());
-        let result: LineString = field.into();
+        let result: GeoLineString = field.into();
         assert_eq!(result, line_string.clone());
 
         // GrpcFieldOption into GeoLineString
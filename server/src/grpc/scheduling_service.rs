@@ -0,0 +1,855 @@
+//! gRPC server implementation of the flight_plan scheduling and lifecycle service
+
+pub use super::server::flight_plan::rpc_scheduling_server::*;
+
+use super::parse_uuid;
+use super::server::flight_plan::{
+    AssetType, CommittedTimeRange, CommittedTimeRangesRequest, CommittedTimeRangesResponse,
+    ConflictingFlightPlansRequest, ConflictingFlightPlansResponse, FlightStatus,
+    Response as FlightPlanResponse, TransitionStatusRequest, VertipadAvailabilityRequest,
+    VertipadAvailabilityResponse,
+};
+use super::server::grpc_geo_types::GeoLineString;
+use super::server::{ReadyRequest, ReadyResponse};
+use crate::common::ArrErr;
+use crate::postgres::util::{validate_line_string, validate_line_string_times};
+use chrono::{DateTime, Utc};
+use tonic::{Request, Response, Status};
+use uuid::Uuid;
+
+/// Validated parameters of a [`VertipadAvailabilityRequest`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct AvailabilityParams {
+    vertipad_id: Uuid,
+    timeslot_start: DateTime<Utc>,
+    timeslot_end: DateTime<Utc>,
+    exclude_id: Option<Uuid>,
+}
+
+/// Validated parameters of a [`CommittedTimeRangesRequest`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct CommittedTimeRangesParams {
+    asset_type: AssetType,
+    asset_id: Uuid,
+    window_start: DateTime<Utc>,
+    window_end: DateTime<Utc>,
+}
+
+/// Validated parameters of a [`ConflictingFlightPlansRequest`]
+#[derive(Debug, Clone, PartialEq)]
+struct ConflictingFlightPlansParams {
+    path: GeoLineString,
+    window_start: DateTime<Utc>,
+    window_end: DateTime<Utc>,
+    min_separation_meters: f64,
+    exclude_id: Option<Uuid>,
+}
+
+/// Validated parameters of a [`TransitionStatusRequest`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct TransitionStatusParams {
+    id: Uuid,
+    flight_status: FlightStatus,
+    changed_by: Uuid,
+}
+
+/// Implementation of gRPC endpoints
+#[derive(Clone, Default, Debug, Copy)]
+pub struct GrpcServer {}
+impl GrpcServer {
+    /// Get name string for service
+    pub fn get_name(&self) -> String {
+        String::from("flight_plan_scheduling")
+    }
+}
+
+#[tonic::async_trait]
+impl RpcScheduling for GrpcServer {
+    /// Returns the ids of all flight plans claiming the provided vertipad during (part of) the proposed timeslot
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Status`] with [`tonic::Code::InvalidArgument`] if the provided ids can not be converted to a [`Uuid`].
+    /// Returns [`Status`] with [`tonic::Code::InvalidArgument`] if no valid timeslot is provided.
+    /// Returns [`Status`] with [`tonic::Code::Internal`] if any error is returned from a db call.
+    #[cfg(not(feature = "stub_server"))]
+    async fn check_vertipad_availability(
+        &self,
+        request: Request<VertipadAvailabilityRequest>,
+    ) -> Result<Response<VertipadAvailabilityResponse>, Status> {
+        grpc_info!("(check_vertipad_availability) {} server.", self.get_name());
+        grpc_debug!("(check_vertipad_availability) request: {:?}", request);
+        let params = get_availability_params(request.into_inner())?;
+        let ids = crate::resources::flight_plan::get_vertipad_conflicts(
+            &params.vertipad_id,
+            &params.timeslot_start,
+            &params.timeslot_end,
+            params.exclude_id.as_ref(),
+        )
+        .await
+        .map_err(super::get_status_with_message)?;
+
+        Ok(Response::new(VertipadAvailabilityResponse {
+            available: ids.is_empty(),
+            conflicting_flight_plan_ids: ids.iter().map(|id| id.to_string()).collect(),
+        }))
+    }
+    #[cfg(feature = "stub_server")]
+    async fn check_vertipad_availability(
+        &self,
+        request: Request<VertipadAvailabilityRequest>,
+    ) -> Result<Response<VertipadAvailabilityResponse>, Status> {
+        use super::server::flight_plan::{FlightStatus, MEM_DATA};
+
+        grpc_warn!(
+            "(check_vertipad_availability MOCK) {} server.",
+            self.get_name()
+        );
+        grpc_debug!("(check_vertipad_availability MOCK) request: {:?}", request);
+        let params = get_availability_params(request.into_inner())?;
+        let vertipad_id = params.vertipad_id.to_string();
+        let exclude_id = params.exclude_id.map(|id| id.to_string());
+
+        let overlaps = |id: &str,
+                        start: &Option<prost_wkt_types::Timestamp>,
+                        end: &Option<prost_wkt_types::Timestamp>| {
+            match (start, end) {
+                (Some(start), Some(end)) => {
+                    let start: DateTime<Utc> = start.clone().into();
+                    let end: DateTime<Utc> = end.clone().into();
+                    id == vertipad_id && start < params.timeslot_end && end > params.timeslot_start
+                }
+                _ => false,
+            }
+        };
+
+        let conflicting_flight_plan_ids: Vec<String> = MEM_DATA
+            .lock()
+            .await
+            .iter()
+            .filter(|object| Some(&object.id) != exclude_id.as_ref())
+            .filter(|object| match &object.data {
+                Some(data) => {
+                    data.flight_status != FlightStatus::Cancelled as i32
+                        && (overlaps(
+                            &data.origin_vertipad_id,
+                            &data.origin_timeslot_start,
+                            &data.origin_timeslot_end,
+                        ) || overlaps(
+                            &data.target_vertipad_id,
+                            &data.target_timeslot_start,
+                            &data.target_timeslot_end,
+                        ))
+                }
+                None => false,
+            })
+            .map(|object| object.id.clone())
+            .collect();
+
+        Ok(Response::new(VertipadAvailabilityResponse {
+            available: conflicting_flight_plan_ids.is_empty(),
+            conflicting_flight_plan_ids,
+        }))
+    }
+
+    /// Returns all time ranges the provided vehicle or pilot is committed to during (part of) the provided window
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Status`] with [`tonic::Code::InvalidArgument`] if the provided id can not be converted to a [`Uuid`].
+    /// Returns [`Status`] with [`tonic::Code::InvalidArgument`] if no valid window is provided.
+    /// Returns [`Status`] with [`tonic::Code::Internal`] if any error is returned from a db call.
+    #[cfg(not(feature = "stub_server"))]
+    async fn get_committed_time_ranges(
+        &self,
+        request: Request<CommittedTimeRangesRequest>,
+    ) -> Result<Response<CommittedTimeRangesResponse>, Status> {
+        grpc_info!("(get_committed_time_ranges) {} server.", self.get_name());
+        grpc_debug!("(get_committed_time_ranges) request: {:?}", request);
+        let params = get_committed_time_ranges_params(request.into_inner())?;
+        let claims = crate::resources::flight_plan::get_committed_time_ranges(
+            params.asset_type,
+            &params.asset_id,
+            &params.window_start,
+            &params.window_end,
+        )
+        .await
+        .map_err(super::get_status_with_message)?;
+
+        Ok(Response::new(CommittedTimeRangesResponse {
+            list: claims
+                .iter()
+                .map(|claim| CommittedTimeRange {
+                    flight_plan_id: claim.id.to_string(),
+                    start: Some(claim.start.into()),
+                    end: Some(claim.end.into()),
+                })
+                .collect(),
+        }))
+    }
+    #[cfg(feature = "stub_server")]
+    async fn get_committed_time_ranges(
+        &self,
+        request: Request<CommittedTimeRangesRequest>,
+    ) -> Result<Response<CommittedTimeRangesResponse>, Status> {
+        use super::server::flight_plan::{FlightStatus, MEM_DATA};
+
+        grpc_warn!(
+            "(get_committed_time_ranges MOCK) {} server.",
+            self.get_name()
+        );
+        grpc_debug!("(get_committed_time_ranges MOCK) request: {:?}", request);
+        let params = get_committed_time_ranges_params(request.into_inner())?;
+        let asset_id = params.asset_id.to_string();
+
+        let mut list: Vec<CommittedTimeRange> = MEM_DATA
+            .lock()
+            .await
+            .iter()
+            .filter_map(|object| {
+                let data = object.data.as_ref()?;
+                if data.flight_status == FlightStatus::Cancelled as i32
+                    || data.flight_status == FlightStatus::Finished as i32
+                {
+                    return None;
+                }
+                let id = match params.asset_type {
+                    AssetType::Vehicle => &data.vehicle_id,
+                    AssetType::Pilot => &data.pilot_id,
+                };
+                let start: DateTime<Utc> = data.origin_timeslot_start.clone()?.into();
+                let end: DateTime<Utc> = data.target_timeslot_end.clone()?.into();
+                if *id != asset_id || start >= params.window_end || end <= params.window_start {
+                    return None;
+                }
+                Some(CommittedTimeRange {
+                    flight_plan_id: object.id.clone(),
+                    start: Some(start.into()),
+                    end: Some(end.into()),
+                })
+            })
+            .collect();
+        list.sort_by_key(|range| {
+            range
+                .start
+                .clone()
+                .map(|start| (start.seconds, start.nanos))
+        });
+
+        Ok(Response::new(CommittedTimeRangesResponse { list }))
+    }
+
+    /// Returns all flight plans passing within the minimum separation of the provided path during (part of) the provided window
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Status`] with [`tonic::Code::InvalidArgument`] if no valid path is provided.
+    /// Returns [`Status`] with [`tonic::Code::InvalidArgument`] if no valid window is provided.
+    /// Returns [`Status`] with [`tonic::Code::InvalidArgument`] if the minimum separation is negative.
+    /// Returns [`Status`] with [`tonic::Code::InvalidArgument`] if the provided id can not be converted to a [`Uuid`].
+    /// Returns [`Status`] with [`tonic::Code::Internal`] if any error is returned from a db call.
+    #[cfg(not(feature = "stub_server"))]
+    async fn find_conflicting_flight_plans(
+        &self,
+        request: Request<ConflictingFlightPlansRequest>,
+    ) -> Result<Response<ConflictingFlightPlansResponse>, Status> {
+        grpc_info!(
+            "(find_conflicting_flight_plans) {} server.",
+            self.get_name()
+        );
+        grpc_debug!("(find_conflicting_flight_plans) request: {:?}", request);
+        let params = get_conflicting_flight_plans_params(request.into_inner())?;
+        let list = crate::resources::flight_plan::conflict::find_conflicting_flight_plans(
+            &params.path,
+            &params.window_start,
+            &params.window_end,
+            params.min_separation_meters,
+            params.exclude_id.as_ref(),
+        )
+        .await
+        .map_err(super::get_status_with_message)?;
+
+        Ok(Response::new(ConflictingFlightPlansResponse { list }))
+    }
+    #[cfg(feature = "stub_server")]
+    async fn find_conflicting_flight_plans(
+        &self,
+        request: Request<ConflictingFlightPlansRequest>,
+    ) -> Result<Response<ConflictingFlightPlansResponse>, Status> {
+        use super::server::flight_plan::{ConflictingFlightPlan, MEM_DATA};
+        use crate::resources::flight_plan::conflict::sort_conflicts;
+
+        grpc_warn!(
+            "(find_conflicting_flight_plans MOCK) {} server.",
+            self.get_name()
+        );
+        grpc_debug!(
+            "(find_conflicting_flight_plans MOCK) request: {:?}",
+            request
+        );
+        let params = get_conflicting_flight_plans_params(request.into_inner())?;
+        let exclude_id = params.exclude_id.map(|id| id.to_string());
+        let (start, end): (prost_wkt_types::Timestamp, prost_wkt_types::Timestamp) =
+            (params.window_start.into(), params.window_end.into());
+
+        let mut list: Vec<ConflictingFlightPlan> = MEM_DATA
+            .lock()
+            .await
+            .iter()
+            .filter(|object| Some(&object.id) != exclude_id.as_ref())
+            .filter_map(|object| {
+                let data = object.data.as_ref()?;
+                if data.flight_status == FlightStatus::Cancelled as i32 {
+                    return None;
+                }
+                let active_start: DateTime<Utc> = data.origin_timeslot_start.clone()?.into();
+                let active_end: DateTime<Utc> = data.target_timeslot_end.clone()?.into();
+                if active_start >= params.window_end || active_end <= params.window_start {
+                    return None;
+                }
+                let (own, other) =
+                    params
+                        .path
+                        .get_concurrent_sections(data.path.as_ref()?, &start, &end)?;
+                let distance_meters = own.get_min_point_distance(&other)?;
+                if distance_meters > params.min_separation_meters {
+                    return None;
+                }
+                Some(ConflictingFlightPlan {
+                    flight_plan: Some(object.clone()),
+                    distance_meters,
+                })
+            })
+            .collect();
+        sort_conflicts(&mut list);
+
+        Ok(Response::new(ConflictingFlightPlansResponse { list }))
+    }
+
+    /// Changes the [`FlightStatus`] of a flight plan, recording who made the change
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Status`] with [`tonic::Code::InvalidArgument`] if the provided ids can not be converted to a [`Uuid`].
+    /// Returns [`Status`] with [`tonic::Code::InvalidArgument`] if an unknown [`FlightStatus`] is provided.
+    /// Returns [`Status`] with [`tonic::Code::NotFound`] if no flight plan exists for the provided id.
+    /// Returns [`Status`] with [`tonic::Code::Internal`] if any error is returned from a db call.
+    #[cfg(not(feature = "stub_server"))]
+    async fn transition_status(
+        &self,
+        request: Request<TransitionStatusRequest>,
+    ) -> Result<Response<FlightPlanResponse>, Status> {
+        use super::server::flight_plan::{Data, Object};
+        use crate::postgres::simple_resource::PsqlType;
+        use crate::resources::base::ResourceObject;
+
+        grpc_info!("(transition_status) {} server.", self.get_name());
+        grpc_debug!("(transition_status) request: {:?}", request);
+        let params = get_transition_status_params(request.into_inner())?;
+        let validation_result = crate::resources::flight_plan::transition_flight_status(
+            &params.id,
+            params.flight_status,
+            &params.changed_by,
+        )
+        .await
+        .map_err(super::get_status_with_message)?;
+
+        let object = if validation_result.success {
+            let row = ResourceObject::<Data>::get_by_id(&params.id).await?;
+            Some(Object {
+                id: params.id.to_string(),
+                data: Some(Data::try_from(row)?),
+            })
+        } else {
+            None
+        };
+        Ok(Response::new(FlightPlanResponse {
+            validation_result: Some(validation_result),
+            object,
+        }))
+    }
+    #[cfg(feature = "stub_server")]
+    async fn transition_status(
+        &self,
+        request: Request<TransitionStatusRequest>,
+    ) -> Result<Response<FlightPlanResponse>, Status> {
+        use super::server::flight_plan::MEM_DATA;
+        use super::server::{ValidationError, ValidationResult};
+        use std::time::SystemTime;
+
+        grpc_warn!("(transition_status MOCK) {} server.", self.get_name());
+        grpc_debug!("(transition_status MOCK) request: {:?}", request);
+        let params = get_transition_status_params(request.into_inner())?;
+        let id = params.id.to_string();
+
+        let mut mem_data = MEM_DATA.lock().await;
+        let object = match mem_data.iter_mut().find(|object| object.id == id) {
+            Some(object) => object,
+            None => {
+                return Err(ArrErr::not_found(format!(
+                    "No [flight_plan] record found for id [{}].",
+                    id
+                ))
+                .into())
+            }
+        };
+        let data = match object.data.as_mut() {
+            Some(data) => data,
+            None => return Err(Status::internal("No data found for flight_plan.")),
+        };
+
+        let current = FlightStatus::try_from(data.flight_status).unwrap_or(FlightStatus::Draft);
+        if !current.can_transition_to(params.flight_status) {
+            return Ok(Response::new(FlightPlanResponse {
+                validation_result: Some(ValidationResult {
+                    success: false,
+                    errors: vec![ValidationError {
+                        field: String::from("flight_status"),
+                        error: format!(
+                            "Invalid [flight_status] transition from [{}] to [{}]",
+                            current.as_str_name(),
+                            params.flight_status.as_str_name()
+                        ),
+                    }],
+                }),
+                object: None,
+            }));
+        }
+
+        if current != params.flight_status {
+            match params.flight_status {
+                FlightStatus::InFlight if data.actual_departure_time.is_none() => {
+                    data.actual_departure_time = Some(SystemTime::now().into())
+                }
+                FlightStatus::Finished if data.actual_arrival_time.is_none() => {
+                    data.actual_arrival_time = Some(SystemTime::now().into())
+                }
+                _ => {}
+            }
+            data.flight_status = params.flight_status as i32;
+        }
+
+        Ok(Response::new(FlightPlanResponse {
+            validation_result: Some(ValidationResult {
+                success: true,
+                errors: vec![],
+            }),
+            object: Some(object.clone()),
+        }))
+    }
+
+    /// Returns ready:true when service is available
+    #[cfg(not(feature = "stub_server"))]
+    async fn is_ready(
+        &self,
+        request: Request<ReadyRequest>,
+    ) -> Result<Response<ReadyResponse>, Status> {
+        grpc_info!("(is_ready) {} server.", self.get_name());
+        grpc_debug!("(is_ready) request: {:?}", request);
+        let response = ReadyResponse {
+            ready: super::health::is_ready(),
+        };
+        Ok(Response::new(response))
+    }
+    #[cfg(feature = "stub_server")]
+    async fn is_ready(
+        &self,
+        request: Request<ReadyRequest>,
+    ) -> Result<Response<ReadyResponse>, Status> {
+        grpc_info!("(is_ready MOCK) {} server.", self.get_name());
+        grpc_debug!("(is_ready MOCK) request: {:?}", request);
+        let response = ReadyResponse { ready: true };
+        Ok(Response::new(response))
+    }
+}
+
+/// Validates the provided [`VertipadAvailabilityRequest`], returning the converted parameters
+///
+/// # Errors
+///
+/// Returns [`Status`] with [`tonic::Code::InvalidArgument`] if the provided ids can not be converted to a [`Uuid`].
+/// Returns [`Status`] with [`tonic::Code::InvalidArgument`] if the timeslot is missing or does not end after its start.
+fn get_availability_params(
+    request: VertipadAvailabilityRequest,
+) -> Result<AvailabilityParams, Status> {
+    let vertipad_id = parse_uuid("vertipad_id", &request.vertipad_id)?;
+    let exclude_id = match &request.exclude_flight_plan_id {
+        Some(id) => Some(parse_uuid("exclude_flight_plan_id", id)?),
+        None => None,
+    };
+    let (timeslot_start, timeslot_end) = parse_time_window(
+        ("timeslot_start", request.timeslot_start),
+        ("timeslot_end", request.timeslot_end),
+    )?;
+
+    Ok(AvailabilityParams {
+        vertipad_id,
+        timeslot_start,
+        timeslot_end,
+        exclude_id,
+    })
+}
+
+/// Validates the provided [`CommittedTimeRangesRequest`], returning the converted parameters
+///
+/// # Errors
+///
+/// Returns [`Status`] with [`tonic::Code::InvalidArgument`] if an unknown [`AssetType`] is provided.
+/// Returns [`Status`] with [`tonic::Code::InvalidArgument`] if the provided id can not be converted to a [`Uuid`].
+/// Returns [`Status`] with [`tonic::Code::InvalidArgument`] if the window is missing or does not end after its start.
+fn get_committed_time_ranges_params(
+    request: CommittedTimeRangesRequest,
+) -> Result<CommittedTimeRangesParams, Status> {
+    let asset_type = AssetType::try_from(request.asset_type).map_err(|_| {
+        ArrErr::invalid_argument(format!(
+            "Unknown [asset_type] provided: {}",
+            request.asset_type
+        ))
+    })?;
+    let asset_id = parse_uuid("asset_id", &request.asset_id)?;
+    let (window_start, window_end) = parse_time_window(
+        ("window_start", request.window_start),
+        ("window_end", request.window_end),
+    )?;
+
+    Ok(CommittedTimeRangesParams {
+        asset_type,
+        asset_id,
+        window_start,
+        window_end,
+    })
+}
+
+/// Validates the provided [`ConflictingFlightPlansRequest`], returning the converted parameters
+///
+/// # Errors
+///
+/// Returns [`Status`] with [`tonic::Code::InvalidArgument`] if the path is missing, has no points or has invalid times.
+/// Returns [`Status`] with [`tonic::Code::InvalidArgument`] if the window is missing or does not end after its start.
+/// Returns [`Status`] with [`tonic::Code::InvalidArgument`] if the minimum separation is negative.
+/// Returns [`Status`] with [`tonic::Code::InvalidArgument`] if the provided id can not be converted to a [`Uuid`].
+fn get_conflicting_flight_plans_params(
+    request: ConflictingFlightPlansRequest,
+) -> Result<ConflictingFlightPlansParams, Status> {
+    let path = match request.path {
+        Some(path) if !path.points.is_empty() => path,
+        _ => {
+            return Err(ArrErr::invalid_argument(
+                "A [path] with at least 1 point should be provided.",
+            )
+            .into())
+        }
+    };
+    let mut errors = vec![];
+    validate_line_string(String::from("path"), &path.clone().into(), &mut errors);
+    validate_line_string_times(String::from("path"), &path, &mut errors);
+    if !errors.is_empty() {
+        let errors: Vec<String> = errors.into_iter().map(|error| error.error).collect();
+        return Err(ArrErr::invalid_argument(errors.join(" ")).into());
+    }
+    let (window_start, window_end) = parse_time_window(
+        ("window_start", request.window_start),
+        ("window_end", request.window_end),
+    )?;
+    if request.min_separation_meters.is_nan() || request.min_separation_meters < 0.0 {
+        return Err(ArrErr::invalid_argument(format!(
+            "[min_separation_meters] should not be negative, got [{}].",
+            request.min_separation_meters
+        ))
+        .into());
+    }
+    let exclude_id = match &request.exclude_flight_plan_id {
+        Some(id) => Some(parse_uuid("exclude_flight_plan_id", id)?),
+        None => None,
+    };
+
+    Ok(ConflictingFlightPlansParams {
+        path,
+        window_start,
+        window_end,
+        min_separation_meters: request.min_separation_meters,
+        exclude_id,
+    })
+}
+
+/// Validates the provided [`TransitionStatusRequest`], returning the converted parameters
+///
+/// # Errors
+///
+/// Returns [`Status`] with [`tonic::Code::InvalidArgument`] if the provided ids can not be converted to a [`Uuid`].
+/// Returns [`Status`] with [`tonic::Code::InvalidArgument`] if an unknown [`FlightStatus`] is provided.
+fn get_transition_status_params(
+    request: TransitionStatusRequest,
+) -> Result<TransitionStatusParams, Status> {
+    let id = parse_uuid("id", &request.id)?;
+    let changed_by = parse_uuid("changed_by", &request.changed_by)?;
+    let flight_status = FlightStatus::try_from(request.flight_status).map_err(|_| {
+        ArrErr::invalid_argument(format!(
+            "Unknown [flight_status] provided: {}",
+            request.flight_status
+        ))
+    })?;
+
+    Ok(TransitionStatusParams {
+        id,
+        flight_status,
+        changed_by,
+    })
+}
+
+/// Converts the provided `(field, value)` start and end pairs into a time window,
+/// making sure both are provided and the window ends after its start
+fn parse_time_window(
+    start: (&str, Option<prost_wkt_types::Timestamp>),
+    end: (&str, Option<prost_wkt_types::Timestamp>),
+) -> Result<(DateTime<Utc>, DateTime<Utc>), ArrErr> {
+    let (start_field, end_field) = (start.0, end.0);
+    let (start, end): (DateTime<Utc>, DateTime<Utc>) = match (start.1, end.1) {
+        (Some(start), Some(end)) => (start.into(), end.into()),
+        _ => {
+            return Err(ArrErr::invalid_argument(format!(
+                "Both [{}] and [{}] should be provided.",
+                start_field, end_field
+            )))
+        }
+    };
+    if end <= start {
+        return Err(ArrErr::invalid_argument(format!(
+            "[{}] ({}) should be after [{}] ({}).",
+            end_field, end, start_field, start
+        )));
+    }
+    Ok((start, end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grpc::server::grpc_geo_types::GeoPoint;
+    use std::time::{Duration, SystemTime};
+
+    fn get_request() -> VertipadAvailabilityRequest {
+        let now = SystemTime::now();
+        VertipadAvailabilityRequest {
+            vertipad_id: Uuid::new_v4().to_string(),
+            timeslot_start: Some(now.into()),
+            timeslot_end: Some((now + Duration::from_secs(300)).into()),
+            exclude_flight_plan_id: Some(Uuid::new_v4().to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_availability_params() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_availability_params) start");
+
+        let request = get_request();
+        let params = get_availability_params(request.clone()).unwrap();
+        assert_eq!(params.vertipad_id.to_string(), request.vertipad_id);
+        assert_eq!(
+            params.exclude_id.map(|id| id.to_string()),
+            request.exclude_flight_plan_id
+        );
+        assert!(params.timeslot_start < params.timeslot_end);
+
+        ut_info!("(test_get_availability_params) success");
+    }
+
+    #[tokio::test]
+    async fn test_get_availability_params_invalid() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_availability_params_invalid) start");
+
+        let invalid = vec![
+            VertipadAvailabilityRequest {
+                vertipad_id: String::from("INVALID"),
+                ..get_request()
+            },
+            VertipadAvailabilityRequest {
+                exclude_flight_plan_id: Some(String::from("INVALID")),
+                ..get_request()
+            },
+            VertipadAvailabilityRequest {
+                timeslot_end: None,
+                ..get_request()
+            },
+            VertipadAvailabilityRequest {
+                timeslot_end: get_request().timeslot_start,
+                ..get_request()
+            },
+        ];
+        for request in invalid {
+            let result = get_availability_params(request);
+            assert!(result.is_err());
+            assert_eq!(result.unwrap_err().code(), tonic::Code::InvalidArgument);
+        }
+
+        ut_info!("(test_get_availability_params_invalid) success");
+    }
+
+    fn get_committed_time_ranges_request() -> CommittedTimeRangesRequest {
+        let now = SystemTime::now();
+        CommittedTimeRangesRequest {
+            asset_type: AssetType::Pilot as i32,
+            asset_id: Uuid::new_v4().to_string(),
+            window_start: Some(now.into()),
+            window_end: Some((now + Duration::from_secs(86400)).into()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_committed_time_ranges_params() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_committed_time_ranges_params) start");
+
+        let request = get_committed_time_ranges_request();
+        let params = get_committed_time_ranges_params(request.clone()).unwrap();
+        assert_eq!(params.asset_type, AssetType::Pilot);
+        assert_eq!(params.asset_id.to_string(), request.asset_id);
+        assert!(params.window_start < params.window_end);
+
+        let invalid = vec![
+            CommittedTimeRangesRequest {
+                asset_type: -1,
+                ..get_committed_time_ranges_request()
+            },
+            CommittedTimeRangesRequest {
+                asset_id: String::from("INVALID"),
+                ..get_committed_time_ranges_request()
+            },
+            CommittedTimeRangesRequest {
+                window_start: None,
+                ..get_committed_time_ranges_request()
+            },
+            CommittedTimeRangesRequest {
+                window_start: get_committed_time_ranges_request().window_end,
+                ..get_committed_time_ranges_request()
+            },
+        ];
+        for request in invalid {
+            let result = get_committed_time_ranges_params(request);
+            assert!(result.is_err());
+            assert_eq!(result.unwrap_err().code(), tonic::Code::InvalidArgument);
+        }
+
+        ut_info!("(test_get_committed_time_ranges_params) success");
+    }
+
+    #[tokio::test]
+    async fn test_get_transition_status_params() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_transition_status_params) start");
+
+        let request = TransitionStatusRequest {
+            id: Uuid::new_v4().to_string(),
+            flight_status: FlightStatus::Boarding as i32,
+            changed_by: Uuid::new_v4().to_string(),
+        };
+        let params = get_transition_status_params(request.clone()).unwrap();
+        assert_eq!(params.id.to_string(), request.id);
+        assert_eq!(params.flight_status, FlightStatus::Boarding);
+        assert_eq!(params.changed_by.to_string(), request.changed_by);
+
+        let invalid = vec![
+            TransitionStatusRequest {
+                id: String::from("INVALID"),
+                ..request.clone()
+            },
+            TransitionStatusRequest {
+                changed_by: String::from("INVALID"),
+                ..request.clone()
+            },
+            TransitionStatusRequest {
+                flight_status: -1,
+                ..request.clone()
+            },
+        ];
+        for request in invalid {
+            let result = get_transition_status_params(request);
+            assert!(result.is_err());
+            assert_eq!(result.unwrap_err().code(), tonic::Code::InvalidArgument);
+        }
+
+        ut_info!("(test_get_transition_status_params) success");
+    }
+
+    fn get_conflicting_flight_plans_request() -> ConflictingFlightPlansRequest {
+        let now = SystemTime::now();
+        let point = |latitude: f64| GeoPoint {
+            longitude: 4.9164,
+            latitude,
+            altitude: Some(120.0),
+        };
+        ConflictingFlightPlansRequest {
+            path: Some(GeoLineString {
+                points: vec![point(52.37466), point(52.38466)],
+                times: vec![now.into(), (now + Duration::from_secs(300)).into()],
+            }),
+            window_start: Some(now.into()),
+            window_end: Some((now + Duration::from_secs(300)).into()),
+            min_separation_meters: 50.0,
+            exclude_flight_plan_id: Some(Uuid::new_v4().to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_conflicting_flight_plans_params() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_conflicting_flight_plans_params) start");
+
+        let request = get_conflicting_flight_plans_request();
+        let params = get_conflicting_flight_plans_params(request.clone()).unwrap();
+        assert_eq!(Some(params.path), request.path);
+        assert_eq!(params.min_separation_meters, 50.0);
+        assert_eq!(
+            params.exclude_id.map(|id| id.to_string()),
+            request.exclude_flight_plan_id
+        );
+        assert!(params.window_start < params.window_end);
+
+        let path = request.path.clone().unwrap();
+        let invalid = vec![
+            ConflictingFlightPlansRequest {
+                path: None,
+                ..request.clone()
+            },
+            ConflictingFlightPlansRequest {
+                path: Some(GeoLineString {
+                    points: vec![],
+                    times: vec![],
+                }),
+                ..request.clone()
+            },
+            ConflictingFlightPlansRequest {
+                path: Some(GeoLineString {
+                    times: vec![path.times[1].clone(), path.times[0].clone()],
+                    ..path.clone()
+                }),
+                ..request.clone()
+            },
+            ConflictingFlightPlansRequest {
+                window_end: None,
+                ..request.clone()
+            },
+            ConflictingFlightPlansRequest {
+                min_separation_meters: -1.0,
+                ..request.clone()
+            },
+            ConflictingFlightPlansRequest {
+                min_separation_meters: f64::NAN,
+                ..request.clone()
+            },
+            ConflictingFlightPlansRequest {
+                exclude_flight_plan_id: Some(String::from("INVALID")),
+                ..request.clone()
+            },
+        ];
+        for request in invalid {
+            let result = get_conflicting_flight_plans_params(request);
+            assert!(result.is_err());
+            assert_eq!(result.unwrap_err().code(), tonic::Code::InvalidArgument);
+        }
+
+        ut_info!("(test_get_conflicting_flight_plans_params) success");
+    }
+}
@@ -1,4 +1,9 @@
 //! gRPC server implementation
+use super::custody_service::{GrpcServer as ParcelCustodyGrpcServer, RpcCustodyServer};
+use super::scheduling_service::{
+    GrpcServer as FlightPlanSchedulingGrpcServer, RpcSchedulingServer,
+};
+use super::tracking_service::{GrpcServer as AdsbTrackingGrpcServer, RpcTrackingServer};
 use super::GrpcSimpleService;
 use super::GrpcSimpleServiceLinked;
 use crate::config::Config;
@@ -6,6 +11,9 @@ use crate::resources::base::ResourceObject;
 use crate::shutdown_signal;
 use serde::{Deserialize, Serialize};
 use std::net::SocketAddr;
+use std::time::Duration;
+use tonic::codegen::BoxStream;
+use tonic::server::NamedService;
 use tonic::transport::Server;
 use tonic::{Request, Status};
 
@@ -23,6 +31,7 @@ grpc_server_simple_service_mod!(parcel_scan);
 grpc_server_simple_service_mod!(scanner);
 grpc_server_simple_service_mod!(user);
 grpc_server_simple_service_mod!(vehicle);
+grpc_server_simple_service_mod!(vehicle_model);
 grpc_server_simple_service_mod!(vertipad);
 grpc_server_simple_service_mod!(vertiport);
 
@@ -54,6 +63,9 @@ grpc_server_group_service_mod!(vertipad);
 
 /// Provide search helpers
 pub mod search {
+    #[cfg(any(feature = "stub_client", feature = "stub_server"))]
+    use super::grpc_geo_types::{GeoLineString, GeoPoint, GeoPolygon};
+
     include!("../../../includes/search.rs");
 }
 
@@ -75,6 +87,10 @@ pub mod grpc_geo_types {
         /// range: -90 - 90
         #[prost(double, tag = "2")]
         pub latitude: f64,
+        /// altitude (z) in meters above the WGS 84 ellipsoid
+        /// points without an altitude are stored as 2D points
+        #[prost(double, optional, tag = "3")]
+        pub altitude: ::core::option::Option<f64>,
     }
     /// Geo Location Line representation
     #[allow(clippy::derive_partial_eq_without_eq)]
@@ -94,6 +110,10 @@ pub mod grpc_geo_types {
         /// list of points
         #[prost(message, repeated, tag = "1")]
         pub points: ::prost::alloc::vec::Vec<GeoPoint>,
+        /// optional list of times at which the points are passed, making the points time-tagged waypoints
+        /// should either be empty or contain one (non decreasing) time for each point
+        #[prost(message, repeated, tag = "2")]
+        pub times: ::prost::alloc::vec::Vec<::prost_wkt_types::Timestamp>,
     }
     /// Geo Location Polygon representation
     #[allow(clippy::derive_partial_eq_without_eq)]
@@ -138,73 +158,18 @@ pub async fn grpc_server(config: Config, shutdown_rx: Option<tokio::sync::onesho
         }
     };
 
-    let (mut health_reporter, health_service) = tonic_health::server::health_reporter();
-    health_reporter
-        .set_serving::<adsb::RpcServiceServer<adsb::GrpcServer>>()
-        .await;
-    health_reporter
-        .set_serving::<flight_plan::RpcServiceServer<flight_plan::GrpcServer>>()
-        .await;
-    health_reporter
-        .set_serving::<flight_plan_parcel::RpcServiceLinkedServer<flight_plan_parcel::GrpcServer>>()
-        .await;
-    health_reporter
-        .set_serving::<group::RpcServiceServer<group::GrpcServer>>()
-        .await;
-    health_reporter
-        .set_serving::<group_user::RpcUserLinkServer<group_user::GrpcServer>>()
-        .await;
-    health_reporter
-        .set_serving::<group_vehicle::RpcVehicleLinkServer<group_vehicle::GrpcServer>>()
-        .await;
-    health_reporter
-        .set_serving::<group_vertipad::RpcVertipadLinkServer<group_vertipad::GrpcServer>>()
-        .await;
-    health_reporter
-        .set_serving::<group_vertiport::RpcVertiportLinkServer<group_vertiport::GrpcServer>>()
-        .await;
-    health_reporter
-        .set_serving::<itinerary::RpcServiceServer<itinerary::GrpcServer>>()
-        .await;
-    health_reporter
-        .set_serving::<itinerary_flight_plan::RpcFlightPlanLinkServer<itinerary_flight_plan::GrpcServer>>()
-        .await;
-    health_reporter
-        .set_serving::<parcel::RpcServiceServer<parcel::GrpcServer>>()
-        .await;
-    health_reporter
-        .set_serving::<parcel_scan::RpcServiceServer<parcel_scan::GrpcServer>>()
-        .await;
-    health_reporter
-        .set_serving::<pilot::RpcServiceServer<pilot::GrpcServer>>()
-        .await;
-    health_reporter
-        .set_serving::<scanner::RpcServiceServer<scanner::GrpcServer>>()
-        .await;
-    health_reporter
-        .set_serving::<user::RpcServiceServer<user::GrpcServer>>()
-        .await;
-    health_reporter
-        .set_serving::<user_group::RpcGroupLinkServer<user_group::GrpcServer>>()
-        .await;
-    health_reporter
-        .set_serving::<vehicle::RpcServiceServer<vehicle::GrpcServer>>()
-        .await;
-    health_reporter
-        .set_serving::<vehicle_group::RpcGroupLinkServer<vehicle_group::GrpcServer>>()
-        .await;
-    health_reporter
-        .set_serving::<vertipad::RpcServiceServer<vertipad::GrpcServer>>()
-        .await;
-    health_reporter
-        .set_serving::<vertipad_group::RpcGroupLinkServer<vertipad_group::GrpcServer>>()
-        .await;
-    health_reporter
-        .set_serving::<vertiport::RpcServiceServer<vertiport::GrpcServer>>()
-        .await;
-    health_reporter
-        .set_serving::<vertiport_group::RpcGroupLinkServer<vertiport_group::GrpcServer>>()
-        .await;
+    let (health_reporter, health_service) = tonic_health::server::health_reporter();
+    let health_monitor = tokio::spawn(super::health::health_monitor(
+        health_reporter,
+        get_service_names(),
+        Duration::from_secs(config.health_check_interval_secs),
+    ));
+    #[cfg(not(feature = "stub_backends"))]
+    let retention = tokio::spawn(crate::postgres::retention::retention(
+        Duration::from_secs(config.retention_interval_secs),
+        config.adsb_retention_days,
+        config.change_log_retention_days,
+    ));
 
     //start server
     grpc_info!(
@@ -214,9 +179,13 @@ pub async fn grpc_server(config: Config, shutdown_rx: Option<tokio::sync::onesho
     match Server::builder()
         .add_service(health_service)
         .add_service(adsb::RpcServiceServer::new(adsb::GrpcServer::default()))
+        .add_service(RpcTrackingServer::new(AdsbTrackingGrpcServer::default()))
         .add_service(flight_plan::RpcServiceServer::new(
             flight_plan::GrpcServer::default(),
         ))
+        .add_service(RpcSchedulingServer::new(
+            FlightPlanSchedulingGrpcServer::default(),
+        ))
         .add_service(flight_plan_parcel::RpcServiceLinkedServer::new(
             flight_plan_parcel::GrpcServer::default(),
         ))
@@ -240,6 +209,7 @@ pub async fn grpc_server(config: Config, shutdown_rx: Option<tokio::sync::onesho
             itinerary_flight_plan::GrpcServer::default(),
         ))
         .add_service(parcel::RpcServiceServer::new(parcel::GrpcServer::default()))
+        .add_service(RpcCustodyServer::new(ParcelCustodyGrpcServer::default()))
         .add_service(parcel_scan::RpcServiceServer::new(
             parcel_scan::GrpcServer::default(),
         ))
@@ -257,6 +227,9 @@ pub async fn grpc_server(config: Config, shutdown_rx: Option<tokio::sync::onesho
         .add_service(vehicle_group::RpcGroupLinkServer::new(
             vehicle_group::GrpcServer::default(),
         ))
+        .add_service(vehicle_model::RpcServiceServer::new(
+            vehicle_model::GrpcServer::default(),
+        ))
         .add_service(vertipad::RpcServiceServer::new(
             vertipad::GrpcServer::default(),
         ))
@@ -277,6 +250,44 @@ pub async fn grpc_server(config: Config, shutdown_rx: Option<tokio::sync::onesho
             grpc_error!("(grpc_server) Could not start gRPC server: {}", e);
         }
     };
+    health_monitor.abort();
+    #[cfg(not(feature = "stub_backends"))]
+    retention.abort();
+}
+
+/// Returns the names of all gRPC services provided by this microservice
+///
+/// Includes the empty service name (`""`), which is used to report the overall server health.
+pub fn get_service_names() -> Vec<&'static str> {
+    vec![
+        "",
+        adsb::RpcServiceServer::<adsb::GrpcServer>::NAME,
+        RpcTrackingServer::<AdsbTrackingGrpcServer>::NAME,
+        flight_plan::RpcServiceServer::<flight_plan::GrpcServer>::NAME,
+        RpcSchedulingServer::<FlightPlanSchedulingGrpcServer>::NAME,
+        flight_plan_parcel::RpcServiceLinkedServer::<flight_plan_parcel::GrpcServer>::NAME,
+        group::RpcServiceServer::<group::GrpcServer>::NAME,
+        group_user::RpcUserLinkServer::<group_user::GrpcServer>::NAME,
+        group_vehicle::RpcVehicleLinkServer::<group_vehicle::GrpcServer>::NAME,
+        group_vertipad::RpcVertipadLinkServer::<group_vertipad::GrpcServer>::NAME,
+        group_vertiport::RpcVertiportLinkServer::<group_vertiport::GrpcServer>::NAME,
+        itinerary::RpcServiceServer::<itinerary::GrpcServer>::NAME,
+        itinerary_flight_plan::RpcFlightPlanLinkServer::<itinerary_flight_plan::GrpcServer>::NAME,
+        parcel::RpcServiceServer::<parcel::GrpcServer>::NAME,
+        RpcCustodyServer::<ParcelCustodyGrpcServer>::NAME,
+        parcel_scan::RpcServiceServer::<parcel_scan::GrpcServer>::NAME,
+        pilot::RpcServiceServer::<pilot::GrpcServer>::NAME,
+        scanner::RpcServiceServer::<scanner::GrpcServer>::NAME,
+        user::RpcServiceServer::<user::GrpcServer>::NAME,
+        user_group::RpcGroupLinkServer::<user_group::GrpcServer>::NAME,
+        vehicle::RpcServiceServer::<vehicle::GrpcServer>::NAME,
+        vehicle_group::RpcGroupLinkServer::<vehicle_group::GrpcServer>::NAME,
+        vehicle_model::RpcServiceServer::<vehicle_model::GrpcServer>::NAME,
+        vertipad::RpcServiceServer::<vertipad::GrpcServer>::NAME,
+        vertipad_group::RpcGroupLinkServer::<vertipad_group::GrpcServer>::NAME,
+        vertiport::RpcServiceServer::<vertiport::GrpcServer>::NAME,
+        vertiport_group::RpcGroupLinkServer::<vertiport_group::GrpcServer>::NAME,
+    ]
 }
 
 #[cfg(test)]
@@ -2,13 +2,19 @@
 
 pub use crate::common::ArrErr;
 
+use futures::StreamExt;
 use std::marker::PhantomData;
 use tokio_postgres::Row;
+use tonic::codegen::BoxStream;
 use tonic::{Code, Request, Response, Status};
 
 use super::server::*;
-use super::GrpcDataObjectType;
+use super::{
+    get_status_with_message, GrpcBatchRequestType, GrpcBatchResponseType, GrpcChangeEventType,
+    GrpcDataObjectType, GrpcListType,
+};
 use crate::postgres::simple_resource::{PsqlObjectType, PsqlType};
+use crate::postgres::watch::PsqlWatch;
 use crate::postgres::PsqlSearch;
 use crate::resources::base::simple_resource::{GenericResourceResult, ObjectType, SimpleResource};
 
@@ -19,6 +25,7 @@ where
     <Self as GrpcSimpleService>::ResourceObject: ObjectType<Self::Data>
         + PsqlType
         + PsqlSearch
+        + PsqlWatch
         + SimpleResource<Self::Data>
         + PsqlObjectType<Self::Data>
         + From<Id>
@@ -28,29 +35,42 @@ where
         + Sync
         + Send,
     <Self as GrpcSimpleService>::Data: GrpcDataObjectType + TryFrom<Row>,
-    <Self as GrpcSimpleService>::List: TryFrom<Vec<Row>>,
-    <Self as GrpcSimpleService>::Object: From<Self::ResourceObject>,
+    <Self as GrpcSimpleService>::List: TryFrom<Vec<Row>> + GrpcListType,
+    <Self as GrpcSimpleService>::Object:
+        From<Self::ResourceObject> + TryFrom<Row, Error = ArrErr> + Send + 'static,
     <Self as GrpcSimpleService>::UpdateObject: Send,
     <Self as GrpcSimpleService>::Response:
         From<GenericResourceResult<Self::ResourceObject, Self::Data>>,
+    <Self as GrpcSimpleService>::InsertManyRequest: GrpcBatchRequestType<Self::Data> + Send,
+    <Self as GrpcSimpleService>::UpdateManyRequest: GrpcBatchRequestType<Self::UpdateObject> + Send,
+    <Self as GrpcSimpleService>::ResponseList: GrpcBatchResponseType<Self::Response>,
+    <Self as GrpcSimpleService>::ChangeEvent: GrpcChangeEventType<Self::Object> + Send + 'static,
     Status:
         From<<Self::Data as TryFrom<Row>>::Error> + From<<Self::List as TryFrom<Vec<Row>>>::Error>,
 {
     /// The type expected for the [`Self::ResourceObject<Self::Data>`] type. Must implement;
-    /// [`ObjectType<Self::Data>`], [`PsqlType`], [`PsqlSearch`],
+    /// [`ObjectType<Self::Data>`], [`PsqlType`], [`PsqlSearch`], [`PsqlWatch`],
     /// [`SimpleResource<Self::Data>`], [`PsqlObjectType<Self::Data>`], `From<[Id]>`, `From<[Self::Data]>`,
     /// `From<[Self::UpdateObject]>`, [`Clone`], [`Sync`], [`Send`]
     type ResourceObject;
     /// The type expected for `Data` structs. Must implement; [`GrpcDataObjectType`], `TryFrom<[Row]>`
     type Data;
-    /// The type expected for `Object` structs. Must implement; `From<[Self::ResourceObject]>`
+    /// The type expected for `Object` structs. Must implement; `From<[Self::ResourceObject]>`, `TryFrom<[Row]>`, [`Send`]
     type Object;
     /// The type expected for `UpdateObject` structs. Must implement; [`Send`]
     type UpdateObject;
-    /// The type expected for `List` structs. Must implement `TryFrom<[Vec<Row>]>`
+    /// The type expected for `List` structs. Must implement `TryFrom<[Vec<Row>]>`, [`GrpcListType`]
     type List;
     /// The type expected for `Response` structs. Must implement; `From<[GenericResourceResult<Self::ResourceObject, Self::Data>]>`
     type Response;
+    /// The type expected for `InsertManyRequest` structs. Must implement; [`GrpcBatchRequestType<Self::Data>`], [`Send`]
+    type InsertManyRequest;
+    /// The type expected for `UpdateManyRequest` structs. Must implement; [`GrpcBatchRequestType<Self::UpdateObject>`], [`Send`]
+    type UpdateManyRequest;
+    /// The type expected for `ResponseList` structs. Must implement; [`GrpcBatchResponseType<Self::Response>`]
+    type ResponseList;
+    /// The type expected for `ChangeEvent` structs. Must implement; [`GrpcChangeEventType<Self::Object>`], [`Send`]
+    type ChangeEvent;
 
     /// Returns a [`tonic`] gRCP [`Response`] containing an object of provided type [`Self::Object`].
     /// `Self::Object` will contain the record data found for the provided [`Id`].
@@ -68,13 +88,47 @@ where
         let mut resource: Self::ResourceObject = id.clone().into();
         let obj: Result<Row, ArrErr> =
             Self::ResourceObject::get_by_id(&resource.try_get_uuid()?).await;
-        if let Ok(obj) = obj {
-            resource.set_data(obj.try_into()?);
-            Ok(Response::new(resource.into()))
-        } else {
-            let error = format!("No resource found for specified uuid: {}", id.id);
-            grpc_error!("(generic_get_by_id) {}", error);
-            Err(Status::new(Code::NotFound, error))
+        match obj {
+            Ok(obj) => {
+                resource.set_data(obj.try_into()?);
+                Ok(Response::new(resource.into()))
+            }
+            Err(ArrErr::NotFound(_)) => {
+                let error = format!("No resource found for specified uuid: {}", id.id);
+                grpc_error!("(generic_get_by_id) {}", error);
+                Err(ArrErr::not_found(error).into())
+            }
+            Err(e) => Err(get_status_with_message(e)),
+        }
+    }
+
+    /// Returns a [`tonic`] gRCP [`Response`] containing an object of provided type [`Self::Object`].
+    /// `Self::Object` will contain the record data found for the provided [`Id`], including soft-deleted records.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Status`] with [`Code::NotFound`] if no record is returned from the database.  
+    /// Returns [`Status`] with [`Code::Internal`] if the provided Id can not be converted to a [`uuid::Uuid`].  
+    /// Returns [`Status`] with [`Code::Internal`] if the resulting [`Row`] data could not be converted into [`Self::Object`].  
+    async fn generic_get_by_id_include_deleted(
+        &self,
+        request: Request<Id>,
+    ) -> Result<Response<Self::Object>, Status> {
+        let id: Id = request.into_inner();
+        let mut resource: Self::ResourceObject = id.clone().into();
+        let obj: Result<Row, ArrErr> =
+            Self::ResourceObject::get_by_id_include_deleted(&resource.try_get_uuid()?).await;
+        match obj {
+            Ok(obj) => {
+                resource.set_data(obj.try_into()?);
+                Ok(Response::new(resource.into()))
+            }
+            Err(ArrErr::NotFound(_)) => {
+                let error = format!("No resource found for specified uuid: {}", id.id);
+                grpc_error!("(generic_get_by_id_include_deleted) {}", error);
+                Err(ArrErr::not_found(error).into())
+            }
+            Err(e) => Err(get_status_with_message(e)),
         }
     }
 
@@ -82,6 +136,9 @@ where
     /// `Self::Object`(TryFrom\<Vec\<Row\>\>) will contain all records found in the database using the the provided [`AdvancedSearchFilter`].
     ///
     /// This method supports paged results.
+    /// The returned list contains the `total_count` of matching records and a `next_page_token`
+    /// which can be provided as `page_token` to get the next page.
+    /// When sorted by distance, the list contains the `distances` in meters of the returned items.
     ///
     /// # Errors
     ///
@@ -93,9 +150,79 @@ where
         request: Request<AdvancedSearchFilter>,
     ) -> Result<Response<Self::List>, Status> {
         let filter: AdvancedSearchFilter = request.into_inner();
-        match Self::ResourceObject::advanced_search(filter).await {
-            Ok(rows) => Ok(Response::new(rows.try_into()?)),
-            Err(e) => Err(Status::new(Code::Internal, e.to_string())),
+        match Self::ResourceObject::advanced_search_paged(filter).await {
+            Ok(result) => {
+                let mut list: Self::List = result.rows.try_into()?;
+                list.set_total_count(result.total_count);
+                list.set_next_page_token(result.next_page_token);
+                list.set_distances(result.distances);
+                Ok(Response::new(list))
+            }
+            Err(e) => Err(get_status_with_message(e)),
+        }
+    }
+
+    /// Returns a [`tonic`] gRCP [`Response`] containing a stream of [`Self::Object`]s.
+    /// The stream will contain all records found in the database using the the provided [`AdvancedSearchFilter`].
+    ///
+    /// Rows are converted and sent as soon as they are received from the database.
+    /// Page numbers and page sizes are ignored, the `page_token` can be used to resume a previous search.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Status`] with [`Code::Internal`] if any error is returned from the db search result.  
+    /// Each streamed item will contain a [`Status`] with [`Code::Internal`] if the [`Row`] could not be received or converted into [`Self::Object`].
+    async fn generic_search_stream(
+        &self,
+        request: Request<AdvancedSearchFilter>,
+    ) -> Result<Response<BoxStream<Self::Object>>, Status> {
+        let filter: AdvancedSearchFilter = request.into_inner();
+        match Self::ResourceObject::advanced_search_stream(filter).await {
+            Ok(rows) => {
+                let objects = rows.map(|row| -> Result<Self::Object, Status> {
+                    let object: Self::Object = row?.try_into()?;
+                    Ok(object)
+                });
+                Ok(Response::new(Box::pin(objects)))
+            }
+            Err(e) => Err(get_status_with_message(e)),
+        }
+    }
+
+    /// Returns a [`tonic`] gRCP [`Response`] containing a stream of [`Self::ChangeEvent`]s.
+    /// The stream will contain an event for each change made to records matching the provided [`AdvancedSearchFilter`].
+    ///
+    /// The filter's `page_token` can be set to the `sequence` of the last received event to resume watching.
+    /// If no `page_token` is provided, only new changes will be returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Status`] with [`Code::InvalidArgument`] if the `page_token` is invalid.  
+    /// Returns [`Status`] with [`Code::Internal`] if the change log could not be read.  
+    /// Each streamed item will contain a [`Status`] with [`Code::Internal`] if the change could not be received or converted into [`Self::Object`].
+    async fn generic_watch(
+        &self,
+        request: Request<AdvancedSearchFilter>,
+    ) -> Result<Response<BoxStream<Self::ChangeEvent>>, Status> {
+        let filter: AdvancedSearchFilter = request.into_inner();
+        match Self::ResourceObject::watch_changes(filter).await {
+            Ok(changes) => {
+                let events = changes.map(|change| -> Result<Self::ChangeEvent, Status> {
+                    let change = change?;
+                    let object: Option<Self::Object> = match change.row {
+                        Some(row) => Some(row.try_into()?),
+                        None => None,
+                    };
+                    Ok(Self::ChangeEvent::from_change(
+                        change.change.sequence,
+                        change.change.change_type,
+                        change.change.id.to_string(),
+                        object,
+                    ))
+                });
+                Ok(Response::new(Box::pin(events)))
+            }
+            Err(e) => Err(get_status_with_message(e)),
         }
     }
 
@@ -121,7 +248,7 @@ where
             "(generic_insert) Inserting with data [{:?}].",
             resource.try_get_data()?
         );
-        let (id, validation_result) =
+        let (id, validation_result, deduplicated) =
             Self::ResourceObject::create(&resource.try_get_data()?).await?;
         if let Some(id) = id {
             resource.set_id(id.to_string());
@@ -130,6 +257,7 @@ where
                 phantom: PhantomData,
                 validation_result,
                 resource: Some(obj),
+                deduplicated,
             };
             Ok(Response::new(result.into()))
         } else {
@@ -141,6 +269,7 @@ where
                 phantom: PhantomData,
                 validation_result,
                 resource: None,
+                deduplicated: false,
             };
             Ok(Response::new(result.into()))
         }
@@ -169,6 +298,7 @@ where
             id: req.try_get_id()?,
         };
         let mut resource: Self::ResourceObject = id.into();
+        resource.set_mask(req.get_mask());
 
         let data = match req.get_data() {
             Some(data) => data,
@@ -186,6 +316,7 @@ where
                 phantom: PhantomData,
                 validation_result,
                 resource: Some(resource),
+                deduplicated: false,
             };
             Ok(Response::new(result.into()))
         } else {
@@ -197,6 +328,7 @@ where
                 phantom: PhantomData,
                 validation_result,
                 resource: None,
+                deduplicated: false,
             };
             Ok(Response::new(result.into()))
         }
@@ -213,16 +345,206 @@ where
         let resource: Self::ResourceObject = id.into();
         match resource.delete().await {
             Ok(_) => Ok(Response::new(())),
-            Err(e) => Err(Status::new(Code::Internal, e.to_string())),
+            Err(e) => Err(get_status_with_message(e)),
+        }
+    }
+
+    /// Returns a [`tonic`] gRCP [`Response`] containing an object of provided type [`Self::ResponseList`].
+    /// `Self::ResponseList` will contain a [`Self::Response`] for each of the provided [`Self::Data`] items,
+    /// in the same order as the request.
+    ///
+    /// All items will be inserted using a single transaction.
+    /// Any errors found during validation or insert will be added to the item's [`ValidationResult`](crate::resources::ValidationResult).
+    /// Using [`BatchMode::AllOrNothing`], no records will be inserted if any of the items fail.
+    /// Using [`BatchMode::BestEffort`], failing items will be skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Status`] with [`Code::InvalidArgument`] if the provided mode is not a valid [`BatchMode`].  
+    /// Returns [`Status`] with [`Code::Internal`] if any error is returned from a db call.  
+    ///
+    async fn generic_insert_many(
+        &self,
+        request: Request<Self::InsertManyRequest>,
+    ) -> Result<Response<Self::ResponseList>, Status> {
+        let request = request.into_inner();
+        let mode = try_get_batch_mode(request.get_mode())?;
+        let data = request.into_list();
+        grpc_debug!(
+            "(generic_insert_many) Inserting [{}] items using mode [{}].",
+            data.len(),
+            mode.as_str_name()
+        );
+
+        let (results, success) = Self::ResourceObject::create_many(&data, mode).await?;
+        let mut list: Vec<Self::Response> = Vec::with_capacity(results.len());
+        for (data, (id, validation_result)) in data.into_iter().zip(results) {
+            let deduplicated = matches!(id, Some((_, true)));
+            let resource = id.map(|(id, _)| {
+                let mut resource: Self::ResourceObject = data.into();
+                resource.set_id(id.to_string());
+                resource
+            });
+            let result = GenericResourceResult {
+                phantom: PhantomData,
+                validation_result,
+                resource,
+                deduplicated,
+            };
+            list.push(result.into());
+        }
+        Ok(Response::new(Self::ResponseList::from_list(list, success)))
+    }
+
+    /// Returns a [`tonic`] gRCP [`Response`] containing an object of provided type [`Self::ResponseList`].
+    /// `Self::ResponseList` will contain a [`Self::Response`] for each of the provided [`Self::UpdateObject`] items,
+    /// in the same order as the request.
+    ///
+    /// All items will be updated using a single transaction.
+    /// Any errors found during validation or update will be added to the item's [`ValidationResult`](crate::resources::ValidationResult).
+    /// Using [`BatchMode::AllOrNothing`], no records will be updated if any of the items fail.
+    /// Using [`BatchMode::BestEffort`], failing items will be skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Status`] with [`Code::InvalidArgument`] if the provided mode is not a valid [`BatchMode`].  
+    /// Returns [`Status`] with [`Code::Internal`] if any error is returned from a db call.  
+    /// Returns [`Status`] with [`Code::Internal`] if the resulting [`Row`] data could not be converted into [`Self::Data`].  
+    ///
+    async fn generic_update_many(
+        &self,
+        request: Request<Self::UpdateManyRequest>,
+    ) -> Result<Response<Self::ResponseList>, Status> {
+        let request = request.into_inner();
+        let mode = try_get_batch_mode(request.get_mode())?;
+        let objects: Vec<Self::ResourceObject> = request
+            .into_list()
+            .into_iter()
+            .map(|object| object.into())
+            .collect();
+        grpc_debug!(
+            "(generic_update_many) Updating [{}] items using mode [{}].",
+            objects.len(),
+            mode.as_str_name()
+        );
+
+        let (results, success) = Self::ResourceObject::update_many(&objects, mode).await?;
+        let mut list: Vec<Self::Response> = Vec::with_capacity(results.len());
+        for (mut resource, (row, validation_result)) in objects.into_iter().zip(results) {
+            let resource = match row {
+                Some(row) => {
+                    resource.set_data(row.try_into()?);
+                    Some(resource)
+                }
+                None => None,
+            };
+            let result = GenericResourceResult {
+                phantom: PhantomData,
+                validation_result,
+                resource,
+                deduplicated: false,
+            };
+            list.push(result.into());
+        }
+        Ok(Response::new(Self::ResponseList::from_list(list, success)))
+    }
+
+    /// Takes a [`DeleteManyRequest`] to set the matching database records as deleted using a single transaction.
+    /// The returned [`DeleteManyResponse`] contains a [`ValidationResult`] for each of the provided ids,
+    /// in the same order as the request.
+    ///
+    /// Using [`BatchMode::AllOrNothing`], no records will be deleted if any of the items fail.
+    /// Using [`BatchMode::BestEffort`], failing items will be skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Status`] with [`Code::InvalidArgument`] if the provided mode is not a valid [`BatchMode`].  
+    /// Returns [`Status`] with [`Code::Internal`] if any error is returned from a db call.  
+    async fn generic_delete_many(
+        &self,
+        request: Request<DeleteManyRequest>,
+    ) -> Result<Response<DeleteManyResponse>, Status> {
+        let request = request.into_inner();
+        let mode = try_get_batch_mode(request.mode)?;
+        let objects: Vec<Self::ResourceObject> =
+            request.ids.into_iter().map(|id| Id { id }.into()).collect();
+        grpc_debug!(
+            "(generic_delete_many) Deleting [{}] items using mode [{}].",
+            objects.len(),
+            mode.as_str_name()
+        );
+
+        let (results, success) = Self::ResourceObject::delete_many(&objects, mode).await?;
+        Ok(Response::new(DeleteManyResponse { success, results }))
+    }
+
+    /// Takes an [`Id`] to restore the matching soft-deleted database record.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Status`] with [`Code::FailedPrecondition`] if the resource does not support soft-deletes, the record is not deleted
+    /// or restoring it would conflict with the time ranges of other records.  
+    /// Returns [`Status`] with [`Code::NotFound`] if the record could not be restored because it no longer exists.  
+    /// Returns [`Status`] with [`Code::Internal`] if any other error is returned from a db call.  
+    async fn generic_restore(&self, request: Request<Id>) -> Result<Response<()>, Status> {
+        let id: Id = request.into_inner();
+        let resource: Self::ResourceObject = id.into();
+        match resource.restore().await {
+            Ok(_) => Ok(Response::new(())),
+            Err(e) => Err(get_status_with_message(e)),
+        }
+    }
+
+    /// Takes an [`Id`] to permanently remove the matching database record and its dependent link rows.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Status`] with [`Code::FailedPrecondition`] if the record is not deleted yet or is still referenced by other records.  
+    /// Returns [`Status`] with [`Code::NotFound`] if the record could not be purged because it no longer exists.  
+    /// Returns [`Status`] with [`Code::Internal`] if any other error is returned from a db call.  
+    async fn generic_purge(&self, request: Request<Id>) -> Result<Response<()>, Status> {
+        let id: Id = request.into_inner();
+        let resource: Self::ResourceObject = id.into();
+        match resource.purge().await {
+            Ok(_) => Ok(Response::new(())),
+            Err(e) => Err(get_status_with_message(e)),
         }
     }
 
     /// Returns ready:true when service is available
+    ///
+    /// The service is available if the last database health check succeeded.
     async fn generic_is_ready(
         &self,
         _request: Request<ReadyRequest>,
     ) -> Result<Response<ReadyResponse>, Status> {
-        let response = ReadyResponse { ready: true };
+        let response = ReadyResponse {
+            ready: super::health::is_ready(),
+        };
         Ok(Response::new(response))
     }
+
+    /// Returns detailed health information of the service as found by the last database health check
+    async fn generic_get_health(
+        &self,
+        _request: Request<ReadyRequest>,
+    ) -> Result<Response<HealthResponse>, Status> {
+        Ok(Response::new(super::health::get_health()))
+    }
+}
+
+/// Converts the provided [`i32`] into a [`BatchMode`]
+///
+/// # Errors
+///
+/// Returns [`Status`] with [`Code::InvalidArgument`] if the provided mode is not a valid [`BatchMode`].
+fn try_get_batch_mode(mode: i32) -> Result<BatchMode, Status> {
+    BatchMode::try_from(mode).map_err(|e| {
+        let error = format!(
+            "Can't convert i32 [{}] into BatchMode Enum value: {}",
+            mode, e
+        );
+        grpc_error!("(try_get_batch_mode) {}", error);
+        Status::new(Code::InvalidArgument, error)
+    })
 }
@@ -4,11 +4,11 @@ use std::collections::HashMap;
 use std::marker::PhantomData;
 
 use tokio_postgres::Row;
-use tonic::{Code, Request, Response, Status};
+use tonic::{Request, Response, Status};
 use uuid::Uuid;
 
 use super::server::*;
-use super::GrpcDataObjectType;
+use super::{get_status_with_message, GrpcDataObjectType, GrpcListType};
 use crate::common::ArrErr;
 use crate::postgres::simple_resource::PsqlType as PsqlSimpleType;
 use crate::postgres::simple_resource_linked::{PsqlObjectType, PsqlType};
@@ -41,7 +41,7 @@ where
     <Self as GrpcSimpleServiceLinked>::LinkedData: GrpcDataObjectType + TryFrom<Row>,
     <Self as GrpcSimpleServiceLinked>::LinkedRowData: GrpcDataObjectType + TryFrom<Row>,
     <Self as GrpcSimpleServiceLinked>::LinkedList: TryFrom<Vec<Row>>,
-    <Self as GrpcSimpleServiceLinked>::LinkedRowDataList: TryFrom<Vec<Row>>,
+    <Self as GrpcSimpleServiceLinked>::LinkedRowDataList: TryFrom<Vec<Row>> + GrpcListType,
     <Self as GrpcSimpleServiceLinked>::LinkedObject: From<Self::LinkedResourceObject>,
     <Self as GrpcSimpleServiceLinked>::LinkedUpdateObject: Send,
     <Self as GrpcSimpleServiceLinked>::LinkedResponse:
@@ -94,7 +94,7 @@ where
     /// Must implement; `TryFrom<[Vec<Row>]>`
     type LinkedList;
     /// The type expected for the RowDataList struct of the linked resource.
-    /// Must implement; `TryFrom<[Vec<Row>]>`
+    /// Must implement; `TryFrom<[Vec<Row>]>`, [`GrpcListType`]
     type LinkedRowDataList;
     /// The type expected for the Response struct of the linked resource.
     /// Must implement; `TryFrom<[Vec<Row>]>`
@@ -126,10 +126,10 @@ where
     ///
     /// # Errors
     ///
-    /// Returns [`Status`] with [`Code::NotFound`] if no record is returned from the database.  
-    /// Returns [`Status`] with [`Code::Internal`] if the provided Ids can not
+    /// Returns [`Status`] with [`Code::NotFound`](tonic::Code::NotFound) if no record is returned from the database.  
+    /// Returns [`Status`] with [`Code::Internal`](tonic::Code::Internal) if the provided Ids can not
     /// be converted to valid [`uuid::Uuid`]s.  
-    /// Returns [`Status`] with [`Code::Internal`] if the resulting [`Row`] data could not be converted into [`Self::LinkedObject`].
+    /// Returns [`Status`] with [`Code::Internal`](tonic::Code::Internal) if the resulting [`Row`] data could not be converted into [`Self::LinkedObject`].
     async fn generic_get_by_id(
         &self,
         request: Request<Ids>,
@@ -137,13 +137,17 @@ where
         let id: Ids = request.into_inner();
         let mut resource: Self::LinkedResourceObject = id.clone().into();
         let obj = Self::LinkedResourceObject::get_for_ids(id.clone().try_into()?).await;
-        if let Ok(obj) = obj {
-            resource.set_data(obj.try_into()?);
-            Ok(Response::new(resource.into()))
-        } else {
-            let error = format!("No resource found for specified uuids: {:?}", id);
-            grpc_error!("(generic_get_by_id) {}", error);
-            Err(Status::new(Code::NotFound, error))
+        match obj {
+            Ok(obj) => {
+                resource.set_data(obj.try_into()?);
+                Ok(Response::new(resource.into()))
+            }
+            Err(ArrErr::NotFound(_)) => {
+                let error = format!("No resource found for specified uuids: {:?}", id);
+                grpc_error!("(generic_get_by_id) {}", error);
+                Err(ArrErr::not_found(error).into())
+            }
+            Err(e) => Err(get_status_with_message(e)),
         }
     }
 
@@ -151,20 +155,29 @@ where
     /// `Self::Object`(TryFrom\<Vec\<Row\>\>) will contain all records found in the database using the the provided [`AdvancedSearchFilter`].
     ///
     /// This method supports paged results.
+    /// The returned list contains the `total_count` of matching records and a `next_page_token`
+    /// which can be provided as `page_token` to get the next page.
+    /// When sorted by distance, the list contains the `distances` in meters of the returned items.
     ///
     /// # Errors
     ///
-    /// Returns [`Status`] with [`Code::Internal`] if any error is returned from the db search result.  
-    /// Returns [`Status`] with [`Code::Internal`] if the resulting [`Vec<Row>`] data could not be converted into [`Self::LinkedObject`].  
+    /// Returns [`Status`] with [`Code::Internal`](tonic::Code::Internal) if any error is returned from the db search result.  
+    /// Returns [`Status`] with [`Code::Internal`](tonic::Code::Internal) if the resulting [`Vec<Row>`] data could not be converted into [`Self::LinkedObject`].  
     ///
     async fn generic_search(
         &self,
         request: Request<AdvancedSearchFilter>,
     ) -> Result<Response<Self::LinkedRowDataList>, Status> {
         let filter: AdvancedSearchFilter = request.into_inner();
-        match Self::LinkedResourceObject::advanced_search(filter).await {
-            Ok(rows) => Ok(Response::new(rows.try_into()?)),
-            Err(e) => Err(Status::new(Code::Internal, e.to_string())),
+        match Self::LinkedResourceObject::advanced_search_paged(filter).await {
+            Ok(result) => {
+                let mut list: Self::LinkedRowDataList = result.rows.try_into()?;
+                list.set_total_count(result.total_count);
+                list.set_next_page_token(result.next_page_token);
+                list.set_distances(result.distances);
+                Ok(Response::new(list))
+            }
+            Err(e) => Err(get_status_with_message(e)),
         }
     }
 
@@ -175,9 +188,9 @@ where
     ///
     /// # Errors
     ///
-    /// Returns [`Status`] with [`Code::NotFound`] if no record exists for the given `id`.
-    /// Returns [`Status`] with [`Code::Internal`] if the provided Id can not be converted to valid [`uuid::Uuid`].  
-    /// Returns [`Status`] with [`Code::Internal`] if any error is returned from the db search result.  
+    /// Returns [`Status`] with [`Code::NotFound`](tonic::Code::NotFound) if no record exists for the given `id`.
+    /// Returns [`Status`] with [`Code::Internal`](tonic::Code::Internal) if the provided Id can not be converted to valid [`uuid::Uuid`].  
+    /// Returns [`Status`] with [`Code::Internal`](tonic::Code::Internal) if any error is returned from the db search result.  
     ///
     async fn generic_unlink(&self, request: Request<Id>) -> Result<Response<()>, Status> {
         let id: Id = request.into_inner();
@@ -189,7 +202,7 @@ where
         {
             let error = format!("No resource found for specified uuids: {:?}", id);
             grpc_error!("(generic_unlink) {}", error);
-            return Err(Status::new(Code::NotFound, error));
+            return Err(ArrErr::not_found(error).into());
         }
 
         match Self::LinkedResourceObject::delete_for_ids(
@@ -202,7 +215,7 @@ where
         .await
         {
             Ok(_) => Ok(tonic::Response::new(())),
-            Err(e) => Err(Status::new(Code::Internal, e.to_string())),
+            Err(e) => Err(get_status_with_message(e)),
         }
     }
 
@@ -215,9 +228,9 @@ where
     ///
     /// # Errors
     ///
-    /// Returns [`Status`] with [`Code::NotFound`] if no record exists for the given `id`.
-    /// Returns [`Status`] with [`Code::Internal`] if the provided Id can not be converted to a [`uuid::Uuid`].  
-    /// Returns [`Status`] with [`Code::Internal`] if any error is returned from the db search result.  
+    /// Returns [`Status`] with [`Code::NotFound`](tonic::Code::NotFound) if no record exists for the given `id`.
+    /// Returns [`Status`] with [`Code::Internal`](tonic::Code::Internal) if the provided Id can not be converted to a [`uuid::Uuid`].  
+    /// Returns [`Status`] with [`Code::Internal`](tonic::Code::Internal) if any error is returned from the db search result.  
     async fn generic_get_linked_ids(&self, request: Request<Id>) -> Result<Response<IdList>, Status>
     where
         Self: Send + 'async_trait,
@@ -237,9 +250,9 @@ where
     ///
     /// # Errors
     ///
-    /// Returns [`Status`] with [`Code::NotFound`] if no record exists for the given `id`.
-    /// Returns [`Status`] with [`Code::Internal`] if the provided Id can not be converted to a [`uuid::Uuid`].  
-    /// Returns [`Status`] with [`Code::Internal`] if any error is returned from the db search result.  
+    /// Returns [`Status`] with [`Code::NotFound`](tonic::Code::NotFound) if no record exists for the given `id`.
+    /// Returns [`Status`] with [`Code::Internal`](tonic::Code::Internal) if the provided Id can not be converted to a [`uuid::Uuid`].  
+    /// Returns [`Status`] with [`Code::Internal`](tonic::Code::Internal) if any error is returned from the db search result.  
     async fn generic_get_linked(
         &self,
         request: Request<Id>,
@@ -254,7 +267,7 @@ where
 
         match Self::OtherResourceObject::advanced_search(filter).await {
             Ok(rows) => Ok(tonic::Response::new(rows.try_into()?)),
-            Err(e) => Err(Status::new(Code::Internal, e.to_string())),
+            Err(e) => Err(get_status_with_message(e)),
         }
     }
 
@@ -294,8 +307,8 @@ where
     ///
     /// # Errors
     ///
-    /// Returns [`Status`] with [`Code::Internal`] if the [`Request`] doesn't contain any data.  
-    /// Returns [`Status`] with [`Code::Internal`] if any error is returned from a db call.
+    /// Returns [`Status`] with [`Code::Internal`](tonic::Code::Internal) if the [`Request`] doesn't contain any data.  
+    /// Returns [`Status`] with [`Code::Internal`](tonic::Code::Internal) if any error is returned from a db call.
     ///
     async fn generic_insert(
         &self,
@@ -337,10 +350,10 @@ where
     ///
     /// # Errors
     ///
-    /// Returns [`Status`] with [`Code::Cancelled`] if the [`Request`] doesn't contain any data.  
-    /// Returns [`Status`] with [`Code::Internal`] if any error is returned from a db call.  
-    /// Returns [`Status`] with [`Code::Internal`] if the provided Ids can not be converted to valid [`uuid::Uuid`]s.  
-    /// Returns [`Status`] with [`Code::Internal`] if the resulting [`Row`] data could not be converted into [`Self::Data`].  
+    /// Returns [`Status`] with [`Code::Cancelled`](tonic::Code::Cancelled) if the [`Request`] doesn't contain any data.  
+    /// Returns [`Status`] with [`Code::Internal`](tonic::Code::Internal) if any error is returned from a db call.  
+    /// Returns [`Status`] with [`Code::Internal`](tonic::Code::Internal) if the provided Ids can not be converted to valid [`uuid::Uuid`]s.  
+    /// Returns [`Status`] with [`Code::Internal`](tonic::Code::Internal) if the resulting [`Row`] data could not be converted into [`Self::Data`].  
     ///
     async fn generic_update(
         &self,
@@ -387,23 +400,58 @@ where
     ///
     /// # Errors
     ///
-    /// Returns [`Status`] with [`Code::NotFound`] if no record is returned from the database.  
-    /// Returns [`Status`] with [`Code::Internal`] if any error is returned from a db call.  
+    /// Returns [`Status`] with [`Code::NotFound`](tonic::Code::NotFound) if no record is returned from the database.  
+    /// Returns [`Status`] with [`Code::Internal`](tonic::Code::Internal) if any error is returned from a db call.  
     async fn generic_delete(&self, request: Request<Ids>) -> Result<Response<()>, Status> {
         let id: Ids = request.into_inner();
         let resource: Self::LinkedResourceObject = id.into();
         match resource.delete().await {
             Ok(_) => Ok(Response::new(())),
-            Err(e) => Err(Status::new(Code::Internal, e.to_string())),
+            Err(e) => Err(get_status_with_message(e)),
+        }
+    }
+
+    /// Takes [`Ids`] to restore the matching soft-deleted database record.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Status`] with [`Code::FailedPrecondition`](tonic::Code::FailedPrecondition) if the resource does not support soft-deletes or the record is not deleted.  
+    /// Returns [`Status`] with [`Code::NotFound`](tonic::Code::NotFound) if the record could not be restored because it no longer exists.  
+    /// Returns [`Status`] with [`Code::Internal`](tonic::Code::Internal) if any other error is returned from a db call.  
+    async fn generic_restore(&self, request: Request<Ids>) -> Result<Response<()>, Status> {
+        let id: Ids = request.into_inner();
+        let resource: Self::LinkedResourceObject = id.into();
+        match resource.restore().await {
+            Ok(_) => Ok(Response::new(())),
+            Err(e) => Err(get_status_with_message(e)),
+        }
+    }
+
+    /// Takes [`Ids`] to permanently remove the matching database record and its dependent link rows.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Status`] with [`Code::FailedPrecondition`](tonic::Code::FailedPrecondition) if the record is not deleted yet.  
+    /// Returns [`Status`] with [`Code::Internal`](tonic::Code::Internal) if any other error is returned from a db call.  
+    async fn generic_purge(&self, request: Request<Ids>) -> Result<Response<()>, Status> {
+        let id: Ids = request.into_inner();
+        let resource: Self::LinkedResourceObject = id.into();
+        match resource.purge().await {
+            Ok(_) => Ok(Response::new(())),
+            Err(e) => Err(get_status_with_message(e)),
         }
     }
 
     /// Returns ready:true when service is available
+    ///
+    /// The service is available if the last database health check succeeded.
     async fn generic_is_ready(
         &self,
         _request: Request<ReadyRequest>,
     ) -> Result<Response<ReadyResponse>, Status> {
-        let response = ReadyResponse { ready: true };
+        let response = ReadyResponse {
+            ready: super::health::is_ready(),
+        };
         Ok(Response::new(response))
     }
 }
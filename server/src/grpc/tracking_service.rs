@@ -0,0 +1,260 @@
+//! gRPC server implementation of the adsb tracking service
+
+pub use super::server::adsb::rpc_tracking_server::*;
+
+use super::server::adsb::{LatestPositionsRequest, LatestPositionsResponse};
+use super::server::{ReadyRequest, ReadyResponse};
+use crate::common::ArrErr;
+use crate::postgres::util::validate_polygon;
+use chrono::{DateTime, Utc};
+use geo_types::Polygon;
+use std::time::{Duration, SystemTime};
+use tonic::{Request, Response, Status};
+
+/// Maximum age in seconds of the messages taken into account if no `max_age_seconds` are provided
+pub const DEFAULT_LATEST_MAX_AGE_SECONDS: u32 = 300;
+
+/// Validated parameters of a [`LatestPositionsRequest`]
+#[derive(Debug, Clone, PartialEq)]
+struct LatestPositionsParams {
+    bounds: Option<Polygon>,
+    since: DateTime<Utc>,
+}
+
+/// Implementation of gRPC endpoints
+#[derive(Clone, Default, Debug, Copy)]
+pub struct GrpcServer {}
+impl GrpcServer {
+    /// Get name string for service
+    pub fn get_name(&self) -> String {
+        String::from("adsb_tracking")
+    }
+}
+
+#[tonic::async_trait]
+impl RpcTracking for GrpcServer {
+    /// Returns the latest known state of each aircraft
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Status`] with [`tonic::Code::InvalidArgument`] if the provided bounds are not a valid polygon.
+    /// Returns [`Status`] with [`tonic::Code::Internal`] if any error is returned from a db call.
+    #[cfg(not(feature = "stub_server"))]
+    async fn get_latest_positions(
+        &self,
+        request: Request<LatestPositionsRequest>,
+    ) -> Result<Response<LatestPositionsResponse>, Status> {
+        grpc_info!("(get_latest_positions) {} server.", self.get_name());
+        grpc_debug!("(get_latest_positions) request: {:?}", request);
+        let params = get_latest_positions_params(request.into_inner())?;
+        let list = crate::resources::adsb::latest::get_latest_positions(
+            params.bounds.as_ref(),
+            &params.since,
+        )
+        .await
+        .map_err(super::get_status_with_message)?;
+
+        Ok(Response::new(LatestPositionsResponse { list }))
+    }
+    #[cfg(feature = "stub_server")]
+    async fn get_latest_positions(
+        &self,
+        request: Request<LatestPositionsRequest>,
+    ) -> Result<Response<LatestPositionsResponse>, Status> {
+        use super::server::adsb::{LatestPosition, Object, MEM_DATA};
+        use super::server::grpc_geo_types::GeoPolygon;
+        use std::collections::BTreeMap;
+
+        grpc_warn!("(get_latest_positions MOCK) {} server.", self.get_name());
+        grpc_debug!("(get_latest_positions MOCK) request: {:?}", request);
+        let params = get_latest_positions_params(request.into_inner())?;
+        let bounds: Option<GeoPolygon> = params.bounds.map(|bounds| bounds.into());
+
+        let timestamp = |object: &Object| -> Option<DateTime<Utc>> {
+            Some(object.data.as_ref()?.network_timestamp.clone()?.into())
+        };
+        let mut latest: BTreeMap<i64, (Object, Option<Object>)> = BTreeMap::new();
+        for object in MEM_DATA.lock().await.iter() {
+            let (data, received) = match (object.data.as_ref(), timestamp(object)) {
+                (Some(data), Some(received)) => (data, received),
+                _ => continue,
+            };
+            if received < params.since {
+                continue;
+            }
+            let entry = latest
+                .entry(data.icao_address)
+                .or_insert_with(|| (object.clone(), None));
+            if timestamp(&entry.0) < Some(received) {
+                entry.0 = object.clone();
+            }
+            if data.position.is_some() && entry.1.as_ref().and_then(timestamp) < Some(received) {
+                entry.1 = Some(object.clone());
+            }
+        }
+
+        let list: Vec<LatestPosition> = latest
+            .into_iter()
+            .filter_map(|(icao_address, (object, positioned))| {
+                let positioned = positioned.and_then(|object| object.data);
+                let position = positioned.as_ref().and_then(|data| data.position);
+                if let Some(bounds) = &bounds {
+                    if !bounds.contains_point(&position?) {
+                        return None;
+                    }
+                }
+                Some(LatestPosition {
+                    icao_address,
+                    latest: Some(object),
+                    position,
+                    position_timestamp: positioned
+                        .as_ref()
+                        .and_then(|data| data.network_timestamp.clone()),
+                    baro_altitude: positioned.and_then(|data| data.baro_altitude),
+                })
+            })
+            .collect();
+
+        Ok(Response::new(LatestPositionsResponse { list }))
+    }
+
+    /// Returns ready:true when service is available
+    #[cfg(not(feature = "stub_server"))]
+    async fn is_ready(
+        &self,
+        request: Request<ReadyRequest>,
+    ) -> Result<Response<ReadyResponse>, Status> {
+        grpc_info!("(is_ready) {} server.", self.get_name());
+        grpc_debug!("(is_ready) request: {:?}", request);
+        let response = ReadyResponse {
+            ready: super::health::is_ready(),
+        };
+        Ok(Response::new(response))
+    }
+    #[cfg(feature = "stub_server")]
+    async fn is_ready(
+        &self,
+        request: Request<ReadyRequest>,
+    ) -> Result<Response<ReadyResponse>, Status> {
+        grpc_info!("(is_ready MOCK) {} server.", self.get_name());
+        grpc_debug!("(is_ready MOCK) request: {:?}", request);
+        let response = ReadyResponse { ready: true };
+        Ok(Response::new(response))
+    }
+}
+
+/// Validates the provided [`LatestPositionsRequest`], returning the converted parameters
+///
+/// The `max_age_seconds` are converted into the timestamp from which messages should be taken into account,
+/// using [`DEFAULT_LATEST_MAX_AGE_SECONDS`] if not provided.
+///
+/// # Errors
+///
+/// Returns [`Status`] with [`tonic::Code::InvalidArgument`] if the provided bounds are not a valid polygon.
+fn get_latest_positions_params(
+    request: LatestPositionsRequest,
+) -> Result<LatestPositionsParams, Status> {
+    let bounds: Option<Polygon> = match request.bounds {
+        Some(bounds) => {
+            let bounds: Polygon = bounds.into();
+            let mut errors = vec![];
+            validate_polygon(String::from("bounds"), &bounds, &mut errors);
+            if !errors.is_empty() {
+                let errors: Vec<String> = errors.into_iter().map(|error| error.error).collect();
+                return Err(ArrErr::invalid_argument(errors.join(" ")).into());
+            }
+            Some(bounds)
+        }
+        None => None,
+    };
+    let max_age = request
+        .max_age_seconds
+        .unwrap_or(DEFAULT_LATEST_MAX_AGE_SECONDS);
+    let since = DateTime::<Utc>::from(SystemTime::now() - Duration::from_secs(max_age.into()));
+
+    Ok(LatestPositionsParams { bounds, since })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grpc::server::grpc_geo_types::{GeoLineString, GeoPoint, GeoPolygon};
+
+    fn get_bounds(points: &[(f64, f64)]) -> GeoPolygon {
+        GeoPolygon {
+            exterior: Some(GeoLineString {
+                points: points
+                    .iter()
+                    .map(|(longitude, latitude)| GeoPoint {
+                        longitude: *longitude,
+                        latitude: *latitude,
+                        altitude: None,
+                    })
+                    .collect(),
+                times: vec![],
+            }),
+            interiors: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_latest_positions_params() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_latest_positions_params) start");
+
+        let before = SystemTime::now();
+        let params = get_latest_positions_params(LatestPositionsRequest {
+            bounds: None,
+            max_age_seconds: None,
+        })
+        .unwrap();
+        let after = SystemTime::now();
+        let default_max_age = Duration::from_secs(DEFAULT_LATEST_MAX_AGE_SECONDS.into());
+        assert_eq!(params.bounds, None);
+        assert!(params.since >= DateTime::<Utc>::from(before - default_max_age));
+        assert!(params.since <= DateTime::<Utc>::from(after - default_max_age));
+
+        let before = SystemTime::now();
+        let params = get_latest_positions_params(LatestPositionsRequest {
+            bounds: Some(get_bounds(&[
+                (4.9, 52.3),
+                (5.0, 52.3),
+                (5.0, 52.4),
+                (4.9, 52.3),
+            ])),
+            max_age_seconds: Some(60),
+        })
+        .unwrap();
+        let after = SystemTime::now();
+        assert!(params.bounds.is_some());
+        assert!(params.since >= DateTime::<Utc>::from(before - Duration::from_secs(60)));
+        assert!(params.since <= DateTime::<Utc>::from(after - Duration::from_secs(60)));
+
+        ut_info!("(test_get_latest_positions_params) success");
+    }
+
+    #[tokio::test]
+    async fn test_get_latest_positions_params_invalid() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_latest_positions_params_invalid) start");
+
+        let invalid = vec![
+            // not a closed loop
+            get_bounds(&[(4.9, 52.3), (5.0, 52.3), (5.0, 52.4)]),
+            // out of range
+            get_bounds(&[(4.9, 52.3), (500.0, 52.3), (5.0, 52.4), (4.9, 52.3)]),
+            // not enough points
+            get_bounds(&[(4.9, 52.3), (4.9, 52.3)]),
+        ];
+        for bounds in invalid {
+            let result = get_latest_positions_params(LatestPositionsRequest {
+                bounds: Some(bounds),
+                max_age_seconds: None,
+            });
+            assert!(result.is_err());
+            assert_eq!(result.unwrap_err().code(), tonic::Code::InvalidArgument);
+        }
+
+        ut_info!("(test_get_latest_positions_params_invalid) success");
+    }
+}
@@ -24,6 +24,13 @@ pub struct Cli {
     /// Indicates if we should rebuild the database. If not found, defaults to false
     #[arg(long)]
     pub rebuild_psql: Option<bool>,
+    /// Indicates if we should apply all pending database migrations. If not found, defaults to false
+    #[arg(long)]
+    pub migrate: Option<bool>,
+    /// Indicates if we should only report pending migrations and database schema differences
+    /// without making any changes. If not found, defaults to false
+    #[arg(long)]
+    pub migrate_dry_run: Option<bool>,
 }
 impl Copy for Cli {}
 
@@ -21,6 +21,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Allow options for psql init or and/ or recreation
     // locally: cargo run -- --init-psql true
     let args = Cli::parse();
+    if let Some(true) = args.migrate_dry_run {
+        info!("(main) Found argument [migrate_dry_run]. Checking database schema now...");
+        #[cfg(not(feature = "stub_backends"))]
+        {
+            let report = svc_storage::postgres::migrations::dry_run().await?;
+            info!("(main) Migration dry run report:\n{}", report);
+        }
+        log::logger().flush();
+        return Ok(());
+    }
     if let Some(rebuild_psql) = args.rebuild_psql {
         if rebuild_psql {
             info!("(main) Found argument [rebuild_psql]. Rebuilding now...");
@@ -35,6 +45,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             svc_storage::postgres::init::create_db().await?;
             info!("(main) PSQL Database creation completed.");
         }
+    } else if let Some(migrate) = args.migrate {
+        if migrate {
+            info!("(main) Found argument [migrate]. Applying database migrations now...");
+            #[cfg(not(feature = "stub_backends"))]
+            svc_storage::postgres::migrations::migrate().await?;
+            info!("(main) PSQL Database migration completed.");
+        }
     }
 
     // Start GRPC Server
@@ -0,0 +1,342 @@
+//! Duplicate detection
+//!
+//! Resources providing a [`Deduplication`] store a single record for data which is received multiple times,
+//! for example the same ADS-B message picked up by several ground receivers.
+//! On insert, the data is merged into an existing duplicate record if found, instead of being inserted as a new record.
+//! Each record is stored with the [`DEDUP_BUCKET_COL`] time bucket of its timestamp, which is unique together with the
+//! deduplication key. Duplicates received at the same time are merged as well, as the insert of the last one will
+//! conflict with the duplicate inserted first.
+
+use super::simple_resource::PsqlType;
+use super::{ArrErr, PsqlData, PsqlField};
+use crate::resources::base::{Deduplication, ResourceDefinition};
+
+use deadpool_postgres::Transaction;
+use uuid::Uuid;
+
+/// psql column name of the time bucket, unique together with the deduplication key columns
+pub(crate) const DEDUP_BUCKET_COL: &str = "dedup_bucket";
+
+/// Adds the [`DEDUP_BUCKET_COL`] value to the insert fields and values of the data, if the resource has a [`Deduplication`].
+///
+/// Returns the `ON CONFLICT` clause to be added to the insert query, skipping the insert if a duplicate
+/// with the same key and time bucket exists. Returns an empty [`String`] if the resource has no [`Deduplication`].
+///
+/// # Errors
+///
+/// Returns [`ArrErr`] if no value is inserted for the deduplication time column
+pub(crate) fn add_dedup_bucket<T>(
+    fields: &mut Vec<String>,
+    inserts: &mut Vec<String>,
+) -> Result<String, ArrErr>
+where
+    T: PsqlType,
+{
+    match T::get_deduplication() {
+        Some(deduplication) => {
+            get_dedup_insert_vars(&T::get_psql_table(), &deduplication, fields, inserts)
+        }
+        None => Ok(String::new()),
+    }
+}
+
+/// Inserts the data using the provided insert query, or merges it into an existing duplicate record if found.
+///
+/// The insert query should contain the `ON CONFLICT` clause returned by [`add_dedup_bucket`]. If the insert
+/// is skipped because of a duplicate inserted by a concurrent transaction, the data is merged into that duplicate.
+/// Returns the id of the record and a [`bool`] indicating if the data was merged into an existing record.
+///
+/// # Errors
+///
+/// Returns [`ArrErr`] if the duplicate the insert conflicted with could not be found
+/// Returns [`ArrErr`] if the data could not be merged, see [`merge_duplicate`]
+/// Returns [`ArrErr`] Database Error if the insert failed
+pub(crate) async fn insert_or_merge<T>(
+    transaction: &Transaction<'_>,
+    insert_sql: &str,
+    params: &[&PsqlField],
+    psql_data: &PsqlData,
+) -> Result<(Uuid, bool), ArrErr>
+where
+    T: PsqlType,
+{
+    if let Some(id) = merge_duplicate::<T>(transaction, psql_data).await? {
+        return Ok((id, true));
+    }
+
+    let id_col = T::try_get_id_field()?;
+    if let Some(row) = transaction.query_opt(insert_sql, params).await? {
+        return Ok((row.get(&*id_col), false));
+    }
+
+    // The insert conflicted with a duplicate inserted since the first merge attempt
+    match merge_duplicate::<T>(transaction, psql_data).await? {
+        Some(id) => Ok((id, true)),
+        None => {
+            let error = format!(
+                "Could not find the duplicate entry the insert conflicted with for table [{}].",
+                T::get_psql_table()
+            );
+            psql_error!("(insert_or_merge) {}", error);
+            Err(ArrErr::Error(error))
+        }
+    }
+}
+
+/// Merges the provided data into an existing duplicate record using the resource's [`Deduplication`], if any.
+///
+/// Returns the id of the record the data has been merged into.
+/// Returns [`None`] if no duplicate record was found, in which case the data should be inserted.
+///
+/// # Errors
+///
+/// Returns [`ArrErr`] "No id column configured for table" id_col could not be found
+/// Returns [`ArrErr`] if no value or field definition is found for one of the deduplication columns
+/// Returns [`ArrErr`] Database Error if a query failed
+async fn merge_duplicate<T>(
+    transaction: &Transaction<'_>,
+    psql_data: &PsqlData,
+) -> Result<Option<Uuid>, ArrErr>
+where
+    T: PsqlType,
+{
+    let deduplication = match T::get_deduplication() {
+        Some(deduplication) => deduplication,
+        None => return Ok(None),
+    };
+    let definition = T::get_definition();
+    let id_col = T::try_get_id_field()?;
+
+    // Key columns first, followed by the time and merge columns
+    let cols: Vec<&String> = deduplication
+        .key_cols
+        .iter()
+        .chain([&deduplication.time_col, &deduplication.merge_col])
+        .collect();
+    let mut params: Vec<&PsqlField> = vec![];
+    for col in cols.iter() {
+        match psql_data.get(*col) {
+            Some(value) => params.push(value.as_ref()),
+            None => {
+                let error = format!(
+                    "No value provided for deduplication column [{}] of table [{}].",
+                    col, definition.psql_table
+                );
+                psql_error!("(merge_duplicate) {}", error);
+                return Err(ArrErr::Error(error));
+            }
+        }
+    }
+    let merge_sql = get_merge_duplicate_query(&definition, &id_col, &deduplication)?;
+
+    psql_debug!("(merge_duplicate) [{}].", merge_sql);
+    let row = transaction.query_opt(&merge_sql, &params[..]).await?;
+    let id: Option<Uuid> = row.map(|row| row.get(&*id_col));
+    if let Some(id) = id {
+        psql_info!(
+            "(merge_duplicate) Merged duplicate into existing entry [{}] of table [{}].",
+            id,
+            definition.psql_table
+        );
+    }
+
+    Ok(id)
+}
+
+/// Returns the query used to merge the data into an existing duplicate.
+///
+/// Parameters are the values of the key columns, followed by the values of the time column and merge column.
+/// All parameters are cast to the type of their column.
+///
+/// # Errors
+///
+/// Returns [`ArrErr`] if no field definition is found for one of the deduplication columns
+fn get_merge_duplicate_query(
+    definition: &ResourceDefinition,
+    id_col: &str,
+    deduplication: &Deduplication,
+) -> Result<String, ArrErr> {
+    let get_param = |index: usize, col: &str| -> Result<String, ArrErr> {
+        match definition.fields.get(col) {
+            Some(field) => Ok(format!("${}::{}", index, field.field_type.name())),
+            None => {
+                let error = format!(
+                    "No field definition found for deduplication column [{}] of table [{}].",
+                    col, definition.psql_table
+                );
+                psql_error!("(get_merge_duplicate_query) {}", error);
+                Err(ArrErr::Error(error))
+            }
+        }
+    };
+
+    let mut conditions: Vec<String> = vec![];
+    for (index, col) in deduplication.key_cols.iter().enumerate() {
+        let param = get_param(index + 1, col)?;
+        conditions.push(format!(r#""{}" = {}"#, col, param));
+    }
+    let time = get_param(deduplication.key_cols.len() + 1, &deduplication.time_col)?;
+    let merge = get_param(deduplication.key_cols.len() + 2, &deduplication.merge_col)?;
+
+    let merge_sql = format!(
+        r#"UPDATE "{table}" SET "{time_col}" = LEAST("{time_col}", {time}), "{merge_col}" = ARRAY(SELECT DISTINCT "value" FROM UNNEST("{merge_col}" || {merge}) AS "value" ORDER BY "value") WHERE "{id_col}" = (SELECT "{id_col}" FROM "{table}" WHERE {conditions} AND "{time_col}" BETWEEN {time} - INTERVAL '{tolerance} milliseconds' AND {time} + INTERVAL '{tolerance} milliseconds' ORDER BY "{time_col}" LIMIT 1) RETURNING "{id_col}""#,
+        table = definition.psql_table,
+        id_col = id_col,
+        time_col = deduplication.time_col,
+        merge_col = deduplication.merge_col,
+        time = time,
+        merge = merge,
+        conditions = conditions.join(" AND "),
+        tolerance = deduplication.tolerance_ms
+    );
+
+    Ok(merge_sql)
+}
+
+/// Adds the [`DEDUP_BUCKET_COL`] to the insert fields, with a value computed from the inserted `time_col` value.
+///
+/// Time is divided in buckets of `tolerance_ms` milliseconds, so records with the same bucket are always duplicates.
+/// Returns the `ON CONFLICT` clause skipping the insert if a record with the same key and bucket exists.
+///
+/// # Errors
+///
+/// Returns [`ArrErr`] if no value is inserted for the deduplication time column
+fn get_dedup_insert_vars(
+    table: &str,
+    deduplication: &Deduplication,
+    fields: &mut Vec<String>,
+    inserts: &mut Vec<String>,
+) -> Result<String, ArrErr> {
+    let time_field = format!(r#""{}""#, deduplication.time_col);
+    let time = match fields.iter().position(|field| *field == time_field) {
+        Some(index) => inserts[index].clone(),
+        None => {
+            let error = format!(
+                "No value provided for deduplication column [{}] of table [{}].",
+                deduplication.time_col, table
+            );
+            psql_error!("(get_dedup_insert_vars) {}", error);
+            return Err(ArrErr::Error(error));
+        }
+    };
+    fields.push(format!(r#""{}""#, DEDUP_BUCKET_COL));
+    inserts.push(format!(
+        "FLOOR(EXTRACT(EPOCH FROM {}::TIMESTAMPTZ) * 1000 / {})::INT8",
+        time,
+        deduplication.tolerance_ms.max(1)
+    ));
+
+    let conflict_cols: Vec<String> = deduplication
+        .key_cols
+        .iter()
+        .map(|col| col.as_str())
+        .chain([DEDUP_BUCKET_COL])
+        .map(|col| format!(r#""{}""#, col))
+        .collect();
+    Ok(format!(
+        " ON CONFLICT ({}) DO NOTHING",
+        conflict_cols.join(", ")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::base::FieldDefinition;
+    use std::collections::HashMap;
+    use tokio_postgres::types::Type as PsqlFieldType;
+
+    fn get_definition() -> ResourceDefinition {
+        ResourceDefinition {
+            psql_table: String::from("adsb"),
+            psql_id_cols: vec![String::from("adsb_id")],
+            fields: HashMap::from([
+                (
+                    String::from("icao_address"),
+                    FieldDefinition::new(PsqlFieldType::INT8, true),
+                ),
+                (
+                    String::from("network_timestamp"),
+                    FieldDefinition::new(PsqlFieldType::TIMESTAMPTZ, true),
+                ),
+                (
+                    String::from("payload"),
+                    FieldDefinition::new(PsqlFieldType::BYTEA, true),
+                ),
+                (
+                    String::from("receivers"),
+                    FieldDefinition::new(PsqlFieldType::TEXT_ARRAY, true),
+                ),
+            ]),
+        }
+    }
+
+    fn get_deduplication() -> Deduplication {
+        Deduplication::new(
+            &["icao_address", "payload"],
+            "network_timestamp",
+            "receivers",
+            1000,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_get_merge_duplicate_query() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_merge_duplicate_query) start");
+
+        let merge_sql =
+            get_merge_duplicate_query(&get_definition(), "adsb_id", &get_deduplication()).unwrap();
+        assert_eq!(
+            merge_sql,
+            r#"UPDATE "adsb" SET "network_timestamp" = LEAST("network_timestamp", $3::timestamptz), "receivers" = ARRAY(SELECT DISTINCT "value" FROM UNNEST("receivers" || $4::_text) AS "value" ORDER BY "value") WHERE "adsb_id" = (SELECT "adsb_id" FROM "adsb" WHERE "icao_address" = $1::int8 AND "payload" = $2::bytea AND "network_timestamp" BETWEEN $3::timestamptz - INTERVAL '1000 milliseconds' AND $3::timestamptz + INTERVAL '1000 milliseconds' ORDER BY "network_timestamp" LIMIT 1) RETURNING "adsb_id""#
+        );
+
+        ut_info!("(test_get_merge_duplicate_query) success");
+    }
+
+    #[tokio::test]
+    async fn test_get_merge_duplicate_query_unknown_column() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_merge_duplicate_query_unknown_column) start");
+
+        let deduplication =
+            Deduplication::new(&["unknown"], "network_timestamp", "receivers", 1000);
+        let result = get_merge_duplicate_query(&get_definition(), "adsb_id", &deduplication);
+        assert!(result.is_err());
+
+        ut_info!("(test_get_merge_duplicate_query_unknown_column) success");
+    }
+
+    #[tokio::test]
+    async fn test_get_dedup_insert_vars() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_dedup_insert_vars) start");
+
+        let mut fields = vec![
+            String::from(r#""icao_address""#),
+            String::from(r#""network_timestamp""#),
+            String::from(r#""payload""#),
+        ];
+        let mut inserts = vec![String::from("$1"), String::from("$2"), String::from("$3")];
+        let on_conflict =
+            get_dedup_insert_vars("adsb", &get_deduplication(), &mut fields, &mut inserts).unwrap();
+        assert_eq!(fields[3], r#""dedup_bucket""#);
+        assert_eq!(
+            inserts[3],
+            "FLOOR(EXTRACT(EPOCH FROM $2::TIMESTAMPTZ) * 1000 / 1000)::INT8"
+        );
+        assert_eq!(
+            on_conflict,
+            r#" ON CONFLICT ("icao_address", "payload", "dedup_bucket") DO NOTHING"#
+        );
+
+        // The bucket can't be computed without a time value
+        let mut fields = vec![String::from(r#""icao_address""#)];
+        let mut inserts = vec![String::from("$1")];
+        let result = get_dedup_insert_vars("adsb", &get_deduplication(), &mut fields, &mut inserts);
+        assert!(result.is_err());
+
+        ut_info!("(test_get_dedup_insert_vars) success");
+    }
+}
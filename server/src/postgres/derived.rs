@@ -0,0 +1,37 @@
+//! Derived fields
+//!
+//! Resources can provide a [`DerivedFieldsHook`](crate::resources::base::DerivedFieldsHook) to populate
+//! read only fields derived from the inserted data, for example by decoding a raw payload.
+//! The hook is called after a record has been inserted or updated, using the same transaction.
+
+use super::simple_resource::PsqlType;
+use super::ArrErr;
+
+use deadpool_postgres::Transaction;
+use uuid::Uuid;
+
+/// Populates the derived fields of the record with the provided id using the resource's
+/// [`DerivedFieldsHook`](crate::resources::base::DerivedFieldsHook), if any.
+///
+/// # Errors
+///
+/// Returns [`ArrErr`] if the hook failed to populate the derived fields
+pub(crate) async fn update_derived_fields<T>(
+    transaction: &Transaction<'_>,
+    id: &Uuid,
+) -> Result<(), ArrErr>
+where
+    T: PsqlType,
+{
+    match T::get_derived_fields_hook() {
+        Some(hook) => {
+            psql_debug!(
+                "(update_derived_fields) Updating derived fields for table [{}] id [{}].",
+                T::get_psql_table(),
+                id
+            );
+            hook(transaction, id).await
+        }
+        None => Ok(()),
+    }
+}
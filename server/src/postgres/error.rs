@@ -0,0 +1,197 @@
+//! Maps PostgreSQL errors to typed [`ArrErr`] variants
+
+use super::ArrErr;
+use crate::grpc::server::{ErrorDetails, ErrorReason};
+use tokio_postgres::error::{DbError, SqlState};
+
+/// Returns a typed [`ArrErr`] for the provided [`tokio_postgres::Error`] if its SQLSTATE is known,
+/// or if the connection to the database has been closed.
+///
+/// Returns [`None`] for all other errors.
+pub(crate) fn get_typed_error(err: &tokio_postgres::Error) -> Option<ArrErr> {
+    match err.as_db_error() {
+        Some(db_error) => get_db_error(db_error),
+        None if err.is_closed() => Some(ArrErr::unavailable("Database connection closed.")),
+        None => None,
+    }
+}
+
+/// Converts a [`DbError`] into a typed [`ArrErr`], including the error's SQLSTATE and
+/// table, column and constraint names if provided by the database.
+fn get_db_error(db_error: &DbError) -> Option<ArrErr> {
+    let reason = get_error_reason(db_error.code())?;
+    let details = ErrorDetails {
+        reason: reason as i32,
+        message: db_error.message().to_owned(),
+        sqlstate: Some(db_error.code().code().to_owned()),
+        table: db_error.table().map(String::from),
+        column: db_error.column().map(String::from),
+        constraint: db_error.constraint().map(String::from),
+    };
+    Some(get_arr_err(reason, details))
+}
+
+/// Returns the [`ErrorReason`] matching the provided SQLSTATE, [`None`] if the SQLSTATE
+/// doesn't map to a specific reason.
+pub(crate) fn get_error_reason(code: &SqlState) -> Option<ErrorReason> {
+    let known_states = [
+        (SqlState::NO_DATA_FOUND, ErrorReason::NotFound),
+        (SqlState::UNIQUE_VIOLATION, ErrorReason::UniqueViolation),
+        (
+            SqlState::EXCLUSION_VIOLATION,
+            ErrorReason::ExclusionViolation,
+        ),
+        (
+            SqlState::FOREIGN_KEY_VIOLATION,
+            ErrorReason::ForeignKeyViolation,
+        ),
+        (
+            SqlState::RESTRICT_VIOLATION,
+            ErrorReason::ForeignKeyViolation,
+        ),
+        (SqlState::CHECK_VIOLATION, ErrorReason::CheckViolation),
+        (SqlState::NOT_NULL_VIOLATION, ErrorReason::NotNullViolation),
+        (
+            SqlState::T_R_SERIALIZATION_FAILURE,
+            ErrorReason::SerializationFailure,
+        ),
+        (
+            SqlState::T_R_DEADLOCK_DETECTED,
+            ErrorReason::SerializationFailure,
+        ),
+        (
+            SqlState::LOCK_NOT_AVAILABLE,
+            ErrorReason::SerializationFailure,
+        ),
+        (SqlState::DATATYPE_MISMATCH, ErrorReason::InvalidArgument),
+        (
+            SqlState::INDETERMINATE_DATATYPE,
+            ErrorReason::InvalidArgument,
+        ),
+        (SqlState::ADMIN_SHUTDOWN, ErrorReason::Unavailable),
+        (SqlState::CRASH_SHUTDOWN, ErrorReason::Unavailable),
+        (SqlState::CANNOT_CONNECT_NOW, ErrorReason::Unavailable),
+    ];
+    if let Some((_, reason)) = known_states.iter().find(|(state, _)| state == code) {
+        return Some(*reason);
+    }
+
+    match code.code().get(..2) {
+        // Class 22 — Data Exception
+        Some("22") => Some(ErrorReason::InvalidArgument),
+        // Class 08 — Connection Exception, Class 53 — Insufficient Resources
+        Some("08") | Some("53") => Some(ErrorReason::Unavailable),
+        _ => None,
+    }
+}
+
+/// Returns the [`ArrErr`] variant used for the provided [`ErrorReason`]
+fn get_arr_err(reason: ErrorReason, details: ErrorDetails) -> ArrErr {
+    match reason {
+        ErrorReason::NotFound => ArrErr::NotFound(details),
+        ErrorReason::UniqueViolation | ErrorReason::ExclusionViolation => {
+            ArrErr::AlreadyExists(details)
+        }
+        ErrorReason::ForeignKeyViolation | ErrorReason::InvalidState => {
+            ArrErr::FailedPrecondition(details)
+        }
+        ErrorReason::CheckViolation
+        | ErrorReason::NotNullViolation
+        | ErrorReason::InvalidArgument => ArrErr::InvalidArgument(details),
+        ErrorReason::SerializationFailure => ArrErr::Aborted(details),
+        ErrorReason::Unavailable => ArrErr::Unavailable(details),
+        ErrorReason::Unspecified => ArrErr::Error(details.message),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_get_error_reason() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_error_reason) start");
+
+        let expected = vec![
+            ("P0002", Some(ErrorReason::NotFound)),
+            ("23505", Some(ErrorReason::UniqueViolation)),
+            ("23P01", Some(ErrorReason::ExclusionViolation)),
+            ("23503", Some(ErrorReason::ForeignKeyViolation)),
+            ("23001", Some(ErrorReason::ForeignKeyViolation)),
+            ("23514", Some(ErrorReason::CheckViolation)),
+            ("23502", Some(ErrorReason::NotNullViolation)),
+            ("40001", Some(ErrorReason::SerializationFailure)),
+            ("40P01", Some(ErrorReason::SerializationFailure)),
+            ("22P02", Some(ErrorReason::InvalidArgument)),
+            ("22007", Some(ErrorReason::InvalidArgument)),
+            ("42804", Some(ErrorReason::InvalidArgument)),
+            ("08006", Some(ErrorReason::Unavailable)),
+            ("53300", Some(ErrorReason::Unavailable)),
+            ("57P03", Some(ErrorReason::Unavailable)),
+            ("42P01", None),
+            ("XX000", None),
+        ];
+        for (code, reason) in expected {
+            assert_eq!(get_error_reason(&SqlState::from_code(code)), reason);
+        }
+
+        ut_info!("(test_get_error_reason) success");
+    }
+
+    #[tokio::test]
+    async fn test_get_arr_err() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_arr_err) start");
+
+        let details = |reason: ErrorReason| ErrorDetails {
+            reason: reason as i32,
+            message: String::from("test"),
+            sqlstate: None,
+            table: Some(String::from("vehicle")),
+            column: None,
+            constraint: None,
+        };
+
+        assert!(matches!(
+            get_arr_err(ErrorReason::NotFound, details(ErrorReason::NotFound)),
+            ArrErr::NotFound(_)
+        ));
+        assert!(matches!(
+            get_arr_err(
+                ErrorReason::UniqueViolation,
+                details(ErrorReason::UniqueViolation)
+            ),
+            ArrErr::AlreadyExists(_)
+        ));
+        assert!(matches!(
+            get_arr_err(
+                ErrorReason::ForeignKeyViolation,
+                details(ErrorReason::ForeignKeyViolation)
+            ),
+            ArrErr::FailedPrecondition(_)
+        ));
+        assert!(matches!(
+            get_arr_err(
+                ErrorReason::CheckViolation,
+                details(ErrorReason::CheckViolation)
+            ),
+            ArrErr::InvalidArgument(_)
+        ));
+        assert!(matches!(
+            get_arr_err(
+                ErrorReason::SerializationFailure,
+                details(ErrorReason::SerializationFailure)
+            ),
+            ArrErr::Aborted(_)
+        ));
+        let err = get_arr_err(ErrorReason::Unavailable, details(ErrorReason::Unavailable));
+        assert!(matches!(err, ArrErr::Unavailable(_)));
+        assert_eq!(
+            err.get_details().and_then(|details| details.table.clone()),
+            Some(String::from("vehicle"))
+        );
+
+        ut_info!("(test_get_arr_err) success");
+    }
+}
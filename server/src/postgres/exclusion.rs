@@ -0,0 +1,470 @@
+//! Range exclusion checks
+//!
+//! Not all supported database backends provide range exclusion constraints (`EXCLUDE USING gist`).
+//! The [`RangeExclusion`]s of a resource are therefore checked after a record has been written,
+//! using the same transaction. The transaction will be rolled back if any overlapping record is found.
+//! Transactions are started using the `SERIALIZABLE` isolation level, making sure concurrent
+//! writes claiming the same time range can not both succeed.
+
+use super::simple_resource::PsqlType;
+use super::{get_psql_client, ArrErr};
+use crate::grpc::server::{ValidationError, ValidationResult};
+use crate::resources::base::{RangeClaim, RangeExclusion, Resource, ResourceDefinition, TimeRange};
+
+use chrono::{DateTime, Utc};
+use deadpool_postgres::{Manager, Object, Transaction};
+use tokio_postgres::IsolationLevel;
+use uuid::Uuid;
+
+/// Alias used for the record being checked
+const SELF_ALIAS: &str = "s";
+/// Alias used for the records being checked against
+const OTHER_ALIAS: &str = "o";
+
+/// Starts a new transaction for the provided client.
+///
+/// Uses the `SERIALIZABLE` isolation level if the resource has any [`RangeExclusion`]s,
+/// the database default isolation level otherwise.
+pub(crate) async fn start_transaction<T>(
+    client: &mut Object<Manager>,
+) -> Result<Transaction<'_>, ArrErr>
+where
+    T: Resource,
+{
+    if T::get_range_exclusions().is_empty() {
+        return Ok(client.transaction().await?);
+    }
+    Ok(client
+        .build_transaction()
+        .isolation_level(IsolationLevel::Serializable)
+        .start()
+        .await?)
+}
+
+/// Checks the time ranges of the record with the provided id against all other records
+/// using the [`RangeExclusion`]s of the resource.
+///
+/// A [`ValidationError`] will be added to the provided [`ValidationResult`] for each time range
+/// overlapping with any of the other records, listing the ids of the conflicting records.
+///
+/// # Errors
+///
+/// Returns [`ArrErr`] "No id column configured for table" id_col could not be found
+/// Returns [`ArrErr`] Database Error if database query execution failed
+pub(crate) async fn check_range_exclusions<T>(
+    transaction: &Transaction<'_>,
+    id: &Uuid,
+    validation_result: &mut ValidationResult,
+) -> Result<(), ArrErr>
+where
+    T: PsqlType,
+{
+    let definition = T::get_definition();
+    let id_col = T::try_get_id_field()?;
+    for exclusion in T::get_range_exclusions() {
+        for range in exclusion.ranges.iter() {
+            let query = get_range_exclusion_query(&definition, &id_col, &exclusion, range);
+            psql_debug!("(check_range_exclusions) [{}].", query);
+
+            let rows = transaction.query(&query, &[id]).await?;
+            if rows.is_empty() {
+                continue;
+            }
+            let ids: Vec<String> = rows
+                .iter()
+                .map(|row| row.get::<usize, Uuid>(0).to_string())
+                .collect();
+            let error = format!(
+                "Time range [{} - {}] for [{}] overlaps with existing [{}] record(s): {}",
+                range.start_col,
+                range.end_col,
+                range.key_col,
+                definition.psql_table,
+                ids.join(", ")
+            );
+            psql_info!("(check_range_exclusions) {}", error);
+            validation_result.success = false;
+            validation_result.errors.push(ValidationError {
+                field: range.key_col.clone(),
+                error,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Returns the ids of all records claiming a time range overlapping with the provided
+/// `start` and `end` for the provided `key`, using the provided [`RangeExclusion`].
+///
+/// An `exclude_id` can be provided to ignore a specific record, for example when
+/// checking if an existing record can be moved to a new time range.
+///
+/// # Errors
+///
+/// Returns [`ArrErr`] "No id column configured for table" id_col could not be found
+/// Returns [`ArrErr`] from [`PoolError`](deadpool::managed::PoolError) if no client connection could be returned from the connection [`Pool`](deadpool::managed::Pool)
+/// Returns [`ArrErr`] Database Error if database query execution failed
+pub async fn get_range_conflicts<T>(
+    exclusion: &RangeExclusion,
+    key: &Uuid,
+    start: &DateTime<Utc>,
+    end: &DateTime<Utc>,
+    exclude_id: Option<&Uuid>,
+) -> Result<Vec<Uuid>, ArrErr>
+where
+    T: PsqlType,
+{
+    let definition = T::get_definition();
+    let id_col = T::try_get_id_field()?;
+    let query = get_range_conflicts_query(&definition, &id_col, exclusion);
+    psql_debug!("(get_range_conflicts) [{}].", query);
+
+    let client = get_psql_client().await?;
+    let stmt = client.prepare_cached(&query).await?;
+    let rows = client.query(&stmt, &[key, start, end, &exclude_id]).await?;
+    Ok(rows.iter().map(|row| row.get::<usize, Uuid>(0)).collect())
+}
+
+/// Returns all time ranges claimed for the provided `key` overlapping with the window
+/// between the provided `start` and `end`, using the provided [`RangeExclusion`].
+///
+/// The claims are ordered by their start time. Deleted and ignored records are skipped.
+///
+/// # Errors
+///
+/// Returns [`ArrErr`] "No id column configured for table" id_col could not be found
+/// Returns [`ArrErr`] from [`PoolError`](deadpool::managed::PoolError) if no client connection could be returned from the connection [`Pool`](deadpool::managed::Pool)
+/// Returns [`ArrErr`] Database Error if database query execution failed
+pub async fn get_range_claims<T>(
+    exclusion: &RangeExclusion,
+    key: &Uuid,
+    start: &DateTime<Utc>,
+    end: &DateTime<Utc>,
+) -> Result<Vec<RangeClaim>, ArrErr>
+where
+    T: PsqlType,
+{
+    let definition = T::get_definition();
+    let id_col = T::try_get_id_field()?;
+    let query = get_range_claims_query(&definition, &id_col, exclusion);
+    psql_debug!("(get_range_claims) [{}].", query);
+
+    let client = get_psql_client().await?;
+    let stmt = client.prepare_cached(&query).await?;
+    let rows = client.query(&stmt, &[key, start, end]).await?;
+    Ok(rows
+        .iter()
+        .map(|row| RangeClaim {
+            id: row.get::<usize, Uuid>(0),
+            start: row.get::<usize, DateTime<Utc>>(1),
+            end: row.get::<usize, DateTime<Utc>>(2),
+        })
+        .collect())
+}
+
+/// Returns the query used to find all other records overlapping with the provided
+/// [`TimeRange`] of the record matching id parameter `$1`
+fn get_range_exclusion_query(
+    definition: &ResourceDefinition,
+    id_col: &str,
+    exclusion: &RangeExclusion,
+    range: &TimeRange,
+) -> String {
+    let mut conditions = vec![
+        format!(r#"{}."{}" = $1"#, SELF_ALIAS, id_col),
+        format!(
+            r#"{}."{}" <> {}."{}""#,
+            OTHER_ALIAS, id_col, SELF_ALIAS, id_col
+        ),
+    ];
+    conditions.append(&mut get_ignore_conditions(
+        definition, exclusion, SELF_ALIAS,
+    ));
+    conditions.append(&mut get_ignore_conditions(
+        definition,
+        exclusion,
+        OTHER_ALIAS,
+    ));
+    conditions.push(get_overlap_condition(
+        exclusion,
+        &format!(r#"{}."{}""#, SELF_ALIAS, range.key_col),
+        &format!(r#"{}."{}""#, SELF_ALIAS, range.start_col),
+        &format!(r#"{}."{}""#, SELF_ALIAS, range.end_col),
+    ));
+
+    format!(
+        r#"SELECT DISTINCT {}."{}" FROM "{}" AS {}, "{}" AS {} WHERE {}"#,
+        OTHER_ALIAS,
+        id_col,
+        definition.psql_table,
+        SELF_ALIAS,
+        definition.psql_table,
+        OTHER_ALIAS,
+        conditions.join(" AND ")
+    )
+}
+
+/// Returns the query used to find all records overlapping with the time range provided
+/// as `$2` (start) and `$3` (end) for key `$1`, ignoring the record matching id `$4` if not `NULL`
+fn get_range_conflicts_query(
+    definition: &ResourceDefinition,
+    id_col: &str,
+    exclusion: &RangeExclusion,
+) -> String {
+    let mut conditions = vec![format!(
+        r#"($4::UUID IS NULL OR {}."{}" <> $4::UUID)"#,
+        OTHER_ALIAS, id_col
+    )];
+    conditions.append(&mut get_ignore_conditions(
+        definition,
+        exclusion,
+        OTHER_ALIAS,
+    ));
+    conditions.push(get_overlap_condition(
+        exclusion,
+        "$1::UUID",
+        "$2::TIMESTAMPTZ",
+        "$3::TIMESTAMPTZ",
+    ));
+
+    format!(
+        r#"SELECT DISTINCT {}."{}" FROM "{}" AS {} WHERE {}"#,
+        OTHER_ALIAS,
+        id_col,
+        definition.psql_table,
+        OTHER_ALIAS,
+        conditions.join(" AND ")
+    )
+}
+
+/// Returns the query used to list the time ranges claimed for key `$1` overlapping with the window
+/// provided as `$2` (start) and `$3` (end), one `SELECT` per [`TimeRange`] of the exclusion
+fn get_range_claims_query(
+    definition: &ResourceDefinition,
+    id_col: &str,
+    exclusion: &RangeExclusion,
+) -> String {
+    let selects: Vec<String> = exclusion
+        .ranges
+        .iter()
+        .map(|range| {
+            let mut conditions = get_ignore_conditions(definition, exclusion, OTHER_ALIAS);
+            conditions.push(format!(
+                r#"{}."{}" = $1::UUID AND {}."{}" < $3::TIMESTAMPTZ AND {}."{}" > $2::TIMESTAMPTZ"#,
+                OTHER_ALIAS,
+                range.key_col,
+                OTHER_ALIAS,
+                range.start_col,
+                OTHER_ALIAS,
+                range.end_col
+            ));
+            format!(
+                r#"SELECT {}."{}" AS "id", {}."{}" AS "start", {}."{}" AS "end" FROM "{}" AS {} WHERE {}"#,
+                OTHER_ALIAS,
+                id_col,
+                OTHER_ALIAS,
+                range.start_col,
+                OTHER_ALIAS,
+                range.end_col,
+                definition.psql_table,
+                OTHER_ALIAS,
+                conditions.join(" AND ")
+            )
+        })
+        .collect();
+
+    format!(r#"{} ORDER BY "start""#, selects.join(" UNION ALL "))
+}
+
+/// Returns the conditions used to skip deleted and ignored records for the provided table alias
+fn get_ignore_conditions(
+    definition: &ResourceDefinition,
+    exclusion: &RangeExclusion,
+    alias: &str,
+) -> Vec<String> {
+    let mut conditions = vec![];
+    if definition.has_field("deleted_at") {
+        conditions.push(format!(r#"{}."deleted_at" IS NULL"#, alias));
+    }
+    for (col, values) in exclusion.ignore.iter() {
+        if values.is_empty() {
+            continue;
+        }
+        let values: Vec<String> = values
+            .iter()
+            .map(|value| format!("'{}'", value.replace('\'', "''")))
+            .collect();
+        conditions.push(format!(
+            r#"{}."{}" NOT IN ({})"#,
+            alias,
+            col,
+            values.join(", ")
+        ));
+    }
+    conditions
+}
+
+/// Returns the condition matching any of the exclusion's time ranges of the other record
+/// overlapping with the provided key, start and end expressions
+fn get_overlap_condition(exclusion: &RangeExclusion, key: &str, start: &str, end: &str) -> String {
+    let ranges: Vec<String> = exclusion
+        .ranges
+        .iter()
+        .map(|range| {
+            format!(
+                r#"({}."{}" = {} AND {}."{}" < {} AND {}."{}" > {})"#,
+                OTHER_ALIAS,
+                range.key_col,
+                key,
+                OTHER_ALIAS,
+                range.start_col,
+                end,
+                OTHER_ALIAS,
+                range.end_col,
+                start
+            )
+        })
+        .collect();
+    format!("({})", ranges.join(" OR "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::base::FieldDefinition;
+    use std::collections::HashMap;
+    use tokio_postgres::types::Type as PsqlFieldType;
+
+    fn get_definition() -> ResourceDefinition {
+        ResourceDefinition {
+            psql_table: String::from("booking"),
+            psql_id_cols: vec![String::from("booking_id")],
+            fields: HashMap::from([
+                (
+                    "pad_id".to_string(),
+                    FieldDefinition::new(PsqlFieldType::UUID, true),
+                ),
+                (
+                    "deleted_at".to_string(),
+                    FieldDefinition::new_internal(PsqlFieldType::TIMESTAMPTZ, false),
+                ),
+            ]),
+        }
+    }
+
+    fn get_exclusion() -> RangeExclusion {
+        RangeExclusion {
+            ranges: vec![
+                TimeRange::new("origin_pad_id", "origin_start", "origin_end"),
+                TimeRange::new("target_pad_id", "target_start", "target_end"),
+            ],
+            ignore: vec![(String::from("status"), vec![String::from("CANCELLED")])],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_overlap_condition() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_overlap_condition) start");
+
+        let condition = get_overlap_condition(&get_exclusion(), "$1", "$2", "$3");
+        assert_eq!(
+            condition,
+            r#"((o."origin_pad_id" = $1 AND o."origin_start" < $3 AND o."origin_end" > $2) OR (o."target_pad_id" = $1 AND o."target_start" < $3 AND o."target_end" > $2))"#
+        );
+
+        ut_info!("(test_get_overlap_condition) success");
+    }
+
+    #[tokio::test]
+    async fn test_get_ignore_conditions() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_ignore_conditions) start");
+
+        let mut exclusion = get_exclusion();
+        exclusion
+            .ignore
+            .push((String::from("note"), vec![String::from("it's")]));
+        exclusion.ignore.push((String::from("empty"), vec![]));
+        assert_eq!(
+            get_ignore_conditions(&get_definition(), &exclusion, "o"),
+            vec![
+                r#"o."deleted_at" IS NULL"#.to_owned(),
+                r#"o."status" NOT IN ('CANCELLED')"#.to_owned(),
+                r#"o."note" NOT IN ('it''s')"#.to_owned(),
+            ]
+        );
+
+        ut_info!("(test_get_ignore_conditions) success");
+    }
+
+    #[tokio::test]
+    async fn test_get_range_exclusion_query() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_range_exclusion_query) start");
+
+        let exclusion = get_exclusion();
+        let query = get_range_exclusion_query(
+            &get_definition(),
+            "booking_id",
+            &exclusion,
+            &exclusion.ranges[1],
+        );
+        assert_eq!(
+            query,
+            concat!(
+                r#"SELECT DISTINCT o."booking_id" FROM "booking" AS s, "booking" AS o WHERE "#,
+                r#"s."booking_id" = $1 AND o."booking_id" <> s."booking_id" AND "#,
+                r#"s."deleted_at" IS NULL AND s."status" NOT IN ('CANCELLED') AND "#,
+                r#"o."deleted_at" IS NULL AND o."status" NOT IN ('CANCELLED') AND "#,
+                r#"((o."origin_pad_id" = s."target_pad_id" AND o."origin_start" < s."target_end" AND o."origin_end" > s."target_start") OR "#,
+                r#"(o."target_pad_id" = s."target_pad_id" AND o."target_start" < s."target_end" AND o."target_end" > s."target_start"))"#
+            )
+        );
+
+        ut_info!("(test_get_range_exclusion_query) success");
+    }
+
+    #[tokio::test]
+    async fn test_get_range_conflicts_query() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_range_conflicts_query) start");
+
+        let query = get_range_conflicts_query(&get_definition(), "booking_id", &get_exclusion());
+        assert_eq!(
+            query,
+            concat!(
+                r#"SELECT DISTINCT o."booking_id" FROM "booking" AS o WHERE "#,
+                r#"($4::UUID IS NULL OR o."booking_id" <> $4::UUID) AND "#,
+                r#"o."deleted_at" IS NULL AND o."status" NOT IN ('CANCELLED') AND "#,
+                r#"((o."origin_pad_id" = $1::UUID AND o."origin_start" < $3::TIMESTAMPTZ AND o."origin_end" > $2::TIMESTAMPTZ) OR "#,
+                r#"(o."target_pad_id" = $1::UUID AND o."target_start" < $3::TIMESTAMPTZ AND o."target_end" > $2::TIMESTAMPTZ))"#
+            )
+        );
+
+        ut_info!("(test_get_range_conflicts_query) success");
+    }
+
+    #[tokio::test]
+    async fn test_get_range_claims_query() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_range_claims_query) start");
+
+        let query = get_range_claims_query(&get_definition(), "booking_id", &get_exclusion());
+        assert_eq!(
+            query,
+            concat!(
+                r#"SELECT o."booking_id" AS "id", o."origin_start" AS "start", o."origin_end" AS "end" FROM "booking" AS o WHERE "#,
+                r#"o."deleted_at" IS NULL AND o."status" NOT IN ('CANCELLED') AND "#,
+                r#"o."origin_pad_id" = $1::UUID AND o."origin_start" < $3::TIMESTAMPTZ AND o."origin_end" > $2::TIMESTAMPTZ"#,
+                r#" UNION ALL "#,
+                r#"SELECT o."booking_id" AS "id", o."target_start" AS "start", o."target_end" AS "end" FROM "booking" AS o WHERE "#,
+                r#"o."deleted_at" IS NULL AND o."status" NOT IN ('CANCELLED') AND "#,
+                r#"o."target_pad_id" = $1::UUID AND o."target_start" < $3::TIMESTAMPTZ AND o."target_end" > $2::TIMESTAMPTZ"#,
+                r#" ORDER BY "start""#
+            )
+        );
+
+        ut_info!("(test_get_range_claims_query) success");
+    }
+}
@@ -3,7 +3,13 @@
 use std::collections::HashMap;
 
 use super::linked_resource::PsqlType as LinkedPsqlType;
+use super::migrations::{
+    get_drop_migrations_table_query, get_migrations, init_migrations_table, record_migrations,
+};
+use super::postgis::SRID;
 use super::simple_resource::PsqlType as SimplePsqlType;
+use super::transition::{get_drop_status_transition_queries, get_status_transition_queries};
+use super::watch::{get_change_log_queries, get_drop_change_log_queries};
 use super::{get_psql_client, ArrErr, PsqlFieldType};
 use crate::grpc::server::*;
 use crate::resources::{
@@ -15,6 +21,8 @@ use crate::resources::{
 /// This function makes sure the tables will be created in the correct order
 pub async fn create_db() -> Result<(), ArrErr> {
     psql_info!("(create_db) Creating database tables.");
+    execute_queries("create_db", get_change_log_queries()).await?;
+    execute_queries("create_db", get_status_transition_queries()).await?;
     ResourceObject::<group::Data>::init_table().await?;
     ResourceObject::<user::Data>::init_table().await?;
     ResourceObject::<user_group::Data>::init_table().await?;
@@ -22,6 +30,7 @@ pub async fn create_db() -> Result<(), ArrErr> {
     ResourceObject::<vertiport_group::Data>::init_table().await?;
     ResourceObject::<vertipad::Data>::init_table().await?;
     ResourceObject::<vertipad_group::Data>::init_table().await?;
+    ResourceObject::<vehicle_model::Data>::init_table().await?;
     ResourceObject::<vehicle::Data>::init_table().await?;
     ResourceObject::<vehicle_group::Data>::init_table().await?;
     ResourceObject::<pilot::Data>::init_table().await?;
@@ -33,6 +42,9 @@ pub async fn create_db() -> Result<(), ArrErr> {
     ResourceObject::<flight_plan_parcel::Data>::init_table().await?;
     ResourceObject::<scanner::Data>::init_table().await?;
     ResourceObject::<parcel_scan::Data>::init_table().await?;
+    // A freshly created database contains all known migrations
+    init_migrations_table().await?;
+    record_migrations(&get_migrations()).await?;
     Ok(())
 }
 
@@ -52,6 +64,7 @@ pub async fn drop_db() -> Result<(), ArrErr> {
     ResourceObject::<pilot::Data>::drop_table().await?;
     ResourceObject::<vehicle_group::Data>::drop_table().await?;
     ResourceObject::<vehicle::Data>::drop_table().await?;
+    ResourceObject::<vehicle_model::Data>::drop_table().await?;
     ResourceObject::<vertipad_group::Data>::drop_table().await?;
     ResourceObject::<vertipad::Data>::drop_table().await?;
     ResourceObject::<vertiport_group::Data>::drop_table().await?;
@@ -59,9 +72,31 @@ pub async fn drop_db() -> Result<(), ArrErr> {
     ResourceObject::<user_group::Data>::drop_table().await?;
     ResourceObject::<user::Data>::drop_table().await?;
     ResourceObject::<group::Data>::drop_table().await?;
+    execute_queries("drop_db", get_drop_status_transition_queries()).await?;
+    execute_queries("drop_db", get_drop_change_log_queries()).await?;
+    execute_queries("drop_db", vec![get_drop_migrations_table_query()]).await?;
     Ok(())
 }
 
+/// Executes the provided queries using a single transaction
+///
+/// # Errors
+///
+/// Returns [`ArrErr`] Database Error if any of the queries failed, after rolling back the transaction
+async fn execute_queries(caller: &str, queries: Vec<String>) -> Result<(), ArrErr> {
+    let mut client = get_psql_client().await?;
+    let transaction = client.transaction().await?;
+    for query in queries {
+        psql_debug!("({}) [{}].", caller, query);
+        if let Err(e) = transaction.execute(&query, &[]).await {
+            psql_error!("({}) Failed to execute query: {}", caller, e);
+            transaction.rollback().await?;
+            return Err(e.into());
+        }
+    }
+    transaction.commit().await.map_err(ArrErr::from)
+}
+
 /// Recreate the database by dropping all tables first (if they exist) and recreating them again
 pub async fn recreate_db() -> Result<(), ArrErr> {
     psql_warn!("(recreate_db) Re-creating database tables.");
@@ -238,9 +273,11 @@ fn get_create_table_fields_sql(fields: &HashMap<String, FieldDefinition>) -> Vec
             PsqlFieldType::INT8 => field_sql.push_str(" BIGINT"),
             PsqlFieldType::NUMERIC => field_sql.push_str(" DOUBLE PRECISION"),
             PsqlFieldType::BYTEA => field_sql.push_str(" BYTEA"),
-            PsqlFieldType::PATH => field_sql.push_str(" GEOMETRY"),
-            PsqlFieldType::POINT => field_sql.push_str(" GEOMETRY"),
-            PsqlFieldType::POLYGON => field_sql.push_str(" GEOMETRY"),
+            PsqlFieldType::TEXT_ARRAY => field_sql.push_str(" TEXT[]"),
+            // Any shape is allowed, points and paths may have an altitude (Z)
+            PsqlFieldType::PATH | PsqlFieldType::POINT | PsqlFieldType::POLYGON => {
+                field_sql.push_str(&format!(" GEOMETRY(GEOMETRY, {})", SRID))
+            }
             _ => field_sql.push_str(&format!(" {}", field.field_type.name().to_uppercase())),
         }
 
@@ -208,8 +208,12 @@ where
         for field in params.iter() {
             ref_params.push(field.as_ref());
         }
-        match client.query_one(&stmt, &ref_params[..]).await {
-            Ok(row) => Ok(row),
+        match client.query_opt(&stmt, &ref_params[..]).await {
+            Ok(Some(row)) => Ok(row),
+            Ok(None) => Err(ArrErr::not_found(format!(
+                "No [{}] found for specified uuids: {:?}",
+                definition.psql_table, ids
+            ))),
             Err(e) => Err(e.into()),
         }
     }
@@ -0,0 +1,937 @@
+//! Versioned database schema migrations
+//!
+//! Applied migrations are recorded in the [`SCHEMA_MIGRATIONS_TABLE`] together with a checksum
+//! of their queries. Migrations are applied in order of their version and should never be changed
+//! once released, a new migration should be added instead.
+//!
+//! All migrations consist of literal SQL, so their checksums never depend on the current resource definitions.
+//!
+//! The [`BASELINE_VERSION`] represents the schema as it existed before migrations were introduced.
+//! An empty database will be created by applying all migrations, starting with the baseline.
+//! Databases created before migrations were introduced will be marked as baseline, after which
+//! all other migrations will be applied.
+
+use super::{get_psql_client, ArrErr, PsqlFieldType};
+use crate::grpc::server::*;
+use crate::resources::base::{Resource, ResourceDefinition, ResourceObject};
+
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+
+/// Name of the table containing all applied migrations
+pub const SCHEMA_MIGRATIONS_TABLE: &str = "schema_migrations";
+/// Version of the baseline migration, representing the schema as it existed before migrations were introduced
+pub const BASELINE_VERSION: i64 = 1;
+
+/// A versioned schema migration
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Migration {
+    /// unique version of the migration, migrations will be applied in order of their version
+    pub version: i64,
+    /// short description of the migration
+    pub name: &'static str,
+    /// queries to execute, in order
+    pub queries: Vec<String>,
+}
+
+impl Migration {
+    /// Returns the hex encoded SHA-256 checksum of the migration's queries
+    pub fn checksum(&self) -> String {
+        openssl::sha::sha256(self.queries.join(";\n").as_bytes())
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+}
+
+/// Migration as recorded in the [`SCHEMA_MIGRATIONS_TABLE`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppliedMigration {
+    /// version of the applied migration
+    pub version: i64,
+    /// name of the applied migration
+    pub name: String,
+    /// checksum of the migration's queries at the time it was applied
+    pub checksum: String,
+}
+
+/// Returns all known migrations, ordered by version
+pub fn get_migrations() -> Vec<Migration> {
+    vec![
+        Migration {
+            version: BASELINE_VERSION,
+            name: "baseline",
+            queries: get_baseline_queries(),
+        },
+        Migration {
+            version: 2,
+            name: "change_log",
+            queries: vec![
+                r#"CREATE TABLE IF NOT EXISTS "change_log" ("sequence" BIGSERIAL PRIMARY KEY, "table_name" TEXT NOT NULL, "operation" TEXT NOT NULL, "record_id" UUID NOT NULL, "created_at" TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW())"#.to_owned(),
+                r#"CREATE INDEX IF NOT EXISTS "change_log_table_name_sequence_idx" ON "change_log" ("table_name", "sequence")"#.to_owned(),
+                r#"CREATE INDEX IF NOT EXISTS "change_log_created_at_idx" ON "change_log" ("created_at")"#.to_owned(),
+            ],
+        },
+        Migration {
+            version: 3,
+            name: "flight_plan_timeslot_indices",
+            queries: vec![
+                r#"CREATE INDEX IF NOT EXISTS flight_plan_origin_timeslot_idx ON "flight_plan" ("origin_vertipad_id", "origin_timeslot_start", "origin_timeslot_end")"#.to_owned(),
+                r#"CREATE INDEX IF NOT EXISTS flight_plan_target_timeslot_idx ON "flight_plan" ("target_vertipad_id", "target_timeslot_start", "target_timeslot_end")"#.to_owned(),
+            ],
+        },
+        Migration {
+            version: 4,
+            name: "flight_plan_asset_timeslot_indices",
+            queries: vec![
+                r#"CREATE INDEX IF NOT EXISTS flight_plan_vehicle_timeslot_idx ON "flight_plan" ("vehicle_id", "origin_timeslot_start", "target_timeslot_end")"#.to_owned(),
+                r#"CREATE INDEX IF NOT EXISTS flight_plan_pilot_timeslot_idx ON "flight_plan" ("pilot_id", "origin_timeslot_start", "target_timeslot_end")"#.to_owned(),
+            ],
+        },
+        Migration {
+            version: 5,
+            name: "status_transition",
+            queries: vec![
+                r#"CREATE TABLE IF NOT EXISTS "status_transition" ("status_transition_id" UUID PRIMARY KEY DEFAULT gen_random_uuid(), "table_name" TEXT NOT NULL, "record_id" UUID NOT NULL, "from_status" TEXT NOT NULL, "to_status" TEXT NOT NULL, "changed_by" UUID NULL, "created_at" TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW())"#.to_owned(),
+                r#"CREATE INDEX IF NOT EXISTS "status_transition_table_name_record_id_idx" ON "status_transition" ("table_name", "record_id", "created_at")"#.to_owned(),
+            ],
+        },
+        // Geometries used to be stored without SRID. Existing columns keep their type, as CockroachDB
+        // can't alter column types inside a transaction, but all values are assigned the WGS 84 SRID.
+        Migration {
+            version: 6,
+            name: "geometry_srid",
+            queries: vec![
+                r#"UPDATE "vertiport" SET "geo_location" = ST_SetSRID("geo_location", 4326) WHERE ST_SRID("geo_location") = 0"#.to_owned(),
+                r#"UPDATE "vertipad" SET "geo_location" = ST_SetSRID("geo_location", 4326) WHERE ST_SRID("geo_location") = 0"#.to_owned(),
+                r#"UPDATE "flight_plan" SET "path" = ST_SetSRID("path", 4326) WHERE ST_SRID("path") = 0"#.to_owned(),
+                r#"UPDATE "parcel_scan" SET "geo_location" = ST_SetSRID("geo_location", 4326) WHERE ST_SRID("geo_location") = 0"#.to_owned(),
+            ],
+        },
+        Migration {
+            version: 7,
+            name: "adsb_decoded_fields",
+            queries: vec![
+                r#"ALTER TABLE "adsb" ADD COLUMN IF NOT EXISTS "position" GEOMETRY(GEOMETRY, 4326)"#.to_owned(),
+                r#"ALTER TABLE "adsb" ADD COLUMN IF NOT EXISTS "baro_altitude" BIGINT"#.to_owned(),
+                r#"ALTER TABLE "adsb" ADD COLUMN IF NOT EXISTS "ground_speed" FLOAT8"#.to_owned(),
+                r#"ALTER TABLE "adsb" ADD COLUMN IF NOT EXISTS "heading" FLOAT8"#.to_owned(),
+                r#"ALTER TABLE "adsb" ADD COLUMN IF NOT EXISTS "callsign" TEXT"#.to_owned(),
+            ],
+        },
+        // Telemetry is searched and expired by "network_timestamp", see [`retention`](super::retention)
+        Migration {
+            version: 8,
+            name: "adsb_network_timestamp_index",
+            queries: vec![
+                r#"CREATE INDEX IF NOT EXISTS adsb_network_timestamp_idx ON "adsb" ("network_timestamp")"#.to_owned(),
+            ],
+        },
+        Migration {
+            version: 9,
+            name: "adsb_latest_positions_index",
+            queries: vec![
+                r#"CREATE INDEX IF NOT EXISTS adsb_icao_address_network_timestamp_idx ON "adsb" ("icao_address", "network_timestamp" DESC)"#.to_owned(),
+            ],
+        },
+        Migration {
+            version: 10,
+            name: "adsb_receivers",
+            queries: vec![
+                r#"ALTER TABLE "adsb" ADD COLUMN IF NOT EXISTS "receivers" TEXT[] DEFAULT '{}' NOT NULL"#.to_owned(),
+            ],
+        },
+        // Vehicles already refer to a vehicle model, placeholder models are created for
+        // the existing references so the foreign key can be added. Their manufacturer and
+        // limits are unknown and left empty, to be provided by the operator.
+        Migration {
+            version: 11,
+            name: "vehicle_model",
+            queries: vec![
+                r#"CREATE TABLE IF NOT EXISTS "vehicle_model" ("vehicle_model_id" UUID DEFAULT uuid_generate_v4() PRIMARY KEY, "manufacturer_id" UUID, "model_name" TEXT NOT NULL, "type" TEXT DEFAULT 'VTOL_CARGO' NOT NULL, "max_payload_kg" FLOAT8, "max_range_km" FLOAT8, "created_at" TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL, "updated_at" TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL, "deleted_at" TIMESTAMP WITH TIME ZONE)"#.to_owned(),
+                r#"CREATE UNIQUE INDEX IF NOT EXISTS vehicle_model_manufacturer_model_name_idx ON "vehicle_model" ("manufacturer_id", "model_name")"#.to_owned(),
+                r#"INSERT INTO "vehicle_model" ("vehicle_model_id", "model_name") SELECT DISTINCT "vehicle_model_id", "vehicle_model_id"::TEXT FROM "vehicle" ON CONFLICT DO NOTHING"#.to_owned(),
+                r#"ALTER TABLE vehicle ADD CONSTRAINT fk_vehicle_model_id FOREIGN KEY(vehicle_model_id) REFERENCES vehicle_model(vehicle_model_id)"#.to_owned(),
+            ],
+        },
+        // The column is added in its own migration (transaction), before creating the index using it.
+        Migration {
+            version: 12,
+            name: "adsb_dedup_bucket",
+            queries: vec![
+                r#"ALTER TABLE "adsb" ADD COLUMN IF NOT EXISTS "dedup_bucket" BIGINT"#.to_owned(),
+            ],
+        },
+        Migration {
+            version: 13,
+            name: "adsb_dedup_index",
+            queries: vec![
+                r#"CREATE UNIQUE INDEX IF NOT EXISTS adsb_dedup_idx ON "adsb" ("icao_address", "payload", "dedup_bucket")"#.to_owned(),
+            ],
+        },
+        // Change log sequence numbers are taken from a single counter row, locked until commit,
+        // so they become visible in commit order. The counter continues after the existing changes.
+        Migration {
+            version: 14,
+            name: "change_log_sequence",
+            queries: vec![
+                r#"CREATE TABLE IF NOT EXISTS "change_log_sequence" ("id" INT8 PRIMARY KEY, "value" INT8 NOT NULL)"#.to_owned(),
+                r#"INSERT INTO "change_log_sequence" ("id", "value") SELECT 1, COALESCE(MAX("sequence"), 0) FROM "change_log" ON CONFLICT DO NOTHING"#.to_owned(),
+            ],
+        },
+    ]
+}
+
+/// Returns the queries creating the schema as it existed before migrations were introduced
+fn get_baseline_queries() -> Vec<String> {
+    vec![
+        r#"CREATE TABLE IF NOT EXISTS "group" ("group_id" UUID DEFAULT uuid_generate_v4() PRIMARY KEY, "name" TEXT NOT NULL, "group_type" TEXT DEFAULT 'DISPLAY' NOT NULL, "description" TEXT NOT NULL, "parent_group_id" UUID, "created_at" TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL, "updated_at" TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL, "deleted_at" TIMESTAMP WITH TIME ZONE)"#.to_owned(),
+        r#"CREATE INDEX IF NOT EXISTS group_group_type_idx ON "group" ("group_type")"#.to_owned(),
+        r#"CREATE TABLE IF NOT EXISTS "user" ("user_id" UUID DEFAULT uuid_generate_v4() PRIMARY KEY, "display_name" TEXT NOT NULL, "email" TEXT NOT NULL, "auth_method" TEXT NOT NULL, "last_login" TIMESTAMP WITH TIME ZONE, "created_at" TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL, "updated_at" TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL, "deleted_at" TIMESTAMP WITH TIME ZONE)"#.to_owned(),
+        r#"CREATE TABLE IF NOT EXISTS "user_group" ("user_id" UUID NOT NULL, "group_id" UUID NOT NULL, PRIMARY KEY("user_id", "group_id") )"#.to_owned(),
+        r#"CREATE TABLE IF NOT EXISTS "vertiport" ("vertiport_id" UUID DEFAULT uuid_generate_v4() PRIMARY KEY, "name" TEXT NOT NULL, "description" TEXT NOT NULL, "geo_location" GEOMETRY NOT NULL, "schedule" TEXT, "created_at" TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL, "updated_at" TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL, "deleted_at" TIMESTAMP WITH TIME ZONE)"#.to_owned(),
+        r#"CREATE INDEX IF NOT EXISTS vertiport_geo_location_idx ON vertiport USING GIST(geo_location)"#.to_owned(),
+        r#"CREATE TABLE IF NOT EXISTS "vertiport_group" ("vertiport_id" UUID NOT NULL, "group_id" UUID NOT NULL, PRIMARY KEY("vertiport_id", "group_id") )"#.to_owned(),
+        r#"CREATE TABLE IF NOT EXISTS "vertipad" ("vertipad_id" UUID DEFAULT uuid_generate_v4() PRIMARY KEY, "vertiport_id" UUID NOT NULL, "name" TEXT NOT NULL, "geo_location" GEOMETRY NOT NULL, "schedule" TEXT, "enabled" BOOL DEFAULT true NOT NULL, "occupied" BOOL DEFAULT false NOT NULL, "created_at" TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL, "updated_at" TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL, "deleted_at" TIMESTAMP WITH TIME ZONE)"#.to_owned(),
+        r#"ALTER TABLE vertipad ADD CONSTRAINT fk_vertiport_id FOREIGN KEY(vertiport_id) REFERENCES vertiport(vertiport_id)"#.to_owned(),
+        r#"CREATE INDEX IF NOT EXISTS vertipad_occupied_idx ON vertipad(occupied)"#.to_owned(),
+        r#"CREATE INDEX IF NOT EXISTS vertipad_geo_location_idx ON vertipad USING GIST(geo_location)"#.to_owned(),
+        r#"CREATE TABLE IF NOT EXISTS "vertipad_group" ("vertipad_id" UUID NOT NULL, "group_id" UUID NOT NULL, PRIMARY KEY("vertipad_id", "group_id") )"#.to_owned(),
+        r#"CREATE TABLE IF NOT EXISTS "vehicle" ("vehicle_id" UUID DEFAULT uuid_generate_v4() PRIMARY KEY, "vehicle_model_id" UUID NOT NULL, "serial_number" TEXT NOT NULL, "registration_number" TEXT NOT NULL, "description" TEXT, "asset_group_id" UUID, "schedule" TEXT, "last_maintenance" TIMESTAMP WITH TIME ZONE, "next_maintenance" TIMESTAMP WITH TIME ZONE, "hangar_id" UUID, "hangar_bay_id" UUID, "created_at" TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL, "updated_at" TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL, "deleted_at" TIMESTAMP WITH TIME ZONE)"#.to_owned(),
+        r#"ALTER TABLE vehicle ADD CONSTRAINT fk_hangar_id FOREIGN KEY(hangar_id) REFERENCES vertiport(vertiport_id)"#.to_owned(),
+        r#"ALTER TABLE vehicle ADD CONSTRAINT fk_hangar_bay_id FOREIGN KEY(hangar_bay_id) REFERENCES vertipad(vertipad_id)"#.to_owned(),
+        r#"CREATE TABLE IF NOT EXISTS "vehicle_group" ("vehicle_id" UUID NOT NULL, "group_id" UUID NOT NULL, PRIMARY KEY("vehicle_id", "group_id") )"#.to_owned(),
+        r#"CREATE TABLE IF NOT EXISTS "pilot" ("pilot_id" UUID DEFAULT uuid_generate_v4() PRIMARY KEY, "first_name" TEXT NOT NULL, "last_name" TEXT NOT NULL, "created_at" TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL, "updated_at" TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL, "deleted_at" TIMESTAMP WITH TIME ZONE)"#.to_owned(),
+        r#"CREATE TABLE IF NOT EXISTS "adsb" ("adsb_id" UUID DEFAULT uuid_generate_v4() PRIMARY KEY, "icao_address" BIGINT NOT NULL, "message_type" BIGINT NOT NULL, "network_timestamp" TIMESTAMP WITH TIME ZONE NOT NULL, "payload" BYTEA NOT NULL)"#.to_owned(),
+        r#"CREATE TABLE IF NOT EXISTS "flight_plan" ("flight_plan_id" UUID DEFAULT uuid_generate_v4() PRIMARY KEY, "pilot_id" UUID NOT NULL, "vehicle_id" UUID NOT NULL, "path" GEOMETRY NOT NULL, "weather_conditions" TEXT, "origin_vertipad_id" UUID NOT NULL, "target_vertipad_id" UUID NOT NULL, "origin_timeslot_start" TIMESTAMP WITH TIME ZONE NOT NULL, "origin_timeslot_end" TIMESTAMP WITH TIME ZONE NOT NULL, "target_timeslot_start" TIMESTAMP WITH TIME ZONE NOT NULL, "target_timeslot_end" TIMESTAMP WITH TIME ZONE NOT NULL, "actual_departure_time" TIMESTAMP WITH TIME ZONE, "actual_arrival_time" TIMESTAMP WITH TIME ZONE, "flight_release_approval" TIMESTAMP WITH TIME ZONE, "flight_plan_submitted" TIMESTAMP WITH TIME ZONE, "carrier_ack" TIMESTAMP WITH TIME ZONE, "approved_by" UUID, "flight_status" TEXT DEFAULT 'DRAFT' NOT NULL, "flight_priority" TEXT DEFAULT 'LOW' NOT NULL, "created_at" TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL, "updated_at" TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL, "deleted_at" TIMESTAMP WITH TIME ZONE)"#.to_owned(),
+        r#"ALTER TABLE "flight_plan" ADD CONSTRAINT fk_origin_vertipad_id FOREIGN KEY("origin_vertipad_id") REFERENCES "vertipad"("vertipad_id")"#.to_owned(),
+        r#"ALTER TABLE "flight_plan" ADD CONSTRAINT fk_target_vertipad_id FOREIGN KEY("target_vertipad_id") REFERENCES "vertipad"("vertipad_id")"#.to_owned(),
+        r#"CREATE INDEX IF NOT EXISTS flight_plan_flight_status_idx ON "flight_plan" ("flight_status")"#.to_owned(),
+        r#"CREATE INDEX IF NOT EXISTS flight_plan_flight_priority_idx ON "flight_plan" ("flight_priority")"#.to_owned(),
+        r#"CREATE TABLE IF NOT EXISTS "itinerary" ("itinerary_id" UUID DEFAULT uuid_generate_v4() PRIMARY KEY, "user_id" UUID NOT NULL, "status" TEXT DEFAULT 'ACTIVE' NOT NULL)"#.to_owned(),
+        r#"ALTER TABLE "itinerary" ADD CONSTRAINT fk_user_id FOREIGN KEY("user_id") REFERENCES "user"("user_id")"#.to_owned(),
+        r#"CREATE TABLE IF NOT EXISTS "itinerary_flight_plan" ("itinerary_id" UUID NOT NULL, "flight_plan_id" UUID NOT NULL, PRIMARY KEY("itinerary_id", "flight_plan_id") )"#.to_owned(),
+        r#"CREATE TABLE IF NOT EXISTS "parcel" ("parcel_id" UUID DEFAULT uuid_generate_v4() PRIMARY KEY, "user_id" UUID NOT NULL, "status" TEXT DEFAULT 'NOTDROPPEDOFF' NOT NULL, "weight_grams" BIGINT NOT NULL, "created_at" TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL, "updated_at" TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL, "deleted_at" TIMESTAMP WITH TIME ZONE)"#.to_owned(),
+        r#"ALTER TABLE "parcel" ADD CONSTRAINT fk_user_id FOREIGN KEY("user_id") REFERENCES "user"("user_id")"#.to_owned(),
+        r#"CREATE TABLE IF NOT EXISTS "flight_plan_parcel" ("flight_plan_id" UUID NOT NULL, "parcel_id" UUID NOT NULL, "acquire" BOOL NOT NULL, "deliver" BOOL NOT NULL, PRIMARY KEY("flight_plan_id", "parcel_id") )"#.to_owned(),
+        r#"CREATE TABLE IF NOT EXISTS "scanner" ("scanner_id" UUID DEFAULT uuid_generate_v4() PRIMARY KEY, "organization_id" UUID NOT NULL, "scanner_type" TEXT DEFAULT 'MOBILE' NOT NULL, "scanner_status" TEXT DEFAULT 'ACTIVE' NOT NULL, "created_at" TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL, "updated_at" TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL, "deleted_at" TIMESTAMP WITH TIME ZONE)"#.to_owned(),
+        r#"CREATE TABLE IF NOT EXISTS "parcel_scan" ("parcel_scan_id" UUID DEFAULT uuid_generate_v4() PRIMARY KEY, "parcel_id" UUID NOT NULL, "scanner_id" UUID NOT NULL, "geo_location" GEOMETRY NOT NULL, "created_at" TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL, "updated_at" TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL, "deleted_at" TIMESTAMP WITH TIME ZONE)"#.to_owned(),
+        r#"ALTER TABLE parcel_scan ADD CONSTRAINT fk_parcel_id FOREIGN KEY(parcel_id) REFERENCES parcel(parcel_id)"#.to_owned(),
+        r#"ALTER TABLE parcel_scan ADD CONSTRAINT fk_scanner_id FOREIGN KEY(scanner_id) REFERENCES scanner(scanner_id)"#.to_owned(),
+        r#"ALTER TABLE parcel_scan ADD CONSTRAINT uk_parcel_id_scanner_id_created_at UNIQUE (parcel_id, scanner_id, created_at)"#.to_owned(),
+        r#"CREATE INDEX IF NOT EXISTS parcel_scan_geo_location_idx ON parcel_scan USING GIST(geo_location)"#.to_owned(),
+    ]
+}
+
+/// Returns the definitions of all resources managed by this service
+pub fn get_resource_definitions() -> Vec<ResourceDefinition> {
+    vec![
+        ResourceObject::<group::Data>::get_definition(),
+        ResourceObject::<user::Data>::get_definition(),
+        ResourceObject::<user_group::Data>::get_definition(),
+        ResourceObject::<vertiport::Data>::get_definition(),
+        ResourceObject::<vertiport_group::Data>::get_definition(),
+        ResourceObject::<vertipad::Data>::get_definition(),
+        ResourceObject::<vertipad_group::Data>::get_definition(),
+        ResourceObject::<vehicle_model::Data>::get_definition(),
+        ResourceObject::<vehicle::Data>::get_definition(),
+        ResourceObject::<vehicle_group::Data>::get_definition(),
+        ResourceObject::<pilot::Data>::get_definition(),
+        ResourceObject::<adsb::Data>::get_definition(),
+        ResourceObject::<flight_plan::Data>::get_definition(),
+        ResourceObject::<itinerary::Data>::get_definition(),
+        ResourceObject::<itinerary_flight_plan::Data>::get_definition(),
+        ResourceObject::<parcel::Data>::get_definition(),
+        ResourceObject::<flight_plan_parcel::Data>::get_definition(),
+        ResourceObject::<scanner::Data>::get_definition(),
+        ResourceObject::<parcel_scan::Data>::get_definition(),
+    ]
+}
+
+/// Applies all pending migrations
+///
+/// Returns the list of migrations that have been applied.
+///
+/// # Errors
+///
+/// Returns [`ArrErr`] if a recorded migration is unknown or its checksum doesn't match,
+/// or if any of the migration queries fail. A failing migration will be rolled back.
+pub async fn migrate() -> Result<Vec<Migration>, ArrErr> {
+    let migrations = get_migrations();
+
+    if !table_exists(SCHEMA_MIGRATIONS_TABLE).await? {
+        let is_empty = get_live_columns().await?.is_empty();
+        init_migrations_table().await?;
+        if is_empty {
+            psql_info!("(migrate) Empty database found, applying all migrations.");
+        } else {
+            psql_info!(
+                "(migrate) Found existing database without [{}], marking as baseline.",
+                SCHEMA_MIGRATIONS_TABLE
+            );
+            record_migrations(&migrations[..1]).await?;
+        }
+    }
+
+    let applied = get_applied_migrations().await?;
+    let pending = get_pending_migrations(&migrations, &applied)?;
+    if pending.is_empty() {
+        psql_info!("(migrate) Database schema is up to date.");
+    }
+    for migration in pending.iter() {
+        apply_migration(migration).await?;
+    }
+    Ok(pending)
+}
+
+/// Returns the current database schema version, being the latest applied migration version
+///
+/// Returns [`None`] if no migrations have been recorded yet.
+///
+/// # Errors
+///
+/// Returns [`ArrErr`] if the [`SCHEMA_MIGRATIONS_TABLE`] could not be read
+pub async fn get_schema_version() -> Result<Option<i64>, ArrErr> {
+    if !table_exists(SCHEMA_MIGRATIONS_TABLE).await? {
+        return Ok(None);
+    }
+    let client = get_psql_client().await?;
+    let query = format!(
+        r#"SELECT MAX("version") FROM "{}""#,
+        SCHEMA_MIGRATIONS_TABLE
+    );
+    let row = client.query_one(&query, &[]).await?;
+    Ok(row.try_get(0)?)
+}
+
+/// Report of the changes that would be made by [`migrate`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationReport {
+    /// migrations that have not been applied yet
+    pub pending: Vec<Migration>,
+    /// differences found between the resource definitions and the live database schema
+    pub diffs: Vec<SchemaDiff>,
+}
+
+impl Display for MigrationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.pending.is_empty() {
+            writeln!(f, "No pending migrations.")?;
+        } else {
+            writeln!(f, "Pending migrations:")?;
+            for migration in self.pending.iter() {
+                writeln!(
+                    f,
+                    "  [{}] {} ({} queries)",
+                    migration.version,
+                    migration.name,
+                    migration.queries.len()
+                )?;
+            }
+        }
+        if self.diffs.is_empty() {
+            write!(f, "Database schema matches the resource definitions.")
+        } else {
+            write!(f, "Database schema differences:")?;
+            for diff in self.diffs.iter() {
+                write!(f, "\n  {}", diff)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Returns the pending migrations and the differences between each [`ResourceDefinition`]
+/// and the live database schema, without making any changes.
+///
+/// # Errors
+///
+/// Returns [`ArrErr`] if a recorded migration is unknown or its checksum doesn't match,
+/// or if the database schema could not be read.
+pub async fn dry_run() -> Result<MigrationReport, ArrErr> {
+    let migrations = get_migrations();
+    let live = get_live_columns().await?;
+
+    let pending = if table_exists(SCHEMA_MIGRATIONS_TABLE).await? {
+        get_pending_migrations(&migrations, &get_applied_migrations().await?)?
+    } else if live.is_empty() {
+        migrations
+    } else {
+        migrations
+            .into_iter()
+            .filter(|migration| migration.version != BASELINE_VERSION)
+            .collect()
+    };
+
+    let diffs = get_resource_definitions()
+        .iter()
+        .flat_map(|definition| diff_definition(definition, live.get(&definition.psql_table)))
+        .collect();
+
+    Ok(MigrationReport { pending, diffs })
+}
+
+/// Returns the migrations that still need to be applied, in order of their version
+///
+/// # Errors
+///
+/// Returns [`ArrErr`] if an applied migration is unknown or its checksum doesn't match the known migration.
+pub(crate) fn get_pending_migrations(
+    migrations: &[Migration],
+    applied: &[AppliedMigration],
+) -> Result<Vec<Migration>, ArrErr> {
+    for applied_migration in applied.iter() {
+        let migration = migrations
+            .iter()
+            .find(|migration| migration.version == applied_migration.version)
+            .ok_or_else(|| {
+                let error = format!(
+                    "Applied migration [{}] {} is unknown, the database is newer than this service.",
+                    applied_migration.version, applied_migration.name
+                );
+                psql_error!("(get_pending_migrations) {}", error);
+                ArrErr::Error(error)
+            })?;
+        if migration.checksum() != applied_migration.checksum {
+            let error = format!(
+                "Checksum mismatch for applied migration [{}] {}, migrations should not be changed once applied.",
+                applied_migration.version, applied_migration.name
+            );
+            psql_error!("(get_pending_migrations) {}", error);
+            return Err(ArrErr::Error(error));
+        }
+    }
+
+    let mut pending: Vec<Migration> = migrations
+        .iter()
+        .filter(|migration| {
+            !applied
+                .iter()
+                .any(|applied_migration| applied_migration.version == migration.version)
+        })
+        .cloned()
+        .collect();
+    pending.sort_by_key(|migration| migration.version);
+    Ok(pending)
+}
+
+/// Creates the [`SCHEMA_MIGRATIONS_TABLE`] if it doesn't exist yet
+pub(crate) async fn init_migrations_table() -> Result<(), ArrErr> {
+    let client = get_psql_client().await?;
+    let query = format!(
+        r#"CREATE TABLE IF NOT EXISTS "{}" ("version" BIGINT PRIMARY KEY, "name" TEXT NOT NULL, "checksum" TEXT NOT NULL, "applied_at" TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW())"#,
+        SCHEMA_MIGRATIONS_TABLE
+    );
+    psql_debug!("(init_migrations_table) [{}].", query);
+    client.execute(&query, &[]).await?;
+    Ok(())
+}
+
+/// Returns the query to drop the [`SCHEMA_MIGRATIONS_TABLE`]
+pub(crate) fn get_drop_migrations_table_query() -> String {
+    format!(r#"DROP TABLE IF EXISTS "{}""#, SCHEMA_MIGRATIONS_TABLE)
+}
+
+/// Marks the provided migrations as applied, without executing their queries
+pub(crate) async fn record_migrations(migrations: &[Migration]) -> Result<(), ArrErr> {
+    let client = get_psql_client().await?;
+    let stmt = client.prepare_cached(&get_record_migration_query()).await?;
+    for migration in migrations {
+        psql_info!(
+            "(record_migrations) Marking migration [{}] {} as applied.",
+            migration.version,
+            migration.name
+        );
+        client
+            .execute(
+                &stmt,
+                &[&migration.version, &migration.name, &migration.checksum()],
+            )
+            .await?;
+    }
+    Ok(())
+}
+
+/// Returns the query used to record an applied migration
+fn get_record_migration_query() -> String {
+    format!(
+        r#"INSERT INTO "{}" ("version", "name", "checksum") VALUES ($1, $2, $3) ON CONFLICT ("version") DO NOTHING"#,
+        SCHEMA_MIGRATIONS_TABLE
+    )
+}
+
+/// Executes the migration's queries and records the migration using a single transaction
+async fn apply_migration(migration: &Migration) -> Result<(), ArrErr> {
+    psql_info!(
+        "(apply_migration) Applying migration [{}] {}.",
+        migration.version,
+        migration.name
+    );
+    let mut client = get_psql_client().await?;
+    let transaction = client.transaction().await?;
+    for query in migration.queries.iter() {
+        psql_debug!("(apply_migration) [{}].", query);
+        if let Err(e) = transaction.execute(query, &[]).await {
+            psql_error!(
+                "(apply_migration) Migration [{}] {} failed: {}",
+                migration.version,
+                migration.name,
+                e
+            );
+            transaction.rollback().await?;
+            return Err(e.into());
+        }
+    }
+    transaction
+        .execute(
+            &get_record_migration_query(),
+            &[&migration.version, &migration.name, &migration.checksum()],
+        )
+        .await?;
+    transaction.commit().await.map_err(ArrErr::from)
+}
+
+/// Returns all migrations recorded in the [`SCHEMA_MIGRATIONS_TABLE`]
+async fn get_applied_migrations() -> Result<Vec<AppliedMigration>, ArrErr> {
+    let client = get_psql_client().await?;
+    let query = format!(
+        r#"SELECT "version", "name", "checksum" FROM "{}" ORDER BY "version""#,
+        SCHEMA_MIGRATIONS_TABLE
+    );
+    let mut applied: Vec<AppliedMigration> = vec![];
+    for row in client.query(&query, &[]).await? {
+        applied.push(AppliedMigration {
+            version: row.try_get("version")?,
+            name: row.try_get("name")?,
+            checksum: row.try_get("checksum")?,
+        });
+    }
+    Ok(applied)
+}
+
+/// Returns `true` if the provided table exists in the current schema
+async fn table_exists(table: &str) -> Result<bool, ArrErr> {
+    let client = get_psql_client().await?;
+    let row = client
+        .query_one(
+            "SELECT EXISTS (SELECT 1 FROM information_schema.tables WHERE table_schema = current_schema() AND table_name = $1)",
+            &[&table],
+        )
+        .await?;
+    Ok(row.try_get(0)?)
+}
+
+/// Column details as found in the live `information_schema`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnInfo {
+    /// the column's `data_type`
+    pub data_type: String,
+    /// `true` if the column allows `NULL` values
+    pub nullable: bool,
+}
+
+/// Returns the columns of all resource tables found in the live database, grouped by table name
+async fn get_live_columns() -> Result<HashMap<String, HashMap<String, ColumnInfo>>, ArrErr> {
+    let tables: Vec<String> = get_resource_definitions()
+        .into_iter()
+        .map(|definition| definition.psql_table)
+        .collect();
+    let client = get_psql_client().await?;
+    let rows = client
+        .query(
+            "SELECT table_name, column_name, data_type, is_nullable FROM information_schema.columns WHERE table_schema = current_schema() AND table_name = ANY($1)",
+            &[&tables],
+        )
+        .await?;
+
+    let mut live: HashMap<String, HashMap<String, ColumnInfo>> = HashMap::new();
+    for row in rows {
+        let table: String = row.try_get("table_name")?;
+        let column: String = row.try_get("column_name")?;
+        let data_type: String = row.try_get("data_type")?;
+        let is_nullable: String = row.try_get("is_nullable")?;
+        live.entry(table).or_default().insert(
+            column,
+            ColumnInfo {
+                data_type: data_type.to_lowercase(),
+                nullable: is_nullable == "YES",
+            },
+        );
+    }
+    Ok(live)
+}
+
+/// Difference between a [`ResourceDefinition`] and the live database schema
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaDiff {
+    /// the resource's table does not exist
+    MissingTable {
+        /// table name
+        table: String,
+    },
+    /// a defined column does not exist
+    MissingColumn {
+        /// table name
+        table: String,
+        /// column name
+        column: String,
+    },
+    /// a column exists which is not part of the definition
+    UnknownColumn {
+        /// table name
+        table: String,
+        /// column name
+        column: String,
+    },
+    /// the column's data type differs from the definition
+    TypeMismatch {
+        /// table name
+        table: String,
+        /// column name
+        column: String,
+        /// data type according to the definition
+        expected: String,
+        /// data type found in the database
+        found: String,
+    },
+    /// the column's nullability differs from the definition
+    NullableMismatch {
+        /// table name
+        table: String,
+        /// column name
+        column: String,
+        /// `true` if the definition allows `NULL` values
+        expected_nullable: bool,
+    },
+}
+
+impl Display for SchemaDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaDiff::MissingTable { table } => write!(f, "[{}] table is missing", table),
+            SchemaDiff::MissingColumn { table, column } => {
+                write!(f, "[{}.{}] column is missing", table, column)
+            }
+            SchemaDiff::UnknownColumn { table, column } => {
+                write!(f, "[{}.{}] column is not defined", table, column)
+            }
+            SchemaDiff::TypeMismatch {
+                table,
+                column,
+                expected,
+                found,
+            } => write!(
+                f,
+                "[{}.{}] expected type [{}], found [{}]",
+                table, column, expected, found
+            ),
+            SchemaDiff::NullableMismatch {
+                table,
+                column,
+                expected_nullable,
+            } => write!(
+                f,
+                "[{}.{}] expected {}",
+                table,
+                column,
+                if *expected_nullable {
+                    "NULL"
+                } else {
+                    "NOT NULL"
+                }
+            ),
+        }
+    }
+}
+
+/// Compares the provided [`ResourceDefinition`] with the live columns of its table
+pub(crate) fn diff_definition(
+    definition: &ResourceDefinition,
+    live: Option<&HashMap<String, ColumnInfo>>,
+) -> Vec<SchemaDiff> {
+    let table = definition.psql_table.clone();
+    let live = match live {
+        Some(live) => live,
+        None => return vec![SchemaDiff::MissingTable { table }],
+    };
+
+    let mut expected: Vec<(String, PsqlFieldType, bool)> = definition
+        .psql_id_cols
+        .iter()
+        .map(|id_col| (id_col.clone(), PsqlFieldType::UUID, false))
+        .collect();
+    for (column, field) in definition.fields.iter() {
+        expected.push((
+            column.clone(),
+            field.field_type.clone(),
+            !field.is_mandatory(),
+        ));
+    }
+    expected.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut diffs: Vec<SchemaDiff> = vec![];
+    for (column, field_type, nullable) in expected.iter() {
+        let info = match live.get(column) {
+            Some(info) => info,
+            None => {
+                diffs.push(SchemaDiff::MissingColumn {
+                    table: table.clone(),
+                    column: column.clone(),
+                });
+                continue;
+            }
+        };
+        if let Some(expected_type) = get_information_schema_type(field_type) {
+            if info.data_type != expected_type {
+                diffs.push(SchemaDiff::TypeMismatch {
+                    table: table.clone(),
+                    column: column.clone(),
+                    expected: expected_type.to_owned(),
+                    found: info.data_type.clone(),
+                });
+            }
+        }
+        if info.nullable != *nullable {
+            diffs.push(SchemaDiff::NullableMismatch {
+                table: table.clone(),
+                column: column.clone(),
+                expected_nullable: *nullable,
+            });
+        }
+    }
+
+    let mut unknown: Vec<&String> = live
+        .keys()
+        .filter(|column| !expected.iter().any(|(name, _, _)| name == *column))
+        .collect();
+    unknown.sort();
+    for column in unknown {
+        diffs.push(SchemaDiff::UnknownColumn {
+            table: table.clone(),
+            column: column.clone(),
+        });
+    }
+    diffs
+}
+
+/// Returns the `information_schema` data type used for columns of the provided [`PsqlFieldType`]
+///
+/// Returns [`None`] for types that can't be compared reliably, like PostGIS geometries.
+fn get_information_schema_type(field_type: &PsqlFieldType) -> Option<&'static str> {
+    match *field_type {
+        PsqlFieldType::TIMESTAMPTZ => Some("timestamp with time zone"),
+        PsqlFieldType::ANYENUM | PsqlFieldType::TEXT => Some("text"),
+        PsqlFieldType::INT2 => Some("smallint"),
+        PsqlFieldType::INT4 => Some("integer"),
+        PsqlFieldType::INT8 => Some("bigint"),
+        PsqlFieldType::NUMERIC | PsqlFieldType::FLOAT8 => Some("double precision"),
+        PsqlFieldType::FLOAT4 => Some("real"),
+        PsqlFieldType::BYTEA => Some("bytea"),
+        PsqlFieldType::BOOL => Some("boolean"),
+        PsqlFieldType::UUID => Some("uuid"),
+        PsqlFieldType::JSON => Some("json"),
+        PsqlFieldType::JSONB => Some("jsonb"),
+        PsqlFieldType::TEXT_ARRAY => Some("array"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::base::FieldDefinition;
+
+    fn get_test_migrations() -> Vec<Migration> {
+        vec![
+            Migration {
+                version: BASELINE_VERSION,
+                name: "baseline",
+                queries: vec![],
+            },
+            Migration {
+                version: 3,
+                name: "third",
+                queries: vec![String::from("SELECT 3")],
+            },
+            Migration {
+                version: 2,
+                name: "second",
+                queries: vec![String::from("SELECT 2")],
+            },
+        ]
+    }
+
+    fn applied(migration: &Migration) -> AppliedMigration {
+        AppliedMigration {
+            version: migration.version,
+            name: migration.name.to_owned(),
+            checksum: migration.checksum(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_migrations() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_migrations) start");
+
+        let migrations = get_migrations();
+        assert_eq!(migrations[0].version, BASELINE_VERSION);
+        assert!(migrations
+            .iter()
+            .all(|migration| !migration.queries.is_empty()));
+        for pair in migrations.windows(2) {
+            assert!(pair[0].version < pair[1].version);
+        }
+        // Checksums should be stable
+        for migration in migrations.iter() {
+            assert_eq!(migration.checksum(), migration.checksum());
+            assert_eq!(migration.checksum().len(), 64);
+        }
+        // All resource tables should be created by a migration
+        for definition in get_resource_definitions() {
+            let create_table = format!(
+                r#"CREATE TABLE IF NOT EXISTS "{}" ("#,
+                definition.psql_table
+            );
+            assert!(
+                migrations
+                    .iter()
+                    .flat_map(|migration| migration.queries.iter())
+                    .any(|query| query.starts_with(&create_table)),
+                "no migration creates table [{}]",
+                definition.psql_table
+            );
+        }
+
+        ut_info!("(test_get_migrations) success");
+    }
+
+    #[tokio::test]
+    async fn test_get_pending_migrations() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_pending_migrations) start");
+
+        let migrations = get_test_migrations();
+
+        // Pending migrations should be sorted by version
+        let result = get_pending_migrations(&migrations, &[applied(&migrations[0])]);
+        assert!(result.is_ok());
+        let versions: Vec<i64> = result.unwrap().iter().map(|m| m.version).collect();
+        assert_eq!(versions, vec![2, 3]);
+
+        let result = get_pending_migrations(
+            &migrations,
+            &[applied(&migrations[0]), applied(&migrations[2])],
+        );
+        assert!(result.is_ok());
+        let versions: Vec<i64> = result.unwrap().iter().map(|m| m.version).collect();
+        assert_eq!(versions, vec![3]);
+
+        // Changed migrations should be rejected
+        let mut changed = applied(&migrations[2]);
+        changed.checksum = Migration {
+            queries: vec![String::from("SELECT 4")],
+            ..migrations[2].clone()
+        }
+        .checksum();
+        let result = get_pending_migrations(&migrations, &[applied(&migrations[0]), changed]);
+        assert!(result.is_err());
+
+        // Unknown migrations should be rejected
+        let unknown = AppliedMigration {
+            version: 10,
+            name: String::from("unknown"),
+            checksum: String::new(),
+        };
+        let result = get_pending_migrations(&migrations, &[unknown]);
+        assert!(result.is_err());
+
+        ut_info!("(test_get_pending_migrations) success");
+    }
+
+    #[tokio::test]
+    async fn test_diff_definition() {
+        crate::get_log_handle().await;
+        ut_info!("(test_diff_definition) start");
+
+        let definition = ResourceDefinition {
+            psql_table: String::from("test"),
+            psql_id_cols: vec![String::from("test_id")],
+            fields: HashMap::from([
+                (
+                    String::from("name"),
+                    FieldDefinition::new(PsqlFieldType::TEXT, true),
+                ),
+                (
+                    String::from("count"),
+                    FieldDefinition::new(PsqlFieldType::INT8, false),
+                ),
+                (
+                    String::from("created_at"),
+                    FieldDefinition::new_read_only(PsqlFieldType::TIMESTAMPTZ, true),
+                ),
+                (
+                    String::from("tags"),
+                    FieldDefinition::new(PsqlFieldType::TEXT_ARRAY, true),
+                ),
+            ]),
+        };
+
+        assert_eq!(
+            diff_definition(&definition, None),
+            vec![SchemaDiff::MissingTable {
+                table: String::from("test")
+            }]
+        );
+
+        let column = |data_type: &str, nullable: bool| ColumnInfo {
+            data_type: data_type.to_owned(),
+            nullable,
+        };
+        let live = HashMap::from([
+            (String::from("test_id"), column("uuid", false)),
+            (String::from("name"), column("text", false)),
+            (String::from("count"), column("bigint", true)),
+            (
+                String::from("created_at"),
+                column("timestamp with time zone", false),
+            ),
+            (String::from("tags"), column("array", false)),
+        ]);
+        assert!(diff_definition(&definition, Some(&live)).is_empty());
+
+        let live = HashMap::from([
+            (String::from("test_id"), column("uuid", false)),
+            (String::from("name"), column("text", true)),
+            (String::from("count"), column("integer", true)),
+            (String::from("tags"), column("array", false)),
+            (String::from("legacy"), column("text", true)),
+        ]);
+        assert_eq!(
+            diff_definition(&definition, Some(&live)),
+            vec![
+                SchemaDiff::TypeMismatch {
+                    table: String::from("test"),
+                    column: String::from("count"),
+                    expected: String::from("bigint"),
+                    found: String::from("integer"),
+                },
+                SchemaDiff::MissingColumn {
+                    table: String::from("test"),
+                    column: String::from("created_at"),
+                },
+                SchemaDiff::NullableMismatch {
+                    table: String::from("test"),
+                    column: String::from("name"),
+                    expected_nullable: false,
+                },
+                SchemaDiff::UnknownColumn {
+                    table: String::from("test"),
+                    column: String::from("legacy"),
+                },
+            ]
+        );
+
+        ut_info!("(test_diff_definition) success");
+    }
+}
@@ -3,16 +3,25 @@
 
 #[macro_use]
 pub mod macros;
+pub mod dedup;
+pub mod derived;
+pub mod exclusion;
 pub mod init;
 pub mod linked_resource;
+pub mod migrations;
+pub mod retention;
 pub mod simple_resource;
 pub mod simple_resource_linked;
+pub mod spatial;
+pub mod transition;
 pub(crate) mod util;
 
+mod error;
 mod pool;
-mod postgis;
+pub(crate) mod postgis;
 mod queries;
 mod search;
+pub mod watch;
 
 use anyhow::Error;
 pub use pool::*;
@@ -22,7 +31,7 @@ use std::collections::HashMap;
 use std::fmt::Debug;
 use tokio_postgres::types::Type as PsqlFieldType;
 
-pub use self::search::{PsqlSearch, SearchCol};
+pub use self::search::{PsqlSearch, SearchCol, SearchResult, SearchRowStream, SortKey};
 pub use crate::common::ArrErr;
 
 /// Provides a more readable format of a dynamic PostgreSQL field value
@@ -41,6 +50,10 @@ pub struct PsqlJsonValue {
 
 impl From<tokio_postgres::Error> for ArrErr {
     fn from(err: tokio_postgres::Error) -> Self {
+        if let Some(typed_err) = error::get_typed_error(&err) {
+            psql_warn!("(from) Error executing DB query: {}", typed_err);
+            return typed_err;
+        }
         let err: Error = err.into();
         psql_error!("(from) Error executing DB query: {}", err);
         ArrErr::Error(err.to_string())
@@ -48,6 +61,14 @@ impl From<tokio_postgres::Error> for ArrErr {
 }
 impl From<deadpool_postgres::PoolError> for ArrErr {
     fn from(err: deadpool_postgres::PoolError) -> Self {
+        match err {
+            deadpool_postgres::PoolError::Backend(err) => return err.into(),
+            deadpool_postgres::PoolError::Timeout(_) | deadpool_postgres::PoolError::Closed => {
+                psql_error!("(from) Postgres pool error: {}", err);
+                return ArrErr::unavailable(err.to_string());
+            }
+            _ => (),
+        }
         let err: Error = err.into();
         psql_error!("(from) Postgres pool error: {}", err);
         ArrErr::Error(err.to_string())
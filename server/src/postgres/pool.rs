@@ -48,6 +48,26 @@ async fn get_psql_pool() -> &'static Pool {
         .await
 }
 
+/// Returns a [`PostgresPool`] using the global database connection pool.
+///
+/// Unlike [`get_psql_client`], this function will not panic if the database is not available.
+/// The global pool will be initialized if the database is ready and no pool has been initialized yet.
+///
+/// # Errors
+///
+/// Returns [`ArrErr`] if the pool could not be created or the database is not ready
+#[cfg(not(feature = "stub_backends"))]
+pub(crate) async fn try_get_postgres_pool() -> Result<PostgresPool, ArrErr> {
+    if let Some(pool) = DB_POOL.get() {
+        return Ok(PostgresPool { pool: pool.clone() });
+    }
+    let pg = PostgresPool::from_config()?;
+    pg.readiness().await?;
+    // The pool might have been initialized by another task in the meantime
+    let pool = DB_POOL.get_or_init(|| async { pg.pool }).await;
+    Ok(PostgresPool { pool: pool.clone() })
+}
+
 pub(crate) async fn get_psql_client() -> Result<Object<Manager>, PoolError<tokio_postgres::Error>> {
     get_psql_pool().await.get().await
 }
@@ -95,98 +115,7 @@ impl PostgresPool {
         );
 
         let pool = if settings.use_tls {
-            psql_info!("(from_config) Initializing connection with TLS settings.");
-            psql_debug!("(from_config) [{:?}].", settings);
-            psql_info!(
-                "(from_config) Try read root cert file: {}",
-                settings.db_ca_cert
-            );
-            let root_cert_file = match fs::read(settings.db_ca_cert.clone()) {
-                Ok(root_cert_file) => root_cert_file,
-                Err(e) => {
-                    let error = format!(
-                        "Unable to read db_ca_cert file [{}]: {}",
-                        settings.db_ca_cert, e
-                    );
-                    psql_error!("(from_config) {}", error);
-                    return Err(ArrErr::Error(error));
-                }
-            };
-            psql_info!("(from_config) Try load root cert file.");
-            let root_cert = match Certificate::from_pem(&root_cert_file) {
-                Ok(root_cert) => root_cert,
-                Err(e) => {
-                    let error = format!(
-                        "Unable to load Certificate from pem file [{}]: {}",
-                        settings.db_ca_cert, e
-                    );
-                    psql_error!("(from_config) {}", error);
-                    return Err(ArrErr::Error(error));
-                }
-            };
-            psql_debug!("(from_config) Root cert load success.");
-
-            // If client cert and key are specified, try using it. Otherwise default to user/pass.
-            // Since the TlsConnector builder sucks
-            let builder = if settings.db_client_cert.is_some() && settings.db_client_key.is_some() {
-                let cert: String = settings
-                    .db_client_cert
-                    .ok_or("No DB_CLIENT_CERT env var found.")
-                    .map_err(|e| ArrErr::Error(e.to_owned()))?;
-                let key: String = settings
-                    .db_client_key
-                    .ok_or("No DB_CLIENT_KEY env var found")
-                    .map_err(|e| ArrErr::Error(e.to_owned()))?;
-                psql_info!("(from_config) Try read client cert file.");
-                let client_cert_file = fs::read(cert.clone()).map_err(|e| {
-                    let error = format!(
-                        "Unable to read client certificate db_client_cert file [{}]: {}",
-                        cert, e
-                    );
-                    psql_error!("(from_config) {}", error);
-                    ArrErr::Error(error)
-                })?;
-                psql_info!("(from_config) Try read client key file.");
-                let client_key_file = fs::read(key.clone()).map_err(|e| {
-                    let error = format!(
-                        "Unable to read client key db_client_key file [{}]: {}",
-                        key, e
-                    );
-                    psql_error!("(from_config) {}", error);
-                    ArrErr::Error(error)
-                })?;
-
-                psql_info!("(from_config) Setting up TLS connection with client cert and key.");
-                TlsConnector::builder()
-                    .add_root_certificate(root_cert)
-                    .identity(
-                        Identity::from_pkcs8(&client_cert_file, &client_key_file).unwrap_or_else(
-                            |e| {
-                                panic!(
-                                    "Unable to create identity from specified cert[{}] and key[{}]: {}",
-                                    cert, key, e
-                                )
-                            },
-                        ),
-                    )
-                    .build()
-                    .unwrap_or_else(|e| {
-                        panic!("Unable to connect build connector custom ca and client certs: {}", e)
-                    })
-            } else {
-                psql_warn!("(from_config) Setting up TLS connection with client password.");
-                TlsConnector::builder()
-                    .add_root_certificate(root_cert)
-                    .build()
-                    .unwrap_or_else(|e| {
-                        panic!(
-                            "Unable to connect build connector custom root ca cert: {}",
-                            e
-                        )
-                    })
-            };
-            let connector = MakeTlsConnector::new(builder);
-
+            let connector = get_tls_connector(&settings)?;
             settings.pg.create_pool(Some(Runtime::Tokio1), connector)?
         } else {
             psql_warn!("(from_config) Setting up database connection without TLS and using client password.");
@@ -218,3 +147,103 @@ impl PostgresPool {
         }
     }
 }
+
+/// Creates a [MakeTlsConnector] using the certificates provided by the configuration settings
+///
+/// # Errors
+///
+/// Returns [ArrErr] if any of the configured certificate files could not be read or loaded
+fn get_tls_connector(settings: &Config) -> Result<MakeTlsConnector, ArrErr> {
+    psql_info!("(get_tls_connector) Initializing connection with TLS settings.");
+    psql_debug!("(get_tls_connector) [{:?}].", settings);
+    psql_info!(
+        "(get_tls_connector) Try read root cert file: {}",
+        settings.db_ca_cert
+    );
+    let root_cert_file = match fs::read(settings.db_ca_cert.clone()) {
+        Ok(root_cert_file) => root_cert_file,
+        Err(e) => {
+            let error = format!(
+                "Unable to read db_ca_cert file [{}]: {}",
+                settings.db_ca_cert, e
+            );
+            psql_error!("(get_tls_connector) {}", error);
+            return Err(ArrErr::Error(error));
+        }
+    };
+    psql_info!("(get_tls_connector) Try load root cert file.");
+    let root_cert = match Certificate::from_pem(&root_cert_file) {
+        Ok(root_cert) => root_cert,
+        Err(e) => {
+            let error = format!(
+                "Unable to load Certificate from pem file [{}]: {}",
+                settings.db_ca_cert, e
+            );
+            psql_error!("(get_tls_connector) {}", error);
+            return Err(ArrErr::Error(error));
+        }
+    };
+    psql_debug!("(get_tls_connector) Root cert load success.");
+
+    // If client cert and key are specified, try using it. Otherwise default to user/pass.
+    // Since the TlsConnector builder sucks
+    let builder = if settings.db_client_cert.is_some() && settings.db_client_key.is_some() {
+        let cert: String = settings
+            .db_client_cert
+            .ok_or("No DB_CLIENT_CERT env var found.")
+            .map_err(|e| ArrErr::Error(e.to_owned()))?;
+        let key: String = settings
+            .db_client_key
+            .ok_or("No DB_CLIENT_KEY env var found")
+            .map_err(|e| ArrErr::Error(e.to_owned()))?;
+        psql_info!("(get_tls_connector) Try read client cert file.");
+        let client_cert_file = fs::read(cert.clone()).map_err(|e| {
+            let error = format!(
+                "Unable to read client certificate db_client_cert file [{}]: {}",
+                cert, e
+            );
+            psql_error!("(get_tls_connector) {}", error);
+            ArrErr::Error(error)
+        })?;
+        psql_info!("(get_tls_connector) Try read client key file.");
+        let client_key_file = fs::read(key.clone()).map_err(|e| {
+            let error = format!(
+                "Unable to read client key db_client_key file [{}]: {}",
+                key, e
+            );
+            psql_error!("(get_tls_connector) {}", error);
+            ArrErr::Error(error)
+        })?;
+
+        psql_info!("(get_tls_connector) Setting up TLS connection with client cert and key.");
+        TlsConnector::builder()
+            .add_root_certificate(root_cert)
+            .identity(
+                Identity::from_pkcs8(&client_cert_file, &client_key_file).unwrap_or_else(|e| {
+                    panic!(
+                        "Unable to create identity from specified cert[{}] and key[{}]: {}",
+                        cert, key, e
+                    )
+                }),
+            )
+            .build()
+            .unwrap_or_else(|e| {
+                panic!(
+                    "Unable to connect build connector custom ca and client certs: {}",
+                    e
+                )
+            })
+    } else {
+        psql_warn!("(get_tls_connector) Setting up TLS connection with client password.");
+        TlsConnector::builder()
+            .add_root_certificate(root_cert)
+            .build()
+            .unwrap_or_else(|e| {
+                panic!(
+                    "Unable to connect build connector custom root ca cert: {}",
+                    e
+                )
+            })
+    };
+    Ok(MakeTlsConnector::new(builder))
+}
@@ -1,13 +1,33 @@
 //! Implement Postgis Traits for our own Structs
+//!
+//! All geometries are stored using the WGS 84 spatial reference system ([`SRID`]).
+//! Points with an altitude are stored as `POINT Z`, line strings containing
+//! points with an altitude are stored as `LINESTRING Z`.
+//! Line strings with times are stored as `LINESTRING M` or `LINESTRING ZM`, using
+//! the number of seconds since the UNIX epoch as measure (M) value.
 
 use crate::grpc::server::grpc_geo_types::{GeoLineString, GeoPoint, GeoPolygon};
 use byteorder::{BigEndian, LittleEndian, ReadBytesExt};
 use bytes::{BufMut, BytesMut};
+use geo_types::Polygon;
 use postgis::ewkb::*;
 use postgres_types::{to_sql_checked, FromSql, IsNull, ToSql, Type};
+use prost_wkt_types::Timestamp;
 use std::error::Error;
 use std::io::{Cursor, Read};
 
+/// Spatial reference id used for all geometries (WGS 84, long/lat in degrees)
+pub const SRID: i32 = 4326;
+
+/// EWKB type flag indicating the geometry has a Z value
+const EWKB_Z_FLAG: u32 = 0x8000_0000;
+/// EWKB type flag indicating the geometry has an M value
+const EWKB_M_FLAG: u32 = 0x4000_0000;
+/// EWKB geometry type of a point
+const EWKB_POINT_TYPE: u32 = 0x01;
+/// EWKB geometry type of a line string
+const EWKB_LINE_STRING_TYPE: u32 = 0x02;
+
 macro_rules! accepts_geography {
     () => {
         fn accepts(ty: &Type) -> bool {
@@ -27,6 +47,61 @@ fn read_f64<R: Read>(raw: &mut R, is_be: bool) -> Result<f64, postgis::error::Er
     })
 }
 
+fn read_u32<R: Read>(raw: &mut R, is_be: bool) -> Result<u32, postgis::error::Error> {
+    Ok(if is_be {
+        raw.read_u32::<BigEndian>()?
+    } else {
+        raw.read_u32::<LittleEndian>()?
+    })
+}
+
+/// Reads a single point, returning the point and its measure (M) value if available
+fn read_point<R: Read>(
+    raw: &mut R,
+    is_be: bool,
+    type_id: u32,
+) -> Result<(GeoPoint, Option<f64>), postgis::error::Error> {
+    let longitude = read_f64(raw, is_be)?;
+    let latitude = read_f64(raw, is_be)?;
+    let altitude = if type_id & EWKB_Z_FLAG == EWKB_Z_FLAG {
+        Some(read_f64(raw, is_be)?)
+    } else {
+        None
+    };
+    let measure = if type_id & EWKB_M_FLAG == EWKB_M_FLAG {
+        Some(read_f64(raw, is_be)?)
+    } else {
+        None
+    };
+    Ok((
+        GeoPoint {
+            longitude,
+            latitude,
+            altitude,
+        },
+        measure,
+    ))
+}
+
+/// Returns the number of seconds since the UNIX epoch for the provided timestamp, used as measure (M) value
+fn get_epoch_seconds(timestamp: &Timestamp) -> f64 {
+    timestamp.seconds as f64 + timestamp.nanos as f64 / 1_000_000_000.0
+}
+
+/// Returns the timestamp for the provided number of seconds since the UNIX epoch
+///
+/// Measures are stored as double precision values, limiting the precision to about a microsecond.
+fn get_timestamp(epoch_seconds: f64) -> Timestamp {
+    let seconds = epoch_seconds.floor();
+    let nanos = ((epoch_seconds - seconds) * 1_000_000_000.0)
+        .round()
+        .min(999_999_999.0);
+    Timestamp {
+        seconds: seconds as i64,
+        nanos: nanos as i32,
+    }
+}
+
 impl postgis::Point for GeoPoint {
     fn x(&self) -> f64 {
         self.longitude
@@ -34,6 +109,9 @@ impl postgis::Point for GeoPoint {
     fn y(&self) -> f64 {
         self.latitude
     }
+    fn opt_z(&self) -> Option<f64> {
+        self.altitude
+    }
 }
 impl EwkbRead for GeoPoint {
     fn point_type() -> PointType {
@@ -42,23 +120,24 @@ impl EwkbRead for GeoPoint {
     fn read_ewkb_body<R: Read>(
         raw: &mut R,
         is_be: bool,
-        _type_id: u32,
+        type_id: u32,
         _srid: Option<i32>,
     ) -> Result<Self, postgis::error::Error> {
-        let longitude = read_f64(raw, is_be)?;
-        let latitude = read_f64(raw, is_be)?;
-        Ok(GeoPoint {
-            longitude,
-            latitude,
-        })
+        // Measures are not supported for points
+        let (point, _) = read_point(raw, is_be, type_id)?;
+        Ok(point)
     }
 }
 impl<'a> AsEwkbPoint<'a> for GeoPoint {
     fn as_ewkb(&'a self) -> EwkbPoint<'a> {
         EwkbPoint {
             geom: self,
-            srid: None,
-            point_type: PointType::Point,
+            srid: Some(SRID),
+            point_type: if self.has_altitude() {
+                PointType::PointZ
+            } else {
+                PointType::Point
+            },
         }
     }
 }
@@ -90,26 +169,63 @@ impl<'a> postgis::LineString<'a> for GeoLineString {
     }
 }
 
-impl From<Point> for GeoPoint {
-    fn from(field: Point) -> Self {
-        GeoPoint {
-            longitude: field.x,
-            latitude: field.y,
+/// Reads a line string, a `POINT` is read as a line string with a single point
+/// (see [`get_line_string_wkt`]).
+impl EwkbRead for GeoLineString {
+    fn point_type() -> PointType {
+        PointType::Point
+    }
+    fn read_ewkb_body<R: Read>(
+        raw: &mut R,
+        is_be: bool,
+        type_id: u32,
+        _srid: Option<i32>,
+    ) -> Result<Self, postgis::error::Error> {
+        let size = match type_id & 0xff {
+            EWKB_POINT_TYPE => 1,
+            EWKB_LINE_STRING_TYPE => read_u32(raw, is_be)? as usize,
+            geometry_type => {
+                return Err(postgis::error::Error::Read(format!(
+                    "unsupported geometry type [{}] for GeoLineString",
+                    geometry_type
+                )))
+            }
+        };
+        let mut line_string = GeoLineString {
+            points: Vec::with_capacity(size),
+            times: vec![],
+        };
+        for _ in 0..size {
+            let (point, measure) = read_point(raw, is_be, type_id)?;
+            line_string.points.push(point);
+            if let Some(measure) = measure {
+                line_string.times.push(get_timestamp(measure));
+            }
         }
+        Ok(line_string)
     }
 }
-impl From<LineString> for GeoLineString {
-    fn from(field: LineString) -> Self {
-        let mut points: Vec<GeoPoint> = vec![];
-        for point in field.points {
-            points.push(point.into())
+impl<'a> FromSql<'a> for GeoLineString {
+    fn from_sql(ty: &Type, raw: &[u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        let mut rdr = Cursor::new(raw);
+        GeoLineString::read_ewkb(&mut rdr)
+            .map_err(|_| format!("cannot convert {} to GeoLineString", ty).into())
+    }
+
+    accepts_geography!();
+}
+
+impl From<LineStringT<GeoPoint>> for GeoLineString {
+    fn from(field: LineStringT<GeoPoint>) -> Self {
+        GeoLineString {
+            points: field.points,
+            times: vec![],
         }
-        GeoLineString { points }
     }
 }
 
-impl From<Polygon> for GeoPolygon {
-    fn from(field: Polygon) -> Self {
+impl From<PolygonT<GeoPoint>> for GeoPolygon {
+    fn from(field: PolygonT<GeoPoint>) -> Self {
         let mut polygon: Self = Self {
             exterior: None,
             interiors: vec![],
@@ -126,3 +242,368 @@ impl From<Polygon> for GeoPolygon {
         polygon
     }
 }
+
+/// Returns the WKT coordinates of the provided point
+///
+/// The altitude will be added if `with_altitude` is `true`, using `0` for points without an altitude.
+/// The time will be added as measure if provided.
+fn get_wkt_coord(point: &GeoPoint, with_altitude: bool, time: Option<&Timestamp>) -> String {
+    let mut coord = format!("{:.15} {:.15}", point.longitude, point.latitude);
+    if with_altitude {
+        coord.push_str(&format!(" {:.15}", point.altitude.unwrap_or(0.0)));
+    }
+    if let Some(time) = time {
+        coord.push_str(&format!(" {:.6}", get_epoch_seconds(time)));
+    }
+    coord
+}
+
+/// Returns the SQL value for the provided WKT using the [`SRID`]
+pub(crate) fn get_geom_from_text_sql(wkt: &str) -> String {
+    format!("ST_GeomFromText('{}', {})", wkt, SRID)
+}
+
+/// Returns the WKT representation of the provided point
+///
+/// POINT expects (x y) which is (long lat), or (x y z) which is (long lat alt)
+pub(crate) fn get_point_wkt(point: &GeoPoint) -> String {
+    if point.has_altitude() {
+        format!("POINT Z({})", get_wkt_coord(point, true, None))
+    } else {
+        format!("POINT({})", get_wkt_coord(point, false, None))
+    }
+}
+
+/// Returns the WKT representation of the provided line string
+///
+/// If any of the points has an altitude, a `LINESTRING Z` is returned in which
+/// points without an altitude get an altitude of `0`.
+/// If the line string has times, the times are added as measures (`LINESTRING M` or `LINESTRING ZM`).
+/// A line string with a single point is returned as `POINT`, as a `LINESTRING` needs at least 2 points.
+/// Such a `POINT` is read back as a line string with a single point.
+pub(crate) fn get_line_string_wkt(line_string: &GeoLineString) -> String {
+    let with_altitude = line_string.has_altitude();
+    let with_times = line_string.has_times();
+    let coords: Vec<String> = line_string
+        .points
+        .iter()
+        .enumerate()
+        .map(|(index, point)| {
+            let time = if with_times {
+                line_string.times.get(index)
+            } else {
+                None
+            };
+            get_wkt_coord(point, with_altitude, time)
+        })
+        .collect();
+    let dimensions = match (with_altitude, with_times) {
+        (true, true) => " ZM",
+        (true, false) => " Z",
+        (false, true) => " M",
+        (false, false) => "",
+    };
+    let geometry_type = if coords.len() == 1 {
+        "POINT"
+    } else {
+        "LINESTRING"
+    };
+    format!("{}{}({})", geometry_type, dimensions, coords.join(","))
+}
+
+/// Returns the WKT representation of the provided polygon
+///
+/// The first ring is the exterior, followed by the interiors (holes).
+pub(crate) fn get_polygon_wkt(polygon: &Polygon) -> String {
+    let ring = |line_string: &geo_types::LineString| {
+        let coords: Vec<String> = line_string
+            .coords()
+            .map(|coord| format!("{:.15} {:.15}", coord.x, coord.y))
+            .collect();
+        format!("({})", coords.join(","))
+    };
+    let mut rings: Vec<String> = vec![ring(polygon.exterior())];
+    rings.extend(polygon.interiors().iter().map(ring));
+    format!("POLYGON({})", rings.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_point(altitude: Option<f64>) -> GeoPoint {
+        GeoPoint {
+            longitude: 4.9164,
+            latitude: 52.37466,
+            altitude,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_geo_point_ewkb() {
+        crate::get_log_handle().await;
+        ut_info!("(test_geo_point_ewkb) start");
+
+        for point in [get_point(None), get_point(Some(120.5))] {
+            let mut out = BytesMut::new();
+            let result = point.to_sql(&Type::BYTEA, &mut out);
+            assert!(result.is_ok());
+
+            let result = GeoPoint::from_sql(&Type::BYTEA, &out);
+            assert!(result.is_ok());
+            assert_eq!(result.unwrap(), point);
+        }
+
+        ut_info!("(test_geo_point_ewkb) success");
+    }
+
+    #[tokio::test]
+    async fn test_geo_point_ewkb_srid() {
+        crate::get_log_handle().await;
+        ut_info!("(test_geo_point_ewkb_srid) start");
+
+        let point = get_point(Some(120.5));
+        let mut out = Vec::new();
+        assert!(point.as_ewkb().write_ewkb(&mut out).is_ok());
+
+        // byte order, then the type id including the Z and SRID flags followed by the SRID
+        let type_id = u32::from_le_bytes([out[1], out[2], out[3], out[4]]);
+        assert_eq!(type_id, 0x0000_0001 | EWKB_Z_FLAG | 0x2000_0000);
+        let srid = i32::from_le_bytes([out[5], out[6], out[7], out[8]]);
+        assert_eq!(srid, SRID);
+
+        let point = get_point(None);
+        let mut out = Vec::new();
+        assert!(point.as_ewkb().write_ewkb(&mut out).is_ok());
+        let type_id = u32::from_le_bytes([out[1], out[2], out[3], out[4]]);
+        assert_eq!(type_id, 0x0000_0001 | 0x2000_0000);
+
+        ut_info!("(test_geo_point_ewkb_srid) success");
+    }
+
+    #[tokio::test]
+    async fn test_get_point_wkt() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_point_wkt) start");
+
+        assert_eq!(
+            get_point_wkt(&get_point(None)),
+            "POINT(4.916400000000000 52.374659999999999)"
+        );
+        assert_eq!(
+            get_point_wkt(&get_point(Some(120.5))),
+            "POINT Z(4.916400000000000 52.374659999999999 120.500000000000000)"
+        );
+        assert_eq!(
+            get_geom_from_text_sql("POINT(1 2)"),
+            "ST_GeomFromText('POINT(1 2)', 4326)"
+        );
+
+        ut_info!("(test_get_point_wkt) success");
+    }
+
+    #[tokio::test]
+    async fn test_get_line_string_wkt() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_line_string_wkt) start");
+
+        let line_string = GeoLineString {
+            points: vec![get_point(None), get_point(None)],
+            times: vec![],
+        };
+        assert_eq!(
+            get_line_string_wkt(&line_string),
+            "LINESTRING(4.916400000000000 52.374659999999999,4.916400000000000 52.374659999999999)"
+        );
+
+        // Points without altitude are placed at 0 if any point has an altitude
+        let line_string = GeoLineString {
+            points: vec![get_point(None), get_point(Some(120.5))],
+            times: vec![],
+        };
+        assert_eq!(
+            get_line_string_wkt(&line_string),
+            "LINESTRING Z(4.916400000000000 52.374659999999999 0.000000000000000,4.916400000000000 52.374659999999999 120.500000000000000)"
+        );
+
+        let timestamp = |seconds: i64| Timestamp { seconds, nanos: 0 };
+        let line_string = GeoLineString {
+            points: vec![get_point(None), get_point(Some(120.5))],
+            times: vec![timestamp(1700000000), timestamp(1700000060)],
+        };
+        assert_eq!(
+            get_line_string_wkt(&line_string),
+            "LINESTRING ZM(4.916400000000000 52.374659999999999 0.000000000000000 1700000000.000000,4.916400000000000 52.374659999999999 120.500000000000000 1700000060.000000)"
+        );
+        let line_string = GeoLineString {
+            points: vec![get_point(None)],
+            times: vec![timestamp(1700000000)],
+        };
+        assert_eq!(
+            get_line_string_wkt(&line_string),
+            "POINT M(4.916400000000000 52.374659999999999 1700000000.000000)"
+        );
+
+        ut_info!("(test_get_line_string_wkt) success");
+    }
+
+    #[tokio::test]
+    async fn test_get_polygon_wkt() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_polygon_wkt) start");
+
+        let polygon = Polygon::new(
+            geo_types::LineString::from(vec![(4.9, 52.3), (5.0, 52.3), (5.0, 52.4), (4.9, 52.3)]),
+            vec![],
+        );
+        assert_eq!(
+            get_polygon_wkt(&polygon),
+            "POLYGON((4.900000000000000 52.299999999999997,5.000000000000000 52.299999999999997,5.000000000000000 52.399999999999999,4.900000000000000 52.299999999999997))"
+        );
+
+        let polygon = Polygon::new(
+            geo_types::LineString::from(vec![(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 0.0)]),
+            vec![geo_types::LineString::from(vec![
+                (1.0, 1.0),
+                (2.0, 1.0),
+                (2.0, 2.0),
+                (1.0, 1.0),
+            ])],
+        );
+        assert_eq!(
+            get_polygon_wkt(&polygon),
+            "POLYGON((0.000000000000000 0.000000000000000,4.000000000000000 0.000000000000000,4.000000000000000 4.000000000000000,0.000000000000000 0.000000000000000),(1.000000000000000 1.000000000000000,2.000000000000000 1.000000000000000,2.000000000000000 2.000000000000000,1.000000000000000 1.000000000000000))"
+        );
+
+        ut_info!("(test_get_polygon_wkt) success");
+    }
+
+    #[tokio::test]
+    async fn test_geo_line_string_ewkb() {
+        crate::get_log_handle().await;
+        ut_info!("(test_geo_line_string_ewkb) start");
+
+        // little endian LINESTRING ZM with SRID and 2 points
+        let mut raw: Vec<u8> = vec![0x01];
+        raw.extend((0x0000_0002 | EWKB_Z_FLAG | EWKB_M_FLAG | 0x2000_0000_u32).to_le_bytes());
+        raw.extend(SRID.to_le_bytes());
+        raw.extend(2_u32.to_le_bytes());
+        for (latitude, altitude, time) in [(52.0, 0.0, 1700000000.25), (53.0, 120.5, 1700000060.5)]
+        {
+            for value in [4.9164, latitude, altitude, time] {
+                raw.extend(f64::to_le_bytes(value));
+            }
+        }
+
+        let result = GeoLineString::from_sql(&Type::BYTEA, &raw);
+        assert!(result.is_ok());
+        let line_string = result.unwrap();
+        assert_eq!(
+            line_string.points,
+            vec![
+                GeoPoint {
+                    longitude: 4.9164,
+                    latitude: 52.0,
+                    altitude: Some(0.0),
+                },
+                GeoPoint {
+                    longitude: 4.9164,
+                    latitude: 53.0,
+                    altitude: Some(120.5),
+                },
+            ]
+        );
+        assert_eq!(
+            line_string.times,
+            vec![
+                Timestamp {
+                    seconds: 1700000000,
+                    nanos: 250_000_000,
+                },
+                Timestamp {
+                    seconds: 1700000060,
+                    nanos: 500_000_000,
+                },
+            ]
+        );
+
+        ut_info!("(test_geo_line_string_ewkb) success");
+    }
+
+    #[tokio::test]
+    async fn test_geo_line_string_ewkb_single_point() {
+        crate::get_log_handle().await;
+        ut_info!("(test_geo_line_string_ewkb_single_point) start");
+
+        // little endian POINT M with SRID, as stored for a line string with a single point
+        let mut raw: Vec<u8> = vec![0x01];
+        raw.extend((EWKB_POINT_TYPE | EWKB_M_FLAG | 0x2000_0000_u32).to_le_bytes());
+        raw.extend(SRID.to_le_bytes());
+        for value in [4.9164, 52.0, 1700000000.0] {
+            raw.extend(f64::to_le_bytes(value));
+        }
+
+        let result = GeoLineString::from_sql(&Type::BYTEA, &raw);
+        assert!(result.is_ok());
+        let line_string = result.unwrap();
+        assert_eq!(
+            line_string.points,
+            vec![GeoPoint {
+                longitude: 4.9164,
+                latitude: 52.0,
+                altitude: None,
+            }]
+        );
+        assert_eq!(
+            line_string.times,
+            vec![Timestamp {
+                seconds: 1700000000,
+                nanos: 0,
+            }]
+        );
+
+        // other geometry types are rejected
+        let mut raw: Vec<u8> = vec![0x01];
+        raw.extend(3_u32.to_le_bytes());
+        raw.extend(1_u32.to_le_bytes());
+        raw.extend(0_u32.to_le_bytes());
+        assert!(GeoLineString::from_sql(&Type::BYTEA, &raw).is_err());
+
+        ut_info!("(test_geo_line_string_ewkb_single_point) success");
+    }
+
+    #[tokio::test]
+    #[cfg(not(any(feature = "stub_backends")))]
+    async fn test_geo_line_string_round_trip() {
+        crate::get_log_handle().await;
+        ut_info!("(test_geo_line_string_round_trip) start");
+
+        let timestamp = |seconds: i64| Timestamp { seconds, nanos: 0 };
+        let client = crate::postgres::get_psql_client().await.unwrap();
+        for line_string in [
+            GeoLineString {
+                points: vec![get_point(None)],
+                times: vec![],
+            },
+            GeoLineString {
+                points: vec![get_point(Some(120.5))],
+                times: vec![timestamp(1700000000)],
+            },
+            GeoLineString {
+                points: vec![get_point(None), get_point(Some(120.5))],
+                times: vec![timestamp(1700000000), timestamp(1700000060)],
+            },
+        ] {
+            let query = format!(
+                "SELECT {}",
+                get_geom_from_text_sql(&get_line_string_wkt(&line_string))
+            );
+            let row = client.query_one(&query, &[]).await.unwrap();
+            let result: GeoLineString = row.get(0);
+            assert_eq!(result.points.len(), line_string.points.len());
+            assert_eq!(result.times, line_string.times);
+        }
+
+        ut_info!("(test_geo_line_string_round_trip) success");
+    }
+}
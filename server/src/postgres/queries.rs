@@ -4,13 +4,32 @@ use super::get_psql_client;
 use super::ArrErr;
 use crate::postgres::{PsqlField, PsqlFieldSend};
 use crate::resources::base::Resource;
+use deadpool_postgres::Transaction;
 use std::collections::HashMap;
 use std::vec;
 use tokio_postgres::Row;
 use uuid::Uuid;
 
 /// Generic get by id function to get a row using the UUID column
+///
+/// Soft-deleted rows (`deleted_at` is set) will not be returned.
 pub async fn get_by_id<V>(id: &Uuid) -> Result<Row, ArrErr>
+where
+    V: Resource + super::simple_resource::PsqlType,
+{
+    get_by_id_with_options::<V>(id, false).await
+}
+
+/// Generic get by id function to get a row using the UUID column
+/// including soft-deleted rows, can be used for audit purposes
+pub async fn get_by_id_include_deleted<V>(id: &Uuid) -> Result<Row, ArrErr>
+where
+    V: Resource + super::simple_resource::PsqlType,
+{
+    get_by_id_with_options::<V>(id, true).await
+}
+
+async fn get_by_id_with_options<V>(id: &Uuid, include_deleted: bool) -> Result<Row, ArrErr>
 where
     V: Resource + super::simple_resource::PsqlType,
 {
@@ -19,10 +38,13 @@ where
     let definition = V::get_definition();
     let id_col = V::try_get_id_field()?;
     let client = get_psql_client().await?;
-    let query = format!(
+    let mut query = format!(
         r#"SELECT * FROM "{}" WHERE "{}" = $1"#,
         definition.psql_table, id_col
     );
+    if !include_deleted && definition.has_field("deleted_at") {
+        query.push_str(r#" AND "deleted_at" IS NULL"#);
+    }
     let stmt = client.prepare_cached(&query).await?;
 
     psql_info!(
@@ -31,11 +53,27 @@ where
         id
     );
     psql_debug!("(get_by_id) [{}].", &query);
-    match client.query_one(&stmt, &[&id]).await {
-        Ok(row) => Ok(row),
+    match client.query_opt(&stmt, &[&id]).await {
+        Ok(Some(row)) => Ok(row),
+        Ok(None) => Err(ArrErr::not_found(format!(
+            "No [{}] found for specified uuid: {}",
+            definition.psql_table, id
+        ))),
         Err(e) => Err(e.into()),
     }
 }
+
+/// Returns the `(table, column)` pairs of all foreign keys referencing the provided table
+pub(crate) async fn get_referencing_columns(
+    transaction: &Transaction<'_>,
+    table: &str,
+) -> Result<Vec<(String, String)>, ArrErr> {
+    let query = r#"SELECT DISTINCT kcu."table_name", kcu."column_name" FROM information_schema.table_constraints tc JOIN information_schema.key_column_usage kcu ON kcu."constraint_schema" = tc."constraint_schema" AND kcu."table_name" = tc."table_name" AND kcu."constraint_name" = tc."constraint_name" JOIN information_schema.constraint_column_usage ccu ON ccu."constraint_schema" = tc."constraint_schema" AND ccu."constraint_name" = tc."constraint_name" WHERE tc."constraint_type" = 'FOREIGN KEY' AND ccu."table_name" = $1 AND tc."table_name" <> $1"#;
+    psql_debug!("(get_referencing_columns) [{}].", query);
+    let rows = transaction.query(query, &[&table]).await?;
+    Ok(rows.iter().map(|row| (row.get(0), row.get(1))).collect())
+}
+
 /// Generic get for id function to get rows for the provided key fields
 /// Since this is a linked resource, the id is expected to be given as a [Vec\<FieldValuePair\>]
 /// to specify the id_column / value pairs to match
@@ -78,8 +116,12 @@ where
     for field in params.iter() {
         ref_params.push(field.as_ref());
     }
-    match client.query_one(&stmt, &ref_params[..]).await {
-        Ok(row) => Ok(row),
+    match client.query_opt(&stmt, &ref_params[..]).await {
+        Ok(Some(row)) => Ok(row),
+        Ok(None) => Err(ArrErr::not_found(format!(
+            "No [{}] found for specified uuids: {:?}",
+            definition.psql_table, ids
+        ))),
         Err(e) => Err(e.into()),
     }
 }
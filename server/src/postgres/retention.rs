@@ -0,0 +1,149 @@
+//! Retention of time series data
+//!
+//! Records of resources providing a retention column expire once their timestamp is older than
+//! the configured retention window. Expired records are removed by CockroachDB's row-level TTL job,
+//! which deletes them in batches in the background using the resource's retention column as
+//! expiration expression (`ttl_expiration_expression`, requires CockroachDB v22.2 or newer).
+//! No changes are recorded for expired records.
+//!
+//! Dropping time-range partitions is not an option here: CockroachDB table partitions only control
+//! replica placement, must be a prefix of the primary key and can not be dropped to remove data.
+//!
+//! Recorded changes are removed periodically in batches by the [`retention`] task.
+
+use super::simple_resource::PsqlType;
+use super::watch::purge_changes_before;
+use super::{get_psql_client, ArrErr};
+use crate::grpc::server::adsb;
+use crate::resources::base::ResourceObject;
+
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+
+/// Returns the query setting the row-level TTL of the table, expiring records `retention_days` days after
+/// their `time_col` value. The row-level TTL will be removed for a retention window of `0` days.
+fn get_row_level_ttl_query(table: &str, time_col: &str, retention_days: u32) -> String {
+    match retention_days {
+        0 => format!(r#"ALTER TABLE "{table}" RESET (ttl)"#),
+        days => format!(
+            r#"ALTER TABLE "{table}" SET (ttl_expiration_expression = '"{time_col}" + INTERVAL ''{days} days''')"#
+        ),
+    }
+}
+
+/// Configures the row-level TTL of the resource's table to expire records `retention_days` days after their
+/// retention column value
+///
+/// Nothing will be changed if the resource has no retention column.
+///
+/// # Errors
+///
+/// Returns [`ArrErr`] from [`PoolError`](deadpool::managed::PoolError) if no client connection could be returned from the connection [`Pool`](deadpool::managed::Pool)
+/// Returns [`ArrErr`] Database Error if the row-level TTL could not be set
+pub async fn set_row_level_ttl<T>(retention_days: u32) -> Result<(), ArrErr>
+where
+    T: PsqlType,
+{
+    let Some(time_col) = T::get_retention_col() else {
+        return Ok(());
+    };
+    let query = get_row_level_ttl_query(&T::get_psql_table(), &time_col, retention_days);
+    psql_debug!("(set_row_level_ttl) [{}].", query);
+    let client = get_psql_client().await?;
+    client.execute(&query, &[]).await?;
+    Ok(())
+}
+
+/// Returns the start of the retention window of `retention_days` days, [`None`] if all data should be kept
+fn get_retention_cutoff(retention_days: u32) -> Option<DateTime<Utc>> {
+    match retention_days {
+        0 => None,
+        days => Some(Utc::now() - chrono::Duration::days(days.into())),
+    }
+}
+
+/// Configures the expiry of ADS-B telemetry (see [`set_row_level_ttl`]) and periodically removes the recorded
+/// changes older than their retention window (see [`purge_changes_before`])
+///
+/// Nothing will be removed for a retention window of `0` days.
+#[cfg(not(tarpaulin_include))]
+// no_coverage: Can not be tested in unittest, needs a running database
+pub async fn retention(
+    interval: Duration,
+    adsb_retention_days: u32,
+    change_log_retention_days: u32,
+) {
+    psql_info!(
+        "(retention) Keeping [{}] days of ADS-B telemetry, removing expired changes with interval [{:?}] keeping [{}] days.",
+        adsb_retention_days,
+        interval,
+        change_log_retention_days
+    );
+    if let Err(e) = set_row_level_ttl::<ResourceObject<adsb::Data>>(adsb_retention_days).await {
+        psql_error!("(retention) Could not set ADS-B telemetry expiry: {}", e);
+    }
+    loop {
+        if let Some(before) = get_retention_cutoff(change_log_retention_days) {
+            match purge_changes_before(&before).await {
+                Ok(removed) => psql_debug!(
+                    "(retention) Removed [{}] changes recorded before [{}].",
+                    removed,
+                    before
+                ),
+                Err(e) => psql_error!("(retention) Could not remove expired changes: {}", e),
+            }
+        }
+        tokio::time::sleep(interval).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::base::Resource;
+
+    #[tokio::test]
+    async fn test_get_row_level_ttl_query() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_row_level_ttl_query) start");
+
+        assert_eq!(
+            get_row_level_ttl_query("adsb", "network_timestamp", 30),
+            String::from(
+                r#"ALTER TABLE "adsb" SET (ttl_expiration_expression = '"network_timestamp" + INTERVAL ''30 days''')"#
+            )
+        );
+        assert_eq!(
+            get_row_level_ttl_query("adsb", "network_timestamp", 0),
+            String::from(r#"ALTER TABLE "adsb" RESET (ttl)"#)
+        );
+
+        ut_info!("(test_get_row_level_ttl_query) success");
+    }
+
+    #[tokio::test]
+    async fn test_get_retention_cutoff() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_retention_cutoff) start");
+
+        assert_eq!(get_retention_cutoff(0), None);
+        let cutoff = get_retention_cutoff(30).unwrap();
+        let expected = Utc::now() - chrono::Duration::days(30);
+        assert!((expected - cutoff).num_seconds().abs() <= 1);
+
+        ut_info!("(test_get_retention_cutoff) success");
+    }
+
+    #[tokio::test]
+    async fn test_adsb_retention_col() {
+        crate::get_log_handle().await;
+        ut_info!("(test_adsb_retention_col) start");
+
+        assert_eq!(
+            ResourceObject::<adsb::Data>::get_retention_col(),
+            Some(String::from("network_timestamp"))
+        );
+
+        ut_info!("(test_adsb_retention_col) success");
+    }
+}
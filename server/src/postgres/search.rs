@@ -1,15 +1,21 @@
+use super::postgis::SRID;
 use super::{get_psql_client, ArrErr, PsqlField, PsqlFieldType};
 use crate::grpc::server::{
-    search::get_single_search_value, AdvancedSearchFilter, ComparisonOperator, PredicateOperator,
-    SortOption, SortOrder,
+    search::get_single_search_value, AdvancedSearchFilter, ComparisonOperator, FilterOption,
+    PredicateOperator, SortOption, SortOrder,
 };
 use crate::postgres::PsqlFieldSend;
-use crate::resources::base::Resource;
+use crate::resources::base::{Resource, ResourceDefinition};
 
 use chrono::{DateTime, Utc};
+use deadpool::managed::Object;
+use deadpool_postgres::Manager;
+use futures::Stream;
 use postgres_types::ToSql;
 use std::collections::VecDeque;
-use tokio_postgres::Row;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio_postgres::{Row, RowStream};
 use uuid::Uuid;
 
 /// struct to save search col values while processing the [AdvancedSearchFilter](crate::resources::AdvancedSearchFilter)
@@ -29,6 +35,66 @@ impl SearchCol {
     }
 }
 
+/// Search results for the requested page, including pagination details
+#[derive(Debug)]
+pub struct SearchResult {
+    /// rows found for the requested page
+    pub rows: Vec<Row>,
+    /// total number of records matching the search filters, regardless of pagination
+    pub total_count: i64,
+    /// opaque cursor pointing to the last returned row, empty if there are no more results
+    pub next_page_token: String,
+    /// distance in meters of each row to the geometry of the distance sort option, empty if not sorted by distance
+    pub distances: Vec<f64>,
+}
+
+/// Column alias used for the distance computed for a distance sort option
+pub const DISTANCE_COL: &str = "distance";
+
+/// Geometry column and Well Known Text geometry used to sort search results on distance
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct DistanceSort {
+    /// the geometry column name as known in the database
+    pub col_name: String,
+    /// the geometry to compute the distance to, in Well Known Text format
+    pub value: String,
+}
+
+/// Column and direction used to sort search results
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct SortKey {
+    /// the sort column name as known in the database
+    pub col_name: String,
+    /// the [postgres_types::Type] of the column
+    pub col_type: PsqlFieldType,
+    /// the [SortOrder] to use for the column
+    pub sort_order: SortOrder,
+}
+
+/// Stream of rows found by [PsqlSearch::advanced_search_stream]
+///
+/// Holds on to the database client until the stream is dropped, so the
+/// connection won't be returned to the pool while rows are still being received.
+pub struct SearchRowStream {
+    _client: Object<Manager>,
+    rows: Pin<Box<RowStream>>,
+}
+impl Stream for SearchRowStream {
+    type Item = Result<Row, ArrErr>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rows
+            .as_mut()
+            .poll_next(cx)
+            .map(|row| row.map(|row| row.map_err(ArrErr::from)))
+    }
+}
+impl std::fmt::Debug for SearchRowStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SearchRowStream").finish_non_exhaustive()
+    }
+}
+
 /// Trait implementing advanced search function for resources
 #[tonic::async_trait]
 pub trait PsqlSearch
@@ -37,62 +103,94 @@ where
 {
     /// Generic search function based on advanced filters
     async fn advanced_search(filter: AdvancedSearchFilter) -> Result<Vec<Row>, ArrErr> {
+        Ok(Self::_search(filter, false).await?.rows)
+    }
+
+    /// Generic search function based on advanced filters
+    ///
+    /// Next to the rows found for the requested page, the result will contain
+    /// the total number of matching records and a cursor for the next page.
+    async fn advanced_search_paged(filter: AdvancedSearchFilter) -> Result<SearchResult, ArrErr> {
+        Self::_search(filter, true).await
+    }
+
+    /// Generic search function based on advanced filters, streaming the resulting rows
+    ///
+    /// Rows are returned as they are received from the database, making it suitable for
+    /// large result sets. Filters, sort options and the `page_token` are applied,
+    /// but no page limit will be used. When sorted by distance, each row will contain
+    /// the computed distance in the [`DISTANCE_COL`] column.
+    async fn advanced_search_stream(
+        filter: AdvancedSearchFilter,
+    ) -> Result<SearchRowStream, ArrErr> {
         let definition = Self::get_definition();
         let client = get_psql_client().await?;
 
-        let mut filter_params: Vec<SearchCol> = vec![];
-        let mut sort_expressions: Vec<String> = vec![];
-        let mut search_query = format!(r#"SELECT * FROM "{}""#, definition.psql_table);
-        let mut next_param_index: i32 = 1;
+        let (mut where_clauses, mut params, next_param_index) =
+            Self::_get_filter_clauses(&definition, &filter)?;
+        let distance_sort = get_distance_sort(&definition, &filter.order_by)?;
+        if let Some(distance_sort) = &distance_sort {
+            where_clauses.push(format!(r#""{}" IS NOT NULL"#, distance_sort.col_name));
+        }
+        let (distance_str, next_param_index) =
+            add_distance_param(distance_sort.as_ref(), &mut params, next_param_index);
+        let sort_keys = Self::_get_sort_keys(&filter.order_by)?;
+        add_keyset_clause(
+            &filter.page_token,
+            &sort_keys,
+            &mut where_clauses,
+            &mut params,
+            next_param_index,
+        )?;
+        let search_query = get_search_query_str(
+            &definition,
+            distance_str.as_deref(),
+            &where_clauses,
+            &sort_keys,
+        );
+        let search_sql = &client.prepare_cached(&search_query).await?;
 
-        // Go over all the filters and compose the search query string.
-        for filter in filter.filters.iter() {
-            let col = filter.search_field.clone();
-
-            // Check if provided search col is part of the primary key
-            let field_type = if definition.get_psql_id_cols().contains(&col) {
-                PsqlFieldType::UUID
-            } else {
-                definition.try_get_field(&col)?.field_type.clone()
-            };
+        psql_info!(
+            "(advanced_search_stream) Streaming table [{}] with query [{}].",
+            definition.psql_table,
+            search_query
+        );
+        psql_debug!("(advanced_search_stream) Params: {:?}", params);
 
-            let operator: PredicateOperator =
-                match PredicateOperator::try_from(filter.predicate_operator) {
-                    Ok(val) => val,
-                    Err(e) => {
-                        return Err(ArrErr::Error(format!(
-                            "Can't convert i32 [{}] into PredicateOperator Enum value: {}",
-                            filter.predicate_operator, e
-                        )));
-                    }
-                };
-            let comparison_operator = match filter.comparison_operator {
-                Some(operator) => match ComparisonOperator::try_from(operator) {
-                    Ok(operator) => operator.as_str_name(),
-                    Err(e) => {
-                        return Err(ArrErr::Error(format!(
-                            "Can't convert i32 [{}] into ComparisonOperator Enum value: {}",
-                            operator, e
-                        )));
-                    }
-                },
-                None => "WHERE",
-            };
+        let mut ref_params: Vec<&PsqlField> = vec![];
+        for field in params.iter() {
+            ref_params.push(field.as_ref());
+        }
+        let rows = client
+            .query_raw(search_sql, ref_params)
+            .await
+            .map_err(ArrErr::from)?;
 
-            let (filter_str, cur_param_index) = get_filter_str(
-                SearchCol {
-                    col_name: col,
-                    col_type: field_type,
-                    value: None,
-                },
-                filter.search_value.clone(),
-                &mut filter_params,
-                next_param_index,
-                operator,
-            )?;
+        Ok(SearchRowStream {
+            _client: client,
+            rows: Box::pin(rows),
+        })
+    }
+
+    /// Composes the `WHERE` clauses and query parameters for the filters of the provided [AdvancedSearchFilter].
+    /// Returns the clauses, the parameters and the index to use for the next query parameter.
+    /// for internal use
+    fn _get_filter_clauses(
+        definition: &ResourceDefinition,
+        filter: &AdvancedSearchFilter,
+    ) -> Result<(Vec<String>, Vec<Box<PsqlFieldSend>>, i32), ArrErr> {
+        let mut filter_params: Vec<SearchCol> = vec![];
+        // Go over all the filters and compose the search query string.
+        let (filter_query, next_param_index) =
+            get_filter_group_str(definition, &filter.filters, &mut filter_params, 1, 0)?;
 
-            search_query.push_str(&format!(" {} {} ", comparison_operator, filter_str));
-            next_param_index = cur_param_index;
+        // Soft-deleted records should not be returned, unless explicitly requested.
+        let mut where_clauses: Vec<String> = vec![];
+        if !filter.include_deleted && definition.has_field("deleted_at") {
+            where_clauses.push(r#""deleted_at" IS NULL"#.to_owned());
+        }
+        if !filter_query.is_empty() {
+            where_clauses.push(format!("({})", filter_query));
         }
 
         // Validate filter params making sure they are conform the column field type.
@@ -102,27 +200,83 @@ where
             params.push(Self::_param_from_search_col(search_col)?);
         }
 
-        // Check if we need to order the results on given parameters
-        if !filter.order_by.is_empty() {
-            for sort_option in filter.order_by.iter() {
-                if definition.has_field(&sort_option.sort_field) {
-                    sort_expressions.push(try_get_sort_str(sort_option)?);
-                } else {
-                    psql_error!(
-                        "(advanced_search) Invalid field provided [{}] for sort order in advanced_search.",
-                        sort_option.sort_field
-                    );
-                }
-            }
-            search_query.push_str(&format!(" ORDER BY {}", sort_expressions.join(",")));
+        Ok((where_clauses, params, next_param_index))
+    }
+
+    /// Composes and executes the search query for the provided filter.
+    /// for internal use
+    async fn _search(
+        filter: AdvancedSearchFilter,
+        with_count: bool,
+    ) -> Result<SearchResult, ArrErr> {
+        let definition = Self::get_definition();
+        let client = get_psql_client().await?;
+
+        let (mut where_clauses, mut params, mut next_param_index) =
+            Self::_get_filter_clauses(&definition, &filter)?;
+
+        // Records without a geometry can not be sorted on distance and will be skipped
+        let distance_sort = get_distance_sort(&definition, &filter.order_by)?;
+        if let Some(distance_sort) = &distance_sort {
+            where_clauses.push(format!(r#""{}" IS NOT NULL"#, distance_sort.col_name));
         }
-        if filter.results_per_page >= 0 && filter.page_number > 0 {
-            let offset: i64 = (filter.results_per_page * (filter.page_number - 1)).into();
+
+        // The total count should not be affected by the requested page
+        let total_count = if with_count {
+            let mut count_query = format!(r#"SELECT COUNT(*) FROM "{}""#, definition.psql_table);
+            if !where_clauses.is_empty() {
+                count_query.push_str(&format!(" WHERE {}", where_clauses.join(" AND ")));
+            }
+            psql_debug!("(advanced_search) Count query [{}].", count_query);
+            let mut ref_params: Vec<&PsqlField> = vec![];
+            for field in params.iter() {
+                ref_params.push(field.as_ref());
+            }
+            let count_sql = &client.prepare_cached(&count_query).await?;
+            client
+                .query_one(count_sql, &ref_params[..])
+                .await
+                .map_err(ArrErr::from)?
+                .try_get::<usize, i64>(0)
+                .map_err(ArrErr::from)?
+        } else {
+            0
+        };
+
+        // The distance is computed in a sub query, so it can be used for sorting and keyset pagination.
+        let (distance_str, param_index) =
+            add_distance_param(distance_sort.as_ref(), &mut params, next_param_index);
+        next_param_index = param_index;
+
+        // The results will always be sorted on the resource's id columns as well,
+        // making sure the order is stable so it can be used for keyset pagination.
+        let sort_keys = Self::_get_sort_keys(&filter.order_by)?;
+        next_param_index = add_keyset_clause(
+            &filter.page_token,
+            &sort_keys,
+            &mut where_clauses,
+            &mut params,
+            next_param_index,
+        )?;
+        let mut search_query = get_search_query_str(
+            &definition,
+            distance_str.as_deref(),
+            &where_clauses,
+            &sort_keys,
+        );
+
+        // Request one more result than needed so we know if there is a next page
+        let paginate = filter.results_per_page >= 0
+            && (filter.page_number > 0 || !filter.page_token.is_empty());
+        if paginate {
             search_query.push_str(&format!(" LIMIT ${}", next_param_index));
-            params.push(Box::new(filter.results_per_page as i64));
+            params.push(Box::new(filter.results_per_page as i64 + 1));
             next_param_index += 1;
-            search_query.push_str(&format!(" OFFSET ${}", next_param_index));
-            params.push(Box::new(offset));
+            if filter.page_token.is_empty() {
+                let offset = get_page_offset(filter.results_per_page, filter.page_number)?;
+                search_query.push_str(&format!(" OFFSET ${}", next_param_index));
+                params.push(Box::new(offset));
+            }
         }
         let search_sql = &client.prepare_cached(&search_query).await?;
 
@@ -137,12 +291,94 @@ where
         for field in params.iter() {
             ref_params.push(field.as_ref());
         }
-        let rows = client
+        let mut rows = client
             .query(search_sql, &ref_params[..])
             .await
             .map_err(ArrErr::from)?;
 
-        Ok(rows)
+        let mut next_page_token = String::new();
+        if paginate && rows.len() > filter.results_per_page as usize {
+            rows.truncate(filter.results_per_page as usize);
+            if let Some(last) = rows.last() {
+                next_page_token = encode_page_token(last, &sort_keys)?;
+            }
+        }
+
+        let mut distances: Vec<f64> = vec![];
+        if distance_sort.is_some() {
+            for row in rows.iter() {
+                distances.push(row.try_get::<&str, f64>(DISTANCE_COL)?);
+            }
+        }
+
+        Ok(SearchResult {
+            rows,
+            total_count,
+            next_page_token,
+            distances,
+        })
+    }
+
+    /// Returns the [`SortKey`]s for the provided sort options, followed by the resource's id columns.
+    /// for internal use
+    fn _get_sort_keys(order_by: &[SortOption]) -> Result<Vec<SortKey>, ArrErr> {
+        let definition = Self::get_definition();
+        let mut sort_keys: Vec<SortKey> = vec![];
+        for sort_option in order_by.iter() {
+            let sort_order = try_get_sort_order(sort_option)?;
+            if sort_option.distance_from.is_some() {
+                sort_keys.push(SortKey {
+                    col_name: DISTANCE_COL.to_owned(),
+                    col_type: PsqlFieldType::FLOAT8,
+                    sort_order,
+                });
+                continue;
+            }
+            match definition.fields.get(&sort_option.sort_field) {
+                Some(field) if !is_cursor_type(&field.field_type) => {
+                    let error = format!(
+                        "Sort column [{}] of type [{}] is not supported.",
+                        sort_option.sort_field,
+                        field.field_type.name()
+                    );
+                    psql_error!("(advanced_search) {}", error);
+                    return Err(ArrErr::invalid_argument(error));
+                }
+                Some(field) => sort_keys.push(SortKey {
+                    col_name: sort_option.sort_field.clone(),
+                    col_type: field.field_type.clone(),
+                    sort_order,
+                }),
+                None if definition
+                    .get_psql_id_cols()
+                    .contains(&sort_option.sort_field) =>
+                {
+                    sort_keys.push(SortKey {
+                        col_name: sort_option.sort_field.clone(),
+                        col_type: PsqlFieldType::UUID,
+                        sort_order,
+                    })
+                }
+                None => {
+                    let error = format!(
+                        "Invalid field provided [{}] for sort order in advanced_search.",
+                        sort_option.sort_field
+                    );
+                    psql_error!("(advanced_search) {}", error);
+                    return Err(ArrErr::invalid_argument(error));
+                }
+            }
+        }
+        for id_col in definition.get_psql_id_cols() {
+            if !sort_keys.iter().any(|key| key.col_name == id_col) {
+                sort_keys.push(SortKey {
+                    col_name: id_col,
+                    col_type: PsqlFieldType::UUID,
+                    sort_order: SortOrder::Asc,
+                });
+            }
+        }
+        Ok(sort_keys)
     }
 
     /// Converts the passed string value for the search field into the right Sql type.
@@ -153,7 +389,7 @@ where
             None => {
                 let err = format!("Search col [{}] has no value: {:?}", col.col_name, col);
                 psql_error!("(_param_from_search_col) {}", err);
-                return Err(ArrErr::Error(err));
+                return Err(ArrErr::invalid_argument(err));
             }
         };
 
@@ -167,7 +403,7 @@ where
                             col.col_name, col_val, e
                         );
                         psql_error!("(_param_from_search_col) {}", err);
-                        return Err(ArrErr::Error(err));
+                        return Err(ArrErr::invalid_argument(err));
                     }
                 };
                 match Self::get_enum_string_val(&col.col_name.clone(), int_val) {
@@ -178,7 +414,7 @@ where
                             col.col_name, col_val, int_val
                         );
                         psql_error!("(_param_from_search_col) {}", err);
-                        Err(ArrErr::Error(err))
+                        Err(ArrErr::invalid_argument(err))
                     }
                 }
             }
@@ -187,6 +423,116 @@ where
     }
 }
 
+/// Maximum nesting depth allowed for [FilterGroup](crate::grpc::server::FilterGroup)s
+const MAX_FILTER_GROUP_DEPTH: usize = 10;
+
+/// Composes the filter string for the provided list of [FilterOption]s.
+///
+/// Filters are combined using their [ComparisonOperator], following SQL operator precedence,
+/// so `a OR b AND c` will be evaluated as `a OR (b AND c)`.
+/// Nested [FilterGroup](crate::grpc::server::FilterGroup)s are compiled recursively and wrapped in parentheses,
+/// which can be used to explicitly group filters.
+///
+/// # Errors
+///
+/// Returns [ArrErr] if a search field is unknown for the resource, an operator
+/// could not be converted or the maximum nesting depth is exceeded.
+pub(crate) fn get_filter_group_str(
+    definition: &ResourceDefinition,
+    filters: &[FilterOption],
+    params: &mut Vec<SearchCol>,
+    cur_param_index: i32,
+    depth: usize,
+) -> Result<(String, i32), ArrErr> {
+    if depth > MAX_FILTER_GROUP_DEPTH {
+        return Err(ArrErr::invalid_argument(format!(
+            "Filter groups can not be nested more than [{}] levels deep.",
+            MAX_FILTER_GROUP_DEPTH
+        )));
+    }
+
+    let mut filter_query = String::new();
+    let mut next_param_index = cur_param_index;
+    for filter in filters.iter() {
+        let comparison_operator = match filter.comparison_operator {
+            Some(operator) => match ComparisonOperator::try_from(operator) {
+                Ok(operator) => operator.as_str_name(),
+                Err(e) => {
+                    return Err(ArrErr::invalid_argument(format!(
+                        "Can't convert i32 [{}] into ComparisonOperator Enum value: {}",
+                        operator, e
+                    )));
+                }
+            },
+            None => "AND",
+        };
+
+        let (filter_str, cur_param_index) = match &filter.group {
+            Some(group) => {
+                let (group_str, cur_param_index) = get_filter_group_str(
+                    definition,
+                    &group.filters,
+                    params,
+                    next_param_index,
+                    depth + 1,
+                )?;
+                if group_str.is_empty() {
+                    // Nothing to filter on, ignore empty groups
+                    continue;
+                }
+                (format!("({})", group_str), cur_param_index)
+            }
+            None => {
+                let col = filter.search_field.clone();
+
+                // Check if provided search col is part of the primary key
+                let field_type = if definition.get_psql_id_cols().contains(&col) {
+                    PsqlFieldType::UUID
+                } else {
+                    definition
+                        .try_get_field(&col)
+                        .map_err(|e| ArrErr::invalid_argument(e.to_string()))?
+                        .field_type
+                        .clone()
+                };
+
+                let operator: PredicateOperator =
+                    match PredicateOperator::try_from(filter.predicate_operator) {
+                        Ok(val) => val,
+                        Err(e) => {
+                            return Err(ArrErr::invalid_argument(format!(
+                                "Can't convert i32 [{}] into PredicateOperator Enum value: {}",
+                                filter.predicate_operator, e
+                            )));
+                        }
+                    };
+
+                let (filter_str, cur_param_index) = get_filter_str(
+                    SearchCol {
+                        col_name: col,
+                        col_type: field_type,
+                        value: None,
+                    },
+                    filter.search_value.clone(),
+                    params,
+                    next_param_index,
+                    operator,
+                )?;
+                (filter_str.trim().to_owned(), cur_param_index)
+            }
+        };
+
+        if filter_query.is_empty() {
+            filter_query = filter_str;
+        } else {
+            filter_query = format!("{} {} {}", filter_query, comparison_operator, filter_str);
+        }
+        next_param_index = cur_param_index;
+    }
+
+    Ok((filter_query, next_param_index))
+}
+
 pub(crate) fn get_filter_str(
     mut search_col: SearchCol,
     values: Vec<String>,
@@ -203,14 +549,14 @@ pub(crate) fn get_filter_str(
     match operator {
         PredicateOperator::Equals => {
             filter_str = format!(r#" "{}" = ${}"#, search_col.col_name, next_param_index);
-            let val: String = get_single_search_value(&values).map_err(ArrErr::Error)?;
+            let val: String = get_single_search_value(&values).map_err(ArrErr::invalid_argument)?;
             search_col.set_value(val);
             params.push(search_col.clone());
             next_param_index += 1;
         }
         PredicateOperator::NotEquals => {
             filter_str = format!(r#" "{}" <> ${}"#, search_col.col_name, next_param_index);
-            let val: String = get_single_search_value(&values).map_err(ArrErr::Error)?;
+            let val: String = get_single_search_value(&values).map_err(ArrErr::invalid_argument)?;
             search_col.set_value(val);
             params.push(search_col.clone());
             next_param_index += 1;
@@ -251,7 +597,7 @@ pub(crate) fn get_filter_str(
             let min = match values.pop_front() {
                 Some(val) => val,
                 None => {
-                    return Err(ArrErr::Error(
+                    return Err(ArrErr::invalid_argument(
                             "Error in advanced search parameters. Between operator is expecting 2 values but got zero.".to_string()
                         ));
                 }
@@ -269,7 +615,7 @@ pub(crate) fn get_filter_str(
             let max = match values.pop_front() {
                 Some(val) => val,
                 None => {
-                    return Err(ArrErr::Error(
+                    return Err(ArrErr::invalid_argument(
                             "Error in advanced search parameters. Between operator is expecting 2 values but got only one.".to_string()
                         ));
                 }
@@ -291,7 +637,8 @@ pub(crate) fn get_filter_str(
                 r#" "{}"::text ILIKE ${}"#,
                 search_col.col_name, next_param_index
             );
-            search_col.set_value(get_single_search_value(&values).map_err(ArrErr::Error)?);
+            search_col
+                .set_value(get_single_search_value(&values).map_err(ArrErr::invalid_argument)?);
             params.push(search_col.clone());
             next_param_index += 1;
         }
@@ -300,86 +647,451 @@ pub(crate) fn get_filter_str(
                 r#" "{}"::text LIKE ${}"#,
                 search_col.col_name, next_param_index
             );
-            search_col.set_value(get_single_search_value(&values).map_err(ArrErr::Error)?);
+            search_col
+                .set_value(get_single_search_value(&values).map_err(ArrErr::invalid_argument)?);
             params.push(search_col.clone());
             next_param_index += 1;
         }
         PredicateOperator::Greater => {
             filter_str = format!(r#" "{}" > ${}"#, search_col.col_name, next_param_index);
-            let val: String = get_single_search_value(&values).map_err(ArrErr::Error)?;
+            let val: String = get_single_search_value(&values).map_err(ArrErr::invalid_argument)?;
             search_col.set_value(val);
             params.push(search_col.clone());
             next_param_index += 1;
         }
         PredicateOperator::GreaterOrEqual => {
             filter_str = format!(r#" "{}" >= ${}"#, search_col.col_name, next_param_index);
-            let val: String = get_single_search_value(&values).map_err(ArrErr::Error)?;
+            let val: String = get_single_search_value(&values).map_err(ArrErr::invalid_argument)?;
             search_col.set_value(val);
             params.push(search_col.clone());
             next_param_index += 1;
         }
         PredicateOperator::Less => {
             filter_str = format!(r#" "{}" < ${}"#, search_col.col_name, next_param_index);
-            let val: String = get_single_search_value(&values).map_err(ArrErr::Error)?;
+            let val: String = get_single_search_value(&values).map_err(ArrErr::invalid_argument)?;
             search_col.set_value(val);
             params.push(search_col.clone());
             next_param_index += 1;
         }
         PredicateOperator::LessOrEqual => {
             filter_str = format!(r#" "{}" <= ${}"#, search_col.col_name, next_param_index);
-            let val: String = get_single_search_value(&values).map_err(ArrErr::Error)?;
+            let val: String = get_single_search_value(&values).map_err(ArrErr::invalid_argument)?;
             search_col.set_value(val);
             params.push(search_col.clone());
             next_param_index += 1;
         }
         PredicateOperator::GeoIntersect => {
             filter_str = format!(
-                r#" st_intersects(st_geomfromtext(${}), "{}")"#,
-                next_param_index, search_col.col_name,
+                r#" st_intersects(st_geomfromtext(${}, {}), "{}")"#,
+                next_param_index, SRID, search_col.col_name,
             );
-            search_col.set_value(get_single_search_value(&values).map_err(ArrErr::Error)?);
+            search_col
+                .set_value(get_single_search_value(&values).map_err(ArrErr::invalid_argument)?);
             params.push(search_col.clone());
             next_param_index += 1;
         }
         PredicateOperator::GeoWithin => {
             filter_str = format!(
-                r#" st_within(st_geomfromtext(${}), "{}")"#,
-                next_param_index, search_col.col_name,
+                r#" st_within(st_geomfromtext(${}, {}), "{}")"#,
+                next_param_index, SRID, search_col.col_name,
             );
-            search_col.set_value(get_single_search_value(&values).map_err(ArrErr::Error)?);
+            search_col
+                .set_value(get_single_search_value(&values).map_err(ArrErr::invalid_argument)?);
             params.push(search_col.clone());
             next_param_index += 1;
         }
         PredicateOperator::GeoDisjoint => {
             filter_str = format!(
-                r#" st_disjoint(st_geomfromtext(${}), "{}")"#,
-                next_param_index, search_col.col_name,
+                r#" st_disjoint(st_geomfromtext(${}, {}), "{}")"#,
+                next_param_index, SRID, search_col.col_name,
+            );
+            search_col
+                .set_value(get_single_search_value(&values).map_err(ArrErr::invalid_argument)?);
+            params.push(search_col.clone());
+            next_param_index += 1;
+        }
+        PredicateOperator::GeoDwithin => {
+            let (geometry, distance) = match &values[..] {
+                [geometry, distance] => (geometry.clone(), distance.clone()),
+                _ => {
+                    return Err(ArrErr::invalid_argument(format!(
+                        "Error in advanced search parameters. GeoDwithin operator is expecting 2 values but got [{}].",
+                        values.len()
+                    )));
+                }
+            };
+            filter_str = format!(
+                r#" st_dwithin("{}"::geography, st_geomfromtext(${}, {})::geography, ${})"#,
+                search_col.col_name,
+                next_param_index,
+                SRID,
+                next_param_index + 1
             );
-            search_col.set_value(get_single_search_value(&values).map_err(ArrErr::Error)?);
+
+            search_col.set_value(geometry);
             params.push(search_col.clone());
             next_param_index += 1;
+
+            params.push(SearchCol {
+                col_name: search_col.col_name.clone(),
+                col_type: PsqlFieldType::FLOAT8,
+                value: Some(distance),
+            });
+            next_param_index += 1;
         }
     }
 
     Ok((filter_str, next_param_index))
 }
 
-pub(crate) fn try_get_sort_str(sort_option: &SortOption) -> Result<String, ArrErr> {
-    let sort_order: SortOrder = match SortOrder::try_from(sort_option.sort_order) {
-        Ok(val) => val,
-        Err(e) => {
-            return Err(ArrErr::Error(format!(
-                "Can't convert i32 [{}] into SortOperator Enum value: {}",
-                sort_option.sort_order, e
+pub(crate) fn try_get_sort_order(sort_option: &SortOption) -> Result<SortOrder, ArrErr> {
+    match SortOrder::try_from(sort_option.sort_order) {
+        Ok(val) => Ok(val),
+        Err(e) => Err(ArrErr::invalid_argument(format!(
+            "Can't convert i32 [{}] into SortOperator Enum value: {}",
+            sort_option.sort_order, e
+        ))),
+    }
+}
+
+/// Returns the `ORDER BY` expression for the given [`SortKey`]
+///
+/// `NULL` values are explicitly sorted first for ascending and last for descending order,
+/// so the keyset conditions match the order regardless of the database defaults.
+pub(crate) fn get_sort_key_str(sort_key: &SortKey) -> String {
+    let nulls = match sort_key.sort_order {
+        SortOrder::Asc => "NULLS FIRST",
+        SortOrder::Desc => "NULLS LAST",
+    };
+    format!(
+        r#""{}" {} {}"#,
+        sort_key.col_name,
+        sort_key.sort_order.as_str_name(),
+        nulls
+    )
+}
+
+/// Composes the keyset condition selecting all rows sorted after the provided cursor values
+///
+/// For sort keys `(a, b)` and cursor values `(x, y)` this results in
+/// `(a > x) OR (a = x AND b > y)`, taking the sort direction and `NULL` values into account.
+pub(crate) fn get_keyset_str(
+    sort_keys: &[SortKey],
+    values: Vec<Option<String>>,
+    params: &mut Vec<Box<PsqlFieldSend>>,
+    cur_param_index: i32,
+) -> Result<(String, i32), ArrErr> {
+    let mut next_param_index = cur_param_index;
+    let mut equals: Vec<String> = vec![];
+    let mut conditions: Vec<String> = vec![];
+
+    for (sort_key, value) in sort_keys.iter().zip(values) {
+        let col = &sort_key.col_name;
+        let (after, equal) = match value {
+            Some(value) => {
+                params.push(cursor_param(sort_key, value)?);
+                let index = next_param_index;
+                next_param_index += 1;
+                let after = match sort_key.sort_order {
+                    SortOrder::Asc => format!(r#""{}" > ${}"#, col, index),
+                    SortOrder::Desc => format!(r#"("{}" < ${} OR "{}" IS NULL)"#, col, index, col),
+                };
+                (Some(after), format!(r#""{}" = ${}"#, col, index))
+            }
+            None => {
+                let after = match sort_key.sort_order {
+                    SortOrder::Asc => Some(format!(r#""{}" IS NOT NULL"#, col)),
+                    // NULL values are sorted last, nothing can follow
+                    SortOrder::Desc => None,
+                };
+                (after, format!(r#""{}" IS NULL"#, col))
+            }
+        };
+        if let Some(after) = after {
+            let mut condition = equals.clone();
+            condition.push(after);
+            conditions.push(format!("({})", condition.join(" AND ")));
+        }
+        equals.push(equal);
+    }
+
+    if conditions.is_empty() {
+        return Ok(("FALSE".to_owned(), next_param_index));
+    }
+    Ok((format!("({})", conditions.join(" OR ")), next_param_index))
+}
+
+/// Adds the keyset clause for the provided page token to the list of `WHERE` clauses.
+/// Nothing will be added if the page token is empty.
+/// Returns the index to use for the next query parameter.
+fn add_keyset_clause(
+    page_token: &str,
+    sort_keys: &[SortKey],
+    where_clauses: &mut Vec<String>,
+    params: &mut Vec<Box<PsqlFieldSend>>,
+    cur_param_index: i32,
+) -> Result<i32, ArrErr> {
+    if page_token.is_empty() {
+        return Ok(cur_param_index);
+    }
+    let (keyset_str, next_param_index) = get_keyset_str(
+        sort_keys,
+        decode_page_token(page_token, sort_keys)?,
+        params,
+        cur_param_index,
+    )?;
+    where_clauses.push(keyset_str);
+    Ok(next_param_index)
+}
+
+/// Returns the number of records to skip for the provided (1 based) page number.
+///
+/// # Errors
+///
+/// Returns [`ArrErr`] if the offset does not fit the database's offset type.
+fn get_page_offset(results_per_page: i32, page_number: i32) -> Result<i64, ArrErr> {
+    i64::from(results_per_page)
+        .checked_mul(i64::from(page_number) - 1)
+        .ok_or_else(|| {
+            let error = format!(
+                "Page number [{}] with [{}] results per page is out of range.",
+                page_number, results_per_page
+            );
+            psql_error!("(get_page_offset) {}", error);
+            ArrErr::invalid_argument(error)
+        })
+}
+
+/// Composes the `SELECT` query string for the provided `WHERE` clauses, ordered by the provided [SortKey]s.
+///
+/// If a `distance` expression is provided, the records are selected from a sub query
+/// adding the distance, so it can be used in the `WHERE` clauses and sort keys.
+pub(crate) fn get_search_query_str(
+    definition: &ResourceDefinition,
+    distance: Option<&str>,
+    where_clauses: &[String],
+    sort_keys: &[SortKey],
+) -> String {
+    let mut search_query = match distance {
+        Some(distance) => format!(
+            r#"SELECT * FROM (SELECT *, {} FROM "{}") AS "{}""#,
+            distance, definition.psql_table, definition.psql_table
+        ),
+        None => format!(r#"SELECT * FROM "{}""#, definition.psql_table),
+    };
+    if !where_clauses.is_empty() {
+        search_query.push_str(&format!(" WHERE {}", where_clauses.join(" AND ")));
+    }
+    let sort_expressions: Vec<String> = sort_keys.iter().map(get_sort_key_str).collect();
+    search_query.push_str(&format!(" ORDER BY {}", sort_expressions.join(",")));
+    search_query
+}
+
+/// Returns the [`DistanceSort`] for the sort option providing a `distance_from` geometry, if any.
+///
+/// # Errors
+///
+/// Returns [`ArrErr`] if more than one distance sort option is provided, or if the
+/// sort field is not a geometry column of the resource.
+pub(crate) fn get_distance_sort(
+    definition: &ResourceDefinition,
+    order_by: &[SortOption],
+) -> Result<Option<DistanceSort>, ArrErr> {
+    let mut distance_sorts = order_by.iter().filter_map(|sort_option| {
+        sort_option
+            .distance_from
+            .as_ref()
+            .map(|value| (sort_option, value))
+    });
+    let (sort_option, value) = match distance_sorts.next() {
+        Some(distance_sort) => distance_sort,
+        None => return Ok(None),
+    };
+    if distance_sorts.next().is_some() {
+        return Err(ArrErr::invalid_argument(
+            "Error in advanced search parameters. Only one distance sort option can be used."
+                .to_string(),
+        ));
+    }
+
+    let field_type = &definition
+        .try_get_field(&sort_option.sort_field)
+        .map_err(|e| ArrErr::invalid_argument(e.to_string()))?
+        .field_type;
+    match *field_type {
+        PsqlFieldType::POINT | PsqlFieldType::POLYGON | PsqlFieldType::PATH => {
+            Ok(Some(DistanceSort {
+                col_name: sort_option.sort_field.clone(),
+                value: value.clone(),
+            }))
+        }
+        _ => Err(ArrErr::invalid_argument(format!(
+            "Sort field [{}] of type [{}] is not a geometry and can not be used to sort on distance.",
+            sort_option.sort_field,
+            field_type.name()
+        ))),
+    }
+}
+
+/// Returns the select expression computing the distance in meters between the [`DistanceSort`]'s
+/// column and the geometry provided as query parameter with the given index.
+pub(crate) fn get_distance_str(distance_sort: &DistanceSort, param_index: i32) -> String {
+    format!(
+        r#"st_distance("{}"::geography, st_geomfromtext(${}, {})::geography) AS "{}""#,
+        distance_sort.col_name, param_index, SRID, DISTANCE_COL
+    )
+}
+
+/// Adds the geometry of the provided [`DistanceSort`] to the list of query parameters.
+/// Nothing will be added if no [`DistanceSort`] is provided.
+/// Returns the distance select expression and the index to use for the next query parameter.
+fn add_distance_param(
+    distance_sort: Option<&DistanceSort>,
+    params: &mut Vec<Box<PsqlFieldSend>>,
+    cur_param_index: i32,
+) -> (Option<String>, i32) {
+    match distance_sort {
+        Some(distance_sort) => {
+            params.push(Box::new(distance_sort.value.clone()));
+            (
+                Some(get_distance_str(distance_sort, cur_param_index)),
+                cur_param_index + 1,
+            )
+        }
+        None => (None, cur_param_index),
+    }
+}
+
+/// Converts a cursor value into the right Sql type for the given [`SortKey`]
+fn cursor_param(sort_key: &SortKey, value: String) -> Result<Box<PsqlFieldSend>, ArrErr> {
+    // Enum values are stored in the cursor using their database string value
+    if sort_key.col_type == PsqlFieldType::ANYENUM {
+        return Ok(Box::new(value));
+    }
+    param_from_search_col(&SearchCol {
+        col_name: sort_key.col_name.clone(),
+        col_type: sort_key.col_type.clone(),
+        value: Some(value),
+    })
+}
+
+/// Returns `true` if columns of the provided type can be used as [`SortKey`] for cursor pagination
+fn is_cursor_type(col_type: &PsqlFieldType) -> bool {
+    matches!(
+        *col_type,
+        PsqlFieldType::UUID
+            | PsqlFieldType::TEXT
+            | PsqlFieldType::VARCHAR
+            | PsqlFieldType::ANYENUM
+            | PsqlFieldType::BOOL
+            | PsqlFieldType::INT2
+            | PsqlFieldType::INT4
+            | PsqlFieldType::INT8
+            | PsqlFieldType::FLOAT4
+            | PsqlFieldType::FLOAT8
+            | PsqlFieldType::TIMESTAMPTZ
+    )
+}
+
+/// Returns the value of the [`SortKey`]'s column for the given [`Row`] as [`String`]
+fn get_cursor_value(row: &Row, sort_key: &SortKey) -> Result<Option<String>, ArrErr> {
+    let col = sort_key.col_name.as_str();
+    let value = match sort_key.col_type {
+        PsqlFieldType::UUID => row
+            .try_get::<&str, Option<Uuid>>(col)?
+            .map(|val| val.to_string()),
+        PsqlFieldType::TEXT | PsqlFieldType::VARCHAR | PsqlFieldType::ANYENUM => {
+            row.try_get::<&str, Option<String>>(col)?
+        }
+        PsqlFieldType::BOOL => row
+            .try_get::<&str, Option<bool>>(col)?
+            .map(|val| val.to_string()),
+        PsqlFieldType::INT2 => row
+            .try_get::<&str, Option<i16>>(col)?
+            .map(|val| val.to_string()),
+        PsqlFieldType::INT4 => row
+            .try_get::<&str, Option<i32>>(col)?
+            .map(|val| val.to_string()),
+        PsqlFieldType::INT8 => row
+            .try_get::<&str, Option<i64>>(col)?
+            .map(|val| val.to_string()),
+        PsqlFieldType::FLOAT4 => row
+            .try_get::<&str, Option<f32>>(col)?
+            .map(|val| val.to_string()),
+        PsqlFieldType::FLOAT8 => row
+            .try_get::<&str, Option<f64>>(col)?
+            .map(|val| val.to_string()),
+        PsqlFieldType::TIMESTAMPTZ => row
+            .try_get::<&str, Option<DateTime<Utc>>>(col)?
+            .map(|val| val.to_rfc3339()),
+        _ => {
+            return Err(ArrErr::invalid_argument(format!(
+                "Sort column [{}] of type [{}] can not be used for cursor pagination.",
+                col,
+                sort_key.col_type.name()
             )));
         }
     };
+    Ok(value)
+}
 
-    Ok(format!(
-        r#""{}" {}"#,
-        sort_option.sort_field,
-        sort_order.as_str_name()
-    ))
+/// Creates an opaque page token containing the sort key values of the given [`Row`]
+pub(crate) fn encode_page_token(row: &Row, sort_keys: &[SortKey]) -> Result<String, ArrErr> {
+    let mut cursor: Vec<(String, Option<String>)> = vec![];
+    for sort_key in sort_keys {
+        cursor.push((sort_key.col_name.clone(), get_cursor_value(row, sort_key)?));
+    }
+    Ok(cursor_to_page_token(&cursor))
+}
+
+/// Encodes the cursor values as hex string
+fn cursor_to_page_token(cursor: &[(String, Option<String>)]) -> String {
+    // Serializing a list of string tuples can not fail
+    let json = serde_json::to_string(cursor).unwrap_or_default();
+    json.as_bytes()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Decodes the page token, returning the cursor values for the provided sort keys
+///
+/// # Errors
+///
+/// Returns [`ArrErr`] if the token is invalid or does not match the provided sort keys
+pub(crate) fn decode_page_token(
+    token: &str,
+    sort_keys: &[SortKey],
+) -> Result<Vec<Option<String>>, ArrErr> {
+    let invalid = |reason: &str| {
+        let error = format!("Invalid page token provided [{}]: {}", token, reason);
+        psql_error!("(decode_page_token) {}", error);
+        ArrErr::invalid_argument(error)
+    };
+
+    if token.len() % 2 != 0 || !token.is_ascii() {
+        return Err(invalid("not a valid hex string"));
+    }
+    let mut bytes: Vec<u8> = Vec::with_capacity(token.len() / 2);
+    for i in (0..token.len()).step_by(2) {
+        bytes.push(
+            u8::from_str_radix(&token[i..i + 2], 16)
+                .map_err(|_| invalid("not a valid hex string"))?,
+        );
+    }
+    let cursor: Vec<(String, Option<String>)> =
+        serde_json::from_slice(&bytes).map_err(|_| invalid("could not parse cursor"))?;
+
+    if cursor.len() != sort_keys.len()
+        || cursor
+            .iter()
+            .zip(sort_keys)
+            .any(|((field, _), sort_key)| *field != sort_key.col_name)
+    {
+        return Err(invalid("sort order does not match the search filter"));
+    }
+
+    Ok(cursor.into_iter().map(|(_, value)| value).collect())
 }
 
 /// Converts the passed string value for a field into the right Sql type.
@@ -393,7 +1105,7 @@ pub(super) fn param_from_search_col(
         None => {
             let err = format!("Search col [{}] has no value: {:?}", col.col_name, col);
             psql_error!("(param_from_search_col) {}", err);
-            return Err(ArrErr::Error(err));
+            return Err(ArrErr::invalid_argument(err));
         }
     };
     match col.col_type {
@@ -405,7 +1117,7 @@ pub(super) fn param_from_search_col(
                     col.col_name, col_val, e
                 );
                 psql_error!("(param_from_search_col) {}", err);
-                Err(ArrErr::Error(err))
+                Err(ArrErr::invalid_argument(err))
             }
         },
         PsqlFieldType::NUMERIC => match col_val.parse::<f64>() {
@@ -416,7 +1128,29 @@ pub(super) fn param_from_search_col(
                     col.col_name, col_val, e
                 );
                 psql_error!("(param_from_search_col) {}", err);
-                Err(ArrErr::Error(err))
+                Err(ArrErr::invalid_argument(err))
+            }
+        },
+        PsqlFieldType::FLOAT4 => match col_val.parse::<f32>() {
+            Ok(val) => Ok(Box::new(val)),
+            Err(e) => {
+                let err = format!(
+                    "Can't convert search col [{}] with value [{}] to f32: {}",
+                    col.col_name, col_val, e
+                );
+                psql_error!("(param_from_search_col) {}", err);
+                Err(ArrErr::invalid_argument(err))
+            }
+        },
+        PsqlFieldType::FLOAT8 => match col_val.parse::<f64>() {
+            Ok(val) => Ok(Box::new(val)),
+            Err(e) => {
+                let err = format!(
+                    "Can't convert search col [{}] with value [{}] to f64: {}",
+                    col.col_name, col_val, e
+                );
+                psql_error!("(param_from_search_col) {}", err);
+                Err(ArrErr::invalid_argument(err))
             }
         },
         PsqlFieldType::INT2 => match col_val.parse::<i16>() {
@@ -427,7 +1161,7 @@ pub(super) fn param_from_search_col(
                     col.col_name, col_val, e
                 );
                 psql_error!("(param_from_search_col) {}", err);
-                Err(ArrErr::Error(err))
+                Err(ArrErr::invalid_argument(err))
             }
         },
         PsqlFieldType::INT4 => match col_val.parse::<i32>() {
@@ -438,7 +1172,7 @@ pub(super) fn param_from_search_col(
                     col.col_name, col_val, e
                 );
                 psql_error!("(param_from_search_col) {}", err);
-                Err(ArrErr::Error(err))
+                Err(ArrErr::invalid_argument(err))
             }
         },
         PsqlFieldType::INT8 => match col_val.parse::<i64>() {
@@ -449,7 +1183,7 @@ pub(super) fn param_from_search_col(
                     col.col_name, col_val, e
                 );
                 psql_error!("(param_from_search_col) {}", err);
-                Err(ArrErr::Error(err))
+                Err(ArrErr::invalid_argument(err))
             }
         },
         PsqlFieldType::UUID => match Uuid::parse_str(col_val) {
@@ -460,7 +1194,7 @@ pub(super) fn param_from_search_col(
                     col.col_name, col_val, e
                 );
                 psql_error!("(param_from_search_col) {}", err);
-                Err(ArrErr::Error(err))
+                Err(ArrErr::invalid_argument(err))
             }
         },
         PsqlFieldType::TIMESTAMPTZ => match col_val.parse::<DateTime<Utc>>() {
@@ -471,7 +1205,7 @@ pub(super) fn param_from_search_col(
                     col.col_name, col_val, e
                 );
                 psql_error!("(param_from_search_col) {}", err);
-                Err(ArrErr::Error(err))
+                Err(ArrErr::invalid_argument(err))
             }
         },
         PsqlFieldType::BYTEA => {
@@ -485,6 +1219,7 @@ pub(super) fn param_from_search_col(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::grpc::server::adsb;
     use crate::resources::base::ResourceObject;
     use crate::test_util::*;
 
@@ -590,4 +1325,429 @@ mod tests {
         }
         ut_info!("(test_get_param_from_search_col) success");
     }
+
+    #[tokio::test]
+    async fn test_get_filter_group_str() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_filter_group_str) start");
+
+        let definition = ResourceObject::<TestData>::get_definition();
+
+        // Mixed operators should follow SQL operator precedence
+        let filter = AdvancedSearchFilter::search_equals(String::from("string"), String::from("a"))
+            .or_equals(String::from("string"), String::from("b"))
+            .and_is_not_null(String::from("bool"));
+        let mut params: Vec<SearchCol> = vec![];
+        let result = get_filter_group_str(&definition, &filter.filters, &mut params, 1, 0);
+        assert!(result.is_ok());
+        let (filter_str, next_param_index) = result.unwrap();
+        assert_eq!(
+            filter_str,
+            r#""string" = $1 OR "string" = $2 AND "bool" IS NOT NULL"#
+        );
+        assert_eq!(next_param_index, 3);
+        assert_eq!(params.len(), 2);
+
+        // Explicit groups should be used to change the evaluation order
+        let filter = AdvancedSearchFilter::search_group(
+            AdvancedSearchFilter::search_equals(String::from("string"), String::from("a"))
+                .or_equals(String::from("string"), String::from("b")),
+        )
+        .and_is_not_null(String::from("bool"));
+        let mut params: Vec<SearchCol> = vec![];
+        let result = get_filter_group_str(&definition, &filter.filters, &mut params, 1, 0);
+        assert!(result.is_ok());
+        let (filter_str, _) = result.unwrap();
+        assert_eq!(
+            filter_str,
+            r#"("string" = $1 OR "string" = $2) AND "bool" IS NOT NULL"#
+        );
+
+        // Nested groups should be wrapped in parentheses
+        let filter = AdvancedSearchFilter::search_equals(String::from("string"), String::from("a"))
+            .and_group(
+                AdvancedSearchFilter::search_equals(String::from("i32"), String::from("1"))
+                    .or_group(
+                        AdvancedSearchFilter::search_is_null(String::from("i64"))
+                            .and_is_null(String::from("bool")),
+                    ),
+            );
+        let mut params: Vec<SearchCol> = vec![];
+        let result = get_filter_group_str(&definition, &filter.filters, &mut params, 3, 0);
+        assert!(result.is_ok());
+        let (filter_str, next_param_index) = result.unwrap();
+        assert_eq!(
+            filter_str,
+            r#""string" = $3 AND ("i32" = $4 OR ("i64" IS NULL AND "bool" IS NULL))"#
+        );
+        assert_eq!(next_param_index, 5);
+        assert_eq!(params.len(), 2);
+
+        // Unknown fields inside a group should return an error
+        let filter = AdvancedSearchFilter::search_group(AdvancedSearchFilter::search_equals(
+            String::from("unknown"),
+            String::from("a"),
+        ));
+        let mut params: Vec<SearchCol> = vec![];
+        let result = get_filter_group_str(&definition, &filter.filters, &mut params, 1, 0);
+        assert!(result.is_err());
+
+        // Groups nested too deep should return an error
+        let mut filter = AdvancedSearchFilter::search_is_null(String::from("string"));
+        for _ in 0..=MAX_FILTER_GROUP_DEPTH {
+            filter = AdvancedSearchFilter::search_group(filter);
+        }
+        let mut params: Vec<SearchCol> = vec![];
+        let result = get_filter_group_str(&definition, &filter.filters, &mut params, 1, 0);
+        assert!(result.is_err());
+
+        ut_info!("(test_get_filter_group_str) success");
+    }
+
+    #[tokio::test]
+    async fn test_get_filter_str_geo_dwithin() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_filter_str_geo_dwithin) start");
+
+        let definition = ResourceObject::<TestData>::get_definition();
+        let filter = AdvancedSearchFilter::search_geo_dwithin(
+            String::from("geo_point"),
+            String::from("POINT(4.9 52.3)"),
+            1500.0,
+        );
+        let mut params: Vec<SearchCol> = vec![];
+        let result = get_filter_group_str(&definition, &filter.filters, &mut params, 2, 0);
+        assert!(result.is_ok());
+        let (filter_str, next_param_index) = result.unwrap();
+        assert_eq!(
+            filter_str,
+            r#"st_dwithin("geo_point"::geography, st_geomfromtext($2, 4326)::geography, $3)"#
+        );
+        assert_eq!(next_param_index, 4);
+        assert_eq!(params.len(), 2);
+        assert_eq!(params[0].col_type, PsqlFieldType::POINT);
+        assert_eq!(params[1].col_type, PsqlFieldType::FLOAT8);
+        assert_eq!(params[1].value, Some(String::from("1500")));
+
+        // Both the geometry and the distance should be provided
+        let mut filter = filter;
+        filter.filters[0].search_value.pop();
+        let mut params: Vec<SearchCol> = vec![];
+        let result = get_filter_group_str(&definition, &filter.filters, &mut params, 1, 0);
+        assert!(result.is_err());
+
+        ut_info!("(test_get_filter_str_geo_dwithin) success");
+    }
+
+    fn get_test_sort_keys() -> Vec<SortKey> {
+        vec![
+            SortKey {
+                col_name: String::from("created_at"),
+                col_type: PsqlFieldType::TIMESTAMPTZ,
+                sort_order: SortOrder::Desc,
+            },
+            SortKey {
+                col_name: String::from("test_id"),
+                col_type: PsqlFieldType::UUID,
+                sort_order: SortOrder::Asc,
+            },
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_get_sort_key_str() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_sort_key_str) start");
+
+        let sort_keys = get_test_sort_keys();
+        assert_eq!(
+            get_sort_key_str(&sort_keys[0]),
+            r#""created_at" DESC NULLS LAST"#
+        );
+        assert_eq!(
+            get_sort_key_str(&sort_keys[1]),
+            r#""test_id" ASC NULLS FIRST"#
+        );
+
+        let sort_option = SortOption {
+            sort_field: String::from("created_at"),
+            sort_order: -1,
+            distance_from: None,
+        };
+        assert!(try_get_sort_order(&sort_option).is_err());
+
+        ut_info!("(test_get_sort_key_str) success");
+    }
+
+    #[tokio::test]
+    async fn test_get_keyset_str() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_keyset_str) start");
+
+        let sort_keys = get_test_sort_keys();
+        let id = uuid::Uuid::new_v4().to_string();
+
+        let mut params: Vec<Box<PsqlFieldSend>> = vec![];
+        let result = get_keyset_str(
+            &sort_keys,
+            vec![Some(chrono::Utc::now().to_rfc3339()), Some(id.clone())],
+            &mut params,
+            3,
+        );
+        assert!(result.is_ok());
+        let (keyset_str, next_param_index) = result.unwrap();
+        assert_eq!(
+            keyset_str,
+            r#"((("created_at" < $3 OR "created_at" IS NULL)) OR ("created_at" = $3 AND "test_id" > $4))"#
+        );
+        assert_eq!(next_param_index, 5);
+        assert_eq!(params.len(), 2);
+
+        // NULL values should not be added as parameters
+        let mut params: Vec<Box<PsqlFieldSend>> = vec![];
+        let result = get_keyset_str(&sort_keys, vec![None, Some(id)], &mut params, 1);
+        assert!(result.is_ok());
+        let (keyset_str, next_param_index) = result.unwrap();
+        assert_eq!(keyset_str, r#"(("created_at" IS NULL AND "test_id" > $1))"#);
+        assert_eq!(next_param_index, 2);
+        assert_eq!(params.len(), 1);
+
+        // Invalid values can not be converted
+        let mut params: Vec<Box<PsqlFieldSend>> = vec![];
+        let result = get_keyset_str(
+            &sort_keys,
+            vec![Some(String::from("invalid")), None],
+            &mut params,
+            1,
+        );
+        assert!(result.is_err());
+
+        ut_info!("(test_get_keyset_str) success");
+    }
+
+    #[tokio::test]
+    async fn test_get_search_query_str() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_search_query_str) start");
+
+        let definition = ResourceObject::<TestData>::get_definition();
+        let sort_keys = get_test_sort_keys();
+
+        let query = get_search_query_str(&definition, None, &[], &sort_keys);
+        assert_eq!(
+            query,
+            format!(
+                r#"SELECT * FROM "{}" ORDER BY "created_at" DESC NULLS LAST,"test_id" ASC NULLS FIRST"#,
+                definition.psql_table
+            )
+        );
+
+        let where_clauses = vec![
+            r#""deleted_at" IS NULL"#.to_owned(),
+            r#"("string" = $1)"#.to_owned(),
+        ];
+        let query = get_search_query_str(&definition, None, &where_clauses, &sort_keys[1..]);
+        assert_eq!(
+            query,
+            format!(
+                r#"SELECT * FROM "{}" WHERE "deleted_at" IS NULL AND ("string" = $1) ORDER BY "test_id" ASC NULLS FIRST"#,
+                definition.psql_table
+            )
+        );
+
+        // An empty page token should not add a keyset clause
+        let mut where_clauses: Vec<String> = vec![];
+        let mut params: Vec<Box<PsqlFieldSend>> = vec![];
+        let result = add_keyset_clause("", &sort_keys, &mut where_clauses, &mut params, 3);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 3);
+        assert!(where_clauses.is_empty());
+
+        let token = cursor_to_page_token(&[
+            (String::from("created_at"), None),
+            (
+                String::from("test_id"),
+                Some(uuid::Uuid::new_v4().to_string()),
+            ),
+        ]);
+        let result = add_keyset_clause(&token, &sort_keys, &mut where_clauses, &mut params, 3);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 4);
+        assert_eq!(where_clauses.len(), 1);
+        assert_eq!(params.len(), 1);
+
+        ut_info!("(test_get_search_query_str) success");
+    }
+
+    #[tokio::test]
+    async fn test_get_page_offset() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_page_offset) start");
+
+        assert_eq!(get_page_offset(10, 1).unwrap(), 0);
+        assert_eq!(get_page_offset(10, 3).unwrap(), 20);
+        // Offsets exceeding i32 are no longer truncated
+        assert_eq!(
+            get_page_offset(i32::MAX, i32::MAX).unwrap(),
+            i64::from(i32::MAX) * (i64::from(i32::MAX) - 1)
+        );
+
+        ut_info!("(test_get_page_offset) success");
+    }
+
+    #[tokio::test]
+    async fn test_get_sort_keys_invalid_field() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_sort_keys_invalid_field) start");
+
+        let sort_option = |sort_field: &str| SortOption {
+            sort_field: String::from(sort_field),
+            sort_order: SortOrder::Asc.into(),
+            distance_from: None,
+        };
+        let result =
+            ResourceObject::<adsb::Data>::_get_sort_keys(&[sort_option("network_timestamp")]);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().len(), 1);
+
+        let result = ResourceObject::<adsb::Data>::_get_sort_keys(&[
+            sort_option("network_timestamp"),
+            sort_option("unknown_field"),
+        ]);
+        assert!(matches!(result, Err(ArrErr::InvalidArgument(_))));
+
+        ut_info!("(test_get_sort_keys_invalid_field) success");
+    }
+
+    #[tokio::test]
+    async fn test_distance_sort() {
+        crate::get_log_handle().await;
+        ut_info!("(test_distance_sort) start");
+
+        let definition = ResourceObject::<TestData>::get_definition();
+        let sort_option = SortOption {
+            sort_field: String::from("geo_point"),
+            sort_order: SortOrder::Asc.into(),
+            distance_from: Some(String::from("POINT(4.9 52.3)")),
+        };
+        let order_by = vec![
+            SortOption {
+                sort_field: String::from("created_at"),
+                sort_order: SortOrder::Desc.into(),
+                distance_from: None,
+            },
+            sort_option.clone(),
+        ];
+
+        let distance_sort = get_distance_sort(&definition, &order_by);
+        assert!(distance_sort.is_ok());
+        let distance_sort = distance_sort.unwrap();
+        assert_eq!(
+            distance_sort,
+            Some(DistanceSort {
+                col_name: String::from("geo_point"),
+                value: String::from("POINT(4.9 52.3)"),
+            })
+        );
+        assert_eq!(
+            get_distance_sort(&definition, &order_by[..1]).unwrap(),
+            None
+        );
+
+        // Only a single distance sort on a geometry column is allowed
+        assert!(
+            get_distance_sort(&definition, &[sort_option.clone(), sort_option.clone()]).is_err()
+        );
+        let mut invalid = sort_option.clone();
+        invalid.sort_field = String::from("string");
+        assert!(get_distance_sort(&definition, &[invalid]).is_err());
+
+        let mut params: Vec<Box<PsqlFieldSend>> = vec![];
+        let (distance_str, next_param_index) =
+            add_distance_param(distance_sort.as_ref(), &mut params, 3);
+        assert_eq!(next_param_index, 4);
+        assert_eq!(params.len(), 1);
+        let distance_str = distance_str.unwrap();
+        assert_eq!(
+            distance_str,
+            r#"st_distance("geo_point"::geography, st_geomfromtext($3, 4326)::geography) AS "distance""#
+        );
+
+        let sort_keys = vec![SortKey {
+            col_name: String::from(DISTANCE_COL),
+            col_type: PsqlFieldType::FLOAT8,
+            sort_order: SortOrder::Asc,
+        }];
+        let query = get_search_query_str(
+            &definition,
+            Some(&distance_str),
+            &[r#""geo_point" IS NOT NULL"#.to_owned()],
+            &sort_keys,
+        );
+        assert_eq!(
+            query,
+            format!(
+                r#"SELECT * FROM (SELECT *, {} FROM "{}") AS "{}" WHERE "geo_point" IS NOT NULL ORDER BY "distance" ASC NULLS FIRST"#,
+                distance_str, definition.psql_table, definition.psql_table
+            )
+        );
+
+        let (distance_str, next_param_index) = add_distance_param(None, &mut params, 4);
+        assert_eq!(distance_str, None);
+        assert_eq!(next_param_index, 4);
+        assert_eq!(params.len(), 1);
+
+        ut_info!("(test_distance_sort) success");
+    }
+
+    #[tokio::test]
+    async fn test_page_token() {
+        crate::get_log_handle().await;
+        ut_info!("(test_page_token) start");
+
+        let sort_keys = get_test_sort_keys();
+        let created_at = chrono::Utc::now().to_rfc3339();
+        let id = uuid::Uuid::new_v4().to_string();
+
+        let token = cursor_to_page_token(&[
+            (String::from("created_at"), Some(created_at.clone())),
+            (String::from("test_id"), Some(id.clone())),
+        ]);
+        let result = decode_page_token(&token, &sort_keys);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), vec![Some(created_at), Some(id.clone())]);
+
+        // The token should not be usable for a different sort order
+        let result = decode_page_token(&token, &sort_keys[1..]);
+        assert!(result.is_err());
+
+        let token = cursor_to_page_token(&[
+            (String::from("updated_at"), None),
+            (String::from("test_id"), Some(id)),
+        ]);
+        assert!(decode_page_token(&token, &sort_keys).is_err());
+
+        // Invalid tokens
+        assert!(decode_page_token("abc", &sort_keys).is_err());
+        assert!(decode_page_token("zz", &sort_keys).is_err());
+        assert!(decode_page_token("7b7d", &sort_keys).is_err());
+
+        ut_info!("(test_page_token) success");
+    }
+
+    #[tokio::test]
+    async fn test_is_cursor_type() {
+        crate::get_log_handle().await;
+        ut_info!("(test_is_cursor_type) start");
+
+        assert!(is_cursor_type(&PsqlFieldType::TIMESTAMPTZ));
+        assert!(is_cursor_type(&PsqlFieldType::ANYENUM));
+        assert!(is_cursor_type(&PsqlFieldType::UUID));
+        assert!(!is_cursor_type(&PsqlFieldType::BYTEA));
+        assert!(!is_cursor_type(&PsqlFieldType::NUMERIC));
+        assert!(!is_cursor_type(&PsqlFieldType::POINT));
+        assert!(!is_cursor_type(&PsqlFieldType::POLYGON));
+
+        ut_info!("(test_is_cursor_type) success");
+    }
 }
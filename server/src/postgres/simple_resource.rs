@@ -1,15 +1,29 @@
 //! Psql Simple resource Traits
 
+use super::dedup::{add_dedup_bucket, insert_or_merge};
+use super::derived::update_derived_fields;
+use super::exclusion::{check_range_exclusions, start_transaction};
 use super::get_psql_client;
-use super::{util::*, ArrErr};
-use crate::grpc::server::ValidationResult;
+use super::spatial::check_spatial_constraints;
+use super::transition::{
+    check_status_transition, get_new_status, get_stamp_update, propagate_status,
+    record_status_transition,
+};
+use super::watch::{notify_watchers, record_change};
+use super::{util::*, ArrErr, PsqlData};
+use crate::grpc::server::{BatchMode, ChangeType, ValidationError, ValidationResult};
 use crate::grpc::GrpcDataObjectType;
 use crate::resources::base::simple_resource::*;
+use crate::resources::base::PropagationTrigger;
 
 use chrono::{DateTime, Utc};
+use deadpool_postgres::Transaction;
 use tokio_postgres::Row;
 use uuid::Uuid;
 
+/// Provides a more readable format of the per item results returned by batch functions
+pub type BatchResults<T> = Vec<(Option<T>, ValidationResult)>;
+
 /// Generic PostgreSQL trait to provide wrappers for common `Resource` functions
 #[tonic::async_trait]
 pub trait PsqlType
@@ -40,31 +54,45 @@ where
         super::queries::get_by_id::<Self>(id).await
     }
 
+    /// Generic get by id function to get a row using the UUID column, including soft-deleted rows
+    async fn get_by_id_include_deleted(id: &Uuid) -> Result<Row, ArrErr> {
+        psql_debug!("(get_by_id_include_deleted) Start [{:?}].", id);
+        super::queries::get_by_id_include_deleted::<Self>(id).await
+    }
+
     /// Generic create function based on resource definition and provided data.
     ///
     /// The data will be validated first, returning all possible errors at once.
     /// If no validation errors are found, a new row will be inserted in the database and the new UUID will be returned.
-    async fn create<'a, T>(data: &T) -> Result<(Option<Uuid>, ValidationResult), ArrErr>
+    /// The insert will be rolled back if the new row violates any of the resource's [range exclusions](Resource::get_range_exclusions).
+    /// If the data is a duplicate of an existing row according to the resource's [deduplication](Resource::get_deduplication),
+    /// it will be merged into the existing row instead and the existing UUID will be returned.
+    ///
+    /// Returns the UUID, the [`ValidationResult`] and a [`bool`] indicating if the data was merged into an existing row.
+    async fn create<'a, T>(data: &T) -> Result<(Option<Uuid>, ValidationResult, bool), ArrErr>
     where
         T: GrpcDataObjectType,
     {
         psql_debug!("(create) Start [{:?}].", data);
-        let (psql_data, validation_result) = validate::<Self>(data)?;
+        let (psql_data, mut validation_result) = validate::<Self>(data)?;
 
         if !validation_result.success {
-            return Ok((None, validation_result));
+            return Ok((None, validation_result, false));
         }
 
         let definition = Self::get_definition();
         let id_col = Self::try_get_id_field()?;
 
-        let (inserts, fields, params) = get_insert_vars(data, &psql_data, &definition, false)?;
+        let (mut inserts, mut fields, params) =
+            get_insert_vars(data, &psql_data, &definition, false)?;
+        let on_conflict = add_dedup_bucket::<Self>(&mut fields, &mut inserts)?;
 
         let insert_sql = &format!(
-            r#"INSERT INTO "{}" ({}) VALUES ({}) RETURNING "{}""#,
+            r#"INSERT INTO "{}" ({}) VALUES ({}){} RETURNING "{}""#,
             definition.psql_table,
             fields.join(", "),
             inserts.join(", "),
+            on_conflict,
             id_col
         );
         psql_debug!("(create) [{}].", insert_sql);
@@ -74,10 +102,345 @@ where
             "(create) Inserting new entry for table [{}].",
             definition.psql_table
         );
-        let client = get_psql_client().await?;
-        let row = client.query_one(insert_sql, &params[..]).await?;
+        let mut client = get_psql_client().await?;
+        let transaction = start_transaction::<Self>(&mut client).await?;
+        let (id, merged) =
+            insert_or_merge::<Self>(&transaction, insert_sql, &params[..], &psql_data).await?;
+        if merged {
+            record_change(
+                &transaction,
+                &definition.psql_table,
+                ChangeType::Updated,
+                &id,
+            )
+            .await?;
+            transaction.commit().await?;
+            notify_watchers();
+            return Ok((Some(id), validation_result, true));
+        }
+        check_inserted::<Self>(&transaction, &id, &mut validation_result).await?;
+        if !validation_result.success {
+            transaction.rollback().await?;
+            return Ok((None, validation_result, false));
+        }
+        record_change(
+            &transaction,
+            &definition.psql_table,
+            ChangeType::Created,
+            &id,
+        )
+        .await?;
+        transaction.commit().await?;
+        notify_watchers();
 
-        Ok((Some(row.get(&*id_col)), validation_result))
+        Ok((Some(id), validation_result, false))
+    }
+
+    /// Generic batch create function based on resource definition and provided data.
+    ///
+    /// All items will be inserted using a single transaction, each item using its own savepoint.
+    /// Every item will be validated first and gets its own [`ValidationResult`], in the same order as the provided data.
+    /// Using [`BatchMode::AllOrNothing`], no items will be inserted if any of the items fail.
+    /// Using [`BatchMode::BestEffort`], valid items will be inserted while failing items will be skipped.
+    ///
+    /// Duplicates of existing rows (including earlier items of the batch) will be merged into the existing row.
+    ///
+    /// Returns the UUIDs and [`ValidationResult`]s per item and a [`bool`] indicating if all items succeeded.
+    /// Each UUID comes with a [`bool`] indicating if the item was merged into an existing row.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArrErr`] "No id column configured for table" id_col could not be found
+    /// Returns [`ArrErr`] from [`PoolError`](deadpool::managed::PoolError) if no client connection could be returned from the connection [`Pool`](deadpool::managed::Pool)
+    /// Returns [`ArrErr`] Database Error if the transaction could not be started or finished
+    async fn create_many<T>(
+        data: &[T],
+        mode: BatchMode,
+    ) -> Result<(BatchResults<(Uuid, bool)>, bool), ArrErr>
+    where
+        T: GrpcDataObjectType,
+    {
+        psql_debug!("(create_many) Start [{}] items.", data.len());
+        let id_col = Self::try_get_id_field()?;
+
+        let mut client = get_psql_client().await?;
+        let mut transaction = start_transaction::<Self>(&mut client).await?;
+
+        let mut results: BatchResults<(Uuid, bool)> = Vec::with_capacity(data.len());
+        let mut success = true;
+        for item in data {
+            let (psql_data, mut validation_result) = match validate::<Self>(item) {
+                Ok(result) => result,
+                Err(e) => {
+                    psql_info!("(create_many) Could not validate item: {}", e);
+                    let mut validation_result = ValidationResult {
+                        success: true,
+                        errors: vec![],
+                    };
+                    add_batch_error(&mut validation_result, "data", e.to_string());
+                    success = false;
+                    results.push((None, validation_result));
+                    continue;
+                }
+            };
+            if !validation_result.success {
+                success = false;
+                results.push((None, validation_result));
+                continue;
+            }
+            if !success && mode == BatchMode::AllOrNothing {
+                // Nothing will be saved, only validate the remaining items
+                results.push((None, validation_result));
+                continue;
+            }
+
+            let savepoint = transaction.transaction().await?;
+            match insert_batch_item::<Self, T>(&savepoint, item, &psql_data, &mut validation_result)
+                .await
+            {
+                Ok(Some(result)) => {
+                    savepoint.commit().await?;
+                    results.push((Some(result), validation_result));
+                }
+                Ok(None) => {
+                    savepoint.rollback().await?;
+                    success = false;
+                    results.push((None, validation_result));
+                }
+                Err(e) => {
+                    savepoint.rollback().await?;
+                    psql_info!("(create_many) Could not insert item: {}", e);
+                    success = false;
+                    add_batch_error(&mut validation_result, &id_col, e.to_string());
+                    results.push((None, validation_result));
+                }
+            }
+        }
+
+        if !finish_batch(transaction, mode, success).await? {
+            for (id, validation_result) in results.iter_mut() {
+                *id = None;
+                fail_rolled_back_item(validation_result, &id_col);
+            }
+        }
+
+        Ok((results, success))
+    }
+}
+
+/// Inserts a single batch item, or merges it into an existing duplicate if found.
+///
+/// Returns the UUID and a [`bool`] indicating if the item was merged into an existing row.
+/// Returns [`None`] if the inserted row failed validation, in which case the errors are added to the [`ValidationResult`].
+///
+/// # Errors
+///
+/// Returns [`ArrErr`] if any of the queries failed
+async fn insert_batch_item<R, T>(
+    transaction: &Transaction<'_>,
+    item: &T,
+    psql_data: &PsqlData,
+    validation_result: &mut ValidationResult,
+) -> Result<Option<(Uuid, bool)>, ArrErr>
+where
+    R: PsqlType,
+    T: GrpcDataObjectType,
+{
+    let definition = R::get_definition();
+    let id_col = R::try_get_id_field()?;
+    let (mut inserts, mut fields, params) = get_insert_vars(item, psql_data, &definition, false)?;
+    let on_conflict = add_dedup_bucket::<R>(&mut fields, &mut inserts)?;
+    let insert_sql = format!(
+        r#"INSERT INTO "{}" ({}) VALUES ({}){} RETURNING "{}""#,
+        definition.psql_table,
+        fields.join(", "),
+        inserts.join(", "),
+        on_conflict,
+        id_col
+    );
+    psql_debug!("(insert_batch_item) [{}].", insert_sql);
+    psql_debug!("(insert_batch_item) [{:?}].", &params);
+
+    let (id, merged) =
+        insert_or_merge::<R>(transaction, &insert_sql, &params[..], psql_data).await?;
+    if merged {
+        record_change(
+            transaction,
+            &definition.psql_table,
+            ChangeType::Updated,
+            &id,
+        )
+        .await?;
+        return Ok(Some((id, true)));
+    }
+    check_inserted::<R>(transaction, &id, validation_result).await?;
+    if validation_result.success {
+        record_change(
+            transaction,
+            &definition.psql_table,
+            ChangeType::Created,
+            &id,
+        )
+        .await?;
+        Ok(Some((id, false)))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Updates the derived fields and status propagations of a newly inserted row
+/// and checks the row against the resource's range exclusions and spatial constraints.
+///
+/// Any violations will be added to the provided [`ValidationResult`].
+///
+/// # Errors
+///
+/// Returns [`ArrErr`] if any of the queries failed
+async fn check_inserted<R>(
+    transaction: &Transaction<'_>,
+    id: &Uuid,
+    validation_result: &mut ValidationResult,
+) -> Result<(), ArrErr>
+where
+    R: PsqlType,
+{
+    update_derived_fields::<R>(transaction, id).await?;
+    propagate_status::<R>(transaction, id, &PropagationTrigger::Insert, None).await?;
+    check_range_exclusions::<R>(transaction, id, validation_result).await?;
+    check_spatial_constraints::<R>(transaction, id, validation_result).await
+}
+
+/// Adds a [`ValidationError`] for a batch item which could not be processed
+fn add_batch_error(validation_result: &mut ValidationResult, field: &str, error: String) {
+    validation_result.success = false;
+    validation_result.errors.push(ValidationError {
+        field: field.to_owned(),
+        error,
+    });
+}
+
+/// Returns the error message used when no active record exists for the provided `id`
+fn get_not_found_error<R: PsqlType>(id: &Uuid) -> String {
+    format!("No [{}] record found for id [{}].", R::get_psql_table(), id)
+}
+
+/// Updates a single batch item.
+///
+/// Returns the updated [`Row`].
+/// Returns [`None`] if the record was not found or the update failed validation,
+/// in which case the errors are added to the [`ValidationResult`].
+///
+/// # Errors
+///
+/// Returns [`ArrErr`] if any of the queries failed
+async fn update_batch_item<R, T>(
+    transaction: &Transaction<'_>,
+    id: &Uuid,
+    data: &T,
+    psql_data: &PsqlData,
+    validation_result: &mut ValidationResult,
+) -> Result<Option<Row>, ArrErr>
+where
+    R: PsqlType,
+    T: GrpcDataObjectType,
+{
+    let definition = R::get_definition();
+    let id_col = R::try_get_id_field()?;
+    let (mut updates, mut params) = get_update_vars(data, psql_data, &definition)?;
+    let new_status = get_new_status::<R>(data, psql_data)?;
+
+    let transition =
+        match check_status_transition::<R>(transaction, id, new_status, validation_result).await {
+            Ok(transition) => transition,
+            Err(ArrErr::NotFound(_)) => {
+                add_batch_error(validation_result, &id_col, get_not_found_error::<R>(id));
+                return Ok(None);
+            }
+            Err(e) => return Err(e),
+        };
+    if !validation_result.success {
+        return Ok(None);
+    }
+    if let Some(transition) = &transition {
+        updates.extend(get_stamp_update::<R>(transition, Some(psql_data)));
+    }
+
+    if definition.has_field("updated_at") {
+        updates.push(r#""updated_at" = NOW()"#.to_string());
+    }
+    let mut update_sql = format!(
+        r#"UPDATE "{}" SET {} WHERE "{}" = ${}"#,
+        definition.psql_table,
+        updates.join(", "),
+        id_col,
+        params.len() + 1
+    );
+    if definition.has_field("deleted_at") {
+        update_sql.push_str(r#" AND "deleted_at" IS NULL"#);
+    }
+    update_sql.push_str(" RETURNING *");
+    params.push(id);
+    psql_debug!("(update_batch_item) [{}].", update_sql);
+    psql_debug!("(update_batch_item) [{:?}].", &params);
+
+    let mut row = match transaction.query_opt(&update_sql, &params[..]).await? {
+        Some(row) => row,
+        None => {
+            add_batch_error(validation_result, &id_col, get_not_found_error::<R>(id));
+            return Ok(None);
+        }
+    };
+    if R::get_derived_fields_hook().is_some() {
+        update_derived_fields::<R>(transaction, id).await?;
+        // Return the row including the updated derived fields
+        let select_sql = format!(
+            r#"SELECT * FROM "{}" WHERE "{}" = $1"#,
+            definition.psql_table, id_col
+        );
+        row = transaction.query_one(&select_sql, &[id]).await?;
+    }
+    if let Some(transition) = &transition {
+        record_status_transition::<R>(transaction, id, transition, None).await?;
+        let trigger = PropagationTrigger::Status(transition.to.clone());
+        propagate_status::<R>(transaction, id, &trigger, None).await?;
+    }
+    check_range_exclusions::<R>(transaction, id, validation_result).await?;
+    check_spatial_constraints::<R>(transaction, id, validation_result).await?;
+    if validation_result.success {
+        record_change(transaction, &definition.psql_table, ChangeType::Updated, id).await?;
+        Ok(Some(row))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Marks a batch item as failed if the batch has been rolled back, since it has not been saved
+fn fail_rolled_back_item(validation_result: &mut ValidationResult, field: &str) {
+    if validation_result.success {
+        add_batch_error(
+            validation_result,
+            field,
+            "Not saved, the batch has been rolled back since one or more items failed.".to_owned(),
+        );
+    }
+}
+
+/// Commits or rolls back the batch transaction depending on the [`BatchMode`] and batch success.
+///
+/// Returns `true` if the transaction was committed.
+async fn finish_batch(
+    transaction: Transaction<'_>,
+    mode: BatchMode,
+    success: bool,
+) -> Result<bool, ArrErr> {
+    if success || mode == BatchMode::BestEffort {
+        transaction.commit().await?;
+        notify_watchers();
+        Ok(true)
+    } else {
+        psql_info!("(finish_batch) One or more items failed, rolling back all changes.");
+        transaction.rollback().await?;
+        Ok(false)
     }
 }
 
@@ -103,6 +466,11 @@ where
 
     /// Update the Object's database record using provided data
     ///
+    /// If the Object has a `mask` set, only the fields provided in the mask will be validated and updated.
+    /// Unknown or read only mask paths will be returned as validation errors.
+    /// The update will be rolled back if the updated row violates any of the resource's [range exclusions](Resource::get_range_exclusions).
+    /// Status changes not allowed by the resource's [status transitions](Resource::get_status_transitions) will be returned as validation errors.
+    ///
     /// returns [Option(Row)] and [ValidationResult]
     ///
     /// # Errors
@@ -110,12 +478,14 @@ where
     /// Returns [`ArrErr`] Validation "Conversion error, unknown field type" if the provided field type could not be matched.
     /// Returns [`ArrErr`] "No id column configured for table" id_col could not be found
     /// Returns [`ArrErr`] if the `id` [`String`] could not be converted to a valid [`Uuid`]
+    /// Returns [`ArrErr::NotFound`] if no record exists for the `id`
     /// Returns [`ArrErr`] from [`PoolError`](deadpool::managed::PoolError) if no client connection could be returned from the connection [`Pool`](deadpool::managed::Pool)
     /// Returns [`ArrErr`] Database Error if database query execution failed
     async fn update<'a>(&self, data: &T) -> Result<(Option<Row>, ValidationResult), ArrErr> {
         psql_debug!("(update) Start [{:?}].", data);
 
-        let (psql_data, validation_result) = validate::<Self>(data)?;
+        let (psql_data, mut validation_result) =
+            validate_with_mask::<Self>(data, &self.get_mask())?;
         if !validation_result.success {
             return Ok((None, validation_result));
         }
@@ -125,18 +495,35 @@ where
         let id = self.try_get_uuid()?;
 
         let (mut updates, mut params) = get_update_vars(data, &psql_data, &definition)?;
+        let new_status = get_new_status::<Self>(data, &psql_data)?;
+
+        let mut client = get_psql_client().await?;
+        let transaction = start_transaction::<Self>(&mut client).await?;
+        let transition =
+            check_status_transition::<Self>(&transaction, &id, new_status, &mut validation_result)
+                .await?;
+        if !validation_result.success {
+            transaction.rollback().await?;
+            return Ok((None, validation_result));
+        }
+        if let Some(transition) = &transition {
+            updates.extend(get_stamp_update::<Self>(transition, Some(&psql_data)));
+        }
 
         if definition.has_field("updated_at") {
             updates.push(r#""updated_at" = NOW()"#.to_string());
         }
 
-        let update_sql = &format!(
-            "UPDATE {} SET {} WHERE {} = ${}",
+        let mut update_sql = format!(
+            r#"UPDATE "{}" SET {} WHERE "{}" = ${}"#,
             definition.psql_table,
             updates.join(", "),
             id_col,
             params.len() + 1
         );
+        if definition.has_field("deleted_at") {
+            update_sql.push_str(r#" AND "deleted_at" IS NULL"#);
+        }
         params.push(&id);
 
         psql_info!(
@@ -147,18 +534,270 @@ where
         psql_debug!("(update) [{}].", update_sql);
         psql_debug!("(update) [{:?}].", &params);
 
-        let client = get_psql_client().await?;
-        client.execute(update_sql, &params[..]).await?;
+        if transaction.execute(&update_sql, &params[..]).await? != 1 {
+            transaction.rollback().await?;
+            return Err(ArrErr::not_found(get_not_found_error::<Self>(&id)));
+        }
+        update_derived_fields::<Self>(&transaction, &id).await?;
+        if let Some(transition) = &transition {
+            record_status_transition::<Self>(&transaction, &id, transition, None).await?;
+            let trigger = PropagationTrigger::Status(transition.to.clone());
+            propagate_status::<Self>(&transaction, &id, &trigger, None).await?;
+        }
+
+        check_range_exclusions::<Self>(&transaction, &id, &mut validation_result).await?;
+        check_spatial_constraints::<Self>(&transaction, &id, &mut validation_result).await?;
+        if !validation_result.success {
+            transaction.rollback().await?;
+            return Ok((None, validation_result));
+        }
+        record_change(
+            &transaction,
+            &definition.psql_table,
+            ChangeType::Updated,
+            &id,
+        )
+        .await?;
+        transaction.commit().await?;
+        notify_watchers();
 
         //TODO(R4): flush shared memcache for this resource when memcache is implemented
         Ok((Some(self.read().await?), validation_result))
     }
 
+    /// Update multiple database records using a single transaction, each item using its own savepoint.
+    ///
+    /// Every object will be validated using its own `data` and `mask` and gets its own [`ValidationResult`],
+    /// in the same order as the provided objects.
+    /// Using [`BatchMode::AllOrNothing`], no records will be updated if any of the items fail.
+    /// Using [`BatchMode::BestEffort`], valid items will be updated while failing items will be skipped.
+    ///
+    /// Returns the updated [`Row`]s and [`ValidationResult`]s per item and a [`bool`] indicating if all items succeeded.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArrErr`] "No id column configured for table" id_col could not be found
+    /// Returns [`ArrErr`] from [`PoolError`](deadpool::managed::PoolError) if no client connection could be returned from the connection [`Pool`](deadpool::managed::Pool)
+    /// Returns [`ArrErr`] Database Error if the transaction could not be started or finished
+    async fn update_many(
+        objects: &[Self],
+        mode: BatchMode,
+    ) -> Result<(BatchResults<Row>, bool), ArrErr>
+    where
+        Self: Sync,
+    {
+        psql_debug!("(update_many) Start [{}] items.", objects.len());
+        let id_col = Self::try_get_id_field()?;
+
+        let mut client = get_psql_client().await?;
+        let mut transaction = start_transaction::<Self>(&mut client).await?;
+
+        let mut results: BatchResults<Row> = Vec::with_capacity(objects.len());
+        let mut success = true;
+        for object in objects {
+            let data = match object.get_data() {
+                Some(data) => data,
+                None => {
+                    let mut validation_result = ValidationResult {
+                        success: true,
+                        errors: vec![],
+                    };
+                    add_batch_error(
+                        &mut validation_result,
+                        "data",
+                        "No data provided for update.".to_owned(),
+                    );
+                    success = false;
+                    results.push((None, validation_result));
+                    continue;
+                }
+            };
+            let (psql_data, mut validation_result) =
+                match validate_with_mask::<Self>(&data, &object.get_mask()) {
+                    Ok(result) => result,
+                    Err(e) => {
+                        psql_info!("(update_many) Could not validate item: {}", e);
+                        let mut validation_result = ValidationResult {
+                            success: true,
+                            errors: vec![],
+                        };
+                        add_batch_error(&mut validation_result, "data", e.to_string());
+                        success = false;
+                        results.push((None, validation_result));
+                        continue;
+                    }
+                };
+            if !validation_result.success {
+                success = false;
+                results.push((None, validation_result));
+                continue;
+            }
+            if !success && mode == BatchMode::AllOrNothing {
+                // Nothing will be saved, only validate the remaining items
+                results.push((None, validation_result));
+                continue;
+            }
+            let id = match object.try_get_uuid() {
+                Ok(id) => id,
+                Err(e) => {
+                    success = false;
+                    add_batch_error(&mut validation_result, &id_col, e.to_string());
+                    results.push((None, validation_result));
+                    continue;
+                }
+            };
+
+            let savepoint = transaction.transaction().await?;
+            match update_batch_item::<Self, T>(
+                &savepoint,
+                &id,
+                &data,
+                &psql_data,
+                &mut validation_result,
+            )
+            .await
+            {
+                Ok(Some(row)) => {
+                    savepoint.commit().await?;
+                    results.push((Some(row), validation_result));
+                }
+                Ok(None) => {
+                    savepoint.rollback().await?;
+                    success = false;
+                    results.push((None, validation_result));
+                }
+                Err(e) => {
+                    savepoint.rollback().await?;
+                    psql_info!("(update_many) Could not update item [{}]: {}", id, e);
+                    success = false;
+                    add_batch_error(&mut validation_result, &id_col, e.to_string());
+                    results.push((None, validation_result));
+                }
+            }
+        }
+
+        if !finish_batch(transaction, mode, success).await? {
+            for (row, validation_result) in results.iter_mut() {
+                *row = None;
+                fail_rolled_back_item(validation_result, &id_col);
+            }
+        }
+
+        //TODO(R4): flush shared memcache for this resource when memcache is implemented
+        Ok((results, success))
+    }
+
+    /// Delete multiple database records using a single transaction, each item using its own savepoint.
+    ///
+    /// Records will be marked as deleted if the resource has a `deleted_at` field, removed otherwise.
+    /// Every object gets its own [`ValidationResult`], in the same order as the provided objects.
+    /// Using [`BatchMode::AllOrNothing`], no records will be deleted if any of the items fail.
+    /// Using [`BatchMode::BestEffort`], failing items will be skipped.
+    ///
+    /// Returns the [`ValidationResult`]s per item and a [`bool`] indicating if all items succeeded.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArrErr`] "No id column configured for table" id_col could not be found
+    /// Returns [`ArrErr`] from [`PoolError`](deadpool::managed::PoolError) if no client connection could be returned from the connection [`Pool`](deadpool::managed::Pool)
+    /// Returns [`ArrErr`] Database Error if the transaction could not be started or finished
+    async fn delete_many(
+        objects: &[Self],
+        mode: BatchMode,
+    ) -> Result<(Vec<ValidationResult>, bool), ArrErr>
+    where
+        Self: Sync,
+    {
+        psql_debug!("(delete_many) Start [{}] items.", objects.len());
+        let definition = Self::get_definition();
+        let id_col = Self::try_get_id_field()?;
+
+        let query = if definition.has_field("deleted_at") {
+            format!(
+                r#"UPDATE "{}" SET "deleted_at" = NOW() WHERE "{}" = $1 AND "deleted_at" IS NULL"#,
+                definition.psql_table, id_col
+            )
+        } else {
+            format!(
+                r#"DELETE FROM "{}" WHERE "{}" = $1"#,
+                definition.psql_table, id_col
+            )
+        };
+        psql_debug!("(delete_many) [{}].", query);
+
+        let mut client = get_psql_client().await?;
+        let mut transaction = start_transaction::<Self>(&mut client).await?;
+        let stmt = transaction.prepare(&query).await?;
+
+        let mut results: Vec<ValidationResult> = Vec::with_capacity(objects.len());
+        let mut success = true;
+        for object in objects {
+            let mut validation_result = ValidationResult {
+                success: true,
+                errors: vec![],
+            };
+            if !success && mode == BatchMode::AllOrNothing {
+                // Nothing will be saved, skip the remaining items
+                results.push(validation_result);
+                continue;
+            }
+            let id = match object.try_get_uuid() {
+                Ok(id) => id,
+                Err(e) => {
+                    success = false;
+                    add_batch_error(&mut validation_result, &id_col, e.to_string());
+                    results.push(validation_result);
+                    continue;
+                }
+            };
+
+            let savepoint = transaction.transaction().await?;
+            match savepoint.execute(&stmt, &[&id]).await {
+                Ok(1) => {
+                    record_change(&savepoint, &definition.psql_table, ChangeType::Deleted, &id)
+                        .await?;
+                    savepoint.commit().await?;
+                }
+                Ok(_) => {
+                    savepoint.rollback().await?;
+                    success = false;
+                    add_batch_error(
+                        &mut validation_result,
+                        &id_col,
+                        format!("No record found for id [{}] (already deleted?).", id),
+                    );
+                }
+                Err(e) => {
+                    savepoint.rollback().await?;
+                    psql_info!("(delete_many) Could not delete item [{}]: {}", id, e);
+                    success = false;
+                    add_batch_error(&mut validation_result, &id_col, e.to_string());
+                }
+            }
+            results.push(validation_result);
+        }
+
+        if !finish_batch(transaction, mode, success).await? {
+            for validation_result in results.iter_mut() {
+                fail_rolled_back_item(validation_result, &id_col);
+            }
+        }
+
+        //TODO(R4): flush shared memcache for this resource when memcache is implemented
+        Ok((results, success))
+    }
+
     /// Returns `true` if the resource has a `deleted_at` field and if it's [`Some`]
     ///
     /// Returns `false` otherwise
     async fn is_archived(&self) -> bool {
-        let data = match self.read().await {
+        let id = match self.try_get_uuid() {
+            Ok(id) => id,
+            Err(_) => {
+                return false;
+            }
+        };
+        let data = match Self::get_by_id_include_deleted(&id).await {
             Ok(data) => data,
             Err(_) => {
                 return false;
@@ -205,9 +844,8 @@ where
                 definition.psql_table,
                 id
             );
-            return Err(ArrErr::Error(
-                "(set_deleted_at_now) [deleted_at] column is already set, will not overwrite."
-                    .to_owned(),
+            return Err(ArrErr::invalid_state(
+                "(set_deleted_at_now) [deleted_at] column is already set, will not overwrite.",
             ));
         }
 
@@ -216,16 +854,26 @@ where
             definition.psql_table,
             id
         );
-        let client = get_psql_client().await?;
+        let mut client = get_psql_client().await?;
+        let transaction = client.transaction().await?;
 
         let query = format!(
             r#"UPDATE "{}" SET "deleted_at" = NOW() WHERE "{}" = $1"#,
             definition.psql_table, id_col
         );
-        let stmt = client.prepare_cached(&query).await?;
-        match client.execute(&stmt, &[&id]).await {
+        let stmt = transaction.prepare_cached(&query).await?;
+        match transaction.execute(&stmt, &[&id]).await {
             Ok(num_rows) => {
                 if num_rows == 1 {
+                    record_change(
+                        &transaction,
+                        &definition.psql_table,
+                        ChangeType::Deleted,
+                        &id,
+                    )
+                    .await?;
+                    transaction.commit().await?;
+                    notify_watchers();
                     //TODO(R4): flush shared memcache for this resource when memcache is implemented
                     Ok(())
                 } else {
@@ -234,13 +882,227 @@ where
                         definition.psql_table, id
                     );
                     psql_info!("(set_deleted_at_now) {}", error);
-                    Err(ArrErr::Error(error))
+                    Err(ArrErr::not_found(error))
                 }
             }
             Err(e) => Err(e.into()),
         }
     }
 
+    /// Restores a soft-deleted database record by clearing the `deleted_at` field using the Object's UUID
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArrErr`] "No \[deleted_at\] column" if the resource does not support soft-deletes
+    /// Returns [`ArrErr`] "No id column configured for table" id_col could not be found
+    /// Returns [`ArrErr`] if the `id` [`String`] could not be converted to a valid [`Uuid`]
+    /// Returns [`ArrErr`] "\[deleted_at\] column is not set" if [`is_archived`](Self::is_archived) returned `false`
+    /// Returns [`ArrErr`] from [`PoolError`](deadpool::managed::PoolError) if no client connection could be returned from the connection [`Pool`](deadpool::managed::Pool)
+    /// Returns [`ArrErr`] "Failed to update \[deleted_at\] col" if database query execution returns zero updated rows
+    /// Returns [`ArrErr`] "Can not restore" if the restored row violates any of the resource's [range exclusions](Resource::get_range_exclusions)
+    /// Returns [`ArrErr`] Database Error if database query execution failed
+    async fn restore(&self) -> Result<(), ArrErr> {
+        psql_debug!("(restore) Start [{:?}].", self.try_get_uuid());
+        let definition = Self::get_definition();
+        if !definition.has_field("deleted_at") {
+            let error = format!(
+                "No [deleted_at] column for [{}], nothing to restore.",
+                definition.psql_table
+            );
+            psql_info!("(restore) {}", error);
+            return Err(ArrErr::invalid_state(error));
+        }
+        let id_col = Self::try_get_id_field()?;
+        let id = self.try_get_uuid()?;
+
+        if !self.is_archived().await {
+            let error = format!(
+                "[deleted_at] column is not set for [{}] with id [{}], nothing to restore.",
+                definition.psql_table, id
+            );
+            psql_info!("(restore) {}", error);
+            return Err(ArrErr::invalid_state(error));
+        }
+
+        psql_info!(
+            "(restore) Clearing [deleted_at] field for [{}]. uuid: {}",
+            definition.psql_table,
+            id
+        );
+        let mut client = get_psql_client().await?;
+        let transaction = start_transaction::<Self>(&mut client).await?;
+
+        let mut query = format!(
+            r#"UPDATE "{}" SET "deleted_at" = NULL"#,
+            definition.psql_table
+        );
+        if definition.has_field("updated_at") {
+            query.push_str(r#", "updated_at" = NOW()"#);
+        }
+        query.push_str(&format!(r#" WHERE "{}" = $1"#, id_col));
+
+        let stmt = transaction.prepare_cached(&query).await?;
+        match transaction.execute(&stmt, &[&id]).await {
+            Ok(num_rows) => {
+                if num_rows == 1 {
+                    let mut validation_result = ValidationResult {
+                        success: true,
+                        errors: vec![],
+                    };
+                    check_range_exclusions::<Self>(&transaction, &id, &mut validation_result)
+                        .await?;
+                    if !validation_result.success {
+                        transaction.rollback().await?;
+                        let errors: Vec<String> = validation_result
+                            .errors
+                            .into_iter()
+                            .map(|error| error.error)
+                            .collect();
+                        let error = format!(
+                            "Can not restore [{}] with id [{}]: {}",
+                            definition.psql_table,
+                            id,
+                            errors.join("; ")
+                        );
+                        psql_info!("(restore) {}", error);
+                        return Err(ArrErr::invalid_state(error));
+                    }
+                    record_change(
+                        &transaction,
+                        &definition.psql_table,
+                        ChangeType::Created,
+                        &id,
+                    )
+                    .await?;
+                    transaction.commit().await?;
+                    notify_watchers();
+                    //TODO(R4): flush shared memcache for this resource when memcache is implemented
+                    Ok(())
+                } else {
+                    let error = format!(
+                        "Failed to update [deleted_at] col for [{}] with id [{}] (does not exist?).",
+                        definition.psql_table, id
+                    );
+                    psql_info!("(restore) {}", error);
+                    Err(ArrErr::not_found(error))
+                }
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Permanently removes the database record using the Object's UUID
+    ///
+    /// Rows referencing the record in any of the resource's [link tables](Resource::get_link_tables)
+    /// will be removed in the same transaction.
+    /// Resources with a `deleted_at` field need to be deleted (archived) before they can be purged.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArrErr`] "No id column configured for table" id_col could not be found
+    /// Returns [`ArrErr`] if the `id` [`String`] could not be converted to a valid [`Uuid`]
+    /// Returns [`ArrErr`] "\[deleted_at\] column is not set" if the resource has a `deleted_at` field and [`is_archived`](Self::is_archived) returned `false`
+    /// Returns [`ArrErr`] from [`PoolError`](deadpool::managed::PoolError) if no client connection could be returned from the connection [`Pool`](deadpool::managed::Pool)
+    /// Returns [`ArrErr`] "Can not purge" if the record is still referenced by records of other tables
+    /// Returns [`ArrErr`] "Failed to purge entry" if database query execution returns zero deleted rows
+    /// Returns [`ArrErr`] Database Error if database query execution failed
+    async fn purge(&self) -> Result<(), ArrErr> {
+        psql_debug!("(purge) Start [{:?}].", self.try_get_uuid());
+        let definition = Self::get_definition();
+        let id_col = Self::try_get_id_field()?;
+        let id = self.try_get_uuid()?;
+
+        if definition.has_field("deleted_at") && !self.is_archived().await {
+            let error = format!(
+                "[deleted_at] column is not set for [{}] with id [{}], refusing to purge.",
+                definition.psql_table, id
+            );
+            psql_info!("(purge) {}", error);
+            return Err(ArrErr::invalid_state(error));
+        }
+
+        let mut client = get_psql_client().await?;
+        let transaction = client.transaction().await?;
+
+        for link_table in Self::get_link_tables() {
+            psql_info!(
+                "(purge) Removing [{}] links for [{}]. uuid: {}",
+                link_table,
+                definition.psql_table,
+                id
+            );
+            let query = format!(r#"DELETE FROM "{}" WHERE "{}" = $1"#, link_table, id_col);
+            psql_debug!("(purge) [{}].", &query);
+            transaction
+                .execute(&query, &[&id])
+                .await
+                .map_err(ArrErr::from)?;
+        }
+
+        // Refuse to purge records which are still referenced by other records,
+        // those need to be purged or updated first.
+        let mut dependents: Vec<String> = vec![];
+        for (table, column) in
+            super::queries::get_referencing_columns(&transaction, &definition.psql_table).await?
+        {
+            let query = format!(
+                r#"SELECT COUNT(*) FROM "{}" WHERE "{}" = $1"#,
+                table, column
+            );
+            psql_debug!("(purge) [{}].", &query);
+            let count: i64 = transaction.query_one(&query, &[&id]).await?.get(0);
+            if count > 0 {
+                dependents.push(format!("{} row(s) in [{}.{}]", count, table, column));
+            }
+        }
+        if !dependents.is_empty() {
+            transaction.rollback().await?;
+            let error = format!(
+                "Can not purge [{}] with id [{}], it is still referenced by {}. Purge or update the referencing records first.",
+                definition.psql_table,
+                id,
+                dependents.join(", ")
+            );
+            psql_info!("(purge) {}", error);
+            return Err(ArrErr::invalid_state(error));
+        }
+
+        psql_info!(
+            "(purge) Purging entry from table [{}]. uuid: {}",
+            definition.psql_table,
+            id
+        );
+        let query = format!(
+            r#"DELETE FROM "{}" WHERE "{}" = $1"#,
+            definition.psql_table, id_col
+        );
+        psql_debug!("(purge) [{}].", &query);
+        let num_rows = transaction
+            .execute(&query, &[&id])
+            .await
+            .map_err(ArrErr::from)?;
+        if num_rows != 1 {
+            let error = format!(
+                "Failed to purge entry for [{}] with id [{}] (does not exist?).",
+                definition.psql_table, id
+            );
+            psql_info!("(purge) {}", error);
+            return Err(ArrErr::not_found(error));
+        }
+        record_change(
+            &transaction,
+            &definition.psql_table,
+            ChangeType::Deleted,
+            &id,
+        )
+        .await?;
+
+        //TODO(R4): flush shared memcache for this resource when memcache is implemented
+        transaction.commit().await?;
+        notify_watchers();
+        Ok(())
+    }
+
     /// Delete database record from the database using the Object's UUID
     ///
     /// # Errors
@@ -261,15 +1123,25 @@ where
             definition.psql_table,
             id
         );
-        let client = get_psql_client().await?;
+        let mut client = get_psql_client().await?;
+        let transaction = client.transaction().await?;
         let query = format!(
             r#"DELETE FROM "{}" WHERE "{}" = $1"#,
             definition.psql_table, id_col
         );
-        let stmt = client.prepare_cached(&query).await?;
-        match client.execute(&stmt, &[&id]).await {
+        let stmt = transaction.prepare_cached(&query).await?;
+        match transaction.execute(&stmt, &[&id]).await {
             Ok(num_rows) => {
                 if num_rows == 1 {
+                    record_change(
+                        &transaction,
+                        &definition.psql_table,
+                        ChangeType::Deleted,
+                        &id,
+                    )
+                    .await?;
+                    transaction.commit().await?;
+                    notify_watchers();
                     //TODO(R4): flush shared memcache for this resource when memcache is implemented
                     Ok(())
                 } else {
@@ -278,7 +1150,7 @@ where
                         definition.psql_table, id
                     );
                     psql_info!("(delete_row) {}", error);
-                    Err(ArrErr::Error(error))
+                    Err(ArrErr::not_found(error))
                 }
             }
             Err(e) => Err(e.into()),
@@ -1,14 +1,18 @@
 //! Psql Simple resource Traits
 
+use super::exclusion::start_transaction;
 use super::get_psql_client;
+use super::postgis::{get_geom_from_text_sql, get_line_string_wkt, get_point_wkt};
+use super::watch::{notify_watchers, record_change};
 use super::{util::*, ArrErr, PsqlData, PsqlField, PsqlFieldSend};
-use crate::grpc::server::ValidationResult;
+use crate::grpc::server::grpc_geo_types::{GeoLineString, GeoPoint};
+use crate::grpc::server::{ChangeType, ValidationResult};
 use crate::grpc::{GrpcDataObjectType, GrpcField};
 use crate::resources::base::simple_resource::*;
 
 use chrono::{DateTime, Utc};
 use deadpool_postgres::Transaction;
-use geo_types::{LineString, Point, Polygon};
+use geo_types::Polygon;
 use std::collections::HashMap;
 use std::vec;
 use tokio_postgres::types::Type as PsqlFieldType;
@@ -171,6 +175,9 @@ where
 
     /// Update the Object's database record using provided data
     ///
+    /// If the Object has a `mask` set, only the fields provided in the mask will be validated and updated.
+    /// Unknown or read only mask paths will be returned as validation errors.
+    ///
     /// returns [Option(Row)] and [ValidationResult]
     ///
     /// # Errors
@@ -178,12 +185,13 @@ where
     /// Returns [`ArrErr`] Validation "Conversion error, unknown field type" if the provided field type could not be matched.
     /// Returns [`ArrErr`] "No id column configured for table" id_col could not be found
     /// Returns [`ArrErr`] if the `id` [`String`] could not be converted to a valid [`Uuid`]
+    /// Returns [`ArrErr::NotFound`] if no record exists for the `id`
     /// Returns [`ArrErr`] from [`PoolError`](deadpool::managed::PoolError) if no client connection could be returned from the connection [`Pool`](deadpool::managed::Pool)
     /// Returns [`ArrErr`] Database Error if database query execution failed
     async fn update<'a>(&self, data: &T) -> Result<(Option<Row>, ValidationResult), ArrErr> {
         psql_debug!("(update) Start [{:?}].", data);
 
-        let (psql_data, validation_result) = validate::<Self>(data)?;
+        let (psql_data, validation_result) = validate_with_mask::<Self>(data, &self.get_mask())?;
         if !validation_result.success {
             return Ok((None, validation_result));
         }
@@ -215,8 +223,24 @@ where
         psql_debug!("(update) [{}].", update_sql);
         psql_debug!("(update) [{:?}].", &params);
 
-        let client = get_psql_client().await?;
-        client.execute(update_sql, &params[..]).await?;
+        let mut client = get_psql_client().await?;
+        let transaction = start_transaction::<Self>(&mut client).await?;
+        if transaction.execute(update_sql, &params[..]).await? != 1 {
+            transaction.rollback().await?;
+            return Err(ArrErr::not_found(format!(
+                "No [{}] record found for id [{}].",
+                definition.psql_table, id
+            )));
+        }
+        record_change(
+            &transaction,
+            &definition.psql_table,
+            ChangeType::Updated,
+            &id,
+        )
+        .await?;
+        transaction.commit().await?;
+        notify_watchers();
 
         //TODO(R4): flush shared memcache for this resource when memcache is implemented
         Ok((Some(self.read().await?), validation_result))
@@ -226,7 +250,13 @@ where
     ///
     /// Returns `false` otherwise
     async fn is_archived(&self) -> bool {
-        let data = match self.read().await {
+        let id = match self.try_get_uuid() {
+            Ok(id) => id,
+            Err(_) => {
+                return false;
+            }
+        };
+        let data = match Self::get_by_id_include_deleted(&id).await {
             Ok(data) => data,
             Err(_) => {
                 return false;
@@ -273,9 +303,8 @@ where
                 definition.psql_table,
                 id
             );
-            return Err(ArrErr::Error(
-                "(set_deleted_at_now) [deleted_at] column is already set, will not overwrite."
-                    .to_owned(),
+            return Err(ArrErr::invalid_state(
+                "(set_deleted_at_now) [deleted_at] column is already set, will not overwrite.",
             ));
         }
 
@@ -284,16 +313,26 @@ where
             definition.psql_table,
             id
         );
-        let client = get_psql_client().await?;
+        let mut client = get_psql_client().await?;
+        let transaction = client.transaction().await?;
 
         let query = format!(
             r#"UPDATE "{}" SET "deleted_at" = NOW() WHERE "{}" = $1"#,
             definition.psql_table, id_col
         );
-        let stmt = client.prepare_cached(&query).await?;
-        match client.execute(&stmt, &[&id]).await {
+        let stmt = transaction.prepare_cached(&query).await?;
+        match transaction.execute(&stmt, &[&id]).await {
             Ok(num_rows) => {
                 if num_rows == 1 {
+                    record_change(
+                        &transaction,
+                        &definition.psql_table,
+                        ChangeType::Deleted,
+                        &id,
+                    )
+                    .await?;
+                    transaction.commit().await?;
+                    notify_watchers();
                     //TODO(R4): flush shared memcache for this resource when memcache is implemented
                     Ok(())
                 } else {
@@ -302,13 +341,119 @@ where
                         definition.psql_table, id
                     );
                     psql_info!("(set_deleted_at_now) {}", error);
-                    Err(ArrErr::Error(error))
+                    Err(ArrErr::not_found(error))
+                }
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Restores a soft-deleted database record by clearing the `deleted_at` field using the Object's UUID
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArrErr`] "No \[deleted_at\] column" if the resource does not support soft-deletes
+    /// Returns [`ArrErr`] "No id column configured for table" id_col could not be found
+    /// Returns [`ArrErr`] if the `id` [`String`] could not be converted to a valid [`Uuid`]
+    /// Returns [`ArrErr`] "\[deleted_at\] column is not set" if [`is_archived`](Self::is_archived) returned `false`
+    /// Returns [`ArrErr`] from [`PoolError`](deadpool::managed::PoolError) if no client connection could be returned from the connection [`Pool`](deadpool::managed::Pool)
+    /// Returns [`ArrErr`] "Failed to update \[deleted_at\] col" if database query execution returns zero updated rows
+    /// Returns [`ArrErr`] Database Error if database query execution failed
+    async fn restore(&self) -> Result<(), ArrErr> {
+        psql_debug!("(restore) Start [{:?}].", self.try_get_uuid());
+        let definition = Self::get_definition();
+        if !definition.has_field("deleted_at") {
+            let error = format!(
+                "No [deleted_at] column for [{}], nothing to restore.",
+                definition.psql_table
+            );
+            psql_info!("(restore) {}", error);
+            return Err(ArrErr::invalid_state(error));
+        }
+        let id_col = Self::try_get_id_field()?;
+        let id = self.try_get_uuid()?;
+
+        if !self.is_archived().await {
+            let error = format!(
+                "[deleted_at] column is not set for [{}] with id [{}], nothing to restore.",
+                definition.psql_table, id
+            );
+            psql_info!("(restore) {}", error);
+            return Err(ArrErr::invalid_state(error));
+        }
+
+        psql_info!(
+            "(restore) Clearing [deleted_at] field for [{}]. uuid: {}",
+            definition.psql_table,
+            id
+        );
+        let mut client = get_psql_client().await?;
+        let transaction = start_transaction::<Self>(&mut client).await?;
+
+        let mut query = format!(
+            r#"UPDATE "{}" SET "deleted_at" = NULL"#,
+            definition.psql_table
+        );
+        if definition.has_field("updated_at") {
+            query.push_str(r#", "updated_at" = NOW()"#);
+        }
+        query.push_str(&format!(r#" WHERE "{}" = $1"#, id_col));
+
+        let stmt = transaction.prepare_cached(&query).await?;
+        match transaction.execute(&stmt, &[&id]).await {
+            Ok(num_rows) => {
+                if num_rows == 1 {
+                    record_change(
+                        &transaction,
+                        &definition.psql_table,
+                        ChangeType::Created,
+                        &id,
+                    )
+                    .await?;
+                    transaction.commit().await?;
+                    notify_watchers();
+                    //TODO(R4): flush shared memcache for this resource when memcache is implemented
+                    Ok(())
+                } else {
+                    let error = format!(
+                        "Failed to update [deleted_at] col for [{}] with id [{}] (does not exist?).",
+                        definition.psql_table, id
+                    );
+                    psql_info!("(restore) {}", error);
+                    Err(ArrErr::not_found(error))
                 }
             }
             Err(e) => Err(e.into()),
         }
     }
 
+    /// Permanently removes the database record(s) matching the Object's ids
+    ///
+    /// Resources with a `deleted_at` field need to be deleted (archived) before they can be purged.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArrErr`] if the `ids` could not be converted to valid [`Uuid`]s
+    /// Returns [`ArrErr`] "\[deleted_at\] column is not set" if the resource has a `deleted_at` field and [`is_archived`](Self::is_archived) returned `false`
+    /// Returns [`ArrErr`] from [`PoolError`](deadpool::managed::PoolError) if no client connection could be returned from the connection [`Pool`](deadpool::managed::Pool)
+    /// Returns [`ArrErr`] Database Error if database query execution failed
+    async fn purge(&self) -> Result<(), ArrErr> {
+        psql_debug!("(purge) Start [{:?}].", self.get_ids());
+        let definition = Self::get_definition();
+        let ids = self.try_get_uuids()?;
+
+        if definition.has_field("deleted_at") && !self.is_archived().await {
+            let error = format!(
+                "[deleted_at] column is not set for [{}] with ids [{:?}], refusing to purge.",
+                definition.psql_table, ids
+            );
+            psql_info!("(purge) {}", error);
+            return Err(ArrErr::invalid_state(error));
+        }
+
+        Self::delete_for_ids(ids, None).await
+    }
+
     /// Delete database record from the database using the Object's UUID
     ///
     /// # Errors
@@ -329,15 +474,25 @@ where
             definition.psql_table,
             id
         );
-        let client = get_psql_client().await?;
+        let mut client = get_psql_client().await?;
+        let transaction = client.transaction().await?;
         let query = format!(
             r#"DELETE FROM "{}" WHERE "{}" = $1"#,
             definition.psql_table, id_col
         );
-        let stmt = client.prepare_cached(&query).await?;
-        match client.execute(&stmt, &[&id]).await {
+        let stmt = transaction.prepare_cached(&query).await?;
+        match transaction.execute(&stmt, &[&id]).await {
             Ok(num_rows) => {
                 if num_rows == 1 {
+                    record_change(
+                        &transaction,
+                        &definition.psql_table,
+                        ChangeType::Deleted,
+                        &id,
+                    )
+                    .await?;
+                    transaction.commit().await?;
+                    notify_watchers();
                     //TODO(R4): flush shared memcache for this resource when memcache is implemented
                     Ok(())
                 } else {
@@ -346,7 +501,7 @@ where
                         definition.psql_table, id
                     );
                     psql_info!("(set_deleted_at_now) {}", error);
-                    Err(ArrErr::Error(error))
+                    Err(ArrErr::not_found(error))
                 }
             }
             Err(e) => Err(e.into()),
@@ -464,15 +619,8 @@ fn get_point_sql_val(point_option: GrpcField) -> Option<String> {
             let point: Option<GrpcField> = val.into();
             match point {
                 Some(val) => {
-                    let val: Point = val.into();
-                    // POINT expects (x y) which is (long lat)
-                    // geo_types::geometry::point::Point has a x and y which
-                    // we've aligned with the POINT(x y)/POINT(long lat)
-                    Some(format!(
-                        "ST_GeomFromText('POINT({:.15} {:.15})')",
-                        val.x(),
-                        val.y()
-                    ))
+                    let val: GeoPoint = val.into();
+                    Some(get_geom_from_text_sql(&get_point_wkt(&val)))
                 }
                 None => None,
             }
@@ -505,10 +653,10 @@ fn get_polygon_sql_val(polygon_option: GrpcField) -> Option<String> {
                         line_str_pairs.push(coord_str);
                     }
 
-                    Some(format!(
-                        "ST_GeomFromText('POLYGON({})')",
+                    Some(get_geom_from_text_sql(&format!(
+                        "POLYGON({})",
                         line_str_pairs.join(",")
-                    ))
+                    )))
                 }
                 None => None,
             }
@@ -523,16 +671,8 @@ fn get_path_sql_val(path_option: GrpcField) -> Option<String> {
             let path: Option<GrpcField> = val.into();
             match path {
                 Some(val) => {
-                    let val: LineString = val.into();
-                    let mut coord_str_pairs: Vec<String> = vec![];
-                    for coord in val.coords() {
-                        coord_str_pairs.push(format!("{:.15} {:.15}", coord.x, coord.y));
-                    }
-
-                    Some(format!(
-                        "ST_GeomFromText('LINESTRING({})')",
-                        coord_str_pairs.join(",")
-                    ))
+                    let val: GeoLineString = val.into();
+                    Some(get_geom_from_text_sql(&get_line_string_wkt(&val)))
                 }
                 None => None,
             }
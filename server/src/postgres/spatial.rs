@@ -0,0 +1,392 @@
+//! Spatial consistency checks
+//!
+//! The [`SpatialCheck`]s of a resource are checked using PostGIS after a record has been written,
+//! using the same transaction. The transaction will be rolled back if any of the checks fail.
+//! The [`DependentSpatialCheck`]s of a resource are checked the same way, making sure the records
+//! referencing the written record still pass their own checks.
+//! Distances are calculated on the spheroid by casting the geometries to `GEOGRAPHY`.
+
+use super::simple_resource::PsqlType;
+use super::ArrErr;
+use crate::config::Config;
+use crate::grpc::server::{ValidationError, ValidationResult};
+use crate::resources::base::{
+    DependentSpatialCheck, ResourceDefinition, SpatialCheck, SpatialRelation,
+};
+
+use deadpool_postgres::Transaction;
+use tokio::sync::OnceCell;
+use uuid::Uuid;
+
+/// Alias used for the record being checked
+const SELF_ALIAS: &str = "s";
+/// Alias used for the referenced record
+const REF_ALIAS: &str = "r";
+
+/// Maximum distance in meters between the start and end points of a path and their referenced geometries
+static PATH_TOLERANCE: OnceCell<f64> = OnceCell::const_new();
+
+/// Returns the configured path tolerance in meters
+async fn get_path_tolerance() -> f64 {
+    *PATH_TOLERANCE
+        .get_or_init(|| async {
+            Config::try_from_env()
+                .unwrap_or_default()
+                .flight_path_tolerance_meters
+        })
+        .await
+}
+
+/// Checks the geometries of the record with the provided id against the geometries of its
+/// referenced records using the [`SpatialCheck`]s of the resource, and the geometries of the
+/// records referencing it using the [`DependentSpatialCheck`]s of the resource.
+///
+/// A [`ValidationError`] will be added to the provided [`ValidationResult`] for each failed check.
+///
+/// # Errors
+///
+/// Returns [`ArrErr`] "No id column configured for table" id_col could not be found
+/// Returns [`ArrErr`] Database Error if database query execution failed
+pub(crate) async fn check_spatial_constraints<T>(
+    transaction: &Transaction<'_>,
+    id: &Uuid,
+    validation_result: &mut ValidationResult,
+) -> Result<(), ArrErr>
+where
+    T: PsqlType,
+{
+    let definition = T::get_definition();
+    let id_col = T::try_get_id_field()?;
+    for check in T::get_spatial_checks() {
+        let query = get_spatial_check_query(&definition, &id_col, &check);
+        psql_debug!("(check_spatial_constraints) [{}].", query);
+
+        let row = match check.relation {
+            SpatialRelation::Within => transaction.query_opt(&query, &[id]).await?,
+            SpatialRelation::StartsAt | SpatialRelation::EndsAt => {
+                let tolerance = get_path_tolerance().await;
+                transaction.query_opt(&query, &[id, &tolerance]).await?
+            }
+        };
+        // No reference or no geometry available, nothing to check
+        let valid: Option<bool> = row.and_then(|row| row.get::<usize, Option<bool>>(0));
+        if valid.unwrap_or(true) {
+            continue;
+        }
+
+        let error = get_spatial_check_error(&definition, &check, get_path_tolerance().await);
+        psql_info!("(check_spatial_constraints) {}", error);
+        validation_result.success = false;
+        validation_result.errors.push(ValidationError {
+            field: check.col.clone(),
+            error,
+        });
+    }
+
+    for dependent in T::get_dependent_spatial_checks() {
+        let Some(source_id_col) = dependent.source.psql_id_cols.first() else {
+            let error = format!(
+                "No id column configured for table {}",
+                dependent.source.psql_table
+            );
+            psql_error!("(check_spatial_constraints) {}", error);
+            return Err(ArrErr::Error(error));
+        };
+        let query = get_dependent_spatial_check_query(&dependent, source_id_col);
+        psql_debug!("(check_spatial_constraints) [{}].", query);
+
+        let rows = match dependent.check.relation {
+            SpatialRelation::Within => transaction.query(&query, &[id]).await?,
+            SpatialRelation::StartsAt | SpatialRelation::EndsAt => {
+                let tolerance = get_path_tolerance().await;
+                transaction.query(&query, &[id, &tolerance]).await?
+            }
+        };
+        if rows.is_empty() {
+            continue;
+        }
+
+        let ids: Vec<String> = rows
+            .iter()
+            .map(|row| row.get::<usize, Uuid>(0).to_string())
+            .collect();
+        let error = format!(
+            "Changing [{}] of [{}] breaks the [{}] records [{}]: {}",
+            dependent.check.ref_col,
+            definition.psql_table,
+            dependent.source.psql_table,
+            ids.join(", "),
+            get_spatial_check_error(
+                &dependent.source,
+                &dependent.check,
+                get_path_tolerance().await
+            )
+        );
+        psql_info!("(check_spatial_constraints) {}", error);
+        validation_result.success = false;
+        validation_result.errors.push(ValidationError {
+            field: dependent.check.ref_col.clone(),
+            error,
+        });
+    }
+    Ok(())
+}
+
+/// Returns the PostGIS condition of the [`SpatialCheck`], comparing the record's geometry
+/// (aliased [`SELF_ALIAS`]) with the referenced geometry (aliased [`REF_ALIAS`])
+///
+/// The path relations use the tolerance in meters provided as parameter `$2`.
+fn get_spatial_condition(check: &SpatialCheck) -> String {
+    let col = format!(r#"{}."{}""#, SELF_ALIAS, check.col);
+    let ref_col = format!(r#"{}."{}""#, REF_ALIAS, check.ref_col);
+    match check.relation {
+        SpatialRelation::Within => format!("ST_Covers({}, {})", ref_col, col),
+        SpatialRelation::StartsAt => format!(
+            "ST_DWithin(ST_StartPoint({})::GEOGRAPHY, {}::GEOGRAPHY, $2)",
+            col, ref_col
+        ),
+        SpatialRelation::EndsAt => format!(
+            "ST_DWithin(ST_EndPoint({})::GEOGRAPHY, {}::GEOGRAPHY, $2)",
+            col, ref_col
+        ),
+    }
+}
+
+/// Returns the query used to check the [`SpatialCheck`] for the record matching id parameter `$1`.
+///
+/// The path relations get the tolerance in meters as parameter `$2`.
+/// The query returns `NULL` if either geometry is missing, and no rows if there is no referenced record.
+fn get_spatial_check_query(
+    definition: &ResourceDefinition,
+    id_col: &str,
+    check: &SpatialCheck,
+) -> String {
+    format!(
+        r#"SELECT {} FROM "{}" AS {} JOIN "{}" AS {} ON {}."{}" = {}."{}" WHERE {}."{}" = $1"#,
+        get_spatial_condition(check),
+        definition.psql_table,
+        SELF_ALIAS,
+        check.ref_table,
+        REF_ALIAS,
+        REF_ALIAS,
+        check.ref_id_col,
+        SELF_ALIAS,
+        check.ref_key_col,
+        SELF_ALIAS,
+        id_col
+    )
+}
+
+/// Returns the query used to get the ids of the records failing the [`DependentSpatialCheck`]
+/// because of the referenced record matching id parameter `$1`.
+///
+/// The path relations get the tolerance in meters as parameter `$2`.
+/// Records without a geometry and soft-deleted records are not returned.
+fn get_dependent_spatial_check_query(
+    dependent: &DependentSpatialCheck,
+    source_id_col: &str,
+) -> String {
+    let check = &dependent.check;
+    let mut query = format!(
+        r#"SELECT {}."{}" FROM "{}" AS {} JOIN "{}" AS {} ON {}."{}" = {}."{}" WHERE {}."{}" = $1 AND NOT {}"#,
+        SELF_ALIAS,
+        source_id_col,
+        dependent.source.psql_table,
+        SELF_ALIAS,
+        check.ref_table,
+        REF_ALIAS,
+        REF_ALIAS,
+        check.ref_id_col,
+        SELF_ALIAS,
+        check.ref_key_col,
+        REF_ALIAS,
+        check.ref_id_col,
+        get_spatial_condition(check)
+    );
+    if dependent.source.has_field("deleted_at") {
+        query.push_str(&format!(r#" AND {}."deleted_at" IS NULL"#, SELF_ALIAS));
+    }
+    query
+}
+
+/// Returns the validation error message for a failed [`SpatialCheck`]
+fn get_spatial_check_error(
+    definition: &ResourceDefinition,
+    check: &SpatialCheck,
+    tolerance: f64,
+) -> String {
+    match check.relation {
+        SpatialRelation::Within => format!(
+            "[{}] of [{}] does not lie within [{}] of the [{}] referenced by [{}]",
+            check.col, definition.psql_table, check.ref_col, check.ref_table, check.ref_key_col
+        ),
+        SpatialRelation::StartsAt => format!(
+            "[{}] of [{}] does not start within {} meters of [{}] of the [{}] referenced by [{}]",
+            check.col,
+            definition.psql_table,
+            tolerance,
+            check.ref_col,
+            check.ref_table,
+            check.ref_key_col
+        ),
+        SpatialRelation::EndsAt => format!(
+            "[{}] of [{}] does not end within {} meters of [{}] of the [{}] referenced by [{}]",
+            check.col,
+            definition.psql_table,
+            tolerance,
+            check.ref_col,
+            check.ref_table,
+            check.ref_key_col
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::base::FieldDefinition;
+    use std::collections::HashMap;
+    use tokio_postgres::types::Type as PsqlFieldType;
+
+    fn get_definition() -> ResourceDefinition {
+        ResourceDefinition {
+            psql_table: String::from("route"),
+            psql_id_cols: vec![String::from("route_id")],
+            fields: HashMap::from([
+                (
+                    "path".to_string(),
+                    FieldDefinition::new(PsqlFieldType::PATH, true),
+                ),
+                (
+                    "origin_pad_id".to_string(),
+                    FieldDefinition::new(PsqlFieldType::UUID, true),
+                ),
+            ]),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_spatial_check_query() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_spatial_check_query) start");
+
+        let check = SpatialCheck::new(
+            "path",
+            SpatialRelation::Within,
+            "area_id",
+            "area",
+            "area_id",
+            "geo_location",
+        );
+        assert_eq!(
+            get_spatial_check_query(&get_definition(), "route_id", &check),
+            concat!(
+                r#"SELECT ST_Covers(r."geo_location", s."path") FROM "route" AS s "#,
+                r#"JOIN "area" AS r ON r."area_id" = s."area_id" WHERE s."route_id" = $1"#
+            )
+        );
+
+        let check = SpatialCheck::new(
+            "path",
+            SpatialRelation::StartsAt,
+            "origin_pad_id",
+            "pad",
+            "pad_id",
+            "geo_location",
+        );
+        assert_eq!(
+            get_spatial_check_query(&get_definition(), "route_id", &check),
+            concat!(
+                r#"SELECT ST_DWithin(ST_StartPoint(s."path")::GEOGRAPHY, r."geo_location"::GEOGRAPHY, $2) FROM "route" AS s "#,
+                r#"JOIN "pad" AS r ON r."pad_id" = s."origin_pad_id" WHERE s."route_id" = $1"#
+            )
+        );
+
+        let check = SpatialCheck::new(
+            "path",
+            SpatialRelation::EndsAt,
+            "target_pad_id",
+            "pad",
+            "pad_id",
+            "geo_location",
+        );
+        assert_eq!(
+            get_spatial_check_query(&get_definition(), "route_id", &check),
+            concat!(
+                r#"SELECT ST_DWithin(ST_EndPoint(s."path")::GEOGRAPHY, r."geo_location"::GEOGRAPHY, $2) FROM "route" AS s "#,
+                r#"JOIN "pad" AS r ON r."pad_id" = s."target_pad_id" WHERE s."route_id" = $1"#
+            )
+        );
+
+        ut_info!("(test_get_spatial_check_query) success");
+    }
+
+    #[tokio::test]
+    async fn test_get_dependent_spatial_check_query() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_dependent_spatial_check_query) start");
+
+        let mut dependent = DependentSpatialCheck {
+            source: get_definition(),
+            check: SpatialCheck::new(
+                "path",
+                SpatialRelation::Within,
+                "area_id",
+                "area",
+                "area_id",
+                "geo_location",
+            ),
+        };
+        assert_eq!(
+            get_dependent_spatial_check_query(&dependent, "route_id"),
+            concat!(
+                r#"SELECT s."route_id" FROM "route" AS s JOIN "area" AS r ON r."area_id" = s."area_id" "#,
+                r#"WHERE r."area_id" = $1 AND NOT ST_Covers(r."geo_location", s."path")"#
+            )
+        );
+
+        dependent.check = SpatialCheck::new(
+            "path",
+            SpatialRelation::StartsAt,
+            "origin_pad_id",
+            "pad",
+            "pad_id",
+            "geo_location",
+        );
+        dependent.source.fields.insert(
+            "deleted_at".to_string(),
+            FieldDefinition::new_internal(PsqlFieldType::TIMESTAMPTZ, false),
+        );
+        assert_eq!(
+            get_dependent_spatial_check_query(&dependent, "route_id"),
+            concat!(
+                r#"SELECT s."route_id" FROM "route" AS s JOIN "pad" AS r ON r."pad_id" = s."origin_pad_id" "#,
+                r#"WHERE r."pad_id" = $1 AND NOT ST_DWithin(ST_StartPoint(s."path")::GEOGRAPHY, r."geo_location"::GEOGRAPHY, $2) "#,
+                r#"AND s."deleted_at" IS NULL"#
+            )
+        );
+
+        ut_info!("(test_get_dependent_spatial_check_query) success");
+    }
+
+    #[tokio::test]
+    async fn test_get_spatial_check_error() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_spatial_check_error) start");
+
+        let check = SpatialCheck::new(
+            "path",
+            SpatialRelation::EndsAt,
+            "target_pad_id",
+            "pad",
+            "pad_id",
+            "geo_location",
+        );
+        assert_eq!(
+            get_spatial_check_error(&get_definition(), &check, 10.0),
+            "[path] of [route] does not end within 10 meters of [geo_location] of the [pad] referenced by [target_pad_id]"
+        );
+
+        ut_info!("(test_get_spatial_check_error) success");
+    }
+}
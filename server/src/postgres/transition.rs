@@ -0,0 +1,614 @@
+//! Status transition checks
+//!
+//! The [`StatusTransitions`] of a resource are checked before a record's status is updated,
+//! using the same transaction. The record will be locked until the transaction is finished,
+//! making sure concurrent updates can not skip a check.
+//! All status changes are recorded in the [`STATUS_TRANSITION_TABLE`].
+//!
+//! Inserts and status transitions of a resource can advance the status of related records
+//! through the resource's [`StatusPropagation`]s, using the same transaction.
+
+use super::exclusion::{check_range_exclusions, start_transaction};
+use super::simple_resource::PsqlType;
+use super::watch::{notify_watchers, record_change};
+use super::{get_psql_client, ArrErr, PsqlData};
+use crate::grpc::server::{ChangeType, ValidationError, ValidationResult};
+use crate::grpc::GrpcDataObjectType;
+use crate::resources::base::{
+    PropagationTrigger, Resource, ResourceDefinition, StatusPropagation, StatusTransitions,
+};
+
+use deadpool_postgres::Transaction;
+use uuid::Uuid;
+
+/// Name of the table containing all recorded status transitions
+pub const STATUS_TRANSITION_TABLE: &str = "status_transition";
+
+/// Status change of a single record
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusTransition {
+    /// status before the transition
+    pub from: String,
+    /// status after the transition
+    pub to: String,
+}
+
+/// Returns the new status value if the resource has [`StatusTransitions`] and the
+/// status column is part of the provided (validated) update data.
+///
+/// # Errors
+///
+/// Returns [`ArrErr`] if the status field could not be read from the provided data
+pub(crate) fn get_new_status<T>(
+    data: &impl GrpcDataObjectType,
+    psql_data: &PsqlData,
+) -> Result<Option<String>, ArrErr>
+where
+    T: Resource,
+{
+    let transitions = match T::get_status_transitions() {
+        Some(transitions) => transitions,
+        None => return Ok(None),
+    };
+    if !psql_data.contains_key(&transitions.status_col) {
+        return Ok(None);
+    }
+    let value: i32 = data.get_field_value(&transitions.status_col)?.into();
+    Ok(T::get_enum_string_val(&transitions.status_col, value))
+}
+
+/// Locks the record with the provided id and checks if its status can be changed to the provided `new_status`.
+///
+/// Returns the [`StatusTransition`] to be recorded after the update if the status changes.
+/// A [`ValidationError`] will be added to the provided [`ValidationResult`] if the transition is not allowed.
+///
+/// # Errors
+///
+/// Returns [`ArrErr`] "No id column configured for table" id_col could not be found
+/// Returns [`ArrErr`] NotFound if no record exists for the provided id, or if the record is soft-deleted
+/// Returns [`ArrErr`] Database Error if database query execution failed
+pub(crate) async fn check_status_transition<T>(
+    transaction: &Transaction<'_>,
+    id: &Uuid,
+    new_status: Option<String>,
+    validation_result: &mut ValidationResult,
+) -> Result<Option<StatusTransition>, ArrErr>
+where
+    T: PsqlType,
+{
+    let (transitions, to) = match (T::get_status_transitions(), new_status) {
+        (Some(transitions), Some(to)) => (transitions, to),
+        _ => return Ok(None),
+    };
+    let definition = T::get_definition();
+    let id_col = T::try_get_id_field()?;
+    let query = get_current_status_query(&definition, &id_col, &transitions);
+    psql_debug!("(check_status_transition) [{}].", query);
+
+    let from: String = match transaction.query_opt(&query, &[id]).await? {
+        Some(row) => row.get(0),
+        None => {
+            return Err(ArrErr::not_found(format!(
+                "No [{}] record found for id [{}].",
+                definition.psql_table, id
+            )))
+        }
+    };
+    if from == to {
+        return Ok(None);
+    }
+    if !transitions.is_allowed(&from, &to) {
+        let error = format!(
+            "Invalid [{}] transition from [{}] to [{}], allowed transitions: [{}]",
+            transitions.status_col,
+            from,
+            to,
+            transitions.get_allowed(&from).join(", ")
+        );
+        psql_info!("(check_status_transition) {}", error);
+        validation_result.success = false;
+        validation_result.errors.push(ValidationError {
+            field: transitions.status_col.clone(),
+            error,
+        });
+        return Ok(None);
+    }
+
+    Ok(Some(StatusTransition { from, to }))
+}
+
+/// Returns the update expression setting the timestamp column configured for the new status of the
+/// provided [`StatusTransition`], keeping any value which has been set already.
+///
+/// Returns [`None`] if no timestamp column is configured for the new status, or if the timestamp column
+/// is part of the provided update data (an explicitly provided value takes precedence).
+pub(crate) fn get_stamp_update<T>(
+    transition: &StatusTransition,
+    psql_data: Option<&PsqlData>,
+) -> Option<String>
+where
+    T: Resource,
+{
+    let transitions = T::get_status_transitions()?;
+    let stamp_col = transitions.stamps.get(&transition.to)?;
+    if psql_data.map_or(false, |data| data.contains_key(stamp_col)) {
+        return None;
+    }
+    Some(get_stamp_expression(stamp_col))
+}
+
+/// Returns the update expression setting the provided timestamp column, keeping any value which has been set already
+fn get_stamp_expression(stamp_col: &str) -> String {
+    format!(r#""{col}" = COALESCE("{col}", NOW())"#, col = stamp_col)
+}
+
+/// Records the provided [`StatusTransition`] in the [`STATUS_TRANSITION_TABLE`].
+///
+/// # Errors
+///
+/// Returns [`ArrErr`] Database Error if database query execution failed
+pub(crate) async fn record_status_transition<T>(
+    transaction: &Transaction<'_>,
+    id: &Uuid,
+    transition: &StatusTransition,
+    changed_by: Option<&Uuid>,
+) -> Result<(), ArrErr>
+where
+    T: Resource,
+{
+    insert_status_transition(
+        transaction,
+        &T::get_psql_table(),
+        id,
+        transition,
+        changed_by,
+    )
+    .await
+}
+
+/// Inserts the provided [`StatusTransition`] of a record of the provided table in the [`STATUS_TRANSITION_TABLE`].
+async fn insert_status_transition(
+    transaction: &Transaction<'_>,
+    psql_table: &str,
+    id: &Uuid,
+    transition: &StatusTransition,
+    changed_by: Option<&Uuid>,
+) -> Result<(), ArrErr> {
+    let query = get_record_transition_query();
+    psql_debug!("(insert_status_transition) [{}].", query);
+    transaction
+        .execute(
+            &query,
+            &[
+                &psql_table,
+                id,
+                &transition.from,
+                &transition.to,
+                &changed_by,
+            ],
+        )
+        .await?;
+    Ok(())
+}
+
+/// Applies the resource's [`StatusPropagation`]s matching the provided [`PropagationTrigger`]
+/// for the record with the provided id.
+///
+/// The related records will be locked until the transaction is finished. Related records with a
+/// status which can not be advanced are left untouched. All status changes are recorded in the
+/// [`STATUS_TRANSITION_TABLE`].
+///
+/// # Errors
+///
+/// Returns [`ArrErr`] "No id column configured for table" if the related resource has no id column
+/// Returns [`ArrErr`] Database Error if database query execution failed
+pub(crate) async fn propagate_status<T>(
+    transaction: &Transaction<'_>,
+    id: &Uuid,
+    trigger: &PropagationTrigger,
+    changed_by: Option<&Uuid>,
+) -> Result<(), ArrErr>
+where
+    T: Resource,
+{
+    for propagation in T::get_status_propagations() {
+        if propagation.trigger != *trigger {
+            continue;
+        }
+        let target_id_col = match propagation.target.psql_id_cols.first() {
+            Some(id_col) => id_col.clone(),
+            None => {
+                let error = format!(
+                    "No id column configured for table {}",
+                    propagation.target.psql_table
+                );
+                psql_error!("(propagate_status) {}", error);
+                return Err(ArrErr::Error(error));
+            }
+        };
+        let from_values: Vec<String> = propagation.advance.keys().cloned().collect();
+        let query = get_propagation_targets_query(&propagation, &target_id_col);
+        psql_debug!("(propagate_status) [{}].", query);
+        let rows = transaction.query(&query, &[id, &from_values]).await?;
+
+        let update_query = get_update_status_query(
+            &propagation.target,
+            &target_id_col,
+            &propagation.target_transitions,
+            None,
+        );
+        for row in rows {
+            let target_id: Uuid = row.get(0);
+            let from: String = row.get(1);
+            let to = match propagation.advance.get(&from) {
+                Some(to) => to.clone(),
+                None => continue,
+            };
+            if !propagation.target_transitions.is_allowed(&from, &to) {
+                psql_warn!(
+                    "(propagate_status) Skipping [{}] record [{}], transition from [{}] to [{}] is not allowed.",
+                    propagation.target.psql_table,
+                    target_id,
+                    from,
+                    to
+                );
+                continue;
+            }
+
+            let query = match propagation.target_transitions.stamps.get(&to) {
+                Some(stamp_col) => get_update_status_query(
+                    &propagation.target,
+                    &target_id_col,
+                    &propagation.target_transitions,
+                    Some(get_stamp_expression(stamp_col)),
+                ),
+                None => update_query.clone(),
+            };
+            psql_debug!("(propagate_status) [{}].", query);
+            transaction.execute(&query, &[&target_id, &to]).await?;
+            record_change(
+                transaction,
+                &propagation.target.psql_table,
+                ChangeType::Updated,
+                &target_id,
+            )
+            .await?;
+            let transition = StatusTransition { from, to };
+            insert_status_transition(
+                transaction,
+                &propagation.target.psql_table,
+                &target_id,
+                &transition,
+                changed_by,
+            )
+            .await?;
+            psql_info!(
+                "(propagate_status) Changed [{}] of [{}] record [{}] from [{}] to [{}], triggered by [{}] record [{}].",
+                propagation.target_transitions.status_col,
+                propagation.target.psql_table,
+                target_id,
+                transition.from,
+                transition.to,
+                T::get_psql_table(),
+                id
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Changes the status of the record with the provided id to `new_status`, recording who made the change.
+///
+/// The transition is checked using the resource's [`StatusTransitions`] and the resource's
+/// [range exclusions](Resource::get_range_exclusions). Returns a [`ValidationResult`]
+/// containing the reasons why the transition was rejected, if any.
+///
+/// # Errors
+///
+/// Returns [`ArrErr`] "No status transitions configured for table" if the resource has no [`StatusTransitions`]
+/// Returns [`ArrErr`] "No id column configured for table" id_col could not be found
+/// Returns [`ArrErr`] NotFound if no record exists for the provided id, or if the record is soft-deleted
+/// Returns [`ArrErr`] from [`PoolError`](deadpool::managed::PoolError) if no client connection could be returned from the connection [`Pool`](deadpool::managed::Pool)
+/// Returns [`ArrErr`] Database Error if database query execution failed
+pub async fn transition_status<T>(
+    id: &Uuid,
+    new_status: String,
+    changed_by: &Uuid,
+) -> Result<ValidationResult, ArrErr>
+where
+    T: PsqlType,
+{
+    let definition = T::get_definition();
+    let transitions = match T::get_status_transitions() {
+        Some(transitions) => transitions,
+        None => {
+            let error = format!(
+                "No status transitions configured for table {}",
+                definition.psql_table
+            );
+            psql_error!("(transition_status) {}", error);
+            return Err(ArrErr::Error(error));
+        }
+    };
+    let id_col = T::try_get_id_field()?;
+    let mut validation_result = ValidationResult {
+        success: true,
+        errors: vec![],
+    };
+
+    let mut client = get_psql_client().await?;
+    let transaction = start_transaction::<T>(&mut client).await?;
+    let transition = check_status_transition::<T>(
+        &transaction,
+        id,
+        Some(new_status.clone()),
+        &mut validation_result,
+    )
+    .await?;
+    let transition = match transition {
+        Some(transition) => transition,
+        None => {
+            // Either rejected or nothing to change
+            transaction.rollback().await?;
+            return Ok(validation_result);
+        }
+    };
+
+    let stamp = get_stamp_update::<T>(&transition, None);
+    let query = get_update_status_query(&definition, &id_col, &transitions, stamp);
+    psql_debug!("(transition_status) [{}].", query);
+    transaction.execute(&query, &[id, &new_status]).await?;
+    record_status_transition::<T>(&transaction, id, &transition, Some(changed_by)).await?;
+    propagate_status::<T>(
+        &transaction,
+        id,
+        &PropagationTrigger::Status(transition.to.clone()),
+        Some(changed_by),
+    )
+    .await?;
+
+    check_range_exclusions::<T>(&transaction, id, &mut validation_result).await?;
+    if !validation_result.success {
+        transaction.rollback().await?;
+        return Ok(validation_result);
+    }
+    record_change(
+        &transaction,
+        &definition.psql_table,
+        ChangeType::Updated,
+        id,
+    )
+    .await?;
+    transaction.commit().await?;
+    notify_watchers();
+
+    psql_info!(
+        "(transition_status) Changed [{}] of [{}] record [{}] from [{}] to [{}], changed by [{}].",
+        transitions.status_col,
+        definition.psql_table,
+        id,
+        transition.from,
+        transition.to,
+        changed_by
+    );
+    Ok(validation_result)
+}
+
+/// Returns the queries needed to create the status transition table
+pub(crate) fn get_status_transition_queries() -> Vec<String> {
+    vec![
+        format!(
+            r#"CREATE TABLE IF NOT EXISTS "{}" ("status_transition_id" UUID PRIMARY KEY DEFAULT gen_random_uuid(), "table_name" TEXT NOT NULL, "record_id" UUID NOT NULL, "from_status" TEXT NOT NULL, "to_status" TEXT NOT NULL, "changed_by" UUID NULL, "created_at" TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW())"#,
+            STATUS_TRANSITION_TABLE
+        ),
+        format!(
+            r#"CREATE INDEX IF NOT EXISTS "{table}_table_name_record_id_idx" ON "{table}" ("table_name", "record_id", "created_at")"#,
+            table = STATUS_TRANSITION_TABLE
+        ),
+    ]
+}
+
+/// Returns the queries needed to drop the status transition table
+pub(crate) fn get_drop_status_transition_queries() -> Vec<String> {
+    vec![format!(
+        r#"DROP TABLE IF EXISTS "{}""#,
+        STATUS_TRANSITION_TABLE
+    )]
+}
+
+/// Returns the query used to get (and lock) the current status of the record matching id parameter `$1`
+///
+/// Soft-deleted records are excluded, so their status can not be changed.
+fn get_current_status_query(
+    definition: &ResourceDefinition,
+    id_col: &str,
+    transitions: &StatusTransitions,
+) -> String {
+    let mut query = format!(
+        r#"SELECT "{}"::TEXT FROM "{}" WHERE "{}" = $1"#,
+        transitions.status_col, definition.psql_table, id_col
+    );
+    if definition.has_field("deleted_at") {
+        query.push_str(r#" AND "deleted_at" IS NULL"#);
+    }
+    query.push_str(" FOR UPDATE");
+    query
+}
+
+/// Returns the query used to set the status of the record matching id parameter `$1` to parameter `$2`,
+/// including the provided timestamp update if any
+fn get_update_status_query(
+    definition: &ResourceDefinition,
+    id_col: &str,
+    transitions: &StatusTransitions,
+    stamp: Option<String>,
+) -> String {
+    let mut updates = vec![format!(r#""{}" = $2"#, transitions.status_col)];
+    updates.extend(stamp);
+    if definition.has_field("updated_at") {
+        updates.push(r#""updated_at" = NOW()"#.to_string());
+    }
+    format!(
+        r#"UPDATE "{}" SET {} WHERE "{}" = $1"#,
+        definition.psql_table,
+        updates.join(", "),
+        id_col
+    )
+}
+
+/// Returns the query used to get (and lock) the related records of a [`StatusPropagation`] which
+/// may be advanced, for the changed record id parameter `$1` and current status values parameter `$2`
+fn get_propagation_targets_query(propagation: &StatusPropagation, target_id_col: &str) -> String {
+    let mut query = format!(
+        r#"SELECT "{id_col}", "{status_col}"::TEXT FROM "{table}" WHERE "{id_col}" IN ({ids}) AND "{status_col}"::TEXT = ANY($2)"#,
+        id_col = target_id_col,
+        status_col = propagation.target_transitions.status_col,
+        table = propagation.target.psql_table,
+        ids = propagation.target_ids_query
+    );
+    if propagation.target.has_field("deleted_at") {
+        query.push_str(r#" AND "deleted_at" IS NULL"#);
+    }
+    query.push_str(" FOR UPDATE");
+    query
+}
+
+/// Returns the query used to record a status transition
+fn get_record_transition_query() -> String {
+    format!(
+        r#"INSERT INTO "{}" ("table_name", "record_id", "from_status", "to_status", "changed_by") VALUES ($1, $2, $3, $4, $5)"#,
+        STATUS_TRANSITION_TABLE
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::base::FieldDefinition;
+    use std::collections::HashMap;
+    use tokio_postgres::types::Type as PsqlFieldType;
+
+    fn get_definition() -> ResourceDefinition {
+        ResourceDefinition {
+            psql_table: String::from("booking"),
+            psql_id_cols: vec![String::from("booking_id")],
+            fields: HashMap::from([
+                (
+                    "status".to_string(),
+                    FieldDefinition::new(PsqlFieldType::ANYENUM, true),
+                ),
+                (
+                    "updated_at".to_string(),
+                    FieldDefinition::new_internal(PsqlFieldType::TIMESTAMPTZ, true),
+                ),
+            ]),
+        }
+    }
+
+    fn get_transitions() -> StatusTransitions {
+        StatusTransitions {
+            status_col: String::from("status"),
+            transitions: HashMap::from([
+                (String::from("NEW"), vec![String::from("STARTED")]),
+                (String::from("STARTED"), vec![String::from("DONE")]),
+            ]),
+            stamps: HashMap::from([(String::from("DONE"), String::from("done_at"))]),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_status_transitions_is_allowed() {
+        crate::get_log_handle().await;
+        ut_info!("(test_status_transitions_is_allowed) start");
+
+        let transitions = get_transitions();
+        assert!(transitions.is_allowed("NEW", "NEW"));
+        assert!(transitions.is_allowed("NEW", "STARTED"));
+        assert!(transitions.is_allowed("STARTED", "DONE"));
+        assert!(!transitions.is_allowed("NEW", "DONE"));
+        assert!(!transitions.is_allowed("DONE", "NEW"));
+        assert!(!transitions.is_allowed("UNKNOWN", "NEW"));
+        assert_eq!(
+            transitions.get_allowed("NEW"),
+            vec![String::from("STARTED")]
+        );
+        assert!(transitions.get_allowed("DONE").is_empty());
+
+        ut_info!("(test_status_transitions_is_allowed) success");
+    }
+
+    #[tokio::test]
+    async fn test_get_status_transition_queries() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_status_transition_queries) start");
+
+        let mut definition = get_definition();
+        let transitions = get_transitions();
+        assert_eq!(
+            get_current_status_query(&definition, "booking_id", &transitions),
+            r#"SELECT "status"::TEXT FROM "booking" WHERE "booking_id" = $1 FOR UPDATE"#
+        );
+        assert_eq!(
+            get_update_status_query(&definition, "booking_id", &transitions, None),
+            r#"UPDATE "booking" SET "status" = $2, "updated_at" = NOW() WHERE "booking_id" = $1"#
+        );
+        assert_eq!(
+            get_update_status_query(
+                &definition,
+                "booking_id",
+                &transitions,
+                Some(String::from(r#""done_at" = COALESCE("done_at", NOW())"#))
+            ),
+            r#"UPDATE "booking" SET "status" = $2, "done_at" = COALESCE("done_at", NOW()), "updated_at" = NOW() WHERE "booking_id" = $1"#
+        );
+        definition.fields.insert(
+            "deleted_at".to_string(),
+            FieldDefinition::new_internal(PsqlFieldType::TIMESTAMPTZ, false),
+        );
+        assert_eq!(
+            get_current_status_query(&definition, "booking_id", &transitions),
+            r#"SELECT "status"::TEXT FROM "booking" WHERE "booking_id" = $1 AND "deleted_at" IS NULL FOR UPDATE"#
+        );
+        assert_eq!(
+            get_record_transition_query(),
+            r#"INSERT INTO "status_transition" ("table_name", "record_id", "from_status", "to_status", "changed_by") VALUES ($1, $2, $3, $4, $5)"#
+        );
+
+        ut_info!("(test_get_status_transition_queries) success");
+    }
+
+    #[tokio::test]
+    async fn test_get_propagation_targets_query() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_propagation_targets_query) start");
+
+        let mut propagation = StatusPropagation {
+            trigger: PropagationTrigger::Insert,
+            target: get_definition(),
+            target_transitions: get_transitions(),
+            target_ids_query: String::from(
+                r#"SELECT "booking_id" FROM "booking_scan" WHERE "booking_scan_id" = $1"#,
+            ),
+            advance: HashMap::from([(String::from("NEW"), String::from("STARTED"))]),
+        };
+        assert_eq!(
+            get_propagation_targets_query(&propagation, "booking_id"),
+            r#"SELECT "booking_id", "status"::TEXT FROM "booking" WHERE "booking_id" IN (SELECT "booking_id" FROM "booking_scan" WHERE "booking_scan_id" = $1) AND "status"::TEXT = ANY($2) FOR UPDATE"#
+        );
+
+        propagation.target.fields.insert(
+            "deleted_at".to_string(),
+            FieldDefinition::new_internal(PsqlFieldType::TIMESTAMPTZ, false),
+        );
+        assert_eq!(
+            get_propagation_targets_query(&propagation, "booking_id"),
+            r#"SELECT "booking_id", "status"::TEXT FROM "booking" WHERE "booking_id" IN (SELECT "booking_id" FROM "booking_scan" WHERE "booking_scan_id" = $1) AND "status"::TEXT = ANY($2) AND "deleted_at" IS NULL FOR UPDATE"#
+        );
+        assert_eq!(
+            get_stamp_expression("done_at"),
+            r#""done_at" = COALESCE("done_at", NOW())"#
+        );
+
+        ut_info!("(test_get_propagation_targets_query) success");
+    }
+}
@@ -1,7 +1,9 @@
 //! PostgreSQL utility functions
 
+use super::postgis::{get_geom_from_text_sql, get_line_string_wkt, get_point_wkt, get_polygon_wkt};
 use super::{PsqlData, PsqlField, PsqlFieldSend};
 use crate::common::ArrErr;
+use crate::grpc::server::grpc_geo_types::{GeoLineString, GeoPoint};
 use crate::grpc::server::ValidationError;
 use crate::grpc::{GrpcDataObjectType, GrpcField};
 use crate::resources::base::{Resource, ResourceDefinition};
@@ -9,6 +11,7 @@ use crate::resources::ValidationResult;
 use chrono::{DateTime, Utc};
 use geo_types::{Coord, LineString, Point, Polygon};
 use lib_common::time::Timestamp;
+use prost_types::FieldMask;
 use serde_json::json;
 use tokio_postgres::types::Type as PsqlFieldType;
 
@@ -182,6 +185,68 @@ pub fn validate_line_string(
     success
 }
 
+/// Validates the times of a [`GeoLineString`] (used by grpc).
+/// The times should either be empty or contain one non decreasing time for each point.
+/// Creates an error entry in the errors list if the times are invalid.
+/// Returns `true` on success, `false` if the times are invalid.
+pub fn validate_line_string_times(
+    field: String,
+    value: &GeoLineString,
+    errors: &mut Vec<ValidationError>,
+) -> bool {
+    if value.times.is_empty() {
+        return true;
+    }
+
+    let mut success = true;
+    if value.times.len() != value.points.len() {
+        let error = format!(
+            "Could not convert [{}] to PATH: The provided LineString contains [{}] times for [{}] points.",
+            field,
+            value.times.len(),
+            value.points.len()
+        );
+        psql_info!("(validate_line_string_times) {}", error);
+        errors.push(ValidationError {
+            field: field.clone(),
+            error,
+        });
+        success = false
+    }
+    if value
+        .times
+        .windows(2)
+        .any(|pair| (pair[1].seconds, pair[1].nanos) < (pair[0].seconds, pair[0].nanos))
+    {
+        let error = format!(
+            "Could not convert [{}] to PATH: The provided LineString times are not in chronological order.",
+            field
+        );
+        psql_info!("(validate_line_string_times) {}", error);
+        errors.push(ValidationError { field, error });
+        success = false
+    }
+
+    success
+}
+
+/// Validates a numeric value which should be greater than zero.
+/// Creates an error entry in the errors list if the value is zero, negative or not a number.
+/// Returns `true` on success, `false` if the value is invalid.
+pub fn validate_positive(field: String, value: f64, errors: &mut Vec<ValidationError>) -> bool {
+    if value > 0.0 {
+        return true;
+    }
+
+    let error = format!(
+        "Invalid value provided for [{}]: [{}] should be greater than zero.",
+        field, value
+    );
+    psql_info!("(validate_positive) {}", error);
+    errors.push(ValidationError { field, error });
+    false
+}
+
 fn validate_coord(
     field: String,
     coord: &Coord,
@@ -378,7 +443,7 @@ pub fn get_update_vars<'a>(
                         if let Ok(point_option) = data.get_field_value(key) {
                             match get_point_sql_val(point_option) {
                                 Some(val) => updates.push(format!(r#""{}" = {}"#, key, val)),
-                                None => continue,
+                                None => updates.push(format!(r#""{}" = NULL"#, key)),
                             };
                         } else {
                             let error = format!(
@@ -396,7 +461,7 @@ pub fn get_update_vars<'a>(
                         if let Ok(polygon_option) = data.get_field_value(key) {
                             match get_polygon_sql_val(polygon_option) {
                                 Some(val) => updates.push(format!(r#""{}" = {}"#, key, val)),
-                                None => continue,
+                                None => updates.push(format!(r#""{}" = NULL"#, key)),
                             };
                         } else {
                             let error = format!(
@@ -414,7 +479,7 @@ pub fn get_update_vars<'a>(
                         if let Ok(path_option) = data.get_field_value(key) {
                             match get_path_sql_val(path_option) {
                                 Some(val) => updates.push(format!(r#""{}" = {}"#, key, val)),
-                                None => continue,
+                                None => updates.push(format!(r#""{}" = NULL"#, key)),
                             };
                         } else {
                             let error = format!(
@@ -455,15 +520,8 @@ fn get_point_sql_val(point_option: GrpcField) -> Option<String> {
             let point: Option<GrpcField> = val.into();
             match point {
                 Some(val) => {
-                    let val: Point = val.into();
-                    // POINT expects (x y) which is (long lat)
-                    // geo_types::geometry::point::Point has a x and y which
-                    // we've aligned with the POINT(x y)/POINT(long lat)
-                    Some(format!(
-                        "ST_GeomFromText('POINT({:.15} {:.15})')",
-                        val.x(),
-                        val.y()
-                    ))
+                    let val: GeoPoint = val.into();
+                    Some(get_geom_from_text_sql(&get_point_wkt(&val)))
                 }
                 None => None,
             }
@@ -479,27 +537,7 @@ fn get_polygon_sql_val(polygon_option: GrpcField) -> Option<String> {
             match polygon {
                 Some(val) => {
                     let val: Polygon = val.into();
-
-                    let mut coord_str_pairs: Vec<String> = vec![];
-                    for coord in val.exterior().coords() {
-                        coord_str_pairs.push(format!("{:.15} {:.15}", coord.x, coord.y));
-                    }
-
-                    let mut line_str_pairs: Vec<String> = vec![];
-                    line_str_pairs.push(format!("({})", coord_str_pairs.join(",")));
-                    for line in val.interiors() {
-                        let mut coord_str_pairs: Vec<String> = vec![];
-                        for coord in line.coords() {
-                            coord_str_pairs.push(format!("{:.15} {:.15}", coord.x, coord.y));
-                        }
-                        let coord_str = format!("({})", coord_str_pairs.join(","));
-                        line_str_pairs.push(coord_str);
-                    }
-
-                    Some(format!(
-                        "ST_GeomFromText('POLYGON({})')",
-                        line_str_pairs.join(",")
-                    ))
+                    Some(get_geom_from_text_sql(&get_polygon_wkt(&val)))
                 }
                 None => None,
             }
@@ -514,16 +552,8 @@ fn get_path_sql_val(path_option: GrpcField) -> Option<String> {
             let path: Option<GrpcField> = val.into();
             match path {
                 Some(val) => {
-                    let val: LineString = val.into();
-                    let mut coord_str_pairs: Vec<String> = vec![];
-                    for coord in val.coords() {
-                        coord_str_pairs.push(format!("{:.15} {:.15}", coord.x, coord.y));
-                    }
-
-                    Some(format!(
-                        "ST_GeomFromText('LINESTRING({})')",
-                        coord_str_pairs.join(",")
-                    ))
+                    let val: GeoLineString = val.into();
+                    Some(get_geom_from_text_sql(&get_line_string_wkt(&val)))
                 }
                 None => None,
             }
@@ -532,7 +562,115 @@ fn get_path_sql_val(path_option: GrpcField) -> Option<String> {
     }
 }
 
+/// Returns a typed `NULL` value for the provided field type.
+///
+/// Geometry types are written using their raw SQL value, so a placeholder is returned for those.
+fn get_null_value(field_type: &PsqlFieldType) -> Box<PsqlFieldSend> {
+    match *field_type {
+        PsqlFieldType::UUID => Box::new(None::<uuid::Uuid>),
+        PsqlFieldType::TIMESTAMPTZ => Box::new(None::<DateTime<Utc>>),
+        PsqlFieldType::INT2 => Box::new(None::<i16>),
+        PsqlFieldType::INT4 => Box::new(None::<i32>),
+        PsqlFieldType::INT8 => Box::new(None::<i64>),
+        PsqlFieldType::FLOAT8 => Box::new(None::<f64>),
+        PsqlFieldType::JSON => Box::new(None::<serde_json::Value>),
+        PsqlFieldType::BOOL => Box::new(None::<bool>),
+        PsqlFieldType::BYTEA => Box::new(None::<Vec<u8>>),
+        PsqlFieldType::TEXT_ARRAY => Box::new(None::<Vec<String>>),
+        _ => Box::new(None::<String>),
+    }
+}
+
+/// Validates the provided data using the resource's [`ResourceDefinition`]
+///
+/// Returns the converted [`PsqlData`] and a [`ValidationResult`] containing all errors found.
 pub fn validate<T>(data: &impl GrpcDataObjectType) -> Result<(PsqlData, ValidationResult), ArrErr>
+where
+    T: Resource,
+{
+    validate_fields::<T>(data, None, vec![])
+}
+
+/// Validates the provided data for an update action, honoring the given [`FieldMask`].
+///
+/// If no mask (or an empty mask) is provided, all fields will be validated just like [`validate`] does.
+/// Otherwise, only the fields listed in the mask will be validated and returned as part of the [`PsqlData`],
+/// which makes sure only those fields will end up in the update statement.
+/// Mask paths can be provided as `field` or as `data.field`.
+/// Unknown, internal or read_only paths will be returned as [`ValidationError`]s.
+pub fn validate_with_mask<T>(
+    data: &impl GrpcDataObjectType,
+    mask: &Option<FieldMask>,
+) -> Result<(PsqlData, ValidationResult), ArrErr>
+where
+    T: Resource,
+{
+    let mut errors: Vec<ValidationError> = vec![];
+    let fields = get_mask_fields::<T>(mask, &mut errors);
+    validate_fields::<T>(data, fields.as_deref(), errors)
+}
+
+/// Converts the [`FieldMask`] paths into a list of resource field names.
+///
+/// Returns [`None`] if no paths were provided, meaning all fields should be used.
+/// Adds a [`ValidationError`] to the provided errors list for each path that can not be updated.
+pub fn get_mask_fields<T>(
+    mask: &Option<FieldMask>,
+    errors: &mut Vec<ValidationError>,
+) -> Option<Vec<String>>
+where
+    T: Resource,
+{
+    let paths = match mask {
+        Some(mask) if !mask.paths.is_empty() => &mask.paths,
+        _ => return None,
+    };
+
+    let definition = T::get_definition();
+    let mut fields: Vec<String> = vec![];
+    for path in paths {
+        let field = path.strip_prefix("data.").unwrap_or(path);
+        match definition.fields.get(field) {
+            Some(field_definition)
+                if !field_definition.is_internal() && !field_definition.is_read_only() =>
+            {
+                if !fields.iter().any(|f| f == field) {
+                    fields.push(field.to_string());
+                }
+            }
+            Some(_) => {
+                psql_info!(
+                    "(get_mask_fields) Mask path [{}] is read only for table [{}].",
+                    path,
+                    definition.psql_table
+                );
+                errors.push(ValidationError {
+                    field: path.clone(),
+                    error: String::from("Field is read only and can not be updated."),
+                });
+            }
+            None => {
+                psql_info!(
+                    "(get_mask_fields) Mask path [{}] does not exist for table [{}].",
+                    path,
+                    definition.psql_table
+                );
+                errors.push(ValidationError {
+                    field: path.clone(),
+                    error: String::from("Unknown field provided in mask."),
+                });
+            }
+        }
+    }
+
+    Some(fields)
+}
+
+fn validate_fields<T>(
+    data: &impl GrpcDataObjectType,
+    mask_fields: Option<&[String]>,
+    mut errors: Vec<ValidationError>,
+) -> Result<(PsqlData, ValidationResult), ArrErr>
 where
     T: Resource,
 {
@@ -541,7 +679,6 @@ where
 
     let mut converted: PsqlData = PsqlData::new();
     let mut success = true;
-    let mut errors: Vec<ValidationError> = vec![];
 
     // Check if we have any id_fields as part of ar data object.
     // They will need to be inserted as well.
@@ -569,6 +706,12 @@ where
             // internal / read_only field, skip for validation
             continue;
         }
+        if let Some(mask_fields) = mask_fields {
+            if !mask_fields.contains(&key) {
+                // field not part of the provided mask, skip for validation
+                continue;
+            }
+        }
 
         let field_value = data.get_field_value(&key)?;
         let val_to_validate = match field_value {
@@ -582,6 +725,10 @@ where
                             psql_error!("(validate) {}", error);
                             return Err(ArrErr::Error(error));
                         }
+                        if mask_fields.is_some() {
+                            // Field explicitly provided in the mask without a value, clear it
+                            converted.insert(key, get_null_value(&field.field_type));
+                        }
                         continue;
                     }
                 }
@@ -626,7 +773,8 @@ where
                 }
             }
             PsqlFieldType::POINT => {
-                if validate_point(key.to_string(), &val_to_validate.into(), &mut errors) {
+                let val: GeoPoint = val_to_validate.into();
+                if validate_point(key.to_string(), &val.into(), &mut errors) {
                     // Will use the raw type for insert/update statements
                     converted.insert(key, Box::new(true));
                 }
@@ -638,7 +786,9 @@ where
                 }
             }
             PsqlFieldType::PATH => {
-                if validate_line_string(key.to_string(), &val_to_validate.into(), &mut errors) {
+                let val: GeoLineString = val_to_validate.into();
+                let times_valid = validate_line_string_times(key.to_string(), &val, &mut errors);
+                if validate_line_string(key.to_string(), &val.into(), &mut errors) && times_valid {
                     // Will use the raw type for insert/update statements
                     converted.insert(key, Box::new(true));
                 }
@@ -661,7 +811,9 @@ where
             }
             PsqlFieldType::FLOAT8 => {
                 let val: f64 = val_to_validate.into();
-                converted.insert(key, Box::new(val));
+                if !field.is_positive() || validate_positive(key.to_string(), val, &mut errors) {
+                    converted.insert(key, Box::new(val));
+                }
             }
             PsqlFieldType::JSON => {
                 let val: Vec<i64> = val_to_validate.into();
@@ -675,6 +827,10 @@ where
                 let val: Vec<u8> = val_to_validate.into();
                 converted.insert(key, Box::new(val));
             }
+            PsqlFieldType::TEXT_ARRAY => {
+                let val: Vec<String> = val_to_validate.into();
+                converted.insert(key, Box::new(val));
+            }
             _ => {
                 let error = format!(
                     "Conversion errors found in fields for table [{}], unknown field type [{}].",
@@ -775,6 +931,28 @@ mod tests {
         ut_info!("(test_validate_dt_invalid) success");
     }
 
+    #[tokio::test]
+    async fn test_validate_positive() {
+        crate::get_log_handle().await;
+        ut_info!("(test_validate_positive) start");
+
+        let mut errors: Vec<ValidationError> = vec![];
+        assert!(validate_positive("value".to_string(), 0.1, &mut errors));
+        assert!(errors.is_empty());
+
+        assert!(!validate_positive("value".to_string(), 0.0, &mut errors));
+        assert!(!validate_positive("value".to_string(), -1.0, &mut errors));
+        assert!(!validate_positive(
+            "value".to_string(),
+            f64::NAN,
+            &mut errors
+        ));
+        assert_eq!(errors.len(), 3);
+        assert!(errors.iter().all(|error| error.field == "value"));
+
+        ut_info!("(test_validate_positive) success");
+    }
+
     #[tokio::test]
     async fn test_validate_point_valid() {
         crate::get_log_handle().await;
@@ -886,6 +1064,65 @@ mod tests {
         ut_info!("(test_validate_line_string_invalid) success");
     }
 
+    #[tokio::test]
+    async fn test_validate_line_string_times() {
+        crate::get_log_handle().await;
+        ut_info!("(test_validate_line_string_times) start");
+
+        let point = GeoPoint {
+            longitude: 4.9164,
+            latitude: 52.37466,
+            altitude: None,
+        };
+        let time = |seconds: i64| Timestamp { seconds, nanos: 0 };
+
+        let mut errors: Vec<ValidationError> = vec![];
+        let line = GeoLineString {
+            points: vec![point, point],
+            times: vec![],
+        };
+        assert!(validate_line_string_times(
+            "line".to_string(),
+            &line,
+            &mut errors
+        ));
+        let line = GeoLineString {
+            points: vec![point, point],
+            times: vec![time(10), time(10)],
+        };
+        assert!(validate_line_string_times(
+            "line".to_string(),
+            &line,
+            &mut errors
+        ));
+        assert!(errors.is_empty());
+
+        let line = GeoLineString {
+            points: vec![point, point],
+            times: vec![time(10)],
+        };
+        assert!(!validate_line_string_times(
+            "line".to_string(),
+            &line,
+            &mut errors
+        ));
+        assert_eq!(errors.len(), 1);
+
+        let line = GeoLineString {
+            points: vec![point, point],
+            times: vec![time(20), time(10)],
+        };
+        assert!(!validate_line_string_times(
+            "line".to_string(),
+            &line,
+            &mut errors
+        ));
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[1].field, "line");
+
+        ut_info!("(test_validate_line_string_times) success");
+    }
+
     #[tokio::test]
     async fn test_get_insert_vars() {
         crate::get_log_handle().await;
@@ -1049,6 +1286,164 @@ mod tests {
         ut_info!("(test_get_update_vars) success");
     }
 
+    #[tokio::test]
+    async fn test_get_update_vars_with_mask() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_update_vars_with_mask) start");
+
+        let uuid = Uuid::new_v4();
+        let optional_uuid = Uuid::new_v4();
+        let timestamp = Some(chrono::Utc::now().into());
+        let optional_timestamp = Some(chrono::Utc::now().into());
+
+        let mut valid_data = get_valid_test_data(
+            uuid,
+            optional_uuid,
+            timestamp.clone(),
+            optional_timestamp.clone(),
+        );
+        // Invalid value for a field that is not part of the mask, should be ignored
+        valid_data.optional_uuid = Some(String::from("INVALID"));
+
+        let mask = Some(FieldMask {
+            paths: vec![
+                String::from("string"),
+                String::from("data.uuid"),
+                String::from("geo_point"),
+            ],
+        });
+        let (psql_data, validation_result) =
+            match validate_with_mask::<ResourceObject<TestData>>(&valid_data, &mask) {
+                Ok(result) => result,
+                Err(e) => {
+                    panic!("Validation errors found but not expected: {}", e);
+                }
+            };
+        assert_eq!(validation_result.success, true);
+        assert_eq!(psql_data.len(), 3);
+
+        let definition = <ResourceObject<TestData>>::get_definition();
+        let (updates, params) = match get_update_vars(&valid_data, &psql_data, &definition) {
+            Ok(result) => result,
+            Err(e) => {
+                panic!("Conversion errors found but not expected: {}", e);
+            }
+        };
+        assert_eq!(updates.len(), 3);
+        assert_eq!(params.len(), 2);
+        for update in updates {
+            let field = update.split('=').collect::<Vec<&str>>()[0]
+                .trim()
+                .to_owned();
+            assert!(
+                [r#""string""#, r#""uuid""#, r#""geo_point""#].contains(&field.as_str()),
+                "unexpected field [{}] in update statements",
+                field
+            );
+        }
+
+        ut_info!("(test_get_update_vars_with_mask) success");
+    }
+
+    #[tokio::test]
+    async fn test_get_update_vars_with_mask_clear_optional() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_update_vars_with_mask_clear_optional) start");
+
+        let mut valid_data = get_valid_test_data(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Some(chrono::Utc::now().into()),
+            Some(chrono::Utc::now().into()),
+        );
+        valid_data.optional_uuid = None;
+        valid_data.optional_geo_point = None;
+
+        let mask = Some(FieldMask {
+            paths: vec![
+                String::from("optional_uuid"),
+                String::from("optional_geo_point"),
+            ],
+        });
+        let (psql_data, validation_result) =
+            match validate_with_mask::<ResourceObject<TestData>>(&valid_data, &mask) {
+                Ok(result) => result,
+                Err(e) => {
+                    panic!("Validation errors found but not expected: {}", e);
+                }
+            };
+        assert_eq!(validation_result.success, true);
+        assert_eq!(psql_data.len(), 2);
+
+        let definition = <ResourceObject<TestData>>::get_definition();
+        let (updates, params) = match get_update_vars(&valid_data, &psql_data, &definition) {
+            Ok(result) => result,
+            Err(e) => {
+                panic!("Conversion errors found but not expected: {}", e);
+            }
+        };
+        assert_eq!(updates.len(), 2);
+        assert_eq!(params.len(), 1);
+        assert!(updates.contains(&String::from(r#""optional_uuid" = $1"#)));
+        assert!(updates.contains(&String::from(r#""optional_geo_point" = NULL"#)));
+        assert_eq!(format!("{:?}", params[0]), "None");
+
+        ut_info!("(test_get_update_vars_with_mask_clear_optional) success");
+    }
+
+    #[tokio::test]
+    async fn test_validate_with_mask_invalid_paths() {
+        crate::get_log_handle().await;
+        ut_info!("(test_validate_with_mask_invalid_paths) start");
+
+        let valid_data = get_valid_test_data(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Some(chrono::Utc::now().into()),
+            Some(chrono::Utc::now().into()),
+        );
+
+        let mask = Some(FieldMask {
+            paths: vec![
+                String::from("string"),
+                String::from("read_only"),
+                String::from("internal"),
+                String::from("data.unknown"),
+            ],
+        });
+        let (_, validation_result) =
+            match validate_with_mask::<ResourceObject<TestData>>(&valid_data, &mask) {
+                Ok(result) => result,
+                Err(e) => {
+                    panic!("Validation errors found but not expected: {}", e);
+                }
+            };
+        assert_eq!(validation_result.success, false);
+        let expected_errors = vec!["read_only", "internal", "data.unknown"];
+        assert_eq!(expected_errors.len(), validation_result.errors.len());
+        assert!(contains_field_errors(&validation_result, &expected_errors));
+
+        // An empty mask should validate all fields
+        let mask = Some(FieldMask { paths: vec![] });
+        let (psql_data, validation_result) =
+            match validate_with_mask::<ResourceObject<TestData>>(&valid_data, &mask) {
+                Ok(result) => result,
+                Err(e) => {
+                    panic!("Validation errors found but not expected: {}", e);
+                }
+            };
+        assert_eq!(validation_result.success, true);
+        let (all_data, _) = match validate::<ResourceObject<TestData>>(&valid_data) {
+            Ok(result) => result,
+            Err(e) => {
+                panic!("Validation errors found but not expected: {}", e);
+            }
+        };
+        assert_eq!(psql_data.len(), all_data.len());
+
+        ut_info!("(test_validate_with_mask_invalid_paths) success");
+    }
+
     #[tokio::test]
     async fn test_validate_invalid_object() {
         crate::get_log_handle().await;
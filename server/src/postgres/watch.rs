@@ -0,0 +1,524 @@
+//! Change data capture for resources
+//!
+//! Every change made to a resource's records is recorded in the [`CHANGE_LOG_TABLE`] by the
+//! write functions of the resource, using the same transaction as the change itself.
+//! After committing, active watchers of this instance are woken up using [`notify_watchers`].
+//! Watchers also poll the change log, so changes made through other instances will be picked up as well.
+//!
+//! The change log provides a sequence number for each change, allowing clients to
+//! resume watching after a reconnect without missing any events.
+//! Sequence numbers are taken from the single counter row in the [`CHANGE_SEQUENCE_TABLE`], which stays locked
+//! until the writing transaction is committed or rolled back. Sequence numbers therefore become visible in commit order
+//! and without gaps, so a watcher which has seen a sequence number has seen all changes before it as well.
+//! This serializes the commits of all write transactions recording changes.
+//! Changes older than the configured retention window are removed by the [`retention`](super::retention) task.
+//!
+//! Linked resources record the changes made through their object functions (update, delete and restore)
+//! using the id of their first id column. Links created or removed through `create` and `delete_for_ids`
+//! are not recorded. Linked resources can not be watched, as watching requires a single id column.
+
+use super::search::PsqlSearch;
+use super::{get_psql_client, ArrErr};
+use crate::grpc::server::{AdvancedSearchFilter, ChangeType};
+use crate::resources::base::Resource;
+
+use chrono::{DateTime, Utc};
+use deadpool_postgres::Transaction;
+use futures::Stream;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc, OnceCell};
+use tokio_postgres::Row;
+use uuid::Uuid;
+
+/// Name of the table containing all recorded changes
+pub const CHANGE_LOG_TABLE: &str = "change_log";
+/// Name of the table containing the counter used to assign sequence numbers to recorded changes
+pub const CHANGE_SEQUENCE_TABLE: &str = "change_log_sequence";
+
+/// Maximum number of change log entries fetched in a single query
+const CHANGE_FETCH_LIMIT: i64 = 500;
+/// Maximum number of change log entries removed in a single query
+const CHANGE_PURGE_BATCH_SIZE: i64 = 1000;
+/// Number of notifications buffered for each watcher before they start lagging
+const CHANGE_NOTIFY_CAPACITY: usize = 1024;
+/// Number of change events buffered for each watcher
+const WATCH_BUFFER_SIZE: usize = 100;
+/// Interval used to check the change log for changes made through other instances
+const WATCH_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Change recorded in the [`CHANGE_LOG_TABLE`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChangeRecord {
+    /// sequence number of the change
+    pub sequence: i64,
+    /// the [`ChangeType`] of the change
+    pub change_type: ChangeType,
+    /// id of the changed record
+    pub id: Uuid,
+}
+
+/// Change emitted by [`PsqlWatch::watch_changes`], including the current record data
+#[derive(Debug)]
+pub struct ChangeRow {
+    /// the recorded change
+    pub change: ChangeRecord,
+    /// the current record [`Row`], [`None`] if the record has been purged
+    pub row: Option<Row>,
+}
+
+/// Stream of changes returned by [`PsqlWatch::watch_changes`]
+#[derive(Debug)]
+pub struct ChangeRowStream {
+    receiver: mpsc::Receiver<Result<ChangeRow, ArrErr>>,
+}
+impl Stream for ChangeRowStream {
+    type Item = Result<ChangeRow, ArrErr>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+/// Trait providing a change stream for resources
+#[tonic::async_trait]
+pub trait PsqlWatch
+where
+    Self: PsqlSearch + Resource + Sized + 'static,
+{
+    /// Watches the resource's table for changes matching the provided filter
+    ///
+    /// The filter's `page_token` can be set to the sequence number of the last received
+    /// change to resume watching. If no `page_token` is provided, only new changes will be returned.
+    /// Deleted records will always be included, so `DELETED` events can be emitted.
+    ///
+    /// # Errors
+    ///
+    /// Returns [ArrErr] if the resource does not have a single id column,
+    /// the `page_token` is not a valid sequence number or the change log could not be read.
+    async fn watch_changes(filter: AdvancedSearchFilter) -> Result<ChangeRowStream, ArrErr> {
+        let definition = Self::get_definition();
+        let table = definition.psql_table.clone();
+        let id_col = match definition.get_psql_id_cols().as_slice() {
+            [id_col] => id_col.clone(),
+            _ => {
+                let error = format!(
+                    "Can not watch changes for table [{}], only resources with a single id column are supported.",
+                    table
+                );
+                psql_error!("(watch_changes) {}", error);
+                return Err(ArrErr::Error(error));
+            }
+        };
+
+        // Subscribe before reading the change log, so no changes can slip through
+        let mut notifications = subscribe_changes().await;
+        let mut last_sequence = if filter.page_token.is_empty() {
+            get_last_sequence(&table).await?
+        } else {
+            filter.page_token.parse::<i64>().map_err(|e| {
+                let error = format!(
+                    "Invalid page token provided [{}], expected a sequence number: {}",
+                    filter.page_token, e
+                );
+                psql_error!("(watch_changes) {}", error);
+                ArrErr::invalid_argument(error)
+            })?
+        };
+        psql_info!(
+            "(watch_changes) Watching table [{}] for changes after sequence [{}].",
+            table,
+            last_sequence
+        );
+
+        let (sender, receiver) = mpsc::channel(WATCH_BUFFER_SIZE);
+        tokio::spawn(async move {
+            loop {
+                let changes = match get_changes_since(&table, last_sequence).await {
+                    Ok(changes) => changes,
+                    Err(e) => {
+                        let _ = sender.send(Err(e)).await;
+                        return;
+                    }
+                };
+                let fetched = changes.len() as i64;
+                for change in changes {
+                    last_sequence = change.sequence;
+                    let row = match Self::_get_change_row(&filter, &id_col, &change).await {
+                        Ok(Some(row)) => row,
+                        Ok(None) => continue,
+                        Err(e) => {
+                            let _ = sender.send(Err(e)).await;
+                            return;
+                        }
+                    };
+                    if sender.send(Ok(ChangeRow { change, row })).await.is_err() {
+                        psql_debug!("(watch_changes) Watcher for table [{}] closed.", table);
+                        return;
+                    }
+                }
+                if fetched == CHANGE_FETCH_LIMIT {
+                    // There might be more changes waiting
+                    continue;
+                }
+
+                tokio::select! {
+                    _ = sender.closed() => {
+                        psql_debug!("(watch_changes) Watcher for table [{}] closed.", table);
+                        return;
+                    }
+                    _ = wait_for_change(&mut notifications) => {}
+                }
+            }
+        });
+
+        Ok(ChangeRowStream { receiver })
+    }
+
+    /// Returns the current [`Row`] of the changed record if it matches the provided filter.
+    ///
+    /// Returns `Some(None)` for deleted records that have been purged, as they can
+    /// not be matched against the filter but should still be reported.
+    /// Returns [`None`] if the change should be skipped.
+    /// for internal use
+    async fn _get_change_row(
+        filter: &AdvancedSearchFilter,
+        id_col: &str,
+        change: &ChangeRecord,
+    ) -> Result<Option<Option<Row>>, ArrErr> {
+        let mut change_filter =
+            AdvancedSearchFilter::search_equals(id_col.to_owned(), change.id.to_string());
+        change_filter.include_deleted = true;
+        let matched = Self::advanced_search(change_filter.clone().and_group(filter.clone()))
+            .await?
+            .into_iter()
+            .next();
+        if matched.is_some() {
+            return Ok(Some(matched));
+        }
+
+        if change.change_type == ChangeType::Deleted
+            && Self::advanced_search(change_filter).await?.is_empty()
+        {
+            return Ok(Some(None));
+        }
+        Ok(None)
+    }
+}
+
+/// Waits until a change is notified, or the poll interval has passed
+async fn wait_for_change(notifications: &mut broadcast::Receiver<()>) {
+    let notified = async {
+        match notifications.recv().await {
+            // We might have missed notifications, check the change log to be sure
+            Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => {}
+            // The sender is never dropped, fall back to polling
+            Err(broadcast::error::RecvError::Closed) => {
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    let _ = tokio::time::timeout(WATCH_POLL_INTERVAL, notified).await;
+}
+
+/// Sender used to wake up the watchers of this instance after changes have been committed
+static CHANGE_SENDER: OnceCell<broadcast::Sender<()>> = OnceCell::const_new();
+
+/// Subscribes to the change notifications of this instance
+async fn subscribe_changes() -> broadcast::Receiver<()> {
+    CHANGE_SENDER
+        .get_or_init(|| async { broadcast::channel(CHANGE_NOTIFY_CAPACITY).0 })
+        .await
+        .subscribe()
+}
+
+/// Wakes up all active watchers of this instance, should be called after changes
+/// recorded by [`record_change`] have been committed.
+///
+/// All watchers are woken up, as a single transaction may change records of multiple tables
+/// (status propagation). Watchers connected to other instances will pick up the changes
+/// on their next poll of the change log.
+pub(crate) fn notify_watchers() {
+    if let Some(sender) = CHANGE_SENDER.get() {
+        // It's fine if there are no active watchers
+        let _ = sender.send(());
+    }
+}
+
+/// Records a change of the provided record in the [`CHANGE_LOG_TABLE`], using the provided transaction
+///
+/// The change will only be visible to watchers once the transaction is committed.
+/// The sequence counter stays locked by the transaction until then, so recording a change should
+/// be one of the last steps of the transaction.
+/// Soft deletes should be recorded as [`ChangeType::Deleted`], restores of soft-deleted records as [`ChangeType::Created`].
+///
+/// # Errors
+///
+/// Returns [`ArrErr`] Database Error if the change could not be inserted
+pub(crate) async fn record_change(
+    transaction: &Transaction<'_>,
+    table: &str,
+    change_type: ChangeType,
+    id: &Uuid,
+) -> Result<(), ArrErr> {
+    let query = get_record_change_query();
+    psql_debug!("(record_change) [{}].", query);
+    let stmt = transaction.prepare_cached(&query).await?;
+    transaction
+        .execute(&stmt, &[&table, &get_change_operation(change_type), id])
+        .await?;
+    Ok(())
+}
+
+/// Removes all changes recorded before the provided time from the [`CHANGE_LOG_TABLE`]
+///
+/// Changes are removed in batches of [`CHANGE_PURGE_BATCH_SIZE`] using separate statements,
+/// so the cleanup never creates a single large transaction.
+/// Returns the number of removed changes.
+///
+/// # Errors
+///
+/// Returns [`ArrErr`] from [`PoolError`](deadpool::managed::PoolError) if no client connection could be returned from the connection [`Pool`](deadpool::managed::Pool)
+/// Returns [`ArrErr`] Database Error if a batch could not be removed
+pub async fn purge_changes_before(before: &DateTime<Utc>) -> Result<u64, ArrErr> {
+    let client = get_psql_client().await?;
+    let query = get_purge_changes_query();
+    psql_debug!("(purge_changes_before) [{}].", query);
+    let stmt = client.prepare_cached(&query).await?;
+
+    let mut removed: u64 = 0;
+    loop {
+        let rows = client
+            .execute(&stmt, &[before, &CHANGE_PURGE_BATCH_SIZE])
+            .await?;
+        removed += rows;
+        if rows < CHANGE_PURGE_BATCH_SIZE as u64 {
+            return Ok(removed);
+        }
+    }
+}
+
+/// Returns the sequence number of the last change recorded for the provided table
+async fn get_last_sequence(table: &str) -> Result<i64, ArrErr> {
+    let client = get_psql_client().await?;
+    let query = format!(
+        r#"SELECT COALESCE(MAX("sequence"), 0) FROM "{}" WHERE "table_name" = $1"#,
+        CHANGE_LOG_TABLE
+    );
+    let stmt = client.prepare_cached(&query).await?;
+    let row = client.query_one(&stmt, &[&table]).await?;
+    Ok(row.try_get(0)?)
+}
+
+/// Returns the changes recorded for the provided table after the provided sequence number
+async fn get_changes_since(table: &str, sequence: i64) -> Result<Vec<ChangeRecord>, ArrErr> {
+    let client = get_psql_client().await?;
+    let query = format!(
+        r#"SELECT "sequence", "operation", "record_id" FROM "{}" WHERE "table_name" = $1 AND "sequence" > $2 ORDER BY "sequence" LIMIT $3"#,
+        CHANGE_LOG_TABLE
+    );
+    let stmt = client.prepare_cached(&query).await?;
+    let rows = client
+        .query(&stmt, &[&table, &sequence, &CHANGE_FETCH_LIMIT])
+        .await?;
+
+    let mut changes: Vec<ChangeRecord> = Vec::with_capacity(rows.len());
+    for row in rows {
+        let operation: String = row.try_get("operation")?;
+        changes.push(ChangeRecord {
+            sequence: row.try_get("sequence")?,
+            change_type: get_change_type(&operation)?,
+            id: row.try_get("record_id")?,
+        });
+    }
+    Ok(changes)
+}
+
+/// Converts an operation stored in the [`CHANGE_LOG_TABLE`] into a [`ChangeType`]
+fn get_change_type(operation: &str) -> Result<ChangeType, ArrErr> {
+    match operation {
+        "INSERT" => Ok(ChangeType::Created),
+        "UPDATE" => Ok(ChangeType::Updated),
+        "DELETE" => Ok(ChangeType::Deleted),
+        _ => {
+            let error = format!("Unknown change operation [{}].", operation);
+            psql_error!("(get_change_type) {}", error);
+            Err(ArrErr::Error(error))
+        }
+    }
+}
+
+/// Converts a [`ChangeType`] into the operation stored in the [`CHANGE_LOG_TABLE`]
+fn get_change_operation(change_type: ChangeType) -> &'static str {
+    match change_type {
+        ChangeType::Created => "INSERT",
+        ChangeType::Updated => "UPDATE",
+        ChangeType::Deleted => "DELETE",
+    }
+}
+
+/// Returns the query used to record a change, taking the next sequence number from the [`CHANGE_SEQUENCE_TABLE`]
+fn get_record_change_query() -> String {
+    format!(
+        r#"WITH "next" AS (UPDATE "{sequence_table}" SET "value" = "value" + 1 WHERE "id" = 1 RETURNING "value") INSERT INTO "{table}" ("sequence", "table_name", "operation", "record_id") SELECT "value", $1, $2, $3 FROM "next""#,
+        sequence_table = CHANGE_SEQUENCE_TABLE,
+        table = CHANGE_LOG_TABLE
+    )
+}
+
+/// Returns the query used to remove a single batch of changes recorded before the provided time
+fn get_purge_changes_query() -> String {
+    format!(
+        r#"DELETE FROM "{table}" WHERE "sequence" IN (SELECT "sequence" FROM "{table}" WHERE "created_at" < $1 ORDER BY "created_at" LIMIT $2)"#,
+        table = CHANGE_LOG_TABLE
+    )
+}
+
+/// Returns the queries needed to create the change log table
+pub(crate) fn get_change_log_queries() -> Vec<String> {
+    vec![
+        format!(
+            r#"CREATE TABLE IF NOT EXISTS "{}" ("sequence" BIGSERIAL PRIMARY KEY, "table_name" TEXT NOT NULL, "operation" TEXT NOT NULL, "record_id" UUID NOT NULL, "created_at" TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW())"#,
+            CHANGE_LOG_TABLE
+        ),
+        format!(
+            r#"CREATE INDEX IF NOT EXISTS "{table}_table_name_sequence_idx" ON "{table}" ("table_name", "sequence")"#,
+            table = CHANGE_LOG_TABLE
+        ),
+        format!(
+            r#"CREATE INDEX IF NOT EXISTS "{table}_created_at_idx" ON "{table}" ("created_at")"#,
+            table = CHANGE_LOG_TABLE
+        ),
+        format!(
+            r#"CREATE TABLE IF NOT EXISTS "{}" ("id" INT8 PRIMARY KEY, "value" INT8 NOT NULL)"#,
+            CHANGE_SEQUENCE_TABLE
+        ),
+        format!(
+            r#"INSERT INTO "{}" ("id", "value") SELECT 1, COALESCE(MAX("sequence"), 0) FROM "{}" ON CONFLICT DO NOTHING"#,
+            CHANGE_SEQUENCE_TABLE, CHANGE_LOG_TABLE
+        ),
+    ]
+}
+
+/// Returns the queries needed to drop the change log tables
+pub(crate) fn get_drop_change_log_queries() -> Vec<String> {
+    vec![
+        format!(r#"DROP TABLE IF EXISTS "{}""#, CHANGE_LOG_TABLE),
+        format!(r#"DROP TABLE IF EXISTS "{}""#, CHANGE_SEQUENCE_TABLE),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_get_change_type() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_change_type) start");
+
+        assert_eq!(get_change_type("INSERT").unwrap(), ChangeType::Created);
+        assert_eq!(get_change_type("UPDATE").unwrap(), ChangeType::Updated);
+        assert_eq!(get_change_type("DELETE").unwrap(), ChangeType::Deleted);
+        assert!(get_change_type("TRUNCATE").is_err());
+
+        ut_info!("(test_get_change_type) success");
+    }
+
+    #[tokio::test]
+    async fn test_get_change_operation() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_change_operation) start");
+
+        for change_type in [
+            ChangeType::Created,
+            ChangeType::Updated,
+            ChangeType::Deleted,
+        ] {
+            assert_eq!(
+                get_change_type(get_change_operation(change_type)).unwrap(),
+                change_type
+            );
+        }
+
+        ut_info!("(test_get_change_operation) success");
+    }
+
+    #[tokio::test]
+    async fn test_get_purge_changes_query() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_purge_changes_query) start");
+
+        assert_eq!(
+            get_purge_changes_query(),
+            String::from(
+                r#"DELETE FROM "change_log" WHERE "sequence" IN (SELECT "sequence" FROM "change_log" WHERE "created_at" < $1 ORDER BY "created_at" LIMIT $2)"#
+            )
+        );
+
+        ut_info!("(test_get_purge_changes_query) success");
+    }
+
+    #[tokio::test]
+    async fn test_get_record_change_query() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_record_change_query) start");
+
+        assert_eq!(
+            get_record_change_query(),
+            String::from(
+                r#"WITH "next" AS (UPDATE "change_log_sequence" SET "value" = "value" + 1 WHERE "id" = 1 RETURNING "value") INSERT INTO "change_log" ("sequence", "table_name", "operation", "record_id") SELECT "value", $1, $2, $3 FROM "next""#
+            )
+        );
+
+        ut_info!("(test_get_record_change_query) success");
+    }
+
+    #[cfg(not(any(feature = "stub_backends")))]
+    #[tokio::test]
+    async fn test_change_sequence_commit_order() {
+        crate::get_log_handle().await;
+        ut_info!("(test_change_sequence_commit_order) start");
+
+        let table = "test_change_sequence_commit_order";
+        let last_sequence = get_last_sequence(table).await.unwrap();
+
+        // The first transaction records its change first, but commits last
+        let mut first_client = get_psql_client().await.unwrap();
+        let first = first_client.transaction().await.unwrap();
+        let first_id = Uuid::new_v4();
+        record_change(&first, table, ChangeType::Created, &first_id)
+            .await
+            .unwrap();
+
+        let second_id = Uuid::new_v4();
+        let second = tokio::spawn(async move {
+            let mut client = get_psql_client().await?;
+            let transaction = client.transaction().await?;
+            record_change(&transaction, table, ChangeType::Created, &second_id).await?;
+            transaction.commit().await?;
+            Ok::<(), ArrErr>(())
+        });
+
+        // The second transaction can't commit its change while the first one is in flight
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        assert!(!second.is_finished());
+        assert!(get_changes_since(table, last_sequence)
+            .await
+            .unwrap()
+            .is_empty());
+
+        first.commit().await.unwrap();
+        second.await.unwrap().unwrap();
+
+        // Watchers see both changes in commit order, without gaps
+        let changes = get_changes_since(table, last_sequence).await.unwrap();
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].id, first_id);
+        assert_eq!(changes[1].id, second_id);
+        assert_eq!(changes[1].sequence, changes[0].sequence + 1);
+
+        ut_info!("(test_change_sequence_commit_order) success");
+    }
+}
@@ -0,0 +1,367 @@
+//! Mode S / ADS-B extended squitter decoder
+//!
+//! Decodes DF17 (ADS-B) and DF18 (non-transponder ADS-B) frames into identification,
+//! airborne position and airborne velocity messages. Positions are transmitted using
+//! Compact Position Reporting (CPR), which needs an even and an odd frame of the same
+//! aircraft to decode a global position, see [`decode_cpr_position`].
+//!
+//! Reference: "The 1090 Megahertz Riddle" by Junzi Sun.
+
+/// Length in bytes of an extended squitter frame
+pub const FRAME_LENGTH: usize = 14;
+/// Maximum number of seconds between an even and odd CPR frame to be paired
+pub const CPR_MAX_PAIR_SECONDS: i64 = 10;
+
+/// Mode S CRC-24 generator polynomial
+const CRC_GENERATOR: u128 = 0x1FF_F409;
+/// Offset of the ME field in bits
+const ME_OFFSET: usize = 32;
+/// Number of CPR latitude zones
+const CPR_NZ: f64 = 15.0;
+/// Maximum value (exclusive) of the 17 bits CPR encoded coordinates
+const CPR_MAX: f64 = 131_072.0;
+/// Characters used to encode callsigns, `#` being an invalid character
+const CALLSIGN_CHARSET: &[u8; 64] =
+    b"#ABCDEFGHIJKLMNOPQRSTUVWXYZ##### ###############0123456789######";
+
+/// CPR encoded position of an airborne position message
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CprPosition {
+    /// `true` for odd frames, `false` for even frames
+    pub odd: bool,
+    /// 17 bits encoded latitude
+    pub lat_cpr: u32,
+    /// 17 bits encoded longitude
+    pub lon_cpr: u32,
+}
+
+/// Decoded ADS-B message
+#[derive(Debug, Clone, PartialEq)]
+pub enum AdsbMessage {
+    /// Aircraft identification (type codes 1-4)
+    Identification {
+        /// callsign without trailing spaces
+        callsign: String,
+    },
+    /// Airborne position (type codes 9-18 and 20-22)
+    AirbornePosition {
+        /// barometric altitude in feet, only available for type codes 9-18 using 25 feet increments
+        baro_altitude: Option<i64>,
+        /// CPR encoded position
+        cpr: CprPosition,
+    },
+    /// Airborne velocity over ground (type code 19, subtypes 1 and 2)
+    AirborneVelocity {
+        /// ground speed in knots
+        ground_speed: f64,
+        /// track angle over ground in degrees, clockwise from true north
+        heading: f64,
+    },
+}
+
+/// Returns the `length` bits of the provided frame starting at bit `start`, most significant bit first
+fn get_bits(frame: &[u8], start: usize, length: usize) -> u64 {
+    (start..start + length).fold(0, |value, bit| {
+        (value << 1) | ((frame[bit / 8] >> (7 - bit % 8)) & 1) as u64
+    })
+}
+
+/// Returns `true` if the CRC-24 parity of the provided 112 bits frame is valid
+pub fn has_valid_crc(frame: &[u8]) -> bool {
+    if frame.len() != FRAME_LENGTH {
+        return false;
+    }
+    let mut remainder = frame
+        .iter()
+        .fold(0_u128, |value, byte| (value << 8) | *byte as u128);
+    for bit in (24..112).rev() {
+        if remainder & (1 << bit) != 0 {
+            remainder ^= CRC_GENERATOR << (bit - 24);
+        }
+    }
+    remainder & 0xFF_FFFF == 0
+}
+
+/// Returns the ICAO address of the provided DF17/DF18 frame
+pub fn get_icao_address(frame: &[u8]) -> i64 {
+    get_bits(frame, 8, 24) as i64
+}
+
+/// Decodes the provided DF17/DF18 frame.
+///
+/// Returns [`None`] if the frame is not a valid extended squitter with an ICAO address, or if
+/// the message type is not supported.
+pub fn decode(frame: &[u8]) -> Option<AdsbMessage> {
+    if !has_valid_crc(frame) {
+        return None;
+    }
+    let downlink_format = get_bits(frame, 0, 5);
+    let capability = get_bits(frame, 5, 3);
+    // DF18 is only decoded for ADS-B messages using an (anonymous) ICAO address
+    if downlink_format != 17 && !(downlink_format == 18 && capability <= 1) {
+        return None;
+    }
+
+    let me = |start: usize, length: usize| get_bits(frame, ME_OFFSET + start, length);
+    match me(0, 5) {
+        1..=4 => decode_identification(frame),
+        type_code @ (9..=18 | 20..=22) => Some(AdsbMessage::AirbornePosition {
+            baro_altitude: match type_code {
+                9..=18 => decode_altitude(me(8, 12)),
+                _ => None,
+            },
+            cpr: CprPosition {
+                odd: me(21, 1) == 1,
+                lat_cpr: me(22, 17) as u32,
+                lon_cpr: me(39, 17) as u32,
+            },
+        }),
+        19 => decode_velocity(frame),
+        _ => None,
+    }
+}
+
+/// Decodes the callsign of an identification message
+fn decode_identification(frame: &[u8]) -> Option<AdsbMessage> {
+    let callsign: String = (0..8)
+        .map(|i| CALLSIGN_CHARSET[get_bits(frame, ME_OFFSET + 8 + i * 6, 6) as usize] as char)
+        .collect();
+    let callsign = callsign.trim_end().to_owned();
+    if callsign.is_empty() || callsign.contains('#') {
+        return None;
+    }
+    Some(AdsbMessage::Identification { callsign })
+}
+
+/// Decodes the 12 bits altitude field of an airborne position message.
+///
+/// Only altitudes using 25 feet increments (Q bit set) are supported.
+fn decode_altitude(altitude: u64) -> Option<i64> {
+    if altitude & 0x10 == 0 {
+        return None;
+    }
+    let n = ((altitude & 0xFE0) >> 1) | (altitude & 0xF);
+    Some(n as i64 * 25 - 1000)
+}
+
+/// Decodes the ground speed and heading of an airborne velocity message
+fn decode_velocity(frame: &[u8]) -> Option<AdsbMessage> {
+    let me = |start: usize, length: usize| get_bits(frame, ME_OFFSET + start, length);
+    let factor = match me(5, 3) {
+        1 => 1.0,
+        2 => 4.0,
+        _ => return None,
+    };
+    let (v_ew, v_ns) = (me(14, 10), me(25, 10));
+    if v_ew == 0 || v_ns == 0 {
+        return None;
+    }
+    let sign = |negative: u64| if negative == 1 { -1.0 } else { 1.0 };
+    let v_x = sign(me(13, 1)) * (v_ew - 1) as f64 * factor;
+    let v_y = sign(me(24, 1)) * (v_ns - 1) as f64 * factor;
+
+    let heading = v_x.atan2(v_y).to_degrees();
+    Some(AdsbMessage::AirborneVelocity {
+        ground_speed: v_x.hypot(v_y),
+        heading: if heading < 0.0 {
+            heading + 360.0
+        } else {
+            heading
+        },
+    })
+}
+
+/// Returns the number of longitude zones for the provided latitude
+fn get_nl(lat: f64) -> i64 {
+    let lat = lat.abs();
+    if lat == 0.0 {
+        return 59;
+    } else if lat == 87.0 {
+        return 2;
+    } else if lat > 87.0 {
+        return 1;
+    }
+    let a = 1.0 - (std::f64::consts::PI / (2.0 * CPR_NZ)).cos();
+    let b = lat.to_radians().cos().powi(2);
+    (2.0 * std::f64::consts::PI / (1.0 - a / b).acos()).floor() as i64
+}
+
+/// Returns the positive remainder of `a / b`
+fn modulo(a: f64, b: f64) -> f64 {
+    a - b * (a / b).floor()
+}
+
+/// Decodes the global position of an even and an odd CPR frame received within
+/// [`CPR_MAX_PAIR_SECONDS`] of each other.
+///
+/// The position is calculated for the odd frame if `odd_is_latest`, for the even frame otherwise.
+/// Returns `(latitude, longitude)` in degrees, or [`None`] if the frames are not an even/odd pair
+/// or if they are in different longitude zones.
+pub fn decode_cpr_position(
+    even: &CprPosition,
+    odd: &CprPosition,
+    odd_is_latest: bool,
+) -> Option<(f64, f64)> {
+    if even.odd || !odd.odd {
+        return None;
+    }
+    let (lat_even_cpr, lon_even_cpr) =
+        (even.lat_cpr as f64 / CPR_MAX, even.lon_cpr as f64 / CPR_MAX);
+    let (lat_odd_cpr, lon_odd_cpr) = (odd.lat_cpr as f64 / CPR_MAX, odd.lon_cpr as f64 / CPR_MAX);
+
+    let (d_lat_even, d_lat_odd) = (360.0 / (4.0 * CPR_NZ), 360.0 / (4.0 * CPR_NZ - 1.0));
+    let j = (59.0 * lat_even_cpr - 60.0 * lat_odd_cpr + 0.5).floor();
+    let normalize = |lat: f64| if lat >= 270.0 { lat - 360.0 } else { lat };
+    let lat_even = normalize(d_lat_even * (modulo(j, 60.0) + lat_even_cpr));
+    let lat_odd = normalize(d_lat_odd * (modulo(j, 59.0) + lat_odd_cpr));
+
+    let nl = get_nl(lat_even);
+    if nl != get_nl(lat_odd) {
+        return None;
+    }
+
+    let (lat, ni, lon_cpr) = if odd_is_latest {
+        (lat_odd, (nl - 1).max(1), lon_odd_cpr)
+    } else {
+        (lat_even, nl.max(1), lon_even_cpr)
+    };
+    let m = (lon_even_cpr * (nl - 1) as f64 - lon_odd_cpr * nl as f64 + 0.5).floor();
+    let lon = (360.0 / ni as f64) * (modulo(m, ni as f64) + lon_cpr);
+    let lon = if lon >= 180.0 { lon - 360.0 } else { lon };
+
+    Some((lat, lon))
+}
+
+/// Converts the hex string of a frame into bytes, used by tests
+#[cfg(test)]
+pub(super) fn get_frame(hex: &str) -> Vec<u8> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+/// Returns the CPR position of the airborne position frame hex string, used by tests
+#[cfg(test)]
+pub(super) fn get_cpr(hex: &str) -> CprPosition {
+    match decode(&get_frame(hex)) {
+        Some(AdsbMessage::AirbornePosition { cpr, .. }) => cpr,
+        message => panic!("Expected airborne position, got {:?}", message),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_has_valid_crc() {
+        crate::get_log_handle().await;
+        ut_info!("(test_has_valid_crc) start");
+
+        assert!(has_valid_crc(&get_frame("8D4840D6202CC371C32CE0576098")));
+        assert!(!has_valid_crc(&get_frame("8D4840D6202CC371C32CE0576198")));
+        assert!(!has_valid_crc(&get_frame("8D4840D6202CC371C32CE05760")));
+        assert!(has_valid_crc(&[0; FRAME_LENGTH]));
+        assert_eq!(decode(&[0; FRAME_LENGTH]), None);
+
+        ut_info!("(test_has_valid_crc) success");
+    }
+
+    #[tokio::test]
+    async fn test_decode_identification() {
+        crate::get_log_handle().await;
+        ut_info!("(test_decode_identification) start");
+
+        let frame = get_frame("8D4840D6202CC371C32CE0576098");
+        assert_eq!(get_icao_address(&frame), 0x4840D6);
+        assert_eq!(
+            decode(&frame),
+            Some(AdsbMessage::Identification {
+                callsign: String::from("KLM1023")
+            })
+        );
+
+        ut_info!("(test_decode_identification) success");
+    }
+
+    #[tokio::test]
+    async fn test_decode_airborne_position() {
+        crate::get_log_handle().await;
+        ut_info!("(test_decode_airborne_position) start");
+
+        let frame = get_frame("8D40621D58C382D690C8AC2863A7");
+        assert_eq!(get_icao_address(&frame), 0x40621D);
+        assert_eq!(
+            decode(&frame),
+            Some(AdsbMessage::AirbornePosition {
+                baro_altitude: Some(38000),
+                cpr: CprPosition {
+                    odd: false,
+                    lat_cpr: 93000,
+                    lon_cpr: 51372,
+                },
+            })
+        );
+
+        ut_info!("(test_decode_airborne_position) success");
+    }
+
+    #[tokio::test]
+    async fn test_decode_cpr_position() {
+        crate::get_log_handle().await;
+        ut_info!("(test_decode_cpr_position) start");
+
+        let even = get_cpr("8D40621D58C382D690C8AC2863A7");
+        let odd = get_cpr("8D40621D58C386435CC412692AD6");
+        assert!(!even.odd);
+        assert!(odd.odd);
+
+        let (lat, lon) = decode_cpr_position(&even, &odd, false).unwrap();
+        assert!((lat - 52.25720).abs() < 0.0001);
+        assert!((lon - 3.91937).abs() < 0.0001);
+
+        let (lat, lon) = decode_cpr_position(&even, &odd, true).unwrap();
+        assert!((lat - 52.26578).abs() < 0.0001);
+        assert!((lon - 3.93891).abs() < 0.0001);
+
+        // Frames should be an even/odd pair
+        assert_eq!(decode_cpr_position(&even, &even, false), None);
+        assert_eq!(decode_cpr_position(&odd, &odd, true), None);
+
+        ut_info!("(test_decode_cpr_position) success");
+    }
+
+    #[tokio::test]
+    async fn test_decode_velocity() {
+        crate::get_log_handle().await;
+        ut_info!("(test_decode_velocity) start");
+
+        match decode(&get_frame("8D485020994409940838175B284F")) {
+            Some(AdsbMessage::AirborneVelocity {
+                ground_speed,
+                heading,
+            }) => {
+                assert!((ground_speed - 159.20).abs() < 0.01);
+                assert!((heading - 182.88).abs() < 0.01);
+            }
+            message => panic!("Expected airborne velocity, got {:?}", message),
+        }
+
+        ut_info!("(test_decode_velocity) success");
+    }
+
+    #[tokio::test]
+    async fn test_get_nl() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_nl) start");
+
+        assert_eq!(get_nl(0.0), 59);
+        assert_eq!(get_nl(52.25720), 36);
+        assert_eq!(get_nl(-52.25720), 36);
+        assert_eq!(get_nl(87.0), 2);
+        assert_eq!(get_nl(89.0), 1);
+
+        ut_info!("(test_get_nl) success");
+    }
+}
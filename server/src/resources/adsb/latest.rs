@@ -0,0 +1,91 @@
+//! Latest known ADS-B state
+//!
+//! Finds the most recent message and decoded position of each aircraft (ICAO address) in a single scan
+//! of the messages received within the requested time window, backed by the `adsb_network_timestamp_idx` index.
+
+use super::{Data, LatestPosition, Object};
+use crate::common::ArrErr;
+use crate::grpc::server::grpc_geo_types::GeoPoint;
+use crate::postgres::get_psql_client;
+use crate::postgres::postgis::{get_polygon_wkt, SRID};
+
+use chrono::{DateTime, Utc};
+use geo_types::Polygon;
+use log::debug;
+use uuid::Uuid;
+
+/// Returns the latest known state of each aircraft, ordered by ICAO address.
+///
+/// Only messages received after `since` are taken into account.
+/// If `bounds` are provided, only aircraft with a latest known position within the polygon are returned.
+///
+/// # Errors
+///
+/// Returns [`ArrErr`] if a returned row could not be converted to adsb [`Data`]
+/// Returns [`ArrErr`] from [`PoolError`](deadpool::managed::PoolError) if no client connection could be returned from the connection [`Pool`](deadpool::managed::Pool)
+/// Returns [`ArrErr`] Database Error if database query execution failed
+pub async fn get_latest_positions(
+    bounds: Option<&Polygon>,
+    since: &DateTime<Utc>,
+) -> Result<Vec<LatestPosition>, ArrErr> {
+    let client = get_psql_client().await?;
+    let query = get_latest_positions_query();
+    debug!("(get_latest_positions) [{}].", query);
+    let stmt = client.prepare_cached(&query).await?;
+    let rows = client
+        .query(&stmt, &[since, &bounds.map(get_polygon_wkt)])
+        .await?;
+
+    let mut list: Vec<LatestPosition> = vec![];
+    for row in rows {
+        let id: Uuid = row.get("adsb_id");
+        let position: Option<GeoPoint> = row.get("latest_position");
+        let position_timestamp: Option<DateTime<Utc>> = row.get("latest_position_timestamp");
+        let baro_altitude: Option<i64> = row.get("latest_position_baro_altitude");
+        let data = Data::try_from(row)?;
+        list.push(LatestPosition {
+            icao_address: data.icao_address,
+            latest: Some(Object {
+                id: id.to_string(),
+                data: Some(data),
+            }),
+            position,
+            position_timestamp: position_timestamp.map(|timestamp| timestamp.into()),
+            baro_altitude,
+        });
+    }
+
+    Ok(list)
+}
+
+/// Returns the query used to get the most recent message and decoded position of each ICAO address.
+///
+/// Only messages received after timestamp `$1` are taken into account.
+/// Only aircraft with a position within polygon WKT `$2` are returned if not `NULL`.
+///
+/// The latest decoded position is taken from the first row of the `positioned` window,
+/// which orders the messages of each aircraft with a position before the messages without one.
+fn get_latest_positions_query() -> String {
+    format!(
+        r#"SELECT * FROM (SELECT DISTINCT ON ("icao_address") *, FIRST_VALUE("position") OVER "positioned" AS "latest_position", FIRST_VALUE(CASE WHEN "position" IS NOT NULL THEN "network_timestamp" END) OVER "positioned" AS "latest_position_timestamp", FIRST_VALUE(CASE WHEN "position" IS NOT NULL THEN "baro_altitude" END) OVER "positioned" AS "latest_position_baro_altitude" FROM "adsb" WHERE "network_timestamp" >= $1::TIMESTAMPTZ WINDOW "positioned" AS (PARTITION BY "icao_address" ORDER BY "position" IS NULL, "network_timestamp" DESC) ORDER BY "icao_address", "network_timestamp" DESC) AS "latest" WHERE ($2::TEXT IS NULL OR ST_Within("latest_position", ST_GeomFromText($2::TEXT, {srid}))) ORDER BY "icao_address""#,
+        srid = SRID
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_get_latest_positions_query() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_latest_positions_query) start");
+
+        assert_eq!(
+            get_latest_positions_query(),
+            r#"SELECT * FROM (SELECT DISTINCT ON ("icao_address") *, FIRST_VALUE("position") OVER "positioned" AS "latest_position", FIRST_VALUE(CASE WHEN "position" IS NOT NULL THEN "network_timestamp" END) OVER "positioned" AS "latest_position_timestamp", FIRST_VALUE(CASE WHEN "position" IS NOT NULL THEN "baro_altitude" END) OVER "positioned" AS "latest_position_baro_altitude" FROM "adsb" WHERE "network_timestamp" >= $1::TIMESTAMPTZ WINDOW "positioned" AS (PARTITION BY "icao_address" ORDER BY "position" IS NULL, "network_timestamp" DESC) ORDER BY "icao_address", "network_timestamp" DESC) AS "latest" WHERE ($2::TEXT IS NULL OR ST_Within("latest_position", ST_GeomFromText($2::TEXT, 4326))) ORDER BY "icao_address""#
+        );
+
+        ut_info!("(test_get_latest_positions_query) success");
+    }
+}
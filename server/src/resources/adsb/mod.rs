@@ -1,24 +1,46 @@
 //! ADS-B
 
+pub mod decoder;
+pub mod latest;
+
 pub use crate::grpc::server::adsb::*;
 
 use chrono::{DateTime, Utc};
+use deadpool_postgres::Transaction;
+use futures::future::BoxFuture;
 use log::debug;
+use once_cell::sync::OnceCell;
 use std::collections::HashMap;
 use tokio_postgres::row::Row;
 use tokio_postgres::types::Type as PsqlFieldType;
 use uuid::Uuid;
 
 use super::base::simple_resource::*;
-use super::base::{FieldDefinition, ResourceDefinition};
+use super::base::{Deduplication, DerivedFieldsHook, FieldDefinition, ResourceDefinition};
 use crate::common::ArrErr;
+use crate::config::Config;
+use crate::grpc::server::grpc_geo_types::GeoPoint;
 use crate::grpc::{GrpcDataObjectType, GrpcField, GrpcFieldOption};
+use crate::postgres::postgis::{get_point_wkt, SRID};
+use decoder::{AdsbMessage, CprPosition, CPR_MAX_PAIR_SECONDS};
 
-crate::build_generic_resource_impl_from!();
+crate::build_generic_resource_impl_from!(deduplicated);
 
 // Generate grpc server implementations
 crate::build_grpc_simple_resource_impl!(adsb);
 
+/// Configured network timestamp tolerance used to detect messages received by multiple receivers
+static DEDUP_TOLERANCE_MS: OnceCell<u64> = OnceCell::new();
+
+/// Returns the configured deduplication tolerance in milliseconds
+fn get_dedup_tolerance_ms() -> u64 {
+    *DEDUP_TOLERANCE_MS.get_or_init(|| {
+        Config::try_from_env()
+            .unwrap_or_default()
+            .adsb_dedup_tolerance_ms
+    })
+}
+
 impl Resource for ResourceObject<Data> {
     fn get_definition() -> ResourceDefinition {
         ResourceDefinition {
@@ -41,9 +63,215 @@ impl Resource for ResourceObject<Data> {
                     String::from("payload"),
                     FieldDefinition::new(PsqlFieldType::BYTEA, true),
                 ),
+                (
+                    String::from("position"),
+                    FieldDefinition::new_read_only(PsqlFieldType::POINT, false),
+                ),
+                (
+                    String::from("baro_altitude"),
+                    FieldDefinition::new_read_only(PsqlFieldType::INT8, false),
+                ),
+                (
+                    String::from("ground_speed"),
+                    FieldDefinition::new_read_only(PsqlFieldType::FLOAT8, false),
+                ),
+                (
+                    String::from("heading"),
+                    FieldDefinition::new_read_only(PsqlFieldType::FLOAT8, false),
+                ),
+                (
+                    String::from("callsign"),
+                    FieldDefinition::new_read_only(PsqlFieldType::TEXT, false),
+                ),
+                (
+                    String::from("receivers"),
+                    FieldDefinition::new(PsqlFieldType::TEXT_ARRAY, true)
+                        .set_default(String::from("'{}'")),
+                ),
+                (
+                    String::from("dedup_bucket"),
+                    FieldDefinition::new_internal(PsqlFieldType::INT8, false),
+                ),
             ]),
         }
     }
+
+    fn get_table_indices() -> Vec<String> {
+        [
+            r#"CREATE INDEX IF NOT EXISTS adsb_icao_address_network_timestamp_idx ON "adsb" ("icao_address", "network_timestamp" DESC)"#.to_string(),
+            r#"CREATE INDEX IF NOT EXISTS adsb_network_timestamp_idx ON "adsb" ("network_timestamp")"#.to_string(),
+            r#"CREATE UNIQUE INDEX IF NOT EXISTS adsb_dedup_idx ON "adsb" ("icao_address", "payload", "dedup_bucket")"#.to_string(),
+        ].to_vec()
+    }
+
+    fn get_derived_fields_hook() -> Option<DerivedFieldsHook> {
+        Some(update_decoded_fields)
+    }
+
+    fn get_retention_col() -> Option<String> {
+        // Telemetry is only kept for the configured number of days
+        Some(String::from("network_timestamp"))
+    }
+
+    fn get_deduplication() -> Option<Deduplication> {
+        // The same message is received by multiple receivers, store it once with all its receivers
+        match get_dedup_tolerance_ms() {
+            0 => None,
+            tolerance_ms => Some(Deduplication::new(
+                &["icao_address", "payload"],
+                "network_timestamp",
+                "receivers",
+                tolerance_ms,
+            )),
+        }
+    }
+}
+
+/// Fields decoded from an ADS-B payload
+#[derive(Debug, Default)]
+struct DecodedFields {
+    position: Option<GeoPoint>,
+    baro_altitude: Option<i64>,
+    ground_speed: Option<f64>,
+    heading: Option<f64>,
+    callsign: Option<String>,
+}
+
+/// Decodes the payload of the inserted ADS-B record with the provided id and stores
+/// the decoded fields.
+///
+/// Airborne positions are CPR encoded, so the position can only be decoded if an opposite
+/// (even/odd) frame of the same ICAO address has been received within [`CPR_MAX_PAIR_SECONDS`].
+/// The position is decoded for the most recent frame of the pair.
+/// Payloads which can not be decoded are stored as is, without decoded fields.
+fn update_decoded_fields<'a>(
+    transaction: &'a Transaction<'a>,
+    id: &'a Uuid,
+) -> BoxFuture<'a, Result<(), ArrErr>> {
+    Box::pin(async move {
+        let row = transaction
+            .query_one(
+                r#"SELECT "icao_address", "network_timestamp", "payload" FROM "adsb" WHERE "adsb_id" = $1"#,
+                &[id],
+            )
+            .await?;
+        let icao_address: i64 = row.get("icao_address");
+        let network_timestamp: DateTime<Utc> = row.get("network_timestamp");
+        let payload: Vec<u8> = row.get("payload");
+
+        let fields = match decoder::decode(&payload) {
+            Some(AdsbMessage::Identification { callsign }) => DecodedFields {
+                callsign: Some(callsign),
+                ..Default::default()
+            },
+            Some(AdsbMessage::AirborneVelocity {
+                ground_speed,
+                heading,
+            }) => DecodedFields {
+                ground_speed: Some(ground_speed),
+                heading: Some(heading),
+                ..Default::default()
+            },
+            Some(AdsbMessage::AirbornePosition { baro_altitude, cpr }) => {
+                let query = get_cpr_pair_query();
+                debug!("(update_decoded_fields) [{}].", query);
+                let rows = transaction
+                    .query(&query, &[&icao_address, id, &network_timestamp])
+                    .await?;
+                let frames: Vec<(DateTime<Utc>, Vec<u8>)> = rows
+                    .iter()
+                    .map(|row| (row.get("network_timestamp"), row.get("payload")))
+                    .collect();
+                DecodedFields {
+                    position: get_cpr_pair_position(&cpr, &network_timestamp, &frames),
+                    baro_altitude,
+                    ..Default::default()
+                }
+            }
+            None => {
+                debug!(
+                    "(update_decoded_fields) Could not decode payload of adsb [{}].",
+                    id
+                );
+                return Ok(());
+            }
+        };
+
+        let query = get_update_decoded_fields_query();
+        debug!("(update_decoded_fields) [{}].", query);
+        transaction
+            .execute(
+                &query,
+                &[
+                    id,
+                    &fields.position.as_ref().map(get_point_wkt),
+                    &fields.baro_altitude,
+                    &fields.ground_speed,
+                    &fields.heading,
+                    &fields.callsign,
+                ],
+            )
+            .await?;
+        Ok(())
+    })
+}
+
+/// Decodes the position of the provided CPR frame received at `timestamp`, using the closest
+/// (in time) opposite frame of the provided `(network_timestamp, payload)` frames.
+///
+/// The position is always decoded for the provided frame's own parity, so every record gets the
+/// position at its own time, regardless of the order in which the frames of a pair were received.
+///
+/// Returns [`None`] if none of the frames is an opposite frame received within [`CPR_MAX_PAIR_SECONDS`],
+/// or if the position could not be decoded from the pair.
+fn get_cpr_pair_position(
+    cpr: &CprPosition,
+    timestamp: &DateTime<Utc>,
+    frames: &[(DateTime<Utc>, Vec<u8>)],
+) -> Option<GeoPoint> {
+    let (_, other) = frames
+        .iter()
+        .filter_map(
+            |(other_timestamp, payload)| match decoder::decode(payload) {
+                Some(AdsbMessage::AirbornePosition { cpr: other, .. }) if other.odd != cpr.odd => {
+                    Some((*other_timestamp, other))
+                }
+                _ => None,
+            },
+        )
+        .filter(|(other_timestamp, _)| {
+            (*timestamp - *other_timestamp).num_seconds().abs() <= CPR_MAX_PAIR_SECONDS
+        })
+        .min_by_key(|(other_timestamp, _)| (*timestamp - *other_timestamp).abs())?;
+
+    let (even, odd) = if cpr.odd {
+        (&other, cpr)
+    } else {
+        (cpr, &other)
+    };
+    let (latitude, longitude) = decoder::decode_cpr_position(even, odd, cpr.odd)?;
+    Some(GeoPoint {
+        longitude,
+        latitude,
+        altitude: None,
+    })
+}
+
+/// Returns the query used to get the frames of ICAO address `$1` received within
+/// [`CPR_MAX_PAIR_SECONDS`] of timestamp `$3`, ignoring adsb id `$2`
+fn get_cpr_pair_query() -> String {
+    format!(
+        r#"SELECT "network_timestamp", "payload" FROM "adsb" WHERE "icao_address" = $1 AND "adsb_id" <> $2 AND "network_timestamp" BETWEEN $3::TIMESTAMPTZ - INTERVAL '{seconds} seconds' AND $3::TIMESTAMPTZ + INTERVAL '{seconds} seconds'"#,
+        seconds = CPR_MAX_PAIR_SECONDS
+    )
+}
+
+/// Returns the query used to store the decoded fields of adsb id `$1`
+fn get_update_decoded_fields_query() -> String {
+    format!(
+        r#"UPDATE "adsb" SET "position" = ST_GeomFromText($2, {}), "baro_altitude" = $3, "ground_speed" = $4, "heading" = $5, "callsign" = $6 WHERE "adsb_id" = $1"#,
+        SRID
+    )
 }
 
 impl GrpcDataObjectType for Data {
@@ -55,6 +283,14 @@ impl GrpcDataObjectType for Data {
                 self.network_timestamp.clone(),
             ))),
             "payload" => Ok(GrpcField::Bytes(self.payload.clone())),
+            "position" => Ok(GrpcField::Option(self.position.into())),
+            "baro_altitude" => Ok(GrpcField::Option(GrpcFieldOption::I64(self.baro_altitude))),
+            "ground_speed" => Ok(GrpcField::Option(GrpcFieldOption::F64(self.ground_speed))),
+            "heading" => Ok(GrpcField::Option(GrpcFieldOption::F64(self.heading))),
+            "callsign" => Ok(GrpcField::Option(GrpcFieldOption::String(
+                self.callsign.clone(),
+            ))),
+            "receivers" => Ok(GrpcField::StringList(self.receivers.clone())),
             _ => Err(ArrErr::Error(format!(
                 "Invalid key specified [{}], no such field found",
                 key
@@ -80,6 +316,12 @@ impl TryFrom<Row> for Data {
             message_type: row.get::<&str, i64>("message_type"),
             network_timestamp,
             payload: row.get::<&str, Vec<u8>>("payload"),
+            position: row.get::<&str, Option<GeoPoint>>("position"),
+            baro_altitude: row.get::<&str, Option<i64>>("baro_altitude"),
+            ground_speed: row.get::<&str, Option<f64>>("ground_speed"),
+            heading: row.get::<&str, Option<f64>>("heading"),
+            callsign: row.get::<&str, Option<String>>("callsign"),
+            receivers: row.get::<&str, Vec<String>>("receivers"),
         })
     }
 }
@@ -88,6 +330,7 @@ impl TryFrom<Row> for Data {
 mod tests {
     use super::*;
     use crate::test_util::*;
+    use std::time::{Duration, SystemTime};
 
     #[tokio::test]
     async fn test_adsb_schema() {
@@ -125,6 +368,12 @@ mod tests {
                 nanos: -1,
             }),
             payload: vec![255, 0, 0, 0],
+            position: None,
+            baro_altitude: None,
+            ground_speed: None,
+            heading: None,
+            callsign: None,
+            receivers: vec![],
         };
 
         let result = validate::<ResourceObject<Data>>(&data);
@@ -139,4 +388,120 @@ mod tests {
         }
         ut_info!("(test_adsb_invalid_data) success");
     }
+
+    #[tokio::test]
+    async fn test_get_cpr_pair_position() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_cpr_pair_position) start");
+
+        // Decoding the CPR positions themselves is tested by the decoder,
+        // only check if the right frames are paired here.
+        let even_hex = "8D40621D58C382D690C8AC2863A7";
+        let odd_hex = "8D40621D58C386435CC412692AD6";
+        let (even, odd) = (decoder::get_frame(even_hex), decoder::get_frame(odd_hex));
+        let (even_cpr, odd_cpr) = (decoder::get_cpr(even_hex), decoder::get_cpr(odd_hex));
+        let get_time = |seconds_ago: u64| -> DateTime<Utc> {
+            (SystemTime::now() - Duration::from_secs(seconds_ago)).into()
+        };
+        let now = get_time(0);
+        let earlier = get_time(1);
+        let get_expected = |odd_is_latest: bool| {
+            decoder::decode_cpr_position(&even_cpr, &odd_cpr, odd_is_latest).map(
+                |(latitude, longitude)| GeoPoint {
+                    longitude,
+                    latitude,
+                    altitude: None,
+                },
+            )
+        };
+
+        // odd frame received last
+        let position = get_cpr_pair_position(&odd_cpr, &now, &[(earlier, even.clone())]);
+        assert!(position.is_some());
+        assert_eq!(position, get_expected(true));
+
+        // even frame received last
+        let position = get_cpr_pair_position(&even_cpr, &now, &[(earlier, odd.clone())]);
+        assert!(position.is_some());
+        assert_eq!(position, get_expected(false));
+
+        // opposite frame received after the own frame, still decoded for the own frame
+        let position = get_cpr_pair_position(&odd_cpr, &earlier, &[(now, even.clone())]);
+        assert!(position.is_some());
+        assert_eq!(position, get_expected(true));
+        let position = get_cpr_pair_position(&even_cpr, &earlier, &[(now, odd.clone())]);
+        assert!(position.is_some());
+        assert_eq!(position, get_expected(false));
+
+        // no opposite frame
+        assert_eq!(
+            get_cpr_pair_position(&even_cpr, &now, &[(earlier, even.clone())]),
+            None
+        );
+
+        // opposite frame received too long ago
+        let too_early = get_time(CPR_MAX_PAIR_SECONDS as u64 + 1);
+        assert_eq!(
+            get_cpr_pair_position(&even_cpr, &now, &[(too_early, odd.clone())]),
+            None
+        );
+
+        ut_info!("(test_get_cpr_pair_position) success");
+    }
+
+    #[tokio::test]
+    async fn test_adsb_deduplication() {
+        crate::get_log_handle().await;
+        ut_info!("(test_adsb_deduplication) start");
+
+        let deduplication = ResourceObject::<Data>::get_deduplication();
+        match get_dedup_tolerance_ms() {
+            0 => assert_eq!(deduplication, None),
+            tolerance_ms => {
+                let deduplication = deduplication.expect("Expected a deduplication");
+                assert_eq!(deduplication.key_cols, vec!["icao_address", "payload"]);
+                assert_eq!(deduplication.time_col, "network_timestamp");
+                assert_eq!(deduplication.merge_col, "receivers");
+                assert_eq!(deduplication.tolerance_ms, tolerance_ms);
+
+                // deduplication columns should be part of the definition
+                let definition = ResourceObject::<Data>::get_definition();
+                for col in deduplication
+                    .key_cols
+                    .iter()
+                    .chain([&deduplication.time_col, &deduplication.merge_col])
+                {
+                    assert!(definition.fields.contains_key(col));
+                }
+            }
+        }
+
+        ut_info!("(test_adsb_deduplication) success");
+    }
+
+    #[tokio::test]
+    async fn test_get_cpr_pair_query() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_cpr_pair_query) start");
+
+        assert_eq!(
+            get_cpr_pair_query(),
+            r#"SELECT "network_timestamp", "payload" FROM "adsb" WHERE "icao_address" = $1 AND "adsb_id" <> $2 AND "network_timestamp" BETWEEN $3::TIMESTAMPTZ - INTERVAL '10 seconds' AND $3::TIMESTAMPTZ + INTERVAL '10 seconds'"#
+        );
+
+        ut_info!("(test_get_cpr_pair_query) success");
+    }
+
+    #[tokio::test]
+    async fn test_get_update_decoded_fields_query() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_update_decoded_fields_query) start");
+
+        assert_eq!(
+            get_update_decoded_fields_query(),
+            r#"UPDATE "adsb" SET "position" = ST_GeomFromText($2, 4326), "baro_altitude" = $3, "ground_speed" = $4, "heading" = $5, "callsign" = $6 WHERE "adsb_id" = $1"#
+        );
+
+        ut_info!("(test_get_update_decoded_fields_query) success");
+    }
 }
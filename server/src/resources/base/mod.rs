@@ -7,7 +7,10 @@ pub mod simple_resource_linked;
 use crate::grpc::server::{Id, IdList, Ids};
 use crate::postgres::PsqlJsonValue;
 use crate::{common::ArrErr, grpc::GrpcDataObjectType};
+use chrono::{DateTime, Utc};
 use core::fmt::Debug;
+use deadpool_postgres::Transaction;
+use futures::future::BoxFuture;
 use log::error;
 use std::collections::HashMap;
 use tokio_postgres::types::Type as PsqlFieldType;
@@ -31,6 +34,54 @@ where
     fn get_table_indices() -> Vec<String> {
         vec![]
     }
+    /// Returns the link tables referencing this resource's id column.
+    /// Rows in these tables will be removed when a record is purged (example implementation can be found in the vertipad module).
+    fn get_link_tables() -> Vec<String> {
+        vec![]
+    }
+    /// Returns the time ranges of the resource which may not overlap with the time ranges of other records.
+    /// Will be checked on insert, update and restore (example implementation can be found in the flight_plan module).
+    fn get_range_exclusions() -> Vec<RangeExclusion> {
+        vec![]
+    }
+    /// Returns the allowed transitions of the resource's status column, if any.
+    /// Will be checked on update (example implementation can be found in the flight_plan module).
+    fn get_status_transitions() -> Option<StatusTransitions> {
+        None
+    }
+    /// Returns the status changes of related records caused by changes of this resource.
+    /// Will be applied on insert and on status transitions (example implementation can be found in the parcel_scan module).
+    fn get_status_propagations() -> Vec<StatusPropagation> {
+        vec![]
+    }
+    /// Returns the spatial relations the resource's geometry columns must have with the geometries of referenced records.
+    /// Will be checked on insert and update (example implementation can be found in the vertipad module).
+    fn get_spatial_checks() -> Vec<SpatialCheck> {
+        vec![]
+    }
+    /// Returns the spatial checks of other resources referencing the resource's geometry columns.
+    /// Will be checked on insert and update, so changing a geometry can't break the records referencing it
+    /// (example implementation can be found in the vertiport module).
+    fn get_dependent_spatial_checks() -> Vec<DependentSpatialCheck> {
+        vec![]
+    }
+    /// Returns the hook populating the resource's derived (read only) fields from the stored data, if any.
+    /// Will be called on insert and update (example implementation can be found in the adsb module).
+    fn get_derived_fields_hook() -> Option<DerivedFieldsHook> {
+        None
+    }
+    /// Returns the timestamp column used to expire the resource's records, if any.
+    /// Expired records will be removed by the database's row-level TTL job, see [`retention`](crate::postgres::retention)
+    /// (example implementation can be found in the adsb module).
+    fn get_retention_col() -> Option<String> {
+        None
+    }
+    /// Returns the duplicate detection of the resource's records, if any.
+    /// Duplicates will be merged into the existing record on insert (example implementation can be found in the adsb module).
+    fn get_deduplication() -> Option<Deduplication> {
+        None
+    }
+
     /// Returns `true` if the given column name is part of the resource's combined id
     fn has_id_col(id_col: &str) -> bool {
         for col in Self::get_definition().get_psql_id_cols() {
@@ -64,6 +115,12 @@ where
     fn set_ids(&mut self, ids: HashMap<String, String>);
     /// Set [`ObjectType<T>`]'s `data` field, to be implemented by trait implementor
     fn set_data(&mut self, data: T);
+    /// Get [`ObjectType<T>`]'s `mask` field, to be implemented by trait implementor
+    fn get_mask(&self) -> Option<::prost_types::FieldMask> {
+        None
+    }
+    /// Set [`ObjectType<T>`]'s `mask` field, to be implemented by trait implementor
+    fn set_mask(&mut self, mask: Option<::prost_types::FieldMask>);
 
     /// Returns [`ObjectType<T>`]'s `data` [`GrpcDataObjectType`] value
     ///
@@ -157,6 +214,231 @@ impl ResourceDefinition {
     }
 }
 
+/// Time range of a resource, claimed for the value of its key column
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TimeRange {
+    /// psql column name of the key claiming the time range
+    pub key_col: String,
+    /// psql column name of the time range start (inclusive)
+    pub start_col: String,
+    /// psql column name of the time range end (exclusive)
+    pub end_col: String,
+}
+
+impl TimeRange {
+    /// Creates a new [`TimeRange`] using the provided column names
+    pub fn new(key_col: &str, start_col: &str, end_col: &str) -> Self {
+        Self {
+            key_col: key_col.to_owned(),
+            start_col: start_col.to_owned(),
+            end_col: end_col.to_owned(),
+        }
+    }
+}
+
+/// Emulates a range exclusion constraint, which is not supported by all database backends.
+///
+/// The time ranges of a record may not overlap with any of the time ranges of another record
+/// if they share the same key value. Records with a `deleted_at` value and records
+/// matching any of the `ignore` values are not taken into account.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RangeExclusion {
+    /// time ranges sharing the same key domain
+    pub ranges: Vec<TimeRange>,
+    /// psql column names with values for which records should be ignored
+    pub ignore: Vec<(String, Vec<String>)>,
+}
+
+/// Time range claimed by a record for a [`RangeExclusion`] key
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RangeClaim {
+    /// id of the record claiming the time range
+    pub id: Uuid,
+    /// start of the claimed time range
+    pub start: DateTime<Utc>,
+    /// end of the claimed time range
+    pub end: DateTime<Utc>,
+}
+
+/// Lifecycle of a resource's status column.
+///
+/// Updates changing the status to a value which is not listed as an allowed transition
+/// for the current status will be rejected. Keeping the current status is always allowed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StatusTransitions {
+    /// psql column name of the status
+    pub status_col: String,
+    /// status values with the status values they can transition to
+    pub transitions: HashMap<String, Vec<String>>,
+    /// status values with the psql column name of the timestamp to set when transitioning to the status
+    pub stamps: HashMap<String, String>,
+}
+
+impl StatusTransitions {
+    /// Returns `true` if the status can transition from the `from` value to the `to` value
+    pub fn is_allowed(&self, from: &str, to: &str) -> bool {
+        from == to
+            || self
+                .transitions
+                .get(from)
+                .map_or(false, |allowed| allowed.iter().any(|value| value == to))
+    }
+
+    /// Returns the status values the `from` value can transition to
+    pub fn get_allowed(&self, from: &str) -> Vec<String> {
+        self.transitions.get(from).cloned().unwrap_or_default()
+    }
+}
+
+/// Change of a resource record causing a [`StatusPropagation`] to be applied
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PropagationTrigger {
+    /// a new record has been inserted
+    Insert,
+    /// the status of the record transitioned to the provided value
+    Status(String),
+}
+
+/// Advances the status of related records when a record of the resource is inserted or changes status.
+///
+/// Related records are selected using the `target_ids_query`, which gets the id of the changed
+/// record as parameter `$1`. Only related records with a current status listed in `advance` will be
+/// changed, and only if the new status is allowed by the `target_transitions`. This makes sure the
+/// status of a related record can never regress.
+#[derive(Clone, Debug)]
+pub struct StatusPropagation {
+    /// change of the record which should be propagated
+    pub trigger: PropagationTrigger,
+    /// resource definition of the related records
+    pub target: ResourceDefinition,
+    /// status lifecycle of the related records
+    pub target_transitions: StatusTransitions,
+    /// query returning the ids of the related records for the changed record id `$1`
+    pub target_ids_query: String,
+    /// current status values of the related records with the status value they should advance to
+    pub advance: HashMap<String, String>,
+}
+
+/// Spatial relation between a geometry of a resource and the geometry of a referenced record
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpatialRelation {
+    /// the geometry must lie inside the referenced geometry (boundary included)
+    Within,
+    /// the first point of the path must lie within the configured tolerance of the referenced geometry
+    StartsAt,
+    /// the last point of the path must lie within the configured tolerance of the referenced geometry
+    EndsAt,
+}
+
+/// Spatial consistency check between a geometry column of a resource and the geometry column
+/// of the record referenced by one of its key columns.
+///
+/// The check is run using PostGIS after a record has been written, using the same transaction.
+/// Records without a geometry or reference value are not checked.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SpatialCheck {
+    /// psql column name of the resource's geometry
+    pub col: String,
+    /// relation the geometry must have with the referenced geometry
+    pub relation: SpatialRelation,
+    /// psql column name of the resource's key referencing the other record
+    pub ref_key_col: String,
+    /// psql table name of the referenced record
+    pub ref_table: String,
+    /// psql column name of the referenced record's id
+    pub ref_id_col: String,
+    /// psql column name of the referenced record's geometry
+    pub ref_col: String,
+}
+
+impl SpatialCheck {
+    /// Creates a new [`SpatialCheck`] for the provided `col` and `relation`, referencing the `ref_col`
+    /// geometry of the `ref_table` record matching the `ref_key_col` value
+    pub fn new(
+        col: &str,
+        relation: SpatialRelation,
+        ref_key_col: &str,
+        ref_table: &str,
+        ref_id_col: &str,
+        ref_col: &str,
+    ) -> Self {
+        Self {
+            col: col.to_owned(),
+            relation,
+            ref_key_col: ref_key_col.to_owned(),
+            ref_table: ref_table.to_owned(),
+            ref_id_col: ref_id_col.to_owned(),
+            ref_col: ref_col.to_owned(),
+        }
+    }
+}
+
+/// [`SpatialCheck`] of another resource referencing the geometry of this resource.
+///
+/// The check is run from the referenced side after a referenced record has been written, so the
+/// referencing records (which are not soft-deleted) still pass their own [`SpatialCheck`].
+#[derive(Clone, Debug)]
+pub struct DependentSpatialCheck {
+    /// resource definition of the referencing records
+    pub source: ResourceDefinition,
+    /// spatial check of the referencing records
+    pub check: SpatialCheck,
+}
+
+impl DependentSpatialCheck {
+    /// Returns the [`SpatialCheck`]s of resource `T` referencing records of the `ref_table`
+    pub fn from_resource<T: Resource>(ref_table: &str) -> Vec<Self> {
+        T::get_spatial_checks()
+            .into_iter()
+            .filter(|check| check.ref_table == ref_table)
+            .map(|check| Self {
+                source: T::get_definition(),
+                check,
+            })
+            .collect()
+    }
+}
+
+/// Duplicate detection of a resource's records
+///
+/// A record is a duplicate of an existing record if the values of all `key_cols` are equal and
+/// their `time_col` values are at most `tolerance_ms` milliseconds apart.
+/// Instead of being inserted, the duplicate is merged into the existing record, keeping the earliest
+/// `time_col` value and the distinct values of both `merge_col` arrays.
+///
+/// The resource's table needs a nullable `dedup_bucket` INT8 column with a unique index on the `key_cols`
+/// and `dedup_bucket`, so duplicates inserted concurrently can't both be stored.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Deduplication {
+    /// psql column names which values must be equal for records to be duplicates
+    pub key_cols: Vec<String>,
+    /// psql column name of the (mandatory) timestamp compared using the tolerance
+    pub time_col: String,
+    /// psql column name of the (mandatory) text array combining the values of all duplicates
+    pub merge_col: String,
+    /// maximum difference in milliseconds between the `time_col` values of duplicates
+    pub tolerance_ms: u64,
+}
+
+impl Deduplication {
+    /// Creates a new [`Deduplication`] of records with equal `key_cols` and `time_col` values within `tolerance_ms`,
+    /// merging their `merge_col` values
+    pub fn new(key_cols: &[&str], time_col: &str, merge_col: &str, tolerance_ms: u64) -> Self {
+        Self {
+            key_cols: key_cols.iter().map(|col| col.to_string()).collect(),
+            time_col: time_col.to_owned(),
+            merge_col: merge_col.to_owned(),
+            tolerance_ms,
+        }
+    }
+}
+
+/// Function populating the derived fields of the record matching the provided id.
+///
+/// The hook is called after a record has been inserted, using the same transaction.
+pub type DerivedFieldsHook =
+    for<'a> fn(&'a Transaction<'a>, &'a Uuid) -> BoxFuture<'a, Result<(), ArrErr>>;
+
 /// Generic resource wrapper struct used to implement our generic traits
 #[derive(Clone, Debug)]
 pub struct ResourceObject<T>
@@ -186,6 +468,12 @@ where
     fn set_data(&mut self, data: T) {
         self.data = Some(data)
     }
+    fn get_mask(&self) -> Option<::prost_types::FieldMask> {
+        self.mask.clone()
+    }
+    fn set_mask(&mut self, mask: Option<::prost_types::FieldMask>) {
+        self.mask = mask
+    }
 }
 
 /// Field definition struct defining field properties
@@ -201,6 +489,8 @@ pub struct FieldDefinition {
     read_only: bool,
     /// [`String`] option to provide a default value used during database inserts
     default: Option<String>,
+    /// [`bool`] to set if numeric field values should be greater than zero
+    positive: bool,
 }
 
 impl FieldDefinition {
@@ -212,6 +502,7 @@ impl FieldDefinition {
             internal: false,
             read_only: false,
             default: None,
+            positive: false,
         }
     }
     /// Create a new internal [`FieldDefinition`] with provided field_type and mandatory setting
@@ -222,6 +513,7 @@ impl FieldDefinition {
             internal: true,
             read_only: true,
             default: None,
+            positive: false,
         }
     }
     /// Create a new read_only [`FieldDefinition`] with provided field_type and mandatory setting
@@ -232,6 +524,7 @@ impl FieldDefinition {
             internal: false,
             read_only: true,
             default: None,
+            positive: false,
         }
     }
 
@@ -257,6 +550,15 @@ impl FieldDefinition {
         self.default = Some(default);
         self.clone()
     }
+    /// Returns [`bool`] positive
+    pub fn is_positive(&self) -> bool {
+        self.positive
+    }
+    /// Requires numeric values of this field to be greater than zero
+    pub fn set_positive(&mut self) -> Self {
+        self.positive = true;
+        self.clone()
+    }
     /// Gets the `default` value for this field
     ///
     /// The function will panic if no default has been set. It's recommended to call
@@ -387,6 +689,19 @@ mod tests {
         assert_eq!(field_def.get_default(), default_value);
     }
 
+    #[test]
+    fn test_field_definition_set_positive() {
+        let field_type = PsqlFieldType::FLOAT8;
+        let mandatory = false;
+        let mut field_def = FieldDefinition::new(field_type, mandatory);
+
+        assert!(!field_def.is_positive());
+
+        field_def.set_positive();
+
+        assert!(field_def.is_positive());
+    }
+
     #[test]
     #[should_panic(expected = "get_default called on a field without a default value")]
     fn test_field_definition_get_default_without_default() {
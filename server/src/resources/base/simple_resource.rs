@@ -14,6 +14,7 @@ pub use super::{ObjectType, Resource, ResourceObject};
 pub use crate::postgres::init::PsqlInitResource;
 pub use crate::postgres::init::PsqlInitSimpleResource;
 pub use crate::postgres::simple_resource::*;
+pub use crate::postgres::watch::PsqlWatch;
 pub use crate::postgres::PsqlSearch;
 
 /// Generic trait providing specific functions for our `simple` resources
@@ -102,6 +103,8 @@ where
     pub resource: Option<T>,
     /// [`ValidationResult`] returned from the update action
     pub validation_result: ValidationResult,
+    /// `true` if the provided data was merged into an existing duplicate record instead of being inserted
+    pub deduplicated: bool,
 }
 
 impl<T> From<Id> for ResourceObject<T>
@@ -146,6 +149,7 @@ where
 macro_rules! build_grpc_simple_resource_impl {
     ($resource:tt) => {
         impl PsqlSearch for ResourceObject<Data> {}
+        impl PsqlWatch for ResourceObject<Data> {}
         impl PsqlInitSimpleResource for ResourceObject<Data> {}
         impl PsqlInitResource for ResourceObject<Data> {
             fn _get_create_table_query() -> String {
@@ -153,6 +157,18 @@ macro_rules! build_grpc_simple_resource_impl {
             }
         }
 
+        impl TryFrom<Row> for Object {
+            type Error = ArrErr;
+
+            fn try_from(row: Row) -> Result<Self, ArrErr> {
+                let id: Uuid = row.get(format!("{}_id", stringify!($resource)).as_str());
+                Ok(Object {
+                    id: id.to_string(),
+                    data: Some(row.try_into()?),
+                })
+            }
+        }
+
         impl TryFrom<Vec<Row>> for List {
             type Error = ArrErr;
 
@@ -161,23 +177,79 @@ macro_rules! build_grpc_simple_resource_impl {
                 let mut res: Vec<Object> = Vec::with_capacity(rows.len());
 
                 for row in rows.into_iter() {
-                    let id: Uuid = row.get(format!("{}_id", stringify!($resource)).as_str());
-                    let converted = Object {
-                        id: id.to_string(),
-                        data: Some(row.try_into()?),
-                    };
-                    res.push(converted);
+                    res.push(row.try_into()?);
+                }
+                let total_count = res.len() as i64;
+                Ok(List {
+                    list: res,
+                    total_count,
+                    next_page_token: String::new(),
+                    distances: vec![],
+                })
+            }
+        }
+
+        impl $crate::grpc::GrpcListType for List {
+            fn set_total_count(&mut self, total_count: i64) {
+                self.total_count = total_count;
+            }
+            fn set_next_page_token(&mut self, next_page_token: String) {
+                self.next_page_token = next_page_token;
+            }
+            fn set_distances(&mut self, distances: Vec<f64>) {
+                self.distances = distances;
+            }
+        }
+
+        impl $crate::grpc::GrpcBatchRequestType<Data> for InsertManyRequest {
+            fn get_mode(&self) -> i32 {
+                self.mode
+            }
+            fn into_list(self) -> Vec<Data> {
+                self.list
+            }
+        }
+
+        impl $crate::grpc::GrpcBatchRequestType<UpdateObject> for UpdateManyRequest {
+            fn get_mode(&self) -> i32 {
+                self.mode
+            }
+            fn into_list(self) -> Vec<UpdateObject> {
+                self.list
+            }
+        }
+
+        impl $crate::grpc::GrpcBatchResponseType<Response> for ResponseList {
+            fn from_list(list: Vec<Response>, success: bool) -> Self {
+                ResponseList { success, list }
+            }
+        }
+
+        impl $crate::grpc::GrpcChangeEventType<Object> for ChangeEvent {
+            fn from_change(
+                sequence: i64,
+                change_type: $crate::grpc::server::ChangeType,
+                id: String,
+                object: Option<Object>,
+            ) -> Self {
+                ChangeEvent {
+                    sequence,
+                    change_type: change_type as i32,
+                    id,
+                    object,
                 }
-                Ok(List { list: res })
             }
         }
     };
 }
 
 /// Generates `From` trait implementations for [`ResourceObject<Data>`] into and from Grpc defined Resource.
+///
+/// Additional [`GenericResourceResult`] fields which are part of the resource's `Response` can be provided,
+/// for example `build_generic_resource_impl_from!(deduplicated)`.
 #[macro_export]
 macro_rules! build_generic_resource_impl_from {
-    () => {
+    ($($response_field:ident),*) => {
         impl From<Object> for ResourceObject<Data> {
             fn from(obj: Object) -> Self {
                 let id_field = match Self::try_get_id_field() {
@@ -241,6 +313,7 @@ macro_rules! build_generic_resource_impl_from {
                 Self {
                     validation_result: Some(obj.validation_result),
                     object: res,
+                    $($response_field: obj.$response_field,)*
                 }
             }
         }
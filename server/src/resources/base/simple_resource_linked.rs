@@ -96,7 +96,13 @@ macro_rules! build_grpc_simple_resource_linked_impl {
                     };
                     res.push(converted);
                 }
-                Ok(List { list: res })
+                let total_count = res.len() as i64;
+                Ok(List {
+                    list: res,
+                    total_count,
+                    next_page_token: String::new(),
+                    distances: vec![],
+                })
             }
         }
 
@@ -110,7 +116,37 @@ macro_rules! build_grpc_simple_resource_linked_impl {
                 for row in rows.into_iter() {
                     res.push(row.try_into()?);
                 }
-                Ok(RowDataList { list: res })
+                let total_count = res.len() as i64;
+                Ok(RowDataList {
+                    list: res,
+                    total_count,
+                    next_page_token: String::new(),
+                    distances: vec![],
+                })
+            }
+        }
+
+        impl $crate::grpc::GrpcListType for List {
+            fn set_total_count(&mut self, total_count: i64) {
+                self.total_count = total_count;
+            }
+            fn set_next_page_token(&mut self, next_page_token: String) {
+                self.next_page_token = next_page_token;
+            }
+            fn set_distances(&mut self, distances: Vec<f64>) {
+                self.distances = distances;
+            }
+        }
+
+        impl $crate::grpc::GrpcListType for RowDataList {
+            fn set_total_count(&mut self, total_count: i64) {
+                self.total_count = total_count;
+            }
+            fn set_next_page_token(&mut self, next_page_token: String) {
+                self.next_page_token = next_page_token;
+            }
+            fn set_distances(&mut self, distances: Vec<f64>) {
+                self.distances = distances;
             }
         }
     };
@@ -0,0 +1,180 @@
+//! Flight plan conflicts
+//!
+//! Finds flight plans passing close to a proposed path during (part of) a time window,
+//! using the `path` geometry and the timeslots of the `flight_plan` table.
+
+use super::{ConflictingFlightPlan, Data, FlightStatus, Object};
+use crate::common::ArrErr;
+use crate::grpc::server::grpc_geo_types::GeoLineString;
+use crate::postgres::get_psql_client;
+use crate::postgres::postgis::{get_line_string_wkt, SRID};
+
+use chrono::{DateTime, Utc};
+use log::debug;
+use prost_wkt_types::Timestamp;
+use uuid::Uuid;
+
+/// Returns all flight plans passing within `min_separation_meters` of the provided path during
+/// (part of) the provided window, ordered by their distance.
+///
+/// Flight plans are active from the start of their origin timeslot until the end of their target timeslot.
+/// If the provided path or a flight plan's path has times, only the sections passed during the same time are compared.
+/// Cancelled and deleted flight plans are ignored.
+///
+/// An `exclude_id` can be provided to ignore a flight plan, for example when it's being rescheduled.
+///
+/// # Errors
+///
+/// Returns [`ArrErr`] if a returned row could not be converted to flight_plan [`Data`]
+/// Returns [`ArrErr`] from [`PoolError`](deadpool::managed::PoolError) if no client connection could be returned from the connection [`Pool`](deadpool::managed::Pool)
+/// Returns [`ArrErr`] Database Error if database query execution failed
+pub async fn find_conflicting_flight_plans(
+    path: &GeoLineString,
+    window_start: &DateTime<Utc>,
+    window_end: &DateTime<Utc>,
+    min_separation_meters: f64,
+    exclude_id: Option<&Uuid>,
+) -> Result<Vec<ConflictingFlightPlan>, ArrErr> {
+    let (start, end): (Timestamp, Timestamp) = ((*window_start).into(), (*window_end).into());
+    let section = path.get_section(&start, &end);
+    if section.points.is_empty() {
+        debug!("(find_conflicting_flight_plans) Path is not flown during the provided window.");
+        return Ok(vec![]);
+    }
+
+    let client = get_psql_client().await?;
+    let query = get_candidates_query();
+    debug!("(find_conflicting_flight_plans) [{}].", query);
+    let stmt = client.prepare_cached(&query).await?;
+    let rows = client
+        .query(
+            &stmt,
+            &[
+                &get_line_string_wkt(&section),
+                window_start,
+                window_end,
+                &min_separation_meters,
+                &exclude_id,
+            ],
+        )
+        .await?;
+
+    let query = get_distance_query();
+    let mut conflicts: Vec<ConflictingFlightPlan> = vec![];
+    for row in rows {
+        let id: Uuid = row.get("flight_plan_id");
+        let mut distance_meters: f64 = row.get("distance_meters");
+        let data = Data::try_from(row)?;
+        let other = data.path.clone().unwrap_or_default();
+
+        // Paths with times are only compared during the time both are flown
+        if section.has_times() || other.has_times() {
+            let (own, other) = match section.get_concurrent_sections(&other, &start, &end) {
+                Some(sections) => sections,
+                None => continue,
+            };
+            debug!("(find_conflicting_flight_plans) [{}].", query);
+            let row = client
+                .query_one(
+                    &query,
+                    &[&get_line_string_wkt(&own), &get_line_string_wkt(&other)],
+                )
+                .await?;
+            distance_meters = row.get("distance_meters");
+            if distance_meters > min_separation_meters {
+                continue;
+            }
+        }
+
+        conflicts.push(ConflictingFlightPlan {
+            flight_plan: Some(Object {
+                id: id.to_string(),
+                data: Some(data),
+            }),
+            distance_meters,
+        });
+    }
+
+    sort_conflicts(&mut conflicts);
+    Ok(conflicts)
+}
+
+/// Orders the provided conflicts by their distance, closest first
+pub fn sort_conflicts(conflicts: &mut [ConflictingFlightPlan]) {
+    conflicts.sort_by(|a, b| a.distance_meters.total_cmp(&b.distance_meters));
+}
+
+/// Returns the SQL expression used to convert the provided geometry to a 2D geography,
+/// making sure distances are calculated in meters
+fn get_geography_sql(geometry: &str) -> String {
+    format!("ST_Force2D({})::GEOGRAPHY", geometry)
+}
+
+/// Returns the query used to find all active flight plans passing within distance `$4` (meters)
+/// of the path WKT `$1` during the window provided as `$2` (start) and `$3` (end),
+/// ignoring the flight plan matching id `$5` if not `NULL`
+fn get_candidates_query() -> String {
+    let path = get_geography_sql(r#""path""#);
+    let other = get_geography_sql(&format!("ST_GeomFromText($1, {})", SRID));
+    format!(
+        r#"SELECT *, ST_Distance({path}, {other}) AS "distance_meters" FROM "flight_plan" WHERE "deleted_at" IS NULL AND "flight_status"::TEXT <> '{}' AND ($5::UUID IS NULL OR "flight_plan_id" <> $5::UUID) AND "origin_timeslot_start" < $3::TIMESTAMPTZ AND "target_timeslot_end" > $2::TIMESTAMPTZ AND ST_DWithin({path}, {other}, $4::FLOAT8)"#,
+        FlightStatus::Cancelled.as_str_name()
+    )
+}
+
+/// Returns the query used to get the distance in meters between the geometry WKTs `$1` and `$2`
+fn get_distance_query() -> String {
+    format!(
+        r#"SELECT ST_Distance({}, {}) AS "distance_meters""#,
+        get_geography_sql(&format!("ST_GeomFromText($1, {})", SRID)),
+        get_geography_sql(&format!("ST_GeomFromText($2, {})", SRID))
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_get_candidates_query() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_candidates_query) start");
+
+        assert_eq!(
+            get_candidates_query(),
+            r#"SELECT *, ST_Distance(ST_Force2D("path")::GEOGRAPHY, ST_Force2D(ST_GeomFromText($1, 4326))::GEOGRAPHY) AS "distance_meters" FROM "flight_plan" WHERE "deleted_at" IS NULL AND "flight_status"::TEXT <> 'CANCELLED' AND ($5::UUID IS NULL OR "flight_plan_id" <> $5::UUID) AND "origin_timeslot_start" < $3::TIMESTAMPTZ AND "target_timeslot_end" > $2::TIMESTAMPTZ AND ST_DWithin(ST_Force2D("path")::GEOGRAPHY, ST_Force2D(ST_GeomFromText($1, 4326))::GEOGRAPHY, $4::FLOAT8)"#
+        );
+
+        ut_info!("(test_get_candidates_query) success");
+    }
+
+    #[tokio::test]
+    async fn test_get_distance_query() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_distance_query) start");
+
+        assert_eq!(
+            get_distance_query(),
+            r#"SELECT ST_Distance(ST_Force2D(ST_GeomFromText($1, 4326))::GEOGRAPHY, ST_Force2D(ST_GeomFromText($2, 4326))::GEOGRAPHY) AS "distance_meters""#
+        );
+
+        ut_info!("(test_get_distance_query) success");
+    }
+
+    #[tokio::test]
+    async fn test_sort_conflicts() {
+        crate::get_log_handle().await;
+        ut_info!("(test_sort_conflicts) start");
+
+        let conflict = |distance_meters: f64| ConflictingFlightPlan {
+            flight_plan: None,
+            distance_meters,
+        };
+        let mut conflicts = vec![conflict(20.0), conflict(0.0), conflict(5.5)];
+        sort_conflicts(&mut conflicts);
+        let distances: Vec<f64> = conflicts.iter().map(|c| c.distance_meters).collect();
+        assert_eq!(distances, vec![0.0, 5.5, 20.0]);
+
+        ut_info!("(test_sort_conflicts) success");
+    }
+}
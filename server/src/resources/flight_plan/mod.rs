@@ -1,6 +1,7 @@
 //! Flight Plans
 
 pub use crate::grpc::server::flight_plan::*;
+pub mod conflict;
 pub mod parcel;
 
 use anyhow::{Context, Result};
@@ -13,11 +14,21 @@ use tokio_postgres::types::Type as PsqlFieldType;
 use uuid::Uuid;
 
 use super::base::simple_resource::*;
-use super::base::{FieldDefinition, ResourceDefinition};
+use super::base::{
+    FieldDefinition, PropagationTrigger, RangeClaim, RangeExclusion, ResourceDefinition,
+    SpatialCheck, SpatialRelation, StatusPropagation, StatusTransitions, TimeRange,
+};
 use crate::common::ArrErr;
 use crate::grpc::get_runtime_handle;
 use crate::grpc::{GrpcDataObjectType, GrpcField, GrpcFieldOption};
+use crate::postgres::exclusion::{get_range_claims, get_range_conflicts};
+use crate::postgres::transition::transition_status;
+use crate::resources::parcel::{self, ParcelStatus};
 use crate::resources::vertipad;
+use crate::resources::ValidationResult;
+
+// Flight status lifecycle shared with the client
+include!("../../../../includes/flight_plan/status.rs");
 
 // Generate `From` trait implementations for GenericResource into and from Grpc defined Resource
 crate::build_generic_resource_impl_from!();
@@ -148,8 +159,222 @@ impl Resource for ResourceObject<Data> {
             r#"ALTER TABLE "flight_plan" ADD CONSTRAINT fk_target_vertipad_id FOREIGN KEY("target_vertipad_id") REFERENCES "vertipad"("vertipad_id")"#.to_string(),
             r#"CREATE INDEX IF NOT EXISTS flight_plan_flight_status_idx ON "flight_plan" ("flight_status")"#.to_string(),
             r#"CREATE INDEX IF NOT EXISTS flight_plan_flight_priority_idx ON "flight_plan" ("flight_priority")"#.to_string(),
+            r#"CREATE INDEX IF NOT EXISTS flight_plan_origin_timeslot_idx ON "flight_plan" ("origin_vertipad_id", "origin_timeslot_start", "origin_timeslot_end")"#.to_string(),
+            r#"CREATE INDEX IF NOT EXISTS flight_plan_target_timeslot_idx ON "flight_plan" ("target_vertipad_id", "target_timeslot_start", "target_timeslot_end")"#.to_string(),
+            r#"CREATE INDEX IF NOT EXISTS flight_plan_vehicle_timeslot_idx ON "flight_plan" ("vehicle_id", "origin_timeslot_start", "target_timeslot_end")"#.to_string(),
+            r#"CREATE INDEX IF NOT EXISTS flight_plan_pilot_timeslot_idx ON "flight_plan" ("pilot_id", "origin_timeslot_start", "target_timeslot_end")"#.to_string(),
         ].to_vec()
     }
+
+    fn get_range_exclusions() -> Vec<RangeExclusion> {
+        vec![
+            get_vertipad_exclusion(),
+            get_asset_exclusion(AssetType::Vehicle),
+            get_asset_exclusion(AssetType::Pilot),
+        ]
+    }
+
+    fn get_status_transitions() -> Option<StatusTransitions> {
+        Some(get_flight_status_transitions())
+    }
+
+    /// Parcels acquired by the flight plan are en route once the flight plan is in flight.
+    /// Parcels delivered by the flight plan have arrived once the flight plan is finished.
+    fn get_status_propagations() -> Vec<StatusPropagation> {
+        vec![
+            parcel::get_parcel_status_propagation(
+                PropagationTrigger::Status(FlightStatus::InFlight.as_str_name().to_owned()),
+                r#"SELECT "parcel_id" FROM "flight_plan_parcel" WHERE "flight_plan_id" = $1 AND "acquire""#,
+                &[
+                    (ParcelStatus::Notdroppedoff, ParcelStatus::Enroute),
+                    (ParcelStatus::Droppedoff, ParcelStatus::Enroute),
+                ],
+            ),
+            parcel::get_parcel_status_propagation(
+                PropagationTrigger::Status(FlightStatus::Finished.as_str_name().to_owned()),
+                r#"SELECT "parcel_id" FROM "flight_plan_parcel" WHERE "flight_plan_id" = $1 AND "deliver""#,
+                &[
+                    (ParcelStatus::Notdroppedoff, ParcelStatus::Arrived),
+                    (ParcelStatus::Droppedoff, ParcelStatus::Arrived),
+                    (ParcelStatus::Enroute, ParcelStatus::Arrived),
+                ],
+            ),
+        ]
+    }
+
+    /// The path of a flight plan must start at its origin vertipad and end at its target vertipad.
+    fn get_spatial_checks() -> Vec<SpatialCheck> {
+        vec![
+            SpatialCheck::new(
+                "path",
+                SpatialRelation::StartsAt,
+                "origin_vertipad_id",
+                "vertipad",
+                "vertipad_id",
+                "geo_location",
+            ),
+            SpatialCheck::new(
+                "path",
+                SpatialRelation::EndsAt,
+                "target_vertipad_id",
+                "vertipad",
+                "vertipad_id",
+                "geo_location",
+            ),
+        ]
+    }
+
+    fn get_link_tables() -> Vec<String> {
+        [
+            "flight_plan_parcel".to_owned(),
+            "itinerary_flight_plan".to_owned(),
+        ]
+        .to_vec()
+    }
+}
+
+/// Returns the [`StatusTransitions`] for the `flight_status` column, based on [`FlightStatus::get_allowed_transitions`].
+///
+/// The `actual_departure_time` will be set when a flight plan transitions to [`FlightStatus::InFlight`],
+/// the `actual_arrival_time` will be set when a flight plan transitions to [`FlightStatus::Finished`].
+pub fn get_flight_status_transitions() -> StatusTransitions {
+    let all = [
+        FlightStatus::Draft,
+        FlightStatus::Ready,
+        FlightStatus::Boarding,
+        FlightStatus::InFlight,
+        FlightStatus::Finished,
+        FlightStatus::Cancelled,
+    ];
+    StatusTransitions {
+        status_col: String::from("flight_status"),
+        transitions: all
+            .iter()
+            .map(|status| {
+                (
+                    status.as_str_name().to_owned(),
+                    status
+                        .get_allowed_transitions()
+                        .iter()
+                        .map(|allowed| allowed.as_str_name().to_owned())
+                        .collect(),
+                )
+            })
+            .collect(),
+        stamps: HashMap::from([
+            (
+                FlightStatus::InFlight.as_str_name().to_owned(),
+                String::from("actual_departure_time"),
+            ),
+            (
+                FlightStatus::Finished.as_str_name().to_owned(),
+                String::from("actual_arrival_time"),
+            ),
+        ]),
+    }
+}
+
+/// Changes the [`FlightStatus`] of the flight plan with the provided id, recording who made the change.
+///
+/// Returns a [`ValidationResult`] containing the reasons why the transition was rejected, if any.
+pub async fn transition_flight_status(
+    id: &Uuid,
+    flight_status: FlightStatus,
+    changed_by: &Uuid,
+) -> Result<ValidationResult, ArrErr> {
+    transition_status::<ResourceObject<Data>>(
+        id,
+        flight_status.as_str_name().to_owned(),
+        changed_by,
+    )
+    .await
+}
+
+/// Returns the [`RangeExclusion`] making sure a vertipad can only be claimed by a single flight plan at a time.
+///
+/// Both the origin and target timeslots are taken into account, as a vertipad can be used as origin
+/// by one flight plan and as target by another. Cancelled flight plans do not claim any timeslots.
+pub fn get_vertipad_exclusion() -> RangeExclusion {
+    RangeExclusion {
+        ranges: vec![
+            TimeRange::new(
+                "origin_vertipad_id",
+                "origin_timeslot_start",
+                "origin_timeslot_end",
+            ),
+            TimeRange::new(
+                "target_vertipad_id",
+                "target_timeslot_start",
+                "target_timeslot_end",
+            ),
+        ],
+        ignore: vec![(
+            String::from("flight_status"),
+            vec![FlightStatus::Cancelled.as_str_name().to_owned()],
+        )],
+    }
+}
+
+/// Returns the ids of all flight plans claiming the provided vertipad during (part of) the provided timeslot.
+///
+/// An `exclude_id` can be provided to ignore a flight plan, for example when it's being rescheduled.
+pub async fn get_vertipad_conflicts(
+    vertipad_id: &Uuid,
+    timeslot_start: &DateTime<Utc>,
+    timeslot_end: &DateTime<Utc>,
+    exclude_id: Option<&Uuid>,
+) -> Result<Vec<Uuid>, ArrErr> {
+    get_range_conflicts::<ResourceObject<Data>>(
+        &get_vertipad_exclusion(),
+        vertipad_id,
+        timeslot_start,
+        timeslot_end,
+        exclude_id,
+    )
+    .await
+}
+
+/// Returns the [`RangeExclusion`] making sure a vehicle or pilot can only be assigned to a single flight plan at a time.
+///
+/// The asset is claimed from the start of the origin timeslot until the end of the target timeslot.
+/// Cancelled and finished flight plans do not claim any assets.
+pub fn get_asset_exclusion(asset_type: AssetType) -> RangeExclusion {
+    let key_col = match asset_type {
+        AssetType::Vehicle => "vehicle_id",
+        AssetType::Pilot => "pilot_id",
+    };
+    RangeExclusion {
+        ranges: vec![TimeRange::new(
+            key_col,
+            "origin_timeslot_start",
+            "target_timeslot_end",
+        )],
+        ignore: vec![(
+            String::from("flight_status"),
+            vec![
+                FlightStatus::Cancelled.as_str_name().to_owned(),
+                FlightStatus::Finished.as_str_name().to_owned(),
+            ],
+        )],
+    }
+}
+
+/// Returns the time ranges the provided vehicle or pilot is committed to, overlapping with the provided window.
+///
+/// The time ranges are ordered by their start time.
+pub async fn get_committed_time_ranges(
+    asset_type: AssetType,
+    asset_id: &Uuid,
+    window_start: &DateTime<Utc>,
+    window_end: &DateTime<Utc>,
+) -> Result<Vec<RangeClaim>, ArrErr> {
+    get_range_claims::<ResourceObject<Data>>(
+        &get_asset_exclusion(asset_type),
+        asset_id,
+        window_start,
+        window_end,
+    )
+    .await
 }
 
 impl GrpcDataObjectType for Data {
@@ -218,7 +443,7 @@ impl TryFrom<Row> for Data {
         debug!("(try_from) Converting Row to flight_plan::Data: {:?}", row);
         let pilot_id: String = row.get::<&str, Uuid>("pilot_id").to_string();
         let vehicle_id: String = row.get::<&str, Uuid>("vehicle_id").to_string();
-        let path = row.get::<&str, postgis::ewkb::LineString>("path");
+        let path = row.get::<&str, GeoLineString>("path");
         let origin_vertipad_id: String = row.get::<&str, Uuid>("origin_vertipad_id").to_string();
         let target_vertipad_id: String = row.get::<&str, Uuid>("target_vertipad_id").to_string();
 
@@ -289,7 +514,7 @@ impl TryFrom<Row> for Data {
         Ok(Data {
             pilot_id,
             vehicle_id,
-            path: Some(path.into()),
+            path: Some(path),
             weather_conditions: row.get("weather_conditions"),
             origin_vertiport_id: Some(origin_vertiport_id),
             origin_vertipad_id,
@@ -314,6 +539,7 @@ impl TryFrom<Row> for Data {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::postgres::transition::{get_stamp_update, StatusTransition};
     use crate::resources::grpc_geo_types::GeoLineString;
     use crate::test_util::*;
 
@@ -349,7 +575,10 @@ mod tests {
         let data = Data {
             pilot_id: String::from("INVALID"),
             vehicle_id: String::from("INVALID"),
-            path: Some(GeoLineString { points: vec![] }),
+            path: Some(GeoLineString {
+                points: vec![],
+                times: vec![],
+            }),
             weather_conditions: Some(String::from("")),
             origin_vertiport_id: None,
             origin_vertipad_id: String::from("INVALID"),
@@ -422,6 +651,154 @@ mod tests {
         ut_info!("(test_flight_plan_invalid_data) success");
     }
 
+    #[tokio::test]
+    async fn test_flight_plan_range_exclusions() {
+        crate::get_log_handle().await;
+        ut_info!("(test_flight_plan_range_exclusions) start");
+
+        let exclusions = ResourceObject::<Data>::get_range_exclusions();
+        assert_eq!(
+            exclusions,
+            vec![
+                get_vertipad_exclusion(),
+                get_asset_exclusion(AssetType::Vehicle),
+                get_asset_exclusion(AssetType::Pilot)
+            ]
+        );
+        assert_eq!(
+            get_asset_exclusion(AssetType::Vehicle).ranges[0].key_col,
+            "vehicle_id"
+        );
+        assert_eq!(
+            get_asset_exclusion(AssetType::Pilot).ranges[0].key_col,
+            "pilot_id"
+        );
+
+        // All exclusion columns should be part of the resource definition
+        let definition = ResourceObject::<Data>::get_definition();
+        for exclusion in exclusions {
+            for range in exclusion.ranges {
+                assert!(definition.has_field(&range.key_col));
+                assert!(definition.has_field(&range.start_col));
+                assert!(definition.has_field(&range.end_col));
+            }
+            for (col, values) in exclusion.ignore {
+                assert!(definition.has_field(&col));
+                assert!(values.contains(&String::from("CANCELLED")));
+                for value in values {
+                    assert!(FlightStatus::from_str_name(&value).is_some());
+                }
+            }
+        }
+
+        ut_info!("(test_flight_plan_range_exclusions) success");
+    }
+
+    #[tokio::test]
+    async fn test_flight_plan_status_transitions() {
+        crate::get_log_handle().await;
+        ut_info!("(test_flight_plan_status_transitions) start");
+
+        let transitions = ResourceObject::<Data>::get_status_transitions().unwrap();
+        assert_eq!(transitions, get_flight_status_transitions());
+
+        let definition = ResourceObject::<Data>::get_definition();
+        assert!(definition.has_field(&transitions.status_col));
+        for col in transitions.stamps.values() {
+            assert!(definition.has_field(col));
+        }
+
+        assert!(transitions.is_allowed("DRAFT", "READY"));
+        assert!(transitions.is_allowed("BOARDING", "IN_FLIGHT"));
+        assert!(transitions.is_allowed("IN_FLIGHT", "FINISHED"));
+        assert!(transitions.is_allowed("FINISHED", "FINISHED"));
+        assert!(!transitions.is_allowed("FINISHED", "DRAFT"));
+        assert!(!transitions.is_allowed("CANCELLED", "READY"));
+        assert!(!transitions.is_allowed("DRAFT", "IN_FLIGHT"));
+
+        let transition = StatusTransition {
+            from: String::from("BOARDING"),
+            to: String::from("IN_FLIGHT"),
+        };
+        assert_eq!(
+            get_stamp_update::<ResourceObject<Data>>(&transition, None),
+            Some(String::from(
+                r#""actual_departure_time" = COALESCE("actual_departure_time", NOW())"#
+            ))
+        );
+        let transition = StatusTransition {
+            from: String::from("DRAFT"),
+            to: String::from("READY"),
+        };
+        assert_eq!(
+            get_stamp_update::<ResourceObject<Data>>(&transition, None),
+            None
+        );
+
+        ut_info!("(test_flight_plan_status_transitions) success");
+    }
+
+    #[tokio::test]
+    async fn test_flight_plan_status_propagations() {
+        crate::get_log_handle().await;
+        ut_info!("(test_flight_plan_status_propagations) start");
+
+        let propagations = ResourceObject::<Data>::get_status_propagations();
+        assert_eq!(propagations.len(), 2);
+        for propagation in &propagations {
+            assert_eq!(propagation.target.psql_table, "parcel");
+            for (from, to) in &propagation.advance {
+                assert!(propagation.target_transitions.is_allowed(from, to));
+            }
+        }
+        assert_eq!(
+            propagations[0].trigger,
+            PropagationTrigger::Status(String::from("IN_FLIGHT"))
+        );
+        assert_eq!(
+            propagations[0].advance.get("DROPPEDOFF"),
+            Some(&String::from("ENROUTE"))
+        );
+        assert_eq!(
+            propagations[1].trigger,
+            PropagationTrigger::Status(String::from("FINISHED"))
+        );
+        assert_eq!(
+            propagations[1].advance.get("ENROUTE"),
+            Some(&String::from("ARRIVED"))
+        );
+        assert_eq!(propagations[1].advance.get("PICKEDUP"), None);
+
+        ut_info!("(test_flight_plan_status_propagations) success");
+    }
+
+    #[tokio::test]
+    async fn test_flight_plan_spatial_checks() {
+        crate::get_log_handle().await;
+        ut_info!("(test_flight_plan_spatial_checks) start");
+
+        let definition = ResourceObject::<Data>::get_definition();
+        let checks = ResourceObject::<Data>::get_spatial_checks();
+        assert_eq!(checks.len(), 2);
+        assert_eq!(checks[0].relation, SpatialRelation::StartsAt);
+        assert_eq!(checks[0].ref_key_col, "origin_vertipad_id");
+        assert_eq!(checks[1].relation, SpatialRelation::EndsAt);
+        assert_eq!(checks[1].ref_key_col, "target_vertipad_id");
+        for check in &checks {
+            assert!(definition.has_field(&check.col));
+            assert!(definition.has_field(&check.ref_key_col));
+            assert_eq!(
+                check.ref_table,
+                ResourceObject::<vertipad::Data>::get_psql_table()
+            );
+            assert!(ResourceObject::<vertipad::Data>::has_id_col(
+                &check.ref_id_col
+            ));
+        }
+
+        ut_info!("(test_flight_plan_spatial_checks) success");
+    }
+
     #[tokio::test]
     async fn test_flight_status_get_enum_string_val() {
         crate::get_log_handle().await;
@@ -76,6 +76,16 @@ impl Resource for ResourceObject<Data> {
         ]
         .to_vec()
     }
+
+    fn get_link_tables() -> Vec<String> {
+        [
+            "user_group".to_owned(),
+            "vehicle_group".to_owned(),
+            "vertipad_group".to_owned(),
+            "vertiport_group".to_owned(),
+        ]
+        .to_vec()
+    }
 }
 
 impl GrpcDataObjectType for Data {
@@ -59,6 +59,10 @@ impl Resource for ResourceObject<Data> {
         ]
         .to_vec()
     }
+
+    fn get_link_tables() -> Vec<String> {
+        ["itinerary_flight_plan".to_owned()].to_vec()
+    }
 }
 
 impl GrpcDataObjectType for Data {
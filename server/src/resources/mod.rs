@@ -12,6 +12,7 @@ pub mod pilot;
 pub mod scanner;
 pub mod user;
 pub mod vehicle;
+pub mod vehicle_model;
 pub mod vertipad;
 pub mod vertiport;
 
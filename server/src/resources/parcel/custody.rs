@@ -0,0 +1,256 @@
+//! Parcel custody timeline
+//!
+//! Combines the scans, flight legs and status changes of a parcel into a single timeline,
+//! using the `parcel_scan`, `flight_plan_parcel`, `flight_plan` and status transition tables.
+
+use super::{
+    CustodyEvent, CustodyEventType, CustodyFlightLeg, CustodyScan, CustodyStatusChange, Data,
+    ParcelStatus,
+};
+use crate::common::ArrErr;
+use crate::grpc::server::grpc_geo_types::GeoPoint;
+use crate::postgres::get_psql_client;
+use crate::postgres::simple_resource::PsqlType;
+use crate::postgres::transition::STATUS_TRANSITION_TABLE;
+use crate::resources::base::{Resource, ResourceObject};
+use crate::resources::flight_plan::FlightStatus;
+
+use chrono::{DateTime, Utc};
+use log::debug;
+use tokio_postgres::Row;
+use uuid::Uuid;
+
+/// Current status and ordered custody events of a parcel
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustodyTimeline {
+    /// current status of the parcel
+    pub status: ParcelStatus,
+    /// scans, flight legs and status changes ordered by their timestamp
+    pub events: Vec<CustodyEvent>,
+}
+
+/// Returns the [`CustodyTimeline`] of the parcel with the provided id.
+///
+/// Deleted scans, deleted flight plans and cancelled flight plans are ignored.
+/// Flight legs are placed at the actual departure time of the flight plan, or at the start of
+/// its origin timeslot if it did not depart yet.
+///
+/// # Errors
+///
+/// Returns [`ArrErr`] NotFound if no parcel exists for the provided id
+/// Returns [`ArrErr`] if a stored status value could not be converted to a [`ParcelStatus`]
+/// Returns [`ArrErr`] from [`PoolError`](deadpool::managed::PoolError) if no client connection could be returned from the connection [`Pool`](deadpool::managed::Pool)
+/// Returns [`ArrErr`] Database Error if database query execution failed
+pub async fn get_custody_timeline(parcel_id: &Uuid) -> Result<CustodyTimeline, ArrErr> {
+    let row = ResourceObject::<Data>::get_by_id(parcel_id).await?;
+    let status = Data::try_from(row)?.status();
+
+    let client = get_psql_client().await?;
+    let mut events: Vec<CustodyEvent> = vec![];
+
+    let query = get_scans_query();
+    debug!("(get_custody_timeline) [{}].", query);
+    for row in client.query(&query, &[parcel_id]).await? {
+        events.push(get_scan_event(&row));
+    }
+
+    let query = get_flight_legs_query();
+    debug!("(get_custody_timeline) [{}].", query);
+    for row in client.query(&query, &[parcel_id]).await? {
+        events.push(get_flight_leg_event(&row));
+    }
+
+    let query = get_status_changes_query();
+    debug!("(get_custody_timeline) [{}].", query);
+    let psql_table = ResourceObject::<Data>::get_psql_table();
+    for row in client.query(&query, &[parcel_id, &psql_table]).await? {
+        events.push(get_status_change_event(&row)?);
+    }
+
+    sort_custody_events(&mut events);
+    Ok(CustodyTimeline { status, events })
+}
+
+/// Orders the provided events by their timestamp.
+///
+/// The sort is stable, events sharing the same timestamp keep their order. Scans and flight legs are
+/// added before status changes, so a status change caused by a scan or flight plan is listed after its cause.
+pub fn sort_custody_events(events: &mut [CustodyEvent]) {
+    events.sort_by_key(|event| {
+        event
+            .timestamp
+            .as_ref()
+            .map(|timestamp| (timestamp.seconds, timestamp.nanos))
+    });
+}
+
+/// Returns the query used to get all scans of the parcel matching id parameter `$1`
+fn get_scans_query() -> String {
+    String::from(
+        r#"SELECT "parcel_scan_id", "scanner_id", "geo_location", "created_at" FROM "parcel_scan" WHERE "parcel_id" = $1 AND "deleted_at" IS NULL"#,
+    )
+}
+
+/// Returns the query used to get all flight legs of the parcel matching id parameter `$1`
+fn get_flight_legs_query() -> String {
+    format!(
+        r#"SELECT fp."flight_plan_id", fpp."acquire", fpp."deliver", fp."origin_vertipad_id", fp."target_vertipad_id", COALESCE(fp."actual_departure_time", fp."origin_timeslot_start") AS "departure", COALESCE(fp."actual_arrival_time", fp."target_timeslot_end") AS "arrival", fp."actual_departure_time" IS NOT NULL AS "departed", fp."actual_arrival_time" IS NOT NULL AS "arrived" FROM "flight_plan_parcel" fpp JOIN "flight_plan" fp ON fp."flight_plan_id" = fpp."flight_plan_id" WHERE fpp."parcel_id" = $1 AND fp."deleted_at" IS NULL AND fp."flight_status"::TEXT <> '{}'"#,
+        FlightStatus::Cancelled.as_str_name()
+    )
+}
+
+/// Returns the query used to get all recorded status changes of the record matching id parameter `$1`
+/// for the table name parameter `$2`
+fn get_status_changes_query() -> String {
+    format!(
+        r#"SELECT "from_status", "to_status", "changed_by", "created_at" FROM "{}" WHERE "record_id" = $1 AND "table_name" = $2"#,
+        STATUS_TRANSITION_TABLE
+    )
+}
+
+#[cfg(not(tarpaulin_include))]
+// no_coverage: Can not be tested in unittest until https://github.com/sfackler/rust-postgres/pull/979 has been merged
+fn get_scan_event(row: &Row) -> CustodyEvent {
+    let timestamp: DateTime<Utc> = row.get("created_at");
+    CustodyEvent {
+        event_type: CustodyEventType::Scan as i32,
+        timestamp: Some(timestamp.into()),
+        scan: Some(CustodyScan {
+            parcel_scan_id: row.get::<&str, Uuid>("parcel_scan_id").to_string(),
+            scanner_id: row.get::<&str, Uuid>("scanner_id").to_string(),
+            geo_location: Some(row.get::<&str, GeoPoint>("geo_location")),
+        }),
+        flight_leg: None,
+        status_change: None,
+    }
+}
+
+#[cfg(not(tarpaulin_include))]
+// no_coverage: Can not be tested in unittest until https://github.com/sfackler/rust-postgres/pull/979 has been merged
+fn get_flight_leg_event(row: &Row) -> CustodyEvent {
+    let departure: DateTime<Utc> = row.get("departure");
+    let arrival: DateTime<Utc> = row.get("arrival");
+    CustodyEvent {
+        event_type: CustodyEventType::FlightLeg as i32,
+        timestamp: Some(departure.into()),
+        scan: None,
+        flight_leg: Some(CustodyFlightLeg {
+            flight_plan_id: row.get::<&str, Uuid>("flight_plan_id").to_string(),
+            acquire: row.get("acquire"),
+            deliver: row.get("deliver"),
+            origin_vertipad_id: row.get::<&str, Uuid>("origin_vertipad_id").to_string(),
+            target_vertipad_id: row.get::<&str, Uuid>("target_vertipad_id").to_string(),
+            departure: Some(departure.into()),
+            arrival: Some(arrival.into()),
+            departed: row.get("departed"),
+            arrived: row.get("arrived"),
+        }),
+        status_change: None,
+    }
+}
+
+#[cfg(not(tarpaulin_include))]
+// no_coverage: Can not be tested in unittest until https://github.com/sfackler/rust-postgres/pull/979 has been merged
+fn get_status_change_event(row: &Row) -> Result<CustodyEvent, ArrErr> {
+    let timestamp: DateTime<Utc> = row.get("created_at");
+    let from_status = parse_status(row.get("from_status"))?;
+    let to_status = parse_status(row.get("to_status"))?;
+    Ok(CustodyEvent {
+        event_type: CustodyEventType::StatusChange as i32,
+        timestamp: Some(timestamp.into()),
+        scan: None,
+        flight_leg: None,
+        status_change: Some(CustodyStatusChange {
+            from_status: from_status as i32,
+            to_status: to_status as i32,
+            changed_by: row
+                .get::<&str, Option<Uuid>>("changed_by")
+                .map(|id| id.to_string()),
+        }),
+    })
+}
+
+/// Converts the provided database value into a [`ParcelStatus`]
+fn parse_status(value: &str) -> Result<ParcelStatus, ArrErr> {
+    ParcelStatus::from_str_name(value).ok_or_else(|| {
+        ArrErr::Error(format!(
+            "Could not convert database value [{}] to ParcelStatus Enum type.",
+            value
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, SystemTime};
+
+    fn get_event(event_type: CustodyEventType, timestamp: SystemTime) -> CustodyEvent {
+        CustodyEvent {
+            event_type: event_type as i32,
+            timestamp: Some(timestamp.into()),
+            scan: None,
+            flight_leg: None,
+            status_change: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sort_custody_events() {
+        crate::get_log_handle().await;
+        ut_info!("(test_sort_custody_events) start");
+
+        let now = SystemTime::now();
+        let later = now + Duration::from_secs(3600);
+        let mut events = vec![
+            get_event(CustodyEventType::Scan, later),
+            get_event(CustodyEventType::Scan, now),
+            get_event(CustodyEventType::FlightLeg, later),
+            get_event(CustodyEventType::StatusChange, now),
+            get_event(CustodyEventType::StatusChange, later),
+        ];
+        sort_custody_events(&mut events);
+
+        let expected = vec![
+            get_event(CustodyEventType::Scan, now),
+            get_event(CustodyEventType::StatusChange, now),
+            get_event(CustodyEventType::Scan, later),
+            get_event(CustodyEventType::FlightLeg, later),
+            get_event(CustodyEventType::StatusChange, later),
+        ];
+        assert_eq!(events, expected);
+
+        ut_info!("(test_sort_custody_events) success");
+    }
+
+    #[tokio::test]
+    async fn test_get_custody_queries() {
+        crate::get_log_handle().await;
+        ut_info!("(test_get_custody_queries) start");
+
+        assert_eq!(
+            get_scans_query(),
+            r#"SELECT "parcel_scan_id", "scanner_id", "geo_location", "created_at" FROM "parcel_scan" WHERE "parcel_id" = $1 AND "deleted_at" IS NULL"#
+        );
+        let query = get_flight_legs_query();
+        assert!(query.contains(r#"WHERE fpp."parcel_id" = $1"#));
+        assert!(query.contains(r#"fp."flight_status"::TEXT <> 'CANCELLED'"#));
+        assert_eq!(
+            get_status_changes_query(),
+            r#"SELECT "from_status", "to_status", "changed_by", "created_at" FROM "status_transition" WHERE "record_id" = $1 AND "table_name" = $2"#
+        );
+
+        ut_info!("(test_get_custody_queries) success");
+    }
+
+    #[tokio::test]
+    async fn test_parse_status() {
+        crate::get_log_handle().await;
+        ut_info!("(test_parse_status) start");
+
+        assert_eq!(parse_status("ENROUTE").unwrap(), ParcelStatus::Enroute);
+        assert!(parse_status("INVALID").is_err());
+
+        ut_info!("(test_parse_status) success");
+    }
+}
@@ -1,6 +1,7 @@
 //! Parcel
 
 pub use crate::grpc::server::parcel::*;
+pub mod custody;
 
 use anyhow::{Context, Result};
 use log::debug;
@@ -10,10 +11,15 @@ use tokio_postgres::types::Type as PsqlFieldType;
 use uuid::Uuid;
 
 use super::base::simple_resource::*;
-use super::base::{FieldDefinition, ResourceDefinition};
+use super::base::{
+    FieldDefinition, PropagationTrigger, ResourceDefinition, StatusPropagation, StatusTransitions,
+};
 use crate::common::ArrErr;
 use crate::grpc::{GrpcDataObjectType, GrpcField};
 
+// Parcel status lifecycle shared with the client
+include!("../../../../includes/parcel/status.rs");
+
 crate::build_generic_resource_impl_from!();
 
 // Generate grpc server implementations
@@ -75,6 +81,56 @@ impl Resource for ResourceObject<Data> {
         ]
         .to_vec()
     }
+
+    fn get_status_transitions() -> Option<StatusTransitions> {
+        Some(get_parcel_status_transitions())
+    }
+
+    fn get_link_tables() -> Vec<String> {
+        ["flight_plan_parcel".to_owned()].to_vec()
+    }
+}
+
+/// Returns the [`StatusTransitions`] for the `status` column, based on [`ParcelStatus::get_allowed_transitions`].
+pub fn get_parcel_status_transitions() -> StatusTransitions {
+    StatusTransitions {
+        status_col: String::from("status"),
+        transitions: ParcelStatus::PROGRESSION
+            .iter()
+            .map(|status| {
+                (
+                    status.as_str_name().to_owned(),
+                    status
+                        .get_allowed_transitions()
+                        .iter()
+                        .map(|allowed| allowed.as_str_name().to_owned())
+                        .collect(),
+                )
+            })
+            .collect(),
+        stamps: HashMap::new(),
+    }
+}
+
+/// Returns a [`StatusPropagation`] advancing the status of the parcels returned by the
+/// `parcel_ids_query` for the provided [`PropagationTrigger`].
+///
+/// The `advance` list contains the current parcel status values with the status value they should advance to.
+pub fn get_parcel_status_propagation(
+    trigger: PropagationTrigger,
+    parcel_ids_query: &str,
+    advance: &[(ParcelStatus, ParcelStatus)],
+) -> StatusPropagation {
+    StatusPropagation {
+        trigger,
+        target: ResourceObject::<Data>::get_definition(),
+        target_transitions: get_parcel_status_transitions(),
+        target_ids_query: parcel_ids_query.to_owned(),
+        advance: advance
+            .iter()
+            .map(|(from, to)| (from.as_str_name().to_owned(), to.as_str_name().to_owned()))
+            .collect(),
+    }
 }
 
 impl GrpcDataObjectType for Data {
@@ -255,4 +311,45 @@ mod tests {
 
         ut_info!("(test_parcel_status_from_str_name) success");
     }
+
+    #[tokio::test]
+    async fn test_parcel_status_transitions() {
+        crate::get_log_handle().await;
+        ut_info!("(test_parcel_status_transitions) start");
+
+        let transitions = ResourceObject::<Data>::get_status_transitions().unwrap();
+        assert_eq!(transitions, get_parcel_status_transitions());
+        assert!(ResourceObject::<Data>::get_definition().has_field(&transitions.status_col));
+
+        assert!(transitions.is_allowed("NOTDROPPEDOFF", "DROPPEDOFF"));
+        assert!(transitions.is_allowed("DROPPEDOFF", "ENROUTE"));
+        assert!(transitions.is_allowed("ENROUTE", "ARRIVED"));
+        assert!(transitions.is_allowed("ARRIVED", "PICKEDUP"));
+        assert!(transitions.is_allowed("PICKEDUP", "COMPLETE"));
+        assert!(!transitions.is_allowed("ENROUTE", "DROPPEDOFF"));
+        assert!(!transitions.is_allowed("COMPLETE", "NOTDROPPEDOFF"));
+        assert!(transitions.get_allowed("COMPLETE").is_empty());
+
+        ut_info!("(test_parcel_status_transitions) success");
+    }
+
+    #[tokio::test]
+    async fn test_parcel_status_propagation() {
+        crate::get_log_handle().await;
+        ut_info!("(test_parcel_status_propagation) start");
+
+        let propagation = get_parcel_status_propagation(
+            PropagationTrigger::Insert,
+            r#"SELECT "parcel_id" FROM "parcel_scan" WHERE "parcel_scan_id" = $1"#,
+            &[(ParcelStatus::Notdroppedoff, ParcelStatus::Droppedoff)],
+        );
+        assert_eq!(propagation.trigger, PropagationTrigger::Insert);
+        assert_eq!(propagation.target.psql_table, "parcel");
+        assert_eq!(
+            propagation.advance,
+            HashMap::from([(String::from("NOTDROPPEDOFF"), String::from("DROPPEDOFF"))])
+        );
+
+        ut_info!("(test_parcel_status_propagation) success");
+    }
 }
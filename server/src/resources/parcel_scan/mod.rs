@@ -9,9 +9,10 @@ use tokio_postgres::types::Type as PsqlFieldType;
 use uuid::Uuid;
 
 use super::base::simple_resource::*;
-use super::base::{FieldDefinition, ResourceDefinition};
+use super::base::{FieldDefinition, PropagationTrigger, ResourceDefinition, StatusPropagation};
 use crate::common::ArrErr;
 use crate::grpc::{GrpcDataObjectType, GrpcField, GrpcFieldOption};
+use crate::resources::parcel::{self, ParcelStatus};
 use chrono::{DateTime, Utc};
 
 crate::build_generic_resource_impl_from!();
@@ -63,6 +64,21 @@ impl Resource for ResourceObject<Data> {
             r#"CREATE INDEX IF NOT EXISTS parcel_scan_geo_location_idx ON parcel_scan USING GIST(geo_location)"#.to_owned(),
         ].to_vec()
     }
+
+    /// A scan marks a parcel as dropped off when it's handed in, and as picked up (and
+    /// completed with the final scan) when it's handed over at its destination.
+    /// Scans of parcels which are on their way don't change the parcel status.
+    fn get_status_propagations() -> Vec<StatusPropagation> {
+        vec![parcel::get_parcel_status_propagation(
+            PropagationTrigger::Insert,
+            r#"SELECT "parcel_id" FROM "parcel_scan" WHERE "parcel_scan_id" = $1"#,
+            &[
+                (ParcelStatus::Notdroppedoff, ParcelStatus::Droppedoff),
+                (ParcelStatus::Arrived, ParcelStatus::Pickedup),
+                (ParcelStatus::Pickedup, ParcelStatus::Complete),
+            ],
+        )]
+    }
 }
 
 impl GrpcDataObjectType for Data {
@@ -135,6 +151,33 @@ mod tests {
         ut_info!("(test_parcel_scan_schema) success");
     }
 
+    #[tokio::test]
+    async fn test_parcel_scan_status_propagations() {
+        crate::get_log_handle().await;
+        ut_info!("(test_parcel_scan_status_propagations) start");
+
+        let propagations = ResourceObject::<Data>::get_status_propagations();
+        assert_eq!(propagations.len(), 1);
+        let propagation = &propagations[0];
+        assert_eq!(propagation.trigger, PropagationTrigger::Insert);
+        assert_eq!(propagation.target.psql_table, "parcel");
+        // Every scan advance must be a forward move
+        for (from, to) in &propagation.advance {
+            assert!(propagation.target_transitions.is_allowed(from, to));
+        }
+        assert_eq!(
+            propagation.advance.get("NOTDROPPEDOFF"),
+            Some(&String::from("DROPPEDOFF"))
+        );
+        assert_eq!(
+            propagation.advance.get("ARRIVED"),
+            Some(&String::from("PICKEDUP"))
+        );
+        assert_eq!(propagation.advance.get("ENROUTE"), None);
+
+        ut_info!("(test_parcel_scan_status_propagations) success");
+    }
+
     #[tokio::test]
     async fn test_parcel_scan_invalid_data() {
         crate::get_log_handle().await;
@@ -68,6 +68,10 @@ impl Resource for ResourceObject<Data> {
             _ => None,
         }
     }
+
+    fn get_link_tables() -> Vec<String> {
+        ["user_group".to_owned()].to_vec()
+    }
 }
 
 impl GrpcDataObjectType for Data {
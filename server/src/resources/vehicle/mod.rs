@@ -90,8 +90,13 @@ impl Resource for ResourceObject<Data> {
         [
             r#"ALTER TABLE vehicle ADD CONSTRAINT fk_hangar_id FOREIGN KEY(hangar_id) REFERENCES vertiport(vertiport_id)"#.to_owned(),
             r#"ALTER TABLE vehicle ADD CONSTRAINT fk_hangar_bay_id FOREIGN KEY(hangar_bay_id) REFERENCES vertipad(vertipad_id)"#.to_owned(),
+            r#"ALTER TABLE vehicle ADD CONSTRAINT fk_vehicle_model_id FOREIGN KEY(vehicle_model_id) REFERENCES vehicle_model(vehicle_model_id)"#.to_owned(),
         ].to_vec()
     }
+
+    fn get_link_tables() -> Vec<String> {
+        ["vehicle_group".to_owned()].to_vec()
+    }
 }
 
 impl GrpcDataObjectType for Data {
@@ -0,0 +1,267 @@
+//! Vehicle Model
+
+pub use crate::grpc::server::vehicle_model::*;
+
+use anyhow::{Context, Result};
+use log::debug;
+use std::collections::HashMap;
+use tokio_postgres::row::Row;
+use tokio_postgres::types::Type as PsqlFieldType;
+use uuid::Uuid;
+
+use super::base::simple_resource::*;
+use super::base::{FieldDefinition, ResourceDefinition};
+use crate::common::ArrErr;
+use crate::grpc::{GrpcDataObjectType, GrpcField, GrpcFieldOption};
+
+// Generate `From` trait implementations for GenericResource into and from Grpc defined Resource
+crate::build_generic_resource_impl_from!();
+
+// Generate grpc server implementations
+crate::build_grpc_simple_resource_impl!(vehicle_model);
+
+impl Resource for ResourceObject<Data> {
+    fn get_definition() -> ResourceDefinition {
+        ResourceDefinition {
+            psql_table: String::from("vehicle_model"),
+            psql_id_cols: vec![String::from("vehicle_model_id")],
+            fields: HashMap::from([
+                (
+                    String::from("manufacturer_id"),
+                    FieldDefinition::new(PsqlFieldType::UUID, false),
+                ),
+                (
+                    String::from("model_name"),
+                    FieldDefinition::new(PsqlFieldType::TEXT, true),
+                ),
+                (
+                    String::from("type"),
+                    FieldDefinition::new(PsqlFieldType::ANYENUM, true)
+                        .set_default(String::from("'VTOL_CARGO'")),
+                ),
+                (
+                    String::from("max_payload_kg"),
+                    FieldDefinition::new(PsqlFieldType::FLOAT8, false).set_positive(),
+                ),
+                (
+                    String::from("max_range_km"),
+                    FieldDefinition::new(PsqlFieldType::FLOAT8, false).set_positive(),
+                ),
+                (
+                    String::from("created_at"),
+                    FieldDefinition::new_internal(PsqlFieldType::TIMESTAMPTZ, true)
+                        .set_default(String::from("CURRENT_TIMESTAMP")),
+                ),
+                (
+                    String::from("updated_at"),
+                    FieldDefinition::new_internal(PsqlFieldType::TIMESTAMPTZ, true)
+                        .set_default(String::from("CURRENT_TIMESTAMP")),
+                ),
+                (
+                    String::from("deleted_at"),
+                    FieldDefinition::new_internal(PsqlFieldType::TIMESTAMPTZ, false),
+                ),
+            ]),
+        }
+    }
+
+    /// Converts raw i32 values into string based on matching Enum value
+    fn get_enum_string_val(field: &str, value: i32) -> Option<String> {
+        match field {
+            "type" => Some(
+                VehicleModelType::try_from(value)
+                    .ok()?
+                    .as_str_name()
+                    .to_string(),
+            ),
+            _ => None,
+        }
+    }
+
+    fn get_table_indices() -> Vec<String> {
+        [
+            r#"CREATE UNIQUE INDEX IF NOT EXISTS vehicle_model_manufacturer_model_name_idx ON "vehicle_model" ("manufacturer_id", "model_name")"#.to_owned(),
+        ].to_vec()
+    }
+}
+
+impl GrpcDataObjectType for Data {
+    fn get_field_value(&self, key: &str) -> Result<GrpcField, ArrErr> {
+        match key {
+            "manufacturer_id" => Ok(GrpcField::Option(GrpcFieldOption::String(
+                self.manufacturer_id.clone(),
+            ))), // ::core::option::Option<::prost::alloc::string::String>,
+            "model_name" => Ok(GrpcField::String(self.model_name.clone())), // ::prost::alloc::string::String,
+            "type" => Ok(GrpcField::I32(self.r#type)),                      // i32,
+            "max_payload_kg" => Ok(GrpcField::Option(GrpcFieldOption::F64(self.max_payload_kg))), // ::core::option::Option<f64>,
+            "max_range_km" => Ok(GrpcField::Option(GrpcFieldOption::F64(self.max_range_km))), // ::core::option::Option<f64>,
+            _ => Err(ArrErr::Error(format!(
+                "Invalid key specified [{}], no such field found",
+                key
+            ))),
+        }
+    }
+}
+
+#[cfg(not(tarpaulin_include))]
+// no_coverage: Can not be tested in unittest until https://github.com/sfackler/rust-postgres/pull/979 has been merged
+impl TryFrom<Row> for Data {
+    type Error = ArrErr;
+
+    fn try_from(row: Row) -> Result<Self, ArrErr> {
+        debug!(
+            "(try_from) Converting Row to vehicle_model::Data: {:?}",
+            row
+        );
+        let r#type = VehicleModelType::from_str_name(row.get("type"))
+            .context("(try_from) Could not convert database value to VehicleModelType Enum type.")?
+            as i32;
+        let manufacturer_id: Option<Uuid> = row.get("manufacturer_id");
+
+        Ok(Data {
+            manufacturer_id: manufacturer_id.map(|val| val.to_string()),
+            model_name: row.get::<&str, String>("model_name"),
+            r#type,
+            max_payload_kg: row.get::<&str, Option<f64>>("max_payload_kg"),
+            max_range_km: row.get::<&str, Option<f64>>("max_range_km"),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::*;
+
+    #[tokio::test]
+    async fn test_vehicle_model_schema() {
+        crate::get_log_handle().await;
+        ut_info!("(test_vehicle_model_schema) start");
+
+        let id = Uuid::new_v4().to_string();
+        let data = mock::get_data_obj();
+        let object: ResourceObject<Data> = Object {
+            id,
+            data: Some(data.clone()),
+        }
+        .into();
+        test_schema::<ResourceObject<Data>, Data>(object);
+
+        let result = validate::<ResourceObject<Data>>(&data);
+        assert!(result.is_ok());
+        if let Ok((sql_fields, validation_result)) = result {
+            ut_info!("{:?}", sql_fields);
+            ut_info!("{:?}", validation_result);
+            assert_eq!(validation_result.success, true);
+        }
+        ut_info!("(test_vehicle_model_schema) success");
+    }
+
+    #[tokio::test]
+    async fn test_vehicle_model_invalid_data() {
+        crate::get_log_handle().await;
+        ut_info!("(test_vehicle_model_invalid_data) start");
+
+        let data = Data {
+            manufacturer_id: Some(String::from("INVALID")),
+            model_name: String::from(""),
+            r#type: -1,
+            max_payload_kg: Some(0.0),
+            max_range_km: Some(-1.0),
+        };
+
+        let result = validate::<ResourceObject<Data>>(&data);
+        assert!(result.is_ok());
+        if let Ok((_, validation_result)) = result {
+            ut_info!("{:?}", validation_result);
+            assert_eq!(validation_result.success, false);
+
+            let expected_errors = vec!["manufacturer_id", "type", "max_payload_kg", "max_range_km"];
+            assert_eq!(expected_errors.len(), validation_result.errors.len());
+            assert!(contains_field_errors(&validation_result, &expected_errors));
+        }
+        ut_info!("(test_vehicle_model_invalid_data) success");
+    }
+
+    #[tokio::test]
+    async fn test_vehicle_model_unknown_limits() {
+        crate::get_log_handle().await;
+        ut_info!("(test_vehicle_model_unknown_limits) start");
+
+        let data = Data {
+            manufacturer_id: None,
+            max_payload_kg: None,
+            max_range_km: None,
+            ..mock::get_data_obj()
+        };
+
+        let result = validate::<ResourceObject<Data>>(&data);
+        assert!(result.is_ok());
+        if let Ok((sql_fields, validation_result)) = result {
+            ut_info!("{:?}", validation_result);
+            assert_eq!(validation_result.success, true);
+            assert!(!sql_fields.contains_key("manufacturer_id"));
+            assert!(!sql_fields.contains_key("max_payload_kg"));
+            assert!(!sql_fields.contains_key("max_range_km"));
+        }
+        ut_info!("(test_vehicle_model_unknown_limits) success");
+    }
+
+    #[tokio::test]
+    async fn test_vehicle_model_get_enum_type_string_val() {
+        crate::get_log_handle().await;
+        ut_info!("(test_vehicle_model_get_enum_type_string_val) start");
+
+        assert_eq!(
+            ResourceObject::<Data>::get_enum_string_val("type", VehicleModelType::VtolCargo.into()),
+            Some(String::from("VTOL_CARGO"))
+        );
+        assert_eq!(
+            ResourceObject::<Data>::get_enum_string_val(
+                "type",
+                VehicleModelType::VtolPassenger.into()
+            ),
+            Some(String::from("VTOL_PASSENGER"))
+        );
+
+        assert_eq!(
+            ResourceObject::<Data>::get_enum_string_val("type", -1),
+            None
+        );
+
+        ut_info!("(test_vehicle_model_get_enum_type_string_val) success");
+    }
+
+    #[tokio::test]
+    async fn test_vehicle_model_type_as_str_name() {
+        crate::get_log_handle().await;
+        ut_info!("(test_vehicle_model_type_as_str_name) start");
+
+        assert_eq!(VehicleModelType::VtolCargo.as_str_name(), "VTOL_CARGO");
+        assert_eq!(
+            VehicleModelType::VtolPassenger.as_str_name(),
+            "VTOL_PASSENGER"
+        );
+
+        ut_info!("(test_vehicle_model_type_as_str_name) success");
+    }
+
+    #[tokio::test]
+    async fn test_vehicle_model_type_from_str_name() {
+        crate::get_log_handle().await;
+        ut_info!("(test_vehicle_model_type_from_str_name) start");
+
+        assert_eq!(
+            VehicleModelType::from_str_name("VTOL_CARGO"),
+            Some(VehicleModelType::VtolCargo)
+        );
+        assert_eq!(
+            VehicleModelType::from_str_name("VTOL_PASSENGER"),
+            Some(VehicleModelType::VtolPassenger)
+        );
+
+        assert_eq!(VehicleModelType::from_str_name("INVALID"), None);
+
+        ut_info!("(test_vehicle_model_type_from_str_name) success");
+    }
+}
@@ -11,7 +11,9 @@ use tokio_postgres::types::Type as PsqlFieldType;
 use uuid::Uuid;
 
 use super::base::simple_resource::*;
-use super::base::{FieldDefinition, ResourceDefinition};
+use super::base::{
+    DependentSpatialCheck, FieldDefinition, ResourceDefinition, SpatialCheck, SpatialRelation,
+};
 use crate::common::ArrErr;
 use crate::grpc::{GrpcDataObjectType, GrpcField, GrpcFieldOption};
 
@@ -76,6 +78,29 @@ impl Resource for ResourceObject<Data> {
             r#"CREATE INDEX IF NOT EXISTS vertipad_geo_location_idx ON vertipad USING GIST(geo_location)"#.to_owned(),
         ].to_vec()
     }
+
+    fn get_link_tables() -> Vec<String> {
+        ["vertipad_group".to_owned()].to_vec()
+    }
+
+    /// A vertipad must be located inside the area of its vertiport.
+    fn get_spatial_checks() -> Vec<SpatialCheck> {
+        vec![SpatialCheck::new(
+            "geo_location",
+            SpatialRelation::Within,
+            "vertiport_id",
+            "vertiport",
+            "vertiport_id",
+            "geo_location",
+        )]
+    }
+
+    /// The flight plans departing from or arriving at a vertipad must keep starting or ending at it.
+    fn get_dependent_spatial_checks() -> Vec<DependentSpatialCheck> {
+        DependentSpatialCheck::from_resource::<ResourceObject<crate::resources::flight_plan::Data>>(
+            "vertipad",
+        )
+    }
 }
 
 impl GrpcDataObjectType for Data {
@@ -199,4 +224,66 @@ mod tests {
         }
         ut_info!("(test_vertipad_invalid_data) success");
     }
+
+    #[tokio::test]
+    async fn test_vertipad_link_tables() {
+        crate::get_log_handle().await;
+        ut_info!("(test_vertipad_link_tables) start");
+
+        let link_tables = ResourceObject::<Data>::get_link_tables();
+        assert_eq!(
+            link_tables,
+            vec![ResourceObject::<super::group::Data>::get_psql_table()]
+        );
+        // purge removes link rows using the resource's id column
+        let id_col = ResourceObject::<Data>::try_get_id_field().unwrap();
+        assert!(ResourceObject::<super::group::Data>::has_id_col(&id_col));
+
+        ut_info!("(test_vertipad_link_tables) success");
+    }
+
+    #[tokio::test]
+    async fn test_vertipad_spatial_checks() {
+        crate::get_log_handle().await;
+        ut_info!("(test_vertipad_spatial_checks) start");
+
+        let checks = ResourceObject::<Data>::get_spatial_checks();
+        assert_eq!(checks.len(), 1);
+        assert_eq!(checks[0].relation, SpatialRelation::Within);
+        assert_eq!(
+            checks[0].ref_table,
+            ResourceObject::<crate::resources::vertiport::Data>::get_psql_table()
+        );
+        assert!(ResourceObject::<Data>::get_definition().has_field(&checks[0].col));
+        assert!(ResourceObject::<Data>::get_definition().has_field(&checks[0].ref_key_col));
+        assert!(
+            ResourceObject::<crate::resources::vertiport::Data>::has_id_col(&checks[0].ref_id_col)
+        );
+
+        ut_info!("(test_vertipad_spatial_checks) success");
+    }
+
+    #[tokio::test]
+    async fn test_vertipad_dependent_spatial_checks() {
+        crate::get_log_handle().await;
+        ut_info!("(test_vertipad_dependent_spatial_checks) start");
+
+        let dependents = ResourceObject::<Data>::get_dependent_spatial_checks();
+        assert_eq!(dependents.len(), 2);
+        for dependent in dependents {
+            assert_eq!(
+                dependent.source.psql_table,
+                ResourceObject::<crate::resources::flight_plan::Data>::get_psql_table()
+            );
+            assert_eq!(
+                dependent.check.ref_table,
+                ResourceObject::<Data>::get_psql_table()
+            );
+            assert!(ResourceObject::<Data>::has_id_col(
+                &dependent.check.ref_id_col
+            ));
+        }
+
+        ut_info!("(test_vertipad_dependent_spatial_checks) success");
+    }
 }
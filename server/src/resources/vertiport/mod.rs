@@ -11,7 +11,7 @@ use tokio_postgres::types::Type as PsqlFieldType;
 use uuid::Uuid;
 
 use super::base::simple_resource::*;
-use super::base::{FieldDefinition, ResourceDefinition};
+use super::base::{DependentSpatialCheck, FieldDefinition, ResourceDefinition};
 use crate::common::ArrErr;
 use crate::grpc::{GrpcDataObjectType, GrpcField, GrpcFieldOption};
 
@@ -66,6 +66,17 @@ impl Resource for ResourceObject<Data> {
             r#"CREATE INDEX IF NOT EXISTS vertiport_geo_location_idx ON vertiport USING GIST(geo_location)"#.to_owned(),
         ].to_vec()
     }
+
+    fn get_link_tables() -> Vec<String> {
+        ["vertiport_group".to_owned()].to_vec()
+    }
+
+    /// The area of a vertiport must keep containing its vertipads.
+    fn get_dependent_spatial_checks() -> Vec<DependentSpatialCheck> {
+        DependentSpatialCheck::from_resource::<ResourceObject<crate::resources::vertipad::Data>>(
+            "vertiport",
+        )
+    }
 }
 
 impl GrpcDataObjectType for Data {
@@ -99,7 +110,7 @@ impl TryFrom<Row> for Data {
     fn try_from(row: Row) -> Result<Self, ArrErr> {
         debug!("(try_from) Converting Row to vertiport::Data: {:?}", row);
         let schedule: Option<String> = row.get("schedule");
-        let geo_location = row.get::<&str, postgis::ewkb::Polygon>("geo_location");
+        let geo_location = row.get::<&str, postgis::ewkb::PolygonT<GeoPoint>>("geo_location");
 
         let created_at: Option<prost_wkt_types::Timestamp> = row
             .get::<&str, Option<DateTime<Utc>>>("created_at")
@@ -192,4 +203,27 @@ mod tests {
         }
         ut_info!("(test_vertiport_invalid_data) success");
     }
+
+    #[tokio::test]
+    async fn test_vertiport_dependent_spatial_checks() {
+        crate::get_log_handle().await;
+        ut_info!("(test_vertiport_dependent_spatial_checks) start");
+
+        let dependents = ResourceObject::<Data>::get_dependent_spatial_checks();
+        assert_eq!(dependents.len(), 1);
+        assert_eq!(
+            dependents[0].source.psql_table,
+            ResourceObject::<crate::resources::vertipad::Data>::get_psql_table()
+        );
+        assert_eq!(
+            dependents[0].check.ref_table,
+            ResourceObject::<Data>::get_psql_table()
+        );
+        assert!(ResourceObject::<Data>::has_id_col(
+            &dependents[0].check.ref_id_col
+        ));
+        assert!(ResourceObject::<Data>::get_definition().has_field(&dependents[0].check.ref_col));
+
+        ut_info!("(test_vertiport_dependent_spatial_checks) success");
+    }
 }
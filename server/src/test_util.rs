@@ -331,13 +331,13 @@ pub(crate) fn validate_test_data_sql_val(field: &str, value: &str) {
         r#""geo_point""# => {
             assert_eq!(
                 value,
-                format!("ST_GeomFromText('POINT({:.15} {:.15})')", 180.0, 90.0)
+                format!("ST_GeomFromText('POINT({:.15} {:.15})', 4326)", 180.0, 90.0)
             );
         }
         r#""geo_polygon""# => {
             assert_eq!(
                 value,
-                format!("ST_GeomFromText('POLYGON(({:.15} {:.15},{:.15} {:.15},{:.15} {:.15},{:.15} {:.15}),({:.15} {:.15},{:.15} {:.15},{:.15} {:.15}),({:.15} {:.15},{:.15} {:.15},{:.15} {:.15},{:.15} {:.15}))')",
+                format!("ST_GeomFromText('POLYGON(({:.15} {:.15},{:.15} {:.15},{:.15} {:.15},{:.15} {:.15}),({:.15} {:.15},{:.15} {:.15},{:.15} {:.15}),({:.15} {:.15},{:.15} {:.15},{:.15} {:.15},{:.15} {:.15}))', 4326)",
                     1.0, 1.0, 2.0, 2.0, 3.0, 3.0, 1.0, 1.0,
                     11.0, 11.0, 12.0, 12.0, 11.0, 11.0,
                     179.1, 89.1, 179.2, 89.2, 179.3, 89.3, 179.1, 89.1
@@ -348,7 +348,7 @@ pub(crate) fn validate_test_data_sql_val(field: &str, value: &str) {
             assert_eq!(
                 value,
                 format!(
-                    "ST_GeomFromText('LINESTRING({:.15} {:.15},{:.15} {:.15},{:.15} {:.15})')",
+                    "ST_GeomFromText('LINESTRING({:.15} {:.15},{:.15} {:.15},{:.15} {:.15})', 4326)",
                     1.0, 1.0, 2.0, 2.0, 3.0, 3.0
                 )
             );
@@ -372,13 +372,16 @@ pub(crate) fn validate_test_data_sql_val(field: &str, value: &str) {
         r#""optional_geo_point""# => {
             assert_eq!(
                 value,
-                format!("ST_GeomFromText('POINT({:.15} {:.15})')", -180.0, -90.0)
+                format!(
+                    "ST_GeomFromText('POINT({:.15} {:.15})', 4326)",
+                    -180.0, -90.0
+                )
             );
         }
         r#""optional_geo_polygon""# => {
             assert_eq!(
                 value,
-                format!("ST_GeomFromText('POLYGON(({:.15} {:.15},{:.15} {:.15},{:.15} {:.15},{:.15} {:.15}),({:.15} {:.15},{:.15} {:.15},{:.15} {:.15}),({:.15} {:.15},{:.15} {:.15},{:.15} {:.15},{:.15} {:.15}))')",
+                format!("ST_GeomFromText('POLYGON(({:.15} {:.15},{:.15} {:.15},{:.15} {:.15},{:.15} {:.15}),({:.15} {:.15},{:.15} {:.15},{:.15} {:.15}),({:.15} {:.15},{:.15} {:.15},{:.15} {:.15},{:.15} {:.15}))', 4326)",
                     -1.0, -1.0, -2.0, -2.0, -3.0, -3.0, -1.0, -1.0,
                     -11.0, -11.0, -12.0, -12.0, -11.0, -11.0,
                     -179.1, -89.1, -179.2, -89.2, -179.3, -89.3, -179.1, -89.1
@@ -389,7 +392,7 @@ pub(crate) fn validate_test_data_sql_val(field: &str, value: &str) {
             assert_eq!(
                 value,
                 format!(
-                    "ST_GeomFromText('LINESTRING({:.15} {:.15},{:.15} {:.15},{:.15} {:.15})')",
+                    "ST_GeomFromText('LINESTRING({:.15} {:.15},{:.15} {:.15},{:.15} {:.15})', 4326)",
                     -1.0, -1.0, -2.0, -2.0, -3.0, -3.0
                 )
             );